@@ -0,0 +1,115 @@
+//! Peak memory and time of loading a large genesis.json
+//!
+//! Writes a synthetic genesis with many storage-heavy accounts, then loads it
+//! once with the buffered path (`read_genesis_json` + `build_genesis_db`) and
+//! once with the streaming `load_genesis_db`, each in a fresh process so the
+//! peak resident set size (VmHWM, Linux only) is measured independently.
+//!
+//!     cargo bench --bench genesis_parse
+//!
+//! `GENESIS_BENCH_ACCOUNTS` sets the account count (default 100000).
+
+use genesis_tool::verify::{build_genesis_db, load_genesis_db, read_genesis_json};
+use std::{
+    env, fs,
+    io::{BufWriter, Write},
+    path::Path,
+    process::Command,
+    time::Instant,
+};
+
+const MODE_VAR: &str = "GENESIS_BENCH_MODE";
+const FILE_VAR: &str = "GENESIS_BENCH_FILE";
+const SLOTS_PER_ACCOUNT: u64 = 8;
+
+fn write_genesis(path: &Path, accounts: u64) -> std::io::Result<()> {
+    let mut out = BufWriter::new(fs::File::create(path)?);
+    write!(out, "{{\"timestamp\":\"0x0\",\"alloc\":{{")?;
+    for i in 0..accounts {
+        if i > 0 {
+            write!(out, ",")?;
+        }
+        write!(
+            out,
+            "\"0x{:040x}\":{{\"balance\":\"0x{:x}\",\"nonce\":1,\"code\":\"0x6000\",\"storage\":{{",
+            i + 1,
+            i
+        )?;
+        for slot in 0..SLOTS_PER_ACCOUNT {
+            if slot > 0 {
+                write!(out, ",")?;
+            }
+            write!(
+                out,
+                "\"0x{:064x}\":\"0x{:064x}\"",
+                slot,
+                i * SLOTS_PER_ACCOUNT + slot
+            )?;
+        }
+        write!(out, "}}}}")?;
+    }
+    write!(out, "}}}}")?;
+    out.flush()
+}
+
+/// Peak resident set size of this process in KiB
+fn peak_rss_kib() -> Option<u64> {
+    fs::read_to_string("/proc/self/status")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn run_mode(mode: &str, path: &str) {
+    let start = Instant::now();
+    let accounts = match mode {
+        "buffered" => {
+            let genesis = read_genesis_json(path).unwrap();
+            build_genesis_db(&genesis).unwrap().accounts.len()
+        }
+        _ => load_genesis_db(path).unwrap().0.accounts.len(),
+    };
+    let peak = peak_rss_kib().map_or("n/a".to_string(), |kib| format!("{} MiB", kib / 1024));
+    println!(
+        "{:<10} {:>10} accounts {:>10.2?} peak RSS {}",
+        mode,
+        accounts,
+        start.elapsed(),
+        peak
+    );
+}
+
+fn main() {
+    if let (Ok(mode), Ok(path)) = (env::var(MODE_VAR), env::var(FILE_VAR)) {
+        run_mode(&mode, &path);
+        return;
+    }
+
+    let accounts = env::var("GENESIS_BENCH_ACCOUNTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(100_000);
+    let path = env::temp_dir().join(format!("genesis_bench_{}.json", std::process::id()));
+    write_genesis(&path, accounts).unwrap();
+    println!(
+        "genesis with {} accounts: {} MiB",
+        accounts,
+        fs::metadata(&path).unwrap().len() / (1024 * 1024)
+    );
+
+    let exe = env::current_exe().unwrap();
+    for mode in ["buffered", "streaming"] {
+        let status = Command::new(&exe)
+            .env(MODE_VAR, mode)
+            .env(FILE_VAR, &path)
+            .status()
+            .unwrap();
+        assert!(status.success(), "{} run failed", mode);
+    }
+    fs::remove_file(&path).unwrap();
+}
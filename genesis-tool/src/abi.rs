@@ -0,0 +1,138 @@
+//! ABI lookups from forge artifacts
+//!
+//! The bytecode directory is forge's `out/`, so next to each `<Name>.hex` the
+//! full artifact sits at `<Name>.sol/<Name>.json`. Function and error
+//! signatures are read from its `abi` to name selectors in traces and revert
+//! reports; contracts without an artifact are simply left undecoded.
+
+use alloy_primitives::{hex, keccak256, Address};
+use serde_json::Value;
+use std::{collections::HashMap, fs, path::PathBuf};
+use tracing::debug;
+
+use crate::registry::ContractsManifest;
+
+/// Forge artifact path for a contract in a bytecode directory
+pub fn artifact_path(byte_code_dir: &str, contract_name: &str) -> PathBuf {
+    PathBuf::from(byte_code_dir)
+        .join(format!("{}.sol", contract_name))
+        .join(format!("{}.json", contract_name))
+}
+
+/// Parsed forge artifact of a contract in a bytecode directory
+pub fn read_artifact(byte_code_dir: &str, contract_name: &str) -> anyhow::Result<Value> {
+    let path = artifact_path(byte_code_dir, contract_name);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read artifact {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse artifact {}: {}", path.display(), e))
+}
+
+/// Creation code (`bytecode.object`) of an artifact, which must be linked
+pub fn creation_code(artifact: &Value, contract_name: &str) -> anyhow::Result<Vec<u8>> {
+    let object = artifact
+        .pointer("/bytecode/object")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("Artifact of {} has no bytecode", contract_name))?;
+    if object.contains("__") {
+        anyhow::bail!(
+            "{} creation code has unlinked library placeholders",
+            contract_name
+        );
+    }
+    hex::decode(object.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid {} creation code: {}", contract_name, e))
+}
+
+/// Canonical type of an ABI parameter, expanding tuples into their components
+pub fn canonical_type(param: &Value) -> String {
+    let ty = param
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    match ty.strip_prefix("tuple") {
+        Some(suffix) => {
+            let components: Vec<String> = param
+                .get("components")
+                .and_then(Value::as_array)
+                .map(|c| c.iter().map(canonical_type).collect())
+                .unwrap_or_default();
+            format!("({}){}", components.join(","), suffix)
+        }
+        None => ty.to_string(),
+    }
+}
+
+/// `name(type,...)` of an ABI function, event or error entry
+pub fn signature(item: &Value) -> Option<String> {
+    let name = item.get("name")?.as_str()?;
+    let inputs: Vec<String> = item
+        .get("inputs")
+        .and_then(Value::as_array)
+        .map(|inputs| inputs.iter().map(canonical_type).collect())
+        .unwrap_or_default();
+    Some(format!("{}({})", name, inputs.join(",")))
+}
+
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AbiRegistry {
+    /// System contract name by address
+    pub names: HashMap<Address, String>,
+    pub functions: HashMap<[u8; 4], String>,
+    pub errors: HashMap<[u8; 4], String>,
+}
+
+impl AbiRegistry {
+    /// Collect names and signatures of every manifest contract with an artifact
+    pub fn load(byte_code_dir: &str, contracts: &ContractsManifest) -> Self {
+        let mut registry = Self::default();
+        for contract in &contracts.contracts {
+            registry
+                .names
+                .insert(contract.address, contract.name.clone());
+            let path = artifact_path(byte_code_dir, &contract.name);
+            let Some(abi) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+                .and_then(|artifact| artifact.get("abi").cloned())
+            else {
+                debug!("No ABI for {} at {}", contract.name, path.display());
+                continue;
+            };
+            registry.add_abi(&abi);
+        }
+        registry
+    }
+
+    pub fn add_abi(&mut self, abi: &Value) {
+        for item in abi.as_array().into_iter().flatten() {
+            let table = match item.get("type").and_then(Value::as_str) {
+                Some("function") => &mut self.functions,
+                Some("error") => &mut self.errors,
+                _ => continue,
+            };
+            if let Some(sig) = signature(item) {
+                table.insert(selector(&sig), sig);
+            }
+        }
+    }
+
+    pub fn name_of(&self, address: &Address) -> Option<&str> {
+        self.names.get(address).map(String::as_str)
+    }
+
+    pub fn function(&self, data: &[u8]) -> Option<&str> {
+        let selector: [u8; 4] = data.get(..4)?.try_into().ok()?;
+        self.functions.get(&selector).map(String::as_str)
+    }
+
+    pub fn error(&self, data: &[u8]) -> Option<&str> {
+        let selector: [u8; 4] = data.get(..4)?.try_into().ok()?;
+        self.errors.get(&selector).map(String::as_str)
+    }
+}
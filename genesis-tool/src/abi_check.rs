@@ -0,0 +1,461 @@
+//! Drift check between the tool's `sol!` bindings and forge artifacts
+//!
+//! The bindings in `genesis.rs`, `verify.rs`, `post_genesis.rs` and the
+//! simulations are hand-copied from the contracts and do not notice when a
+//! struct gains, loses or reorders a field. Each binding is compared with the
+//! ABI in `<Name>.sol/<Name>.json`: the function signature must exist, and
+//! struct parameters and return values are compared field by field (name and
+//! type), so a mismatch points at the exact field instead of a revert or a
+//! garbled decode.
+//!
+//! Struct names are not compared, since the tool prefixes its copies (`Sol...`).
+//! Non-struct return types are not covered.
+
+use alloy_sol_types::{SolCall, SolStruct};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::{collections::HashMap, fs};
+use tracing::{error, info, warn};
+
+use crate::{
+    abi::{artifact_path, signature},
+    genesis::{self, IValidatorManagement, SolGenesisInitParams, SolInitialValidator},
+    post_genesis::IJWKManagerQueries,
+    simulate::{self, governance, key_rotation, staking},
+    upgrade::IUpgradeTargets,
+    verify,
+};
+
+/// Field list of each struct in an EIP-712 encode type, as `(type, name)`
+pub(crate) type StructDefs = HashMap<String, Vec<(String, String)>>;
+
+/// A struct passed to or returned from a bound function
+#[derive(Debug, Clone)]
+struct StructParam {
+    output: bool,
+    index: usize,
+    root: &'static str,
+    encode_type: String,
+}
+
+/// One `sol!` function binding and the contract whose artifact defines it
+#[derive(Debug, Clone)]
+struct Binding {
+    source: &'static str,
+    contract: &'static str,
+    name: &'static str,
+    signature: &'static str,
+    structs: Vec<StructParam>,
+}
+
+impl Binding {
+    fn new<C: SolCall>(source: &'static str, contract: &'static str) -> Self {
+        let signature = C::SIGNATURE;
+        Self {
+            source,
+            contract,
+            name: signature.split('(').next().unwrap_or(signature),
+            signature,
+            structs: Vec::new(),
+        }
+    }
+
+    fn input<S: SolStruct>(self, index: usize) -> Self {
+        self.with_struct::<S>(false, index)
+    }
+
+    fn output<S: SolStruct>(self, index: usize) -> Self {
+        self.with_struct::<S>(true, index)
+    }
+
+    fn with_struct<S: SolStruct>(mut self, output: bool, index: usize) -> Self {
+        self.structs.push(StructParam {
+            output,
+            index,
+            root: S::NAME,
+            encode_type: S::eip712_encode_type().into_owned(),
+        });
+        self
+    }
+}
+
+/// Every binding the tool encodes calls or decodes results with
+fn bindings() -> Vec<Binding> {
+    vec![
+        Binding::new::<genesis::Genesis::initializeCall>("genesis.rs", "Genesis")
+            .input::<SolGenesisInitParams>(0),
+        Binding::new::<genesis::Genesis::beginInitializeCall>("genesis.rs", "Genesis")
+            .input::<SolGenesisInitParams>(0),
+        Binding::new::<genesis::Genesis::addValidatorBatchCall>("genesis.rs", "Genesis")
+            .input::<SolInitialValidator>(0),
+        Binding::new::<genesis::Genesis::finishInitializeCall>("genesis.rs", "Genesis"),
+        Binding::new::<IValidatorManagement::getActiveValidatorsCall>(
+            "genesis.rs",
+            "ValidatorManagement",
+        )
+        .output::<IValidatorManagement::ValidatorConsensusInfo>(0),
+        Binding::new::<verify::getActiveValidatorsCall>("verify.rs", "ValidatorManagement")
+            .output::<verify::ValidatorConsensusInfo>(0),
+        Binding::new::<verify::epochIntervalMicrosCall>("verify.rs", "EpochConfig"),
+        Binding::new::<IJWKManagerQueries::getProviderJWKsCall>("post_genesis.rs", "JWKManager")
+            .output::<IJWKManagerQueries::ProviderJWKs>(0),
+        Binding::new::<simulate::onBlockStartCall>("simulate", "Blocker"),
+        Binding::new::<simulate::nowMicrosecondsCall>("simulate", "Timestamp"),
+        Binding::new::<simulate::isTransitionInProgressCall>("simulate", "Reconfiguration"),
+        Binding::new::<simulate::finishTransitionCall>("simulate", "Reconfiguration"),
+        Binding::new::<simulate::lastReconfigurationTimeCall>("simulate", "Reconfiguration"),
+        Binding::new::<simulate::currentEpochCall>("simulate", "Reconfiguration"),
+        Binding::new::<governance::IGovernanceConfig::setForNextEpochCall>(
+            "simulate/governance.rs",
+            "GovernanceConfig",
+        ),
+        Binding::new::<governance::IStaking::getPoolVotingPowerCall>(
+            "simulate/governance.rs",
+            "Staking",
+        ),
+        Binding::new::<governance::IGovernance::createProposalCall>(
+            "simulate/governance.rs",
+            "Governance",
+        ),
+        Binding::new::<governance::IGovernance::voteCall>("simulate/governance.rs", "Governance"),
+        Binding::new::<governance::IGovernance::executeCall>(
+            "simulate/governance.rs",
+            "Governance",
+        ),
+        Binding::new::<staking::IStakingPools::getPoolStakerCall>("simulate/staking.rs", "Staking"),
+        Binding::new::<staking::IStakePool::withdrawAvailableCall>(
+            "simulate/staking.rs",
+            "StakePool",
+        ),
+        Binding::new::<key_rotation::IValidatorKeys::getValidatorCall>(
+            "simulate/key_rotation.rs",
+            "ValidatorManagement",
+        )
+        .output::<key_rotation::IValidatorKeys::ValidatorRecord>(0),
+        Binding::new::<key_rotation::IValidatorKeys::rotateConsensusKeyCall>(
+            "simulate/key_rotation.rs",
+            "ValidatorManagement",
+        ),
+        Binding::new::<IUpgradeTargets::setForNextEpochCall>("upgrade.rs", "VersionConfig"),
+        Binding::new::<IUpgradeTargets::governanceReconfigureCall>("upgrade.rs", "Reconfiguration"),
+    ]
+}
+
+/// Differences found for one binding
+#[derive(Debug, Clone)]
+pub struct AbiDrift {
+    pub binding: String,
+    pub differences: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AbiCheckReport {
+    pub checked: usize,
+    pub drifts: Vec<AbiDrift>,
+    /// Contracts without an artifact in the bytecode directory
+    pub skipped: Vec<String>,
+}
+
+impl AbiCheckReport {
+    pub fn is_clean(&self) -> bool {
+        self.drifts.is_empty()
+    }
+
+    pub fn log(&self) {
+        for contract in &self.skipped {
+            warn!(
+                "No artifact for {}; its bindings were not checked",
+                contract
+            );
+        }
+        for drift in &self.drifts {
+            error!("❌ {} drifted from the artifact:", drift.binding);
+            for difference in &drift.differences {
+                error!("   {}", difference);
+            }
+        }
+        if self.is_clean() {
+            info!("✅ {} binding(s) match the artifact ABIs", self.checked);
+        }
+    }
+}
+
+/// Compare every binding with the artifacts in `byte_code_dir`
+pub fn check_bindings(byte_code_dir: &str) -> Result<AbiCheckReport> {
+    let mut report = AbiCheckReport::default();
+    let mut abis: HashMap<&str, Option<Value>> = HashMap::new();
+    for binding in bindings() {
+        if !abis.contains_key(binding.contract) {
+            let abi = load_abi(byte_code_dir, binding.contract)?;
+            if abi.is_none() {
+                report.skipped.push(binding.contract.to_string());
+            }
+            abis.insert(binding.contract, abi);
+        }
+        let Some(abi) = &abis[binding.contract] else {
+            continue;
+        };
+        report.checked += 1;
+        let differences = compare_binding(&binding, abi);
+        if !differences.is_empty() {
+            report.drifts.push(AbiDrift {
+                binding: format!("{}.{} ({})", binding.contract, binding.name, binding.source),
+                differences,
+            });
+        }
+    }
+    Ok(report)
+}
+
+/// The `abi` of a contract's artifact; `None` when there is no artifact
+fn load_abi(byte_code_dir: &str, contract: &str) -> Result<Option<Value>> {
+    let path = artifact_path(byte_code_dir, contract);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content =
+        fs::read_to_string(&path).context(format!("Failed to read {}", path.display()))?;
+    let artifact: Value = serde_json::from_str(&content)
+        .context(format!("Failed to parse artifact {}", path.display()))?;
+    let abi = artifact
+        .get("abi")
+        .cloned()
+        .context(format!("Artifact {} has no abi", path.display()))?;
+    Ok(Some(abi))
+}
+
+fn compare_binding(binding: &Binding, abi: &Value) -> Vec<String> {
+    let candidates: Vec<&Value> = abi
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| {
+            item.get("type").and_then(Value::as_str) == Some("function")
+                && item.get("name").and_then(Value::as_str) == Some(binding.name)
+        })
+        .collect();
+    if candidates.is_empty() {
+        return vec![format!(
+            "function {} is missing from the artifact",
+            binding.name
+        )];
+    }
+
+    let mut signature_mismatch = None;
+    let function = match candidates
+        .iter()
+        .find(|item| signature(item).as_deref() == Some(binding.signature))
+    {
+        Some(function) => *function,
+        None => {
+            let artifact_signatures: Vec<String> = candidates
+                .iter()
+                .filter_map(|item| signature(item))
+                .collect();
+            let mismatch = format!(
+                "signature: tool has {}, artifact has {}",
+                binding.signature,
+                artifact_signatures.join(" | ")
+            );
+            // Field diffs against the only overload explain the mismatch
+            if candidates.len() > 1 {
+                return vec![mismatch];
+            }
+            signature_mismatch = Some(mismatch);
+            candidates[0]
+        }
+    };
+
+    let mut differences = Vec::new();
+
+    for param in &binding.structs {
+        let side = if param.output { "outputs" } else { "inputs" };
+        let Some(abi_param) = function
+            .get(side)
+            .and_then(Value::as_array)
+            .and_then(|params| params.get(param.index))
+        else {
+            differences.push(format!(
+                "{} {} of {} is missing from the artifact",
+                side.trim_end_matches('s'),
+                param.index,
+                binding.name
+            ));
+            continue;
+        };
+        let defs = parse_encode_type(&param.encode_type);
+        let path = abi_struct_name(abi_param).unwrap_or_else(|| param.root.to_string());
+        match abi_param.get("components").and_then(Value::as_array) {
+            Some(components) => diff_fields(&path, param.root, &defs, components, &mut differences),
+            None => differences.push(format!(
+                "{}: tool has struct {}, artifact has {}",
+                path,
+                param.root,
+                abi_type(abi_param)
+            )),
+        }
+    }
+    // Spelled out only when the field diffs do not already explain it
+    match signature_mismatch {
+        Some(mismatch) if differences.is_empty() => differences.push(mismatch),
+        Some(_) => differences.insert(0, "signature differs from the artifact".to_string()),
+        None => {}
+    }
+    differences
+}
+
+/// Parse `A(uint256 x,B b)B(bool y)` into each struct's fields
+pub(crate) fn parse_encode_type(encode_type: &str) -> StructDefs {
+    let mut defs = StructDefs::new();
+    for def in encode_type.split(')').filter(|def| !def.is_empty()) {
+        let Some((name, fields)) = def.split_once('(') else {
+            continue;
+        };
+        let fields = fields
+            .split(',')
+            .filter(|field| !field.is_empty())
+            .filter_map(|field| field.rsplit_once(' '))
+            .map(|(ty, name)| (ty.to_string(), name.to_string()))
+            .collect();
+        defs.insert(name.to_string(), fields);
+    }
+    defs
+}
+
+/// Split `T[2][]` into `T` and `[2][]`
+pub(crate) fn split_array_suffix(ty: &str) -> (&str, &str) {
+    match ty.find('[') {
+        Some(pos) => ty.split_at(pos),
+        None => (ty, ""),
+    }
+}
+
+/// Struct name from an ABI parameter's `internalType` (`struct Genesis.X[]`)
+fn abi_struct_name(param: &Value) -> Option<String> {
+    let internal = param
+        .get("internalType")?
+        .as_str()?
+        .strip_prefix("struct ")?;
+    let (name, _) = split_array_suffix(internal);
+    Some(name.rsplit('.').next().unwrap_or(name).to_string())
+}
+
+/// Readable type of an ABI parameter, naming structs instead of `tuple`
+fn abi_type(param: &Value) -> String {
+    let ty = param
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    match (ty.strip_prefix("tuple"), abi_struct_name(param)) {
+        (Some(suffix), Some(name)) => format!("{}{}", name, suffix),
+        _ => ty.to_string(),
+    }
+}
+
+/// Compare a tool struct with the ABI components at `path`
+fn diff_fields(
+    path: &str,
+    tool_struct: &str,
+    defs: &StructDefs,
+    components: &[Value],
+    differences: &mut Vec<String>,
+) {
+    let fields = defs.get(tool_struct).map(Vec::as_slice).unwrap_or_default();
+    let abi_names: Vec<&str> = components
+        .iter()
+        .map(|c| c.get("name").and_then(Value::as_str).unwrap_or_default())
+        .collect();
+    let (mut i, mut j) = (0, 0);
+    while i < fields.len() || j < components.len() {
+        match (fields.get(i), components.get(j)) {
+            (Some((ty, name)), Some(component)) => {
+                let abi_name = abi_names[j];
+                if name != abi_name {
+                    // A name that reappears further on the other side means a
+                    // field was added or removed here rather than renamed
+                    let removed = fields[i + 1..].iter().any(|(_, n)| n == abi_name)
+                        && !abi_names[j + 1..].contains(&name.as_str());
+                    let added = abi_names[j + 1..].contains(&name.as_str())
+                        && !fields[i + 1..].iter().any(|(_, n)| n == abi_name);
+                    if removed {
+                        differences
+                            .push(format!("{}.{} ({}) is not in the artifact", path, name, ty));
+                        i += 1;
+                        continue;
+                    }
+                    if added {
+                        differences.push(format!(
+                            "{}.{} ({}) is missing from the tool",
+                            path,
+                            abi_name,
+                            abi_type(component)
+                        ));
+                        j += 1;
+                        continue;
+                    }
+                    differences.push(format!(
+                        "{} field {}: tool has `{}`, artifact has `{}`",
+                        path, j, name, abi_name
+                    ));
+                }
+                let field_path = format!("{}.{}", path, abi_name);
+                diff_type(&field_path, ty, defs, component, differences);
+                i += 1;
+                j += 1;
+            }
+            (Some((ty, name)), None) => {
+                differences.push(format!("{}.{} ({}) is not in the artifact", path, name, ty));
+                i += 1;
+            }
+            (None, Some(component)) => {
+                differences.push(format!(
+                    "{}.{} ({}) is missing from the tool",
+                    path,
+                    abi_names[j],
+                    abi_type(component)
+                ));
+                j += 1;
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+fn diff_type(
+    path: &str,
+    ty: &str,
+    defs: &StructDefs,
+    component: &Value,
+    differences: &mut Vec<String>,
+) {
+    let (tool_base, tool_suffix) = split_array_suffix(ty);
+    let abi_ty = component
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let (abi_base, abi_suffix) = split_array_suffix(abi_ty);
+    let tool_is_struct = defs.contains_key(tool_base);
+    let abi_components = component.get("components").and_then(Value::as_array);
+
+    match (tool_is_struct, abi_components) {
+        (true, Some(components)) if abi_base == "tuple" => {
+            if tool_suffix != abi_suffix {
+                differences.push(format!(
+                    "{}: tool has {}, artifact has {}",
+                    path,
+                    ty,
+                    abi_type(component)
+                ));
+            }
+            let nested_path = format!("{}{}", path, "[]".repeat(abi_suffix.matches('[').count()));
+            diff_fields(&nested_path, tool_base, defs, components, differences);
+        }
+        _ if !tool_is_struct && ty == abi_ty => {}
+        _ => differences.push(format!(
+            "{}: tool has {}, artifact has {}",
+            path,
+            ty,
+            abi_type(component)
+        )),
+    }
+}
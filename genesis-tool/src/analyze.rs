@@ -0,0 +1,212 @@
+//! Stake concentration of the initial validator set
+//!
+//! Consensus needs more than 2/3 of the voting power to commit and stalls once
+//! more than 1/3 is offline, so the useful numbers are how few validators hold
+//! those fractions (the smaller the count, the fewer parties can halt or
+//! control the chain) and how much power sits with each operator address,
+//! which may run several validators.
+
+use revm_primitives::U256;
+use std::collections::BTreeMap;
+use tracing::info;
+
+use crate::genesis::{format_ether, parse_amount, GenesisConfig};
+
+/// Voting power shares reported for the largest validators
+const TOP_N: [usize; 3] = [1, 3, 5];
+
+/// Voting power held by one operator address
+#[derive(Debug, Clone)]
+pub struct OperatorStake {
+    pub operator: String,
+    pub monikers: Vec<String>,
+    pub power: U256,
+}
+
+#[derive(Debug, Clone)]
+pub struct StakeAnalysis {
+    pub total_power: U256,
+    /// Share of the `n` largest validators, in basis points
+    pub top_shares: Vec<(usize, u64)>,
+    /// Fewest validators holding more than 1/3 of the voting power
+    pub to_exceed_one_third: usize,
+    /// Fewest validators holding more than 2/3 of the voting power
+    pub to_exceed_two_thirds: usize,
+    /// Operators by voting power, largest first
+    pub operators: Vec<OperatorStake>,
+}
+
+/// `part / total` in basis points
+pub fn basis_points(part: U256, total: U256) -> u64 {
+    if total.is_zero() {
+        return 0;
+    }
+    (part.saturating_mul(U256::from(10_000)) / total).saturating_to()
+}
+
+/// Basis points as a percentage with two decimals
+pub fn format_basis_points(bps: u64) -> String {
+    format!("{}.{:02}%", bps / 100, bps % 100)
+}
+
+/// Whether `part` is more than `numerator / 3` of `total`
+fn exceeds_thirds(part: U256, total: U256, numerator: u64) -> bool {
+    part.saturating_mul(U256::from(3)) > total.saturating_mul(U256::from(numerator))
+}
+
+impl StakeAnalysis {
+    pub fn new(config: &GenesisConfig) -> Self {
+        let powers: Vec<U256> = config
+            .validators
+            .iter()
+            .map(|v| parse_amount(&v.voting_power).unwrap_or_default())
+            .collect();
+        let total_power = powers
+            .iter()
+            .fold(U256::ZERO, |sum, p| sum.saturating_add(*p));
+
+        let mut sorted = powers.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        let top_shares = TOP_N
+            .iter()
+            .filter(|n| **n <= sorted.len())
+            .map(|n| {
+                let top = sorted[..*n]
+                    .iter()
+                    .fold(U256::ZERO, |sum, p| sum.saturating_add(*p));
+                (*n, basis_points(top, total_power))
+            })
+            .collect();
+        let fewest_exceeding = |numerator: u64| {
+            let mut sum = U256::ZERO;
+            sorted
+                .iter()
+                .position(|p| {
+                    sum = sum.saturating_add(*p);
+                    exceeds_thirds(sum, total_power, numerator)
+                })
+                .map_or(sorted.len(), |i| i + 1)
+        };
+
+        let mut by_operator: BTreeMap<String, OperatorStake> = BTreeMap::new();
+        for (v, power) in config.validators.iter().zip(&powers) {
+            let entry = by_operator
+                .entry(v.operator.to_lowercase())
+                .or_insert_with(|| OperatorStake {
+                    operator: v.operator.clone(),
+                    monikers: Vec::new(),
+                    power: U256::ZERO,
+                });
+            entry.monikers.push(v.moniker.clone());
+            entry.power = entry.power.saturating_add(*power);
+        }
+        let mut operators: Vec<OperatorStake> = by_operator.into_values().collect();
+        operators.sort_by(|a, b| b.power.cmp(&a.power));
+
+        StakeAnalysis {
+            total_power,
+            top_shares,
+            to_exceed_one_third: fewest_exceeding(1),
+            to_exceed_two_thirds: fewest_exceeding(2),
+            operators,
+        }
+    }
+
+    /// Operators running several validators whose combined power exceeds
+    /// `numerator / 3` of the total
+    pub fn operators_exceeding(&self, numerator: u64) -> Vec<&OperatorStake> {
+        self.operators
+            .iter()
+            .filter(|o| o.monikers.len() > 1 && exceeds_thirds(o.power, self.total_power, numerator))
+            .collect()
+    }
+
+    pub fn log(&self) {
+        info!(
+            "Stake concentration: {} to halt (>1/3), {} to commit (>2/3), total voting power {}",
+            self.to_exceed_one_third,
+            self.to_exceed_two_thirds,
+            format_ether(self.total_power)
+        );
+        for (n, bps) in &self.top_shares {
+            info!("  top {} validator(s): {}", n, format_basis_points(*bps));
+        }
+        for o in self.operators.iter().filter(|o| o.monikers.len() > 1) {
+            info!(
+                "  operator {} runs {} ({})",
+                o.operator,
+                o.monikers.join(", "),
+                format_basis_points(basis_points(o.power, self.total_power))
+            );
+        }
+    }
+}
+
+/// Print each validator's stake, voting power and share of the total
+pub fn print_stake_distribution(config: &GenesisConfig) {
+    let amount = |s: &str| parse_amount(s).unwrap_or_default();
+    let total_power = config
+        .validators
+        .iter()
+        .fold(U256::ZERO, |sum, v| sum.saturating_add(amount(&v.voting_power)));
+    let total_stake = config
+        .validators
+        .iter()
+        .fold(U256::ZERO, |sum, v| sum.saturating_add(amount(&v.stake_amount)));
+    let footer = format!("{} validator(s)", config.validators.len());
+    let width = config
+        .validators
+        .iter()
+        .map(|v| v.moniker.len())
+        .chain(["moniker".len(), footer.len()])
+        .max()
+        .unwrap_or_default();
+    let share = |power: U256| format_basis_points(basis_points(power, total_power));
+
+    println!(
+        "{:<width$}  {:>28}  {:>28}  {:>8}",
+        "moniker", "stake", "voting power", "share"
+    );
+    for v in &config.validators {
+        let power = amount(&v.voting_power);
+        println!(
+            "{:<width$}  {:>28}  {:>28}  {:>8}",
+            v.moniker,
+            format_ether(amount(&v.stake_amount)),
+            format_ether(power),
+            share(power)
+        );
+    }
+    println!(
+        "{:<width$}  {:>28}  {:>28}  {:>8}",
+        footer,
+        format_ether(total_stake),
+        format_ether(total_power),
+        share(total_power)
+    );
+}
+
+/// Print the concentration summary after the distribution table
+pub fn print_analysis(analysis: &StakeAnalysis) {
+    println!();
+    for (n, bps) in &analysis.top_shares {
+        println!("top {} validator(s) hold {}", n, format_basis_points(*bps));
+    }
+    println!(
+        "{} validator(s) exceed 1/3 of the voting power and can halt the chain",
+        analysis.to_exceed_one_third
+    );
+    println!(
+        "{} validator(s) exceed 2/3 of the voting power and can commit blocks alone",
+        analysis.to_exceed_two_thirds
+    );
+    for o in &analysis.operators {
+        println!(
+            "operator {}: {} validator(s), {} ({})",
+            o.operator,
+            o.monikers.len(),
+            format_basis_points(basis_points(o.power, analysis.total_power)),
+            o.monikers.join(", ")
+        );
+    }
+}
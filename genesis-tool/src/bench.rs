@@ -0,0 +1,206 @@
+//! Timing of the genesis pipeline
+//!
+//! `bench` runs the pipeline `generate` runs, minus config validation and
+//! fetching, several times over one config and times each phase:
+//!
+//! - `deploy`: load the runtime code, build the genesis transactions and
+//!   deploy the system contracts into a fresh database
+//! - `execute`: run the genesis transactions
+//! - `emit`: write genesis.json and the other outputs to a scratch directory
+//! - `verify`: the post-genesis checks
+//!
+//! The report holds each phase's min, median, mean and max and doubles as a
+//! baseline: compared with an earlier report, a phase whose median grew by
+//! more than the allowed percentage (and by at least [`MIN_REGRESSION_MS`]) is
+//! a regression, so slowdowns of the REVM pipeline show up before configs with
+//! hundreds of validators hit them.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+use crate::{
+    execute::{self, BuildInputs, BuildState},
+    genesis::{self, GenesisConfig},
+    post_genesis,
+    registry::ContractsManifest,
+};
+
+/// Median growth below this is scheduling noise rather than a regression
+pub const MIN_REGRESSION_MS: f64 = 5.0;
+
+/// Phases in pipeline order
+const PHASES: [&str; 4] = ["deploy", "execute", "emit", "verify"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseStats {
+    pub phase: String,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+}
+
+impl PhaseStats {
+    fn new(phase: &str, samples: &[Duration]) -> Self {
+        let mut ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+        ms.sort_by(f64::total_cmp);
+        let mid = ms.len() / 2;
+        let median = if ms.len().is_multiple_of(2) { (ms[mid - 1] + ms[mid]) / 2.0 } else { ms[mid] };
+        PhaseStats {
+            phase: phase.to_string(),
+            min_ms: ms[0],
+            median_ms: median,
+            mean_ms: ms.iter().sum::<f64>() / ms.len() as f64,
+            max_ms: ms[ms.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchReport {
+    pub tool_version: String,
+    pub config: String,
+    pub validators: usize,
+    pub transactions: usize,
+    pub iterations: usize,
+    pub phases: Vec<PhaseStats>,
+}
+
+impl BenchReport {
+    pub fn log(&self) {
+        info!(
+            "Benchmark of {} ({} validator(s), {} transaction(s)) over {} iteration(s):",
+            self.config, self.validators, self.transactions, self.iterations
+        );
+        for stats in &self.phases {
+            info!(
+                "  {:<8} median {:>10.2} ms  (min {:.2}, mean {:.2}, max {:.2})",
+                stats.phase, stats.median_ms, stats.min_ms, stats.mean_ms, stats.max_ms
+            );
+        }
+    }
+
+    /// Phases whose median grew by more than `max_regression_pct` percent over
+    /// `baseline`, described for the log
+    pub fn regressions(&self, baseline: &BenchReport, max_regression_pct: f64) -> Vec<String> {
+        if baseline.validators != self.validators || baseline.transactions != self.transactions {
+            warn!(
+                "Baseline ran {} validator(s) and {} transaction(s), this run {} and {}; timings are not comparable",
+                baseline.validators, baseline.transactions, self.validators, self.transactions
+            );
+        }
+        let mut regressions = Vec::new();
+        for stats in &self.phases {
+            let Some(base) = baseline.phases.iter().find(|b| b.phase == stats.phase) else {
+                continue;
+            };
+            let limit = base.median_ms * (1.0 + max_regression_pct / 100.0);
+            if stats.median_ms > limit && stats.median_ms - base.median_ms >= MIN_REGRESSION_MS {
+                regressions.push(format!(
+                    "{}: median {:.2} ms, baseline {:.2} ms (+{:.0}%, limit +{}%)",
+                    stats.phase,
+                    stats.median_ms,
+                    base.median_ms,
+                    (stats.median_ms / base.median_ms - 1.0) * 100.0,
+                    max_regression_pct
+                ));
+            }
+        }
+        regressions
+    }
+}
+
+/// Time one run of the pipeline, writing the outputs to `output_dir`
+fn run_once(
+    byte_code_dir: &str,
+    contracts: &ContractsManifest,
+    config: &GenesisConfig,
+    output_dir: &str,
+    timings: &mut HashMap<&'static str, Vec<Duration>>,
+) -> Result<usize> {
+    let mut time = |phase: &'static str, started: Instant| {
+        timings.entry(phase).or_default().push(started.elapsed());
+    };
+
+    let started = Instant::now();
+    let inputs = BuildInputs::prepare(byte_code_dir, contracts, config, None)?;
+    let db = execute::deployment_db(contracts, &inputs);
+    time("deploy", started);
+
+    let started = Instant::now();
+    let bundle_state = execute::execute_transactions(&db, config, &inputs.transactions)?;
+    time("execute", started);
+
+    let transactions = inputs.transactions.len();
+    let mut state = BuildState {
+        inputs_hash: inputs.hash,
+        byte_code_dir: byte_code_dir.to_string(),
+        contracts: contracts.clone(),
+        runtime_code: inputs.runtime_code,
+        total_stake: inputs.total_stake,
+        call_funding: inputs.call_funding,
+        evm_spec: config.evm_spec,
+        dynamic_abi: inputs.dynamic_abi,
+        bundle_state,
+        extra_accounts: HashMap::new(),
+    };
+    state.set_extra_accounts(&genesis::extra_alloc_accounts(config)?);
+    let started = Instant::now();
+    let genesis = execute::assemble_genesis(&state, config, output_dir, true)?;
+    time("emit", started);
+
+    let started = Instant::now();
+    post_genesis::verify_result(&genesis, config);
+    time("verify", started);
+    Ok(transactions)
+}
+
+/// Run the pipeline `iterations` times over `config_file` and summarize each phase
+pub fn run_bench(
+    byte_code_dir: &str,
+    config_file: &str,
+    iterations: usize,
+    work_dir: &Path,
+) -> Result<BenchReport> {
+    if iterations == 0 {
+        anyhow::bail!("--iterations must be at least 1");
+    }
+    let config = genesis::load_genesis_config_with_overrides(config_file, &[])?;
+    let contracts = ContractsManifest::resolve(byte_code_dir, None)?;
+    contracts.preflight(byte_code_dir)?;
+    fs::create_dir_all(work_dir).context(format!("Failed to create {}", work_dir.display()))?;
+    let output_dir = work_dir.to_str().context("work directory is not valid UTF-8")?;
+
+    let mut timings = HashMap::new();
+    let mut transactions = 0;
+    for i in 0..iterations {
+        info!("=== Benchmark iteration {} of {} ===", i + 1, iterations);
+        transactions = run_once(byte_code_dir, &contracts, &config, output_dir, &mut timings)?;
+    }
+    Ok(BenchReport {
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        config: config_file.to_string(),
+        validators: config.validators.len(),
+        transactions,
+        iterations,
+        phases: PHASES.iter().map(|phase| PhaseStats::new(phase, &timings[phase])).collect(),
+    })
+}
+
+pub fn load_report(path: &str) -> Result<BenchReport> {
+    let content = fs::read_to_string(path).context(format!("Failed to read {}", path))?;
+    serde_json::from_str(&content).context(format!("Failed to decode benchmark report {}", path))
+}
+
+pub fn write_report(report: &BenchReport, path: &str) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(report)? + "\n").context(format!("Failed to write {}", path))
+}
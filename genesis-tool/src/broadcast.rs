@@ -0,0 +1,197 @@
+//! Replay of a forge script broadcast into genesis
+//!
+//! `replayBroadcast` (or `generate --replay-broadcast`) names a forge broadcast
+//! file, e.g. `broadcast/Deploy.s.sol/1/run-latest.json`. Its transactions run
+//! after `Genesis.initialize`, the `postGenesisCalls` and the vesting
+//! deployments, in the recorded order, each sent by its recorded `from` with a
+//! zero gas price; a sender is credited the value it sends, like hook callers.
+//! So a whole protocol deployment is baked into genesis deterministically.
+//!
+//! Contract addresses follow from sender nonces, so every sender has to start
+//! at nonce 0 and send nothing else at genesis, and the recorded nonces and
+//! `contractAddress`es are checked against the replay before execution. A
+//! broadcast that deploys through the CREATE2 factory gets the factory first,
+//! created the way it is on public chains, at [`CREATE2_FACTORY`].
+
+use alloy_primitives::{address, hex, keccak256};
+use anyhow::{Context, Result};
+use revm_primitives::{Address, Bytes, TxEnv, TxKind, U256};
+use serde::Deserialize;
+use std::{collections::HashMap, fs};
+use tracing::warn;
+
+use crate::{execute::scaffolding_accounts, genesis::GenesisConfig};
+
+/// Deterministic deployment proxy forge sends `CREATE2` deployments through
+pub const CREATE2_FACTORY: Address = address!("4e59b44847b379578588920ca78fbf26c0b4956c");
+
+/// Sender of the factory's presigned deployment, at nonce 0
+const CREATE2_FACTORY_DEPLOYER: Address = address!("3fab184622dc19b6109349b94811493bf2a45362");
+
+/// Creation code of [`CREATE2_FACTORY`]
+const CREATE2_FACTORY_INIT_CODE: &str = "604580600e600039806000f350fe7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf3";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Broadcast {
+    pub transactions: Vec<BroadcastTransaction>,
+    /// Chain ID the script ran against
+    #[serde(default)]
+    pub chain: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastTransaction {
+    /// `CREATE`, `CREATE2` or `CALL`
+    pub transaction_type: String,
+    #[serde(default)]
+    pub contract_name: Option<String>,
+    #[serde(default)]
+    pub contract_address: Option<Address>,
+    pub transaction: RecordedTransaction,
+    /// Contracts the transaction created besides its own target
+    #[serde(default)]
+    pub additional_contracts: Vec<AdditionalContract>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecordedTransaction {
+    pub from: Address,
+    #[serde(default)]
+    pub to: Option<Address>,
+    #[serde(default)]
+    pub value: Option<U256>,
+    /// `data` in broadcasts of older forge versions
+    #[serde(alias = "data", default)]
+    pub input: Bytes,
+    #[serde(default)]
+    pub nonce: Option<U256>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdditionalContract {
+    pub address: Address,
+}
+
+pub fn load_broadcast(path: &str) -> Result<Broadcast> {
+    let content = fs::read_to_string(path).context(format!("Failed to read broadcast {}", path))?;
+    serde_json::from_str(&content).context(format!("Failed to decode forge broadcast {}", path))
+}
+
+fn replay_txn(caller: Address, transact_to: TxKind, value: U256, data: Bytes) -> TxEnv {
+    TxEnv {
+        caller,
+        gas_limit: u64::MAX,
+        gas_price: U256::ZERO,
+        transact_to,
+        value,
+        data,
+        ..Default::default()
+    }
+}
+
+/// Where a recorded `CREATE2` lands: the factory takes the salt followed by
+/// the init code
+fn create2_address(input: &[u8]) -> Option<Address> {
+    (input.len() >= 32).then(|| {
+        let (salt, init_code) = input.split_at(32);
+        CREATE2_FACTORY.create2(<[u8; 32]>::try_from(salt).unwrap(), keccak256(init_code))
+    })
+}
+
+/// Transactions of the config's broadcast, checked against their recorded
+/// nonces and contract addresses; empty when none is set
+pub fn replay_txns(config: &GenesisConfig) -> Result<Vec<TxEnv>> {
+    let Some(path) = &config.replay_broadcast else {
+        return Ok(Vec::new());
+    };
+    let broadcast = load_broadcast(path)?;
+    if let Some(chain) = broadcast.chain.filter(|chain| *chain != config.chain_id) {
+        warn!(
+            "{} was recorded on chain {}, genesis is chain {}; code reading block.chainid may behave differently",
+            path, chain, config.chain_id
+        );
+    }
+
+    let scaffolding = scaffolding_accounts(config);
+    let mut nonces: HashMap<Address, u64> = HashMap::new();
+    let mut txns = Vec::new();
+    for (i, recorded) in broadcast.transactions.iter().enumerate() {
+        let label = format!(
+            "{} transaction {} ({})",
+            path,
+            i,
+            recorded.contract_name.as_deref().unwrap_or(&recorded.transaction_type)
+        );
+        let tx = &recorded.transaction;
+        if scaffolding.contains_key(&tx.from) {
+            anyhow::bail!("{}: sender {:?} also sends genesis transactions", label, tx.from);
+        }
+        let nonce = nonces.entry(tx.from).or_insert(0);
+        if let Some(recorded_nonce) = tx.nonce.filter(|n| *n != U256::from(*nonce)) {
+            anyhow::bail!(
+                "{}: recorded with nonce {}, replays at nonce {}; broadcast from a fresh sender so contract addresses match",
+                label,
+                recorded_nonce,
+                nonce
+            );
+        }
+        let (transact_to, expected) = match (recorded.transaction_type.as_str(), tx.to) {
+            ("CREATE", _) | (_, None) => (TxKind::Create, Some(tx.from.create(*nonce))),
+            ("CREATE2", Some(to)) => (TxKind::Call(to), create2_address(&tx.input)),
+            (_, Some(to)) => (TxKind::Call(to), None),
+        };
+        if let (Some(recorded_address), Some(expected)) = (recorded.contract_address, expected) {
+            if recorded_address != expected {
+                anyhow::bail!(
+                    "{}: recorded contract address {:?}, replay creates {:?}",
+                    label,
+                    recorded_address,
+                    expected
+                );
+            }
+        }
+        *nonce += 1;
+        txns.push(replay_txn(
+            tx.from,
+            transact_to,
+            tx.value.unwrap_or_default(),
+            tx.input.clone(),
+        ));
+    }
+
+    if txns.iter().any(|tx| tx.transact_to == TxKind::Call(CREATE2_FACTORY)) {
+        let init_code = hex::decode(CREATE2_FACTORY_INIT_CODE).expect("valid factory init code");
+        txns.insert(
+            0,
+            replay_txn(
+                CREATE2_FACTORY_DEPLOYER,
+                TxKind::Create,
+                U256::ZERO,
+                Bytes::from(init_code),
+            ),
+        );
+    }
+    Ok(txns)
+}
+
+/// Contracts the config's broadcast recorded as created, with their names
+pub fn replayed_contracts(config: &GenesisConfig) -> Result<Vec<(String, Address)>> {
+    let Some(path) = &config.replay_broadcast else {
+        return Ok(Vec::new());
+    };
+    let mut contracts = Vec::new();
+    for recorded in load_broadcast(path)?.transactions {
+        let name = recorded.contract_name.unwrap_or_else(|| "(unnamed)".to_string());
+        if recorded.transaction_type.starts_with("CREATE") {
+            if let Some(address) = recorded.contract_address {
+                contracts.push((name.clone(), address));
+            }
+        }
+        for additional in recorded.additional_contracts {
+            contracts.push((format!("created by {}", name), additional.address));
+        }
+    }
+    Ok(contracts)
+}
@@ -0,0 +1,368 @@
+//! Runtime bytecode post-processing before deployment
+//!
+//! solc leaves immutables as zeroed 32-byte slots in the runtime code and
+//! records their offsets in the artifact's `immutableReferences`, keyed by the
+//! AST id of the variable. Since genesis places runtime code directly instead of
+//! running constructors, those slots are filled here from the contract's
+//! `immutables` entry in the contracts manifest.
+//!
+//! Unlinked artifacts carry 40-character library placeholders in their hex,
+//! either `__$<first 17 bytes of keccak256(fully qualified name)>$__` (solc
+//! 0.5+) or the legacy `__<name padded with _>__`. They are replaced with the
+//! addresses from the manifest's `libraries` section before decoding.
+//!
+//! Genesis alloc is not subject to the EIP-170 runtime or EIP-3860 initcode
+//! size limits, but code over them could never be redeployed by a transaction,
+//! so oversized contracts are flagged at deployment.
+//!
+//! Code comparisons can ignore the CBOR metadata solc appends to runtime code
+//! (compiler version, source hashes), which changes without any change in
+//! behaviour.
+//!
+//! A `.hex` exported from `bytecode.object` instead of `deployedBytecode.object`
+//! holds creation code, which deploys fine but only ever runs its constructor.
+//! [`is_creation_code`] tells the two apart by running the code as initcode.
+
+use alloy_primitives::{keccak256, Address, U256};
+use anyhow::{Context, Result};
+use revm::{EvmBuilder, InMemoryDB};
+use revm_primitives::{
+    hex, Bytes, Env, ExecutionResult, Output, TxKind, MAX_CODE_SIZE, MAX_INITCODE_SIZE,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+};
+use tracing::{info, warn};
+
+/// solc only emits immutables as full words
+const IMMUTABLE_SIZE: usize = 32;
+/// Placeholders take the place of a 20-byte address in hex
+const PLACEHOLDER_LEN: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImmutablesConfig {
+    /// solc or forge artifact JSON holding `deployedBytecode.immutableReferences`
+    pub artifact: String,
+    /// Value per immutable, keyed by AST id or variable name: hex (left-padded
+    /// to 32 bytes), decimal, `true` or `false`
+    pub values: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImmutableReference {
+    start: usize,
+    length: usize,
+}
+
+/// `deployedBytecode` of a forge artifact, or `evm.deployedBytecode` of solc output
+fn deployed_bytecode(artifact: &Value) -> Option<&Value> {
+    artifact
+        .get("deployedBytecode")
+        .or_else(|| artifact.get("evm")?.get("deployedBytecode"))
+}
+
+/// Length of the creation code in an artifact, when it carries one
+pub fn artifact_initcode_len(path: &str) -> Result<Option<usize>> {
+    let content = fs::read_to_string(path).context(format!("Failed to read artifact {}", path))?;
+    let artifact: Value =
+        serde_json::from_str(&content).context(format!("Failed to parse artifact {}", path))?;
+    let object = artifact
+        .get("bytecode")
+        .or_else(|| artifact.get("evm")?.get("bytecode"))
+        .and_then(|bytecode| bytecode.get("object"))
+        .and_then(Value::as_str);
+    Ok(object.map(|o| o.strip_prefix("0x").unwrap_or(o).len() / 2))
+}
+
+/// EIP-170 and EIP-3860 violations; without the creation code, the runtime
+/// code it would have to embed is used as a lower bound for the initcode
+pub fn code_size_violations(runtime_len: usize, initcode_len: Option<usize>) -> Vec<String> {
+    let mut violations = Vec::new();
+    if runtime_len > MAX_CODE_SIZE {
+        violations.push(format!(
+            "runtime code is {} bytes, over the EIP-170 limit of {}",
+            runtime_len, MAX_CODE_SIZE
+        ));
+    }
+    match initcode_len {
+        Some(len) if len > MAX_INITCODE_SIZE => violations.push(format!(
+            "initcode is {} bytes, over the EIP-3860 limit of {}",
+            len, MAX_INITCODE_SIZE
+        )),
+        None if runtime_len > MAX_INITCODE_SIZE => violations.push(format!(
+            "initcode is at least {} bytes, over the EIP-3860 limit of {}",
+            runtime_len, MAX_INITCODE_SIZE
+        )),
+        _ => {}
+    }
+    violations
+}
+
+/// Names of immutable state variables by AST id, when the artifact carries its AST
+fn immutable_names(node: &Value, names: &mut HashMap<String, String>) {
+    match node {
+        Value::Object(map) => {
+            if map.get("nodeType").and_then(Value::as_str) == Some("VariableDeclaration")
+                && map.get("mutability").and_then(Value::as_str) == Some("immutable")
+            {
+                if let (Some(id), Some(name)) = (
+                    map.get("id").and_then(Value::as_u64),
+                    map.get("name").and_then(Value::as_str),
+                ) {
+                    names.insert(id.to_string(), name.to_string());
+                }
+            }
+            map.values().for_each(|v| immutable_names(v, names));
+        }
+        Value::Array(items) => items.iter().for_each(|v| immutable_names(v, names)),
+        _ => {}
+    }
+}
+
+fn parse_immutable_value(value: &str) -> Result<[u8; IMMUTABLE_SIZE]> {
+    let word = match value.trim() {
+        "true" => U256::from(1),
+        "false" => U256::ZERO,
+        v if v.starts_with("0x") => {
+            let bytes = hex::decode(&v[2..]).context(format!("Invalid hex value {}", v))?;
+            if bytes.len() > IMMUTABLE_SIZE {
+                anyhow::bail!("Value {} is longer than 32 bytes", v);
+            }
+            U256::from_be_slice(&bytes)
+        }
+        v => v
+            .parse::<U256>()
+            .map_err(|e| anyhow::anyhow!("Invalid value {}: {}", v, e))?,
+    };
+    Ok(word.to_be_bytes())
+}
+
+/// Fill every immutable slot of `code`; fails unless each reference in the
+/// artifact gets exactly one configured value
+pub fn patch_immutables(code: &mut [u8], config: &ImmutablesConfig) -> Result<()> {
+    let content = fs::read_to_string(&config.artifact)
+        .context(format!("Failed to read artifact {}", config.artifact))?;
+    let artifact: Value = serde_json::from_str(&content)
+        .context(format!("Failed to parse artifact {}", config.artifact))?;
+    let deployed = deployed_bytecode(&artifact)
+        .ok_or_else(|| anyhow::anyhow!("Artifact {} has no deployedBytecode", config.artifact))?;
+    let references: BTreeMap<String, Vec<ImmutableReference>> = deployed
+        .get("immutableReferences")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .context(format!(
+            "Invalid immutableReferences in {}",
+            config.artifact
+        ))?
+        .unwrap_or_default();
+
+    // Catch artifacts that do not belong to the deployed code
+    if let Some(object) = deployed.get("object").and_then(Value::as_str) {
+        let object = object.strip_prefix("0x").unwrap_or(object);
+        if !object.contains("__") && object.len() / 2 != code.len() {
+            anyhow::bail!(
+                "Artifact {} describes {} bytes of runtime code, but the deployed code is {} bytes",
+                config.artifact,
+                object.len() / 2,
+                code.len()
+            );
+        }
+    }
+
+    let mut names = HashMap::new();
+    if let Some(ast) = artifact.get("ast") {
+        immutable_names(ast, &mut names);
+    }
+    let describe = |id: &str| match names.get(id) {
+        Some(name) => format!("{} (AST id {})", name, id),
+        None => format!("AST id {}", id),
+    };
+
+    let mut values = HashMap::new();
+    for (key, value) in &config.values {
+        let id = if references.contains_key(key) {
+            key.clone()
+        } else {
+            let mut matching = names.iter().filter(|(_, name)| *name == key);
+            match (matching.next(), matching.next()) {
+                (Some((id, _)), None) if references.contains_key(id) => id.clone(),
+                (Some(_), Some(_)) => anyhow::bail!(
+                    "Immutable name {} is ambiguous in {}; use the AST id",
+                    key,
+                    config.artifact
+                ),
+                _ => anyhow::bail!("{} has no immutable reference {}", config.artifact, key),
+            }
+        };
+        let word = parse_immutable_value(value)
+            .context(format!("Immutable {} in {}", key, config.artifact))?;
+        if values.insert(id.clone(), word).is_some() {
+            anyhow::bail!("Immutable {} is configured more than once", describe(&id));
+        }
+    }
+
+    let missing: Vec<String> = references
+        .keys()
+        .filter(|id| !values.contains_key(*id))
+        .map(|id| describe(id))
+        .collect();
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "No value configured for immutable(s) of {}: {}",
+            config.artifact,
+            missing.join(", ")
+        );
+    }
+
+    for (id, offsets) in &references {
+        let word = &values[id];
+        for offset in offsets {
+            let end = offset.start + offset.length;
+            if offset.length != IMMUTABLE_SIZE || end > code.len() {
+                anyhow::bail!(
+                    "Immutable {} reference {}..{} does not fit the {}-byte code",
+                    describe(id),
+                    offset.start,
+                    end,
+                    code.len()
+                );
+            }
+            let slot = &mut code[offset.start..end];
+            if slot.iter().any(|b| *b != 0) && slot != word.as_slice() {
+                warn!(
+                    "Immutable {} at offset {} already holds 0x{}; overwriting",
+                    describe(id),
+                    offset.start,
+                    hex::encode(&slot)
+                );
+            }
+            slot.copy_from_slice(word);
+        }
+        info!(
+            "Patched immutable {} at {} offset(s) with 0x{}",
+            describe(id),
+            offsets.len(),
+            hex::encode(word)
+        );
+    }
+    Ok(())
+}
+
+/// Placeholders a library may appear as, for its fully qualified `file:Name`
+fn library_placeholders(name: &str) -> [String; 2] {
+    let hash = keccak256(name.as_bytes());
+    let legacy: String = name.chars().take(PLACEHOLDER_LEN - 4).collect();
+    [
+        format!("__${}$__", hex::encode(&hash[..17])),
+        format!("__{:_<36}__", legacy),
+    ]
+}
+
+/// Substitute library placeholders in hex bytecode; fails on any placeholder
+/// without a configured library
+pub fn link_libraries(code_hex: &str, libraries: &BTreeMap<String, Address>) -> Result<String> {
+    if !code_hex.contains("__") {
+        return Ok(code_hex.to_string());
+    }
+    let mut addresses = HashMap::new();
+    for (name, address) in libraries {
+        for placeholder in library_placeholders(name) {
+            addresses.insert(placeholder, hex::encode(address));
+        }
+    }
+
+    let mut linked = String::with_capacity(code_hex.len());
+    let mut unresolved = Vec::new();
+    let mut rest = code_hex;
+    while let Some(pos) = rest.find("__") {
+        linked.push_str(&rest[..pos]);
+        let Some(placeholder) = rest
+            .get(pos..pos + PLACEHOLDER_LEN)
+            .filter(|p| p.ends_with("__"))
+        else {
+            anyhow::bail!(
+                "Malformed library placeholder at hex offset {}",
+                code_hex.len() - rest.len() + pos
+            );
+        };
+        match addresses.get(placeholder) {
+            Some(address) => linked.push_str(address),
+            None => {
+                unresolved.push(placeholder.to_string());
+                linked.push_str(placeholder);
+            }
+        }
+        rest = &rest[pos + PLACEHOLDER_LEN..];
+    }
+    linked.push_str(rest);
+
+    if !unresolved.is_empty() {
+        unresolved.sort();
+        unresolved.dedup();
+        anyhow::bail!(
+            "Unresolved library link reference(s) {}; add the fully qualified library name to `libraries`",
+            unresolved.join(", ")
+        );
+    }
+    Ok(linked)
+}
+
+/// Runtime code without the trailing solc CBOR metadata, when present
+pub fn strip_metadata(code: &[u8]) -> &[u8] {
+    let Some(len_bytes) = code.len().checked_sub(2).map(|i| &code[i..]) else {
+        return code;
+    };
+    let cbor_len = u16::from_be_bytes([len_bytes[0], len_bytes[1]]) as usize;
+    let Some(start) = code.len().checked_sub(cbor_len + 2) else {
+        return code;
+    };
+    // The metadata is a CBOR map: 0xa0..=0xb7 for up to 23 entries
+    match code.get(start) {
+        Some(0xa0..=0xb7) if cbor_len > 0 => &code[..start],
+        _ => code,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeComparison {
+    Identical,
+    /// Only the trailing solc metadata differs
+    EquivalentModuloMetadata,
+    Different,
+}
+
+pub fn compare_code(a: &[u8], b: &[u8]) -> CodeComparison {
+    if a == b {
+        CodeComparison::Identical
+    } else if strip_metadata(a) == strip_metadata(b) {
+        CodeComparison::EquivalentModuloMetadata
+    } else {
+        CodeComparison::Different
+    }
+}
+
+/// Whether `code` is creation rather than runtime code. Run as initcode with no
+/// calldata, solc creation code returns the runtime code; runtime code stops,
+/// reverts in its dispatcher or returns nothing.
+pub fn is_creation_code(code: &[u8]) -> bool {
+    let mut env = Env::default();
+    // Probe oversized contracts too
+    env.cfg.limit_contract_code_size = Some(4 * MAX_CODE_SIZE);
+    env.tx.transact_to = TxKind::Create;
+    env.tx.data = Bytes::copy_from_slice(code);
+    let mut evm = EvmBuilder::default()
+        .with_db(InMemoryDB::default())
+        .with_env(Box::new(env))
+        .build();
+    matches!(
+        evm.transact().map(|result| result.result),
+        Ok(ExecutionResult::Success {
+            output: Output::Create(runtime, _),
+            ..
+        }) if !runtime.is_empty()
+    )
+}
@@ -0,0 +1,96 @@
+//! Content-addressed cache of system contract runtime code
+//!
+//! Every `generate` (and every `watch` rebuild) links libraries into and
+//! patches immutables of 20+ artifacts. The resulting runtime code is stored
+//! under `~/.cache/genesis-tool/runtime` (or `$XDG_CACHE_HOME/genesis-tool`),
+//! keyed by the sha256 of the `.hex` file contents, the manifest's libraries,
+//! the contract's immutable values and the artifact they are patched from, so
+//! any change to those inputs misses the cache. `generate --no-cache` bypasses
+//! it. A cache that cannot be read or written only costs the recomputation.
+
+use crate::{execute::load_runtime_bytecode, registry::SystemContract};
+use alloy_primitives::Address;
+use anyhow::{Context, Result};
+use revm_primitives::hex;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tracing::debug;
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Compute runtime code from the artifacts for the rest of the process
+pub fn disable() {
+    DISABLED.store(true, Ordering::Relaxed);
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+    };
+    Some(base.join("genesis-tool").join("runtime"))
+}
+
+/// sha256 over everything [`load_runtime_bytecode`] reads for `contract`
+fn cache_key(
+    byte_code_dir: &str,
+    contract: &SystemContract,
+    libraries: &BTreeMap<String, Address>,
+) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    let hex_path = contract.hex_path(byte_code_dir);
+    hasher.update(fs::read(&hex_path).context(format!("Failed to read {}", hex_path))?);
+    hasher.update(serde_json::to_vec(libraries)?);
+    if let Some(immutables) = &contract.immutables {
+        hasher.update(serde_json::to_vec(immutables)?);
+        hasher.update(
+            fs::read(&immutables.artifact)
+                .context(format!("Failed to read artifact {}", immutables.artifact))?,
+        );
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Runtime code of `contract` from the cache, or loaded from its artifact and
+/// stored for the next run
+pub fn runtime_code(
+    byte_code_dir: &str,
+    contract: &SystemContract,
+    libraries: &BTreeMap<String, Address>,
+) -> Result<Vec<u8>> {
+    let entry = match (DISABLED.load(Ordering::Relaxed), cache_dir()) {
+        (false, Some(dir)) => cache_key(byte_code_dir, contract, libraries)
+            .ok()
+            .map(|key| dir.join(format!("{}.hex", key))),
+        _ => None,
+    };
+    let Some(entry) = entry else {
+        return load_runtime_bytecode(byte_code_dir, contract, libraries);
+    };
+    if let Some(code) = fs::read_to_string(&entry)
+        .ok()
+        .and_then(|cached| hex::decode(cached.trim()).ok())
+    {
+        debug!("Runtime code of {} from cache {}", contract.name, entry.display());
+        return Ok(code);
+    }
+
+    let code = load_runtime_bytecode(byte_code_dir, contract, libraries)?;
+    // Written under a temporary name first so a concurrent run never reads a
+    // partial entry
+    let stored = entry.parent().map(fs::create_dir_all).transpose().and_then(|_| {
+        let partial = entry.with_extension(format!("{}.partial", std::process::id()));
+        fs::write(&partial, hex::encode(&code))?;
+        fs::rename(&partial, &entry)
+    });
+    if let Err(e) = stored {
+        debug!("Failed to cache runtime code of {} at {}: {}", contract.name, entry.display(), e);
+    }
+    Ok(code)
+}
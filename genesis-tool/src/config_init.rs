@@ -0,0 +1,163 @@
+//! `config init` scaffolding
+//!
+//! Writes a commented template genesis config so new chain operators don't have to
+//! reverse-engineer the schema from the `GenesisConfig` structs. Comments are carried
+//! in `_comment` keys (ignored by the deserializer), matching the files in `config/`.
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use std::{fs, path::Path};
+use tracing::info;
+
+use crate::{genesis::GenesisConfig, utils::JWK_MANAGER_ADDR};
+
+/// 2^63: one half in the 64.64 fixed-point format used by RandomnessConfig
+const FIXED_POINT_ONE_HALF: u128 = 1 << 63;
+/// floor(2^64 * 2 / 3): two thirds in the 64.64 fixed-point format
+const FIXED_POINT_TWO_THIRDS: u128 = 12297829382473034410;
+
+/// Build a commented template config with `validator_count` placeholder validators
+pub fn config_template(validator_count: usize, chain_id: u64) -> Value {
+    let validators: Vec<Value> = (1..=validator_count)
+        .map(|i| {
+            let id = format!("{:x}", i);
+            json!({
+                "_comment": format!(
+                    "PLACEHOLDER validator-{} — replace operator/owner/staker/consensusPubkey/consensusPop/network before genesis.",
+                    i
+                ),
+                "operator": format!("0x{:0>40}", id),
+                "owner": format!("0x{:0>40}", id),
+                "staker": format!("0x{:0>40}", id),
+                "stakeAmount": "100000000000000000000",
+                "moniker": format!("validator-{}", i),
+                "consensusPubkey": format!("0x{:0>96}", id),
+                "consensusPop": format!("0x{}", "0".repeat(192)),
+                "networkAddresses": format!("/ip4/127.0.0.1/tcp/6180/noise-ik/{:0>64}/handshake/0", id),
+                "fullnodeAddresses": format!("/ip4/127.0.0.1/tcp/6182/noise-ik/{:0>64}/handshake/0", id),
+                "votingPower": "100000000000000000000"
+            })
+        })
+        .collect();
+
+    json!({
+        "_comment": "Genesis config for Genesis.initialize(GenesisInitParams), generated by `genesis-tool config init`. All 'PLACEHOLDER' values must be finalized before genesis. Amounts are in wei unless given a unit (\"1_000 ether\", \"5 gwei\"); hex and scientific notation (\"1e18\") are accepted. Durations are in microseconds.",
+
+        "chainId": chain_id,
+
+        "validatorConfig": {
+            "_comment": "ValidatorConfig.initialize params. Bonds in wei; votingPowerIncreaseLimitPct caps per-epoch voting power growth.",
+            "minimumBond": "1000000000000000000",
+            "maximumBond": "1000000000000000000000000",
+            "unbondingDelayMicros": 604800000000u64,
+            "allowValidatorSetChange": true,
+            "votingPowerIncreaseLimitPct": 20,
+            "maxValidatorSetSize": "100",
+            "autoEvictEnabled": false,
+            "autoEvictThresholdPct": 0
+        },
+
+        "stakingConfig": {
+            "_comment": "StakingConfig.initialize params - for governance staking. 1 day lockup and unbonding delay.",
+            "minimumStake": "1000000000000000000",
+            "lockupDurationMicros": 86400000000u64,
+            "unbondingDelayMicros": 86400000000u64
+        },
+
+        "governanceConfig": {
+            "_comment": "GovernanceConfig.initialize params. requiredProposerStake should clear minimumStake comfortably; 7 day voting period.",
+            "minVotingThreshold": "1000000000000000000",
+            "requiredProposerStake": "10000000000000000000",
+            "votingDurationMicros": 604800000000u64
+        },
+
+        "_comment_governanceOwner": "PLACEHOLDER: owner address for the Governance contract (manages executor set). REQUIRED; must be non-zero.",
+        "governanceOwner": "0x0000000000000000000000000000000000000001",
+
+        "_comment_epochIntervalMicros": "2 hours",
+        "epochIntervalMicros": 7200000000u64,
+
+        "majorVersion": 1,
+
+        "_comment_consensusConfig": "BCS-encoded OnChainConsensusConfig (hex). Alternatively set consensusConfigStruct, e.g. {\"v4\": {\"alg\": {\"jolteonV2\": {...}}, \"vtxn\": {...}, \"windowSize\": null}}, which is encoded into this field.",
+        "consensusConfig": "0x0301010a00000000000000280000000000000001010000000a000000000000000100010200000000000000000020000000000000",
+
+        "_comment_executionConfig": "BCS-encoded OnChainExecutionConfig (hex)",
+        "executionConfig": "0x00",
+
+        "randomnessConfig": {
+            "_comment": "RandomnessConfig - variant: 0=Off, 1=V2. Thresholds are fixed-point (value / 2^64): 1/2 secrecy, 2/3 reconstruction.",
+            "variant": 1,
+            "configV2": {
+                "secrecyThreshold": FIXED_POINT_ONE_HALF,
+                "reconstructionThreshold": FIXED_POINT_TWO_THIRDS,
+                "fastPathSecrecyThreshold": FIXED_POINT_TWO_THIRDS
+            }
+        },
+
+        "oracleConfig": {
+            "_comment": "NativeOracle.initialize - sourceType 1 = JWK, 0 = Blockchain. callbacks are parallel to sourceTypes.",
+            "sourceTypes": [1],
+            "callbacks": [JWK_MANAGER_ADDR.to_checksum(None)],
+            "bridgeConfig": {
+                "deploy": false,
+                "trustedBridge": "",
+                "trustedSourceId": ""
+            },
+            "tasks": [],
+            "onDemandTasks": []
+        },
+
+        "jwkConfig": {
+            "_comment": "JWKManager.initialize - hex-encoded issuer URLs and one JWK list per issuer",
+            "issuers": [],
+            "jwks": []
+        },
+
+        "_initialLockedUntilMicrosComment": "PLACEHOLDER: lockup expiry for genesis stake pools, as launch_ts_seconds_utc * 1_000_000 + lockup.",
+        "initialLockedUntilMicros": 1798848000000000u64,
+
+        "_comment_genesisTimestampSecs": "Optional genesis block timestamp (Unix seconds); falls back to the template default when null.",
+        "genesisTimestampSecs": null,
+
+        "_comment_evmSpec": "Optional hardfork genesis is executed under (e.g. \"cancun\"); should match what greth enables at block 0. Defaults to revm's latest when null.",
+        "evmSpec": null,
+
+        "_comment_extraAlloc": "Optional extra genesis accounts: { \"0xAddress\": { \"balance\": \"1000 ether\", \"nonce\": 0, \"code\": \"0x...\", \"storage\": {} } }. Addresses in the reserved system range 0x...1625F0000-0x...1625F5FFF must also be listed in allowReservedAlloc.",
+        "extraAlloc": {},
+
+        "_validatorsComment": "Initial validator set. votingPower normally equals stakeAmount.",
+        "validators": validators
+    })
+}
+
+/// Write the template to `output`, refusing to overwrite unless `force` is set
+pub fn write_config_template(
+    output: &str,
+    validator_count: usize,
+    chain_id: u64,
+    force: bool,
+) -> Result<()> {
+    let path = Path::new(output);
+    if path.exists() && !force {
+        anyhow::bail!("{} already exists (use --force to overwrite)", output);
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let template = config_template(validator_count, chain_id);
+
+    // The template must always round-trip through the real config schema
+    serde_json::from_value::<GenesisConfig>(template.clone())
+        .context("BUG: generated config template does not match GenesisConfig")?;
+
+    fs::write(path, serde_json::to_string_pretty(&template)? + "\n")
+        .context(format!("Failed to write {}", output))?;
+
+    info!(
+        "Wrote genesis config template with {} placeholder validators to {}",
+        validator_count, output
+    );
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! CSV import of initial validators
+//!
+//! Operators usually collect validator registrations in a spreadsheet. This module
+//! parses such an export into `InitialValidator` entries so it can be merged with
+//! (or replace) the `validators` array of the JSON genesis config.
+//!
+//! Expected header (column order is free, optional columns may be omitted):
+//!
+//! ```text
+//! operator,owner,staker,stakeAmount,moniker,consensusPubkey,consensusPop,networkAddresses,fullnodeAddresses,votingPower
+//! ```
+//!
+//! - `staker` defaults to `owner`
+//! - `fullnodeAddresses` defaults to `networkAddresses`
+//! - `votingPower` defaults to `stakeAmount`
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use tracing::info;
+
+use crate::genesis::{GenesisConfig, InitialValidator};
+
+#[derive(Debug, Deserialize)]
+struct CsvValidatorRow {
+    operator: String,
+    owner: String,
+    #[serde(default)]
+    staker: Option<String>,
+    #[serde(rename = "stakeAmount", alias = "stake")]
+    stake_amount: String,
+    moniker: String,
+    #[serde(rename = "consensusPubkey", alias = "pubkey")]
+    consensus_pubkey: String,
+    #[serde(rename = "consensusPop", alias = "pop")]
+    consensus_pop: String,
+    #[serde(rename = "networkAddresses", alias = "networkAddress")]
+    network_addresses: String,
+    #[serde(rename = "fullnodeAddresses", alias = "fullnodeAddress", default)]
+    fullnode_addresses: Option<String>,
+    #[serde(rename = "votingPower", default)]
+    voting_power: Option<String>,
+}
+
+impl From<CsvValidatorRow> for InitialValidator {
+    fn from(row: CsvValidatorRow) -> Self {
+        let non_empty = |v: Option<String>| v.filter(|s| !s.is_empty());
+        InitialValidator {
+            staker: non_empty(row.staker).unwrap_or_else(|| row.owner.clone()),
+            fullnode_addresses: non_empty(row.fullnode_addresses)
+                .unwrap_or_else(|| row.network_addresses.clone()),
+            voting_power: non_empty(row.voting_power).unwrap_or_else(|| row.stake_amount.clone()),
+            operator: row.operator,
+            owner: row.owner,
+            stake_amount: row.stake_amount,
+            moniker: row.moniker,
+            consensus_pubkey: row.consensus_pubkey,
+            consensus_pop: row.consensus_pop,
+            network_addresses: row.network_addresses,
+        }
+    }
+}
+
+/// 1-based line of the record starting at `byte`. The csv reader counts
+/// neither comment nor blank lines, and places a record at the start of the
+/// comments and blank lines before it, so those are skipped here.
+fn record_line(content: &str, byte: usize) -> usize {
+    let (before, after) = content.split_at(byte.min(content.len()));
+    let skipped = after
+        .lines()
+        .take_while(|line| line.trim().is_empty() || line.starts_with('#'))
+        .count();
+    before.matches('\n').count() + skipped + 1
+}
+
+/// Parse a validators CSV file into `InitialValidator` entries
+pub fn read_validators_csv(path: &str) -> Result<Vec<InitialValidator>> {
+    let content = fs::read_to_string(path)
+        .context(format!("Failed to open validators CSV: {}", path))?;
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .comment(Some(b'#'))
+        .from_reader(content.as_bytes());
+
+    let mut validators = Vec::new();
+    for row in reader.deserialize::<CsvValidatorRow>() {
+        let row = row.map_err(|e| {
+            let line = e
+                .position()
+                .map_or("?".to_string(), |pos| record_line(&content, pos.byte() as usize).to_string());
+            anyhow::Error::new(e).context(format!("Invalid validator row at line {} of {}", line, path))
+        })?;
+        validators.push(row.into());
+    }
+
+    info!("Loaded {} validators from {}", validators.len(), path);
+    Ok(validators)
+}
+
+/// Merge CSV validators into the config.
+///
+/// With `replace`, the JSON `validators` array is discarded. Otherwise CSV rows
+/// override JSON entries with the same consensus pubkey and are appended if new.
+pub fn apply_validators_csv(config: &mut GenesisConfig, path: &str, replace: bool) -> Result<()> {
+    let imported = read_validators_csv(path)?;
+
+    if replace {
+        info!(
+            "Replacing {} config validators with {} from CSV",
+            config.validators.len(),
+            imported.len()
+        );
+        config.validators = imported;
+        return Ok(());
+    }
+
+    for validator in imported {
+        let key = validator.consensus_pubkey.to_lowercase();
+        match config
+            .validators
+            .iter_mut()
+            .find(|v| v.consensus_pubkey.to_lowercase() == key)
+        {
+            Some(existing) => {
+                info!("CSV overrides config validator {}", existing.moniker);
+                *existing = validator;
+            }
+            None => config.validators.push(validator),
+        }
+    }
+    info!("Validator count after CSV merge: {}", config.validators.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\
+# Devnet validators
+moniker,operator,owner,stakeAmount,consensusPubkey,consensusPop,networkAddresses
+
+v1,0x1,0x1,1,0xaa,0xbb,/ip4/127.0.0.1
+# second batch
+# (missing fields)
+v2,0x2,0x2,1,0xcc
+";
+
+    #[test]
+    fn record_line_skips_comments_and_blank_lines() {
+        let v1 = CSV.find("\n\nv1").unwrap() + 1;
+        assert_eq!(record_line(CSV, v1), 4);
+        let v2 = CSV.find("# second").unwrap();
+        assert_eq!(record_line(CSV, v2), 7);
+        assert_eq!(record_line(CSV, 0), 2);
+    }
+
+    #[test]
+    fn invalid_row_reports_its_file_line() {
+        let path = std::env::temp_dir().join(format!("validators-{}.csv", std::process::id()));
+        fs::write(&path, CSV).unwrap();
+        let err = read_validators_csv(path.to_str().unwrap()).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(
+            format!("{:#}", err).starts_with("Invalid validator row at line 7 of "),
+            "{:#}",
+            err
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! State databases for loading genesis allocs
+//!
+//! [`StateStore`] is what alloc loading and verification need from a database:
+//! revm's `DatabaseRef` for execution plus batched account insertion.
+//! `InMemoryDB` is the default. A forked-mainnet alloc can exceed available
+//! memory, so with the `redb` feature [`RedbStore`] keeps the state in an
+//! on-disk redb database instead, selected with `--db-backend redb`.
+
+use alloy_primitives::{Address, U256};
+use anyhow::Result;
+use revm::{DatabaseRef, InMemoryDB};
+use revm_primitives::AccountInfo;
+use std::path::PathBuf;
+
+/// Account info and storage slots of one decoded alloc entry
+pub type DecodedAccount = (Address, AccountInfo, Vec<(U256, U256)>);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DbBackend {
+    /// Everything in memory (default)
+    #[default]
+    Memory,
+    /// On-disk redb database; requires building with `--features redb`
+    Redb,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DbOptions {
+    pub backend: DbBackend,
+    /// Directory for on-disk databases (defaults to the system temp directory)
+    pub dir: Option<PathBuf>,
+}
+
+/// Database that genesis allocs can be loaded into and executed against
+pub trait StateStore: DatabaseRef {
+    /// Insert a batch of accounts with their storage
+    fn insert_accounts(&mut self, accounts: Vec<DecodedAccount>) -> Result<()>;
+
+    fn has_account(&self, address: Address) -> bool {
+        matches!(self.basic_ref(address), Ok(Some(_)))
+    }
+}
+
+impl StateStore for InMemoryDB {
+    fn insert_accounts(&mut self, accounts: Vec<DecodedAccount>) -> Result<()> {
+        for (address, info, storage) in accounts {
+            self.insert_account_info(address, info);
+            for (slot, value) in storage {
+                self.insert_account_storage(address, slot, value)
+                    .expect("Failed to insert storage");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "redb")]
+pub use on_disk::{RedbStore, StoreError};
+
+#[cfg(feature = "redb")]
+mod on_disk {
+    use super::{DecodedAccount, StateStore};
+    use alloy_primitives::{keccak256, Address, B256, U256};
+    use anyhow::{Context, Result};
+    use redb::{Database, ReadableTable, TableDefinition};
+    use revm::DatabaseRef;
+    use revm_primitives::{AccountInfo, Bytecode, KECCAK_EMPTY};
+    use std::{fmt, path::Path};
+
+    /// address => balance (32) | nonce (8) | code hash (32)
+    const ACCOUNTS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("accounts");
+    /// code hash => raw bytecode
+    const CODE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("code");
+    /// address (20) | slot (32) => value (32)
+    const STORAGE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("storage");
+
+    #[derive(Debug, Clone)]
+    pub struct StoreError(pub String);
+
+    impl fmt::Display for StoreError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl std::error::Error for StoreError {}
+
+    fn store_err(e: impl fmt::Display) -> StoreError {
+        StoreError(e.to_string())
+    }
+
+    fn storage_key(address: &Address, slot: &U256) -> [u8; 52] {
+        let mut key = [0u8; 52];
+        key[..20].copy_from_slice(address.as_slice());
+        key[20..].copy_from_slice(&slot.to_be_bytes::<32>());
+        key
+    }
+
+    pub struct RedbStore {
+        db: Database,
+    }
+
+    impl RedbStore {
+        /// Create a fresh database at `path`, replacing any existing file
+        pub fn create(path: &Path) -> Result<Self> {
+            if path.exists() {
+                std::fs::remove_file(path)
+                    .context(format!("Failed to replace {}", path.display()))?;
+            }
+            let db = Database::create(path)
+                .context(format!("Failed to create database {}", path.display()))?;
+            // Create the tables up front so reads never hit a missing table
+            let txn = db.begin_write()?;
+            txn.open_table(ACCOUNTS)?;
+            txn.open_table(CODE)?;
+            txn.open_table(STORAGE)?;
+            txn.commit()?;
+            Ok(Self { db })
+        }
+    }
+
+    impl StateStore for RedbStore {
+        fn insert_accounts(&mut self, accounts: Vec<DecodedAccount>) -> Result<()> {
+            let txn = self.db.begin_write()?;
+            {
+                let mut account_table = txn.open_table(ACCOUNTS)?;
+                let mut code_table = txn.open_table(CODE)?;
+                let mut storage_table = txn.open_table(STORAGE)?;
+                for (address, info, storage) in accounts {
+                    let mut value = Vec::with_capacity(72);
+                    value.extend_from_slice(&info.balance.to_be_bytes::<32>());
+                    value.extend_from_slice(&info.nonce.to_be_bytes());
+                    value.extend_from_slice(info.code_hash.as_slice());
+                    account_table.insert(address.as_slice(), value.as_slice())?;
+                    if let Some(code) = info.code.filter(|code| !code.is_empty()) {
+                        code_table.insert(info.code_hash.as_slice(), code.original_byte_slice())?;
+                    }
+                    for (slot, value) in storage {
+                        storage_table.insert(
+                            storage_key(&address, &slot).as_slice(),
+                            value.to_be_bytes::<32>().as_slice(),
+                        )?;
+                    }
+                }
+            }
+            txn.commit()?;
+            Ok(())
+        }
+    }
+
+    impl DatabaseRef for RedbStore {
+        type Error = StoreError;
+
+        fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            let txn = self.db.begin_read().map_err(store_err)?;
+            let table = txn.open_table(ACCOUNTS).map_err(store_err)?;
+            let Some(value) = table.get(address.as_slice()).map_err(store_err)? else {
+                return Ok(None);
+            };
+            let value = value.value();
+            if value.len() != 72 {
+                return Err(StoreError(format!("Corrupt account record for {:?}", address)));
+            }
+            let mut nonce = [0u8; 8];
+            nonce.copy_from_slice(&value[32..40]);
+            Ok(Some(AccountInfo {
+                balance: U256::from_be_slice(&value[..32]),
+                nonce: u64::from_be_bytes(nonce),
+                code_hash: B256::from_slice(&value[40..]),
+                // Loaded on demand through code_by_hash_ref
+                code: None,
+            }))
+        }
+
+        fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+            if code_hash == KECCAK_EMPTY {
+                return Ok(Bytecode::default());
+            }
+            let txn = self.db.begin_read().map_err(store_err)?;
+            let table = txn.open_table(CODE).map_err(store_err)?;
+            let code = table.get(code_hash.as_slice()).map_err(store_err)?;
+            Ok(code.map_or_else(Bytecode::default, |code| {
+                Bytecode::new_raw(code.value().to_vec().into())
+            }))
+        }
+
+        fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+            let txn = self.db.begin_read().map_err(store_err)?;
+            let table = txn.open_table(STORAGE).map_err(store_err)?;
+            let value = table
+                .get(storage_key(&address, &index).as_slice())
+                .map_err(store_err)?;
+            Ok(value.map_or(U256::ZERO, |value| U256::from_be_slice(value.value())))
+        }
+
+        fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+            // Same stand-in hashes as revm's EmptyDB
+            Ok(keccak256(number.to_string().as_bytes()))
+        }
+    }
+}
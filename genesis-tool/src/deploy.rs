@@ -0,0 +1,307 @@
+//! `deploy`: genesis on a running anvil or hardhat node
+//!
+//! Contract developers can test against a real RPC rather than only the
+//! embedded EVM. The accounts of the deployment database (system contract code,
+//! the `Genesis` and system caller funding, hook caller funding) are written to
+//! the node with its `setCode`/`setBalance`/`setNonce` methods, then every
+//! genesis transaction is sent in order. `Genesis.initialize` and the other
+//! system calls only accept their own callers, so those are impersonated; a
+//! transaction whose caller is the `--private-key` account is signed and sent
+//! raw instead. Finally the `extraAlloc` accounts are written as in the alloc.
+//!
+//! Unlike the generated genesis, the node charges gas, so impersonated callers
+//! get [`GAS_FUNDING_ETHER`] on top of their genesis balance, and the scaffolding
+//! accounts stay as execution leaves them.
+
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy_rlp::{Encodable, Header};
+use anyhow::{Context, Result};
+use revm::{db::PlainAccount, InMemoryDB};
+use revm_primitives::{hex, TxEnv, TxKind};
+use serde_json::{json, Value};
+use std::{collections::HashMap, thread, time::Duration};
+use tracing::{info, warn};
+
+use crate::{
+    execute::{deployment_db, BuildInputs},
+    genesis::GenesisConfig,
+    keys,
+    registry::ContractsManifest,
+    rpc::{parse_hex_u64, to_hex_quantity, RpcClient},
+};
+
+/// Ether added to each impersonated caller's balance to pay for gas
+pub const GAS_FUNDING_ETHER: u64 = 1000;
+
+/// How long to wait for a transaction's receipt
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct DeployOptions {
+    pub rpc_url: String,
+    /// Hex secp256k1 key; signs the transactions whose caller is its account
+    pub private_key: Option<String>,
+}
+
+/// Development node and the prefix of its state-editing methods
+struct DevNode {
+    client: RpcClient,
+    namespace: &'static str,
+}
+
+impl DevNode {
+    fn connect(rpc_url: &str) -> Result<Self> {
+        let client = RpcClient::new(rpc_url);
+        let version: String = client
+            .call("web3_clientVersion", json!([]))
+            .context(format!("Failed to reach {}", rpc_url))?;
+        let namespace = if version.to_lowercase().starts_with("anvil") {
+            "anvil"
+        } else if version.to_lowercase().starts_with("hardhat") {
+            "hardhat"
+        } else {
+            anyhow::bail!(
+                "{} is {}; deploy needs anvil or hardhat to set code and impersonate system callers",
+                rpc_url,
+                version
+            );
+        };
+        info!("Deploying to {} ({})", rpc_url, version);
+        Ok(DevNode { client, namespace })
+    }
+
+    fn set(&self, method: &str, params: Value) -> Result<()> {
+        let method = format!("{}_{}", self.namespace, method);
+        self.client.call::<Value>(&method, params).map(|_| ())
+    }
+
+    fn set_account(&self, address: Address, balance: U256, nonce: u64, code: Option<Bytes>) -> Result<()> {
+        self.set("setBalance", json!([address, balance]))?;
+        self.set("setNonce", json!([address, to_hex_quantity(nonce)]))?;
+        if let Some(code) = code.filter(|code| !code.is_empty()) {
+            self.set("setCode", json!([address, code]))?;
+        }
+        Ok(())
+    }
+
+    fn set_storage(&self, address: Address, slot: U256, value: U256) -> Result<()> {
+        // hardhat takes the slot as a QUANTITY and rejects leading zeros; anvil
+        // takes a 32-byte word. U256 serializes as a QUANTITY.
+        let slot = match self.namespace {
+            "hardhat" => json!(slot),
+            _ => json!(B256::from(slot)),
+        };
+        self.set("setStorageAt", json!([address, slot, B256::from(value)]))
+    }
+
+    fn wait_for_receipt(&self, hash: &str) -> Result<Value> {
+        let mut waited = Duration::ZERO;
+        loop {
+            let receipt: Value = self.client.call("eth_getTransactionReceipt", json!([hash]))?;
+            if !receipt.is_null() {
+                return Ok(receipt);
+            }
+            if waited >= RECEIPT_TIMEOUT {
+                anyhow::bail!(
+                    "no receipt for {} after {:?}; is the node mining (anvil --no-mining)?",
+                    hash,
+                    RECEIPT_TIMEOUT
+                );
+            }
+            thread::sleep(Duration::from_millis(200));
+            waited += Duration::from_millis(200);
+        }
+    }
+}
+
+/// JSON-RPC call object of a genesis transaction sent from its own caller
+fn call_object(tx: &TxEnv) -> Value {
+    let mut call = json!({
+        "from": tx.caller,
+        "value": tx.value,
+        "data": Bytes::from(tx.data.to_vec()),
+    });
+    if let TxKind::Call(to) = tx.transact_to {
+        call["to"] = json!(to);
+    }
+    call
+}
+
+fn encode_uint(value: U256, out: &mut Vec<u8>) {
+    value.to_be_bytes_trimmed_vec().as_slice().encode(out);
+}
+
+/// RLP list of the already encoded `fields`
+fn rlp_list(fields: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    Header { list: true, payload_length: fields.len() }.encode(&mut out);
+    out.extend_from_slice(fields);
+    out
+}
+
+/// EIP-155 signed legacy transaction
+fn sign_legacy_transaction(
+    secret: &[u8; 32],
+    tx: &TxEnv,
+    nonce: u64,
+    gas_price: U256,
+    gas: u64,
+    chain_id: u64,
+) -> Result<Bytes> {
+    let signing_key = k256::ecdsa::SigningKey::from_slice(secret)
+        .map_err(|e| anyhow::anyhow!("Invalid private key: {}", e))?;
+    let to = match tx.transact_to {
+        TxKind::Call(to) => to.to_vec(),
+        TxKind::Create => Vec::new(),
+    };
+    let mut fields = Vec::new();
+    nonce.encode(&mut fields);
+    encode_uint(gas_price, &mut fields);
+    gas.encode(&mut fields);
+    to.as_slice().encode(&mut fields);
+    encode_uint(tx.value, &mut fields);
+    tx.data.as_ref().encode(&mut fields);
+
+    let mut unsigned = fields.clone();
+    chain_id.encode(&mut unsigned);
+    0u8.encode(&mut unsigned);
+    0u8.encode(&mut unsigned);
+    let (signature, recovery_id) = signing_key
+        .sign_prehash_recoverable(keccak256(rlp_list(&unsigned)).as_slice())
+        .map_err(|e| anyhow::anyhow!("Failed to sign transaction: {}", e))?;
+
+    let mut signed = fields;
+    (recovery_id.to_byte() as u64 + 35 + 2 * chain_id).encode(&mut signed);
+    encode_uint(U256::from_be_slice(&signature.r().to_bytes()), &mut signed);
+    encode_uint(U256::from_be_slice(&signature.s().to_bytes()), &mut signed);
+    Ok(Bytes::from(rlp_list(&signed)))
+}
+
+/// Write the deployment database to the node
+fn etch_deployment(node: &DevNode, db: &InMemoryDB, callers: &[Address], signer: Option<Address>) -> Result<()> {
+    let gas_funding = U256::from(GAS_FUNDING_ETHER) * U256::from(10).pow(U256::from(18));
+    for (address, account) in &db.accounts {
+        let info = &account.info;
+        let mut balance = info.balance;
+        if callers.contains(address) && Some(*address) != signer {
+            balance = balance.saturating_add(gas_funding);
+        }
+        let code = info.code.as_ref().map(|code| code.original_bytes());
+        node.set_account(*address, balance, info.nonce, code)
+            .context(format!("Failed to set up {:?}", address))?;
+    }
+    // Callers funded only for gas, e.g. the multisig deployer
+    for caller in callers {
+        if !db.accounts.contains_key(caller) && Some(*caller) != signer {
+            node.set("setBalance", json!([caller, gas_funding]))?;
+        }
+    }
+    Ok(())
+}
+
+/// Send one genesis transaction and wait for it to succeed
+fn send_transaction(
+    node: &DevNode,
+    tx: &TxEnv,
+    signer: Option<&keys::EcdsaKey>,
+    chain_id: u64,
+) -> Result<String> {
+    let call = call_object(tx);
+    let estimate: String = node
+        .client
+        .call("eth_estimateGas", json!([call]))
+        .context("the node rejects the transaction (run generate --trace for the call trace)")?;
+    let estimate = parse_hex_u64(&estimate)?;
+    let gas = estimate.saturating_add(estimate / 5);
+
+    let hash: String = match signer.filter(|key| key.address == tx.caller) {
+        Some(key) => {
+            let nonce: String = node
+                .client
+                .call("eth_getTransactionCount", json!([key.address, "pending"]))?;
+            let gas_price: U256 = node.client.call("eth_gasPrice", json!([]))?;
+            let raw = sign_legacy_transaction(
+                &key.secret_key,
+                tx,
+                parse_hex_u64(&nonce)?,
+                gas_price,
+                gas,
+                chain_id,
+            )?;
+            node.client.call("eth_sendRawTransaction", json!([raw]))?
+        }
+        None => {
+            node.set("impersonateAccount", json!([tx.caller]))?;
+            let mut call = call;
+            call["gas"] = json!(to_hex_quantity(gas));
+            let sent = node.client.call("eth_sendTransaction", json!([call]));
+            node.set("stopImpersonatingAccount", json!([tx.caller]))?;
+            sent?
+        }
+    };
+    let receipt = node.wait_for_receipt(&hash)?;
+    if receipt["status"].as_str() != Some("0x1") {
+        anyhow::bail!("transaction {} reverted (run generate --trace for the call trace)", hash);
+    }
+    Ok(hash)
+}
+
+/// Deploy the system contracts to the node at `options.rpc_url`, run the
+/// genesis transactions of `inputs` there and write `extra_accounts`
+pub fn deploy_genesis(
+    options: &DeployOptions,
+    contracts: &ContractsManifest,
+    config: &GenesisConfig,
+    inputs: &BuildInputs,
+    extra_accounts: &HashMap<Address, PlainAccount>,
+) -> Result<()> {
+    let node = DevNode::connect(&options.rpc_url)?;
+    let chain_id = node.client.chain_id()?;
+    if chain_id != config.chain_id {
+        warn!(
+            "Node chain ID is {}, the config's chainId is {}; start anvil with --chain-id {} to match",
+            chain_id, config.chain_id, config.chain_id
+        );
+    }
+    let signer = options
+        .private_key
+        .as_deref()
+        .map(|key| {
+            let secret = hex::decode(key.trim()).context("--private-key is not hex")?;
+            keys::ecdsa_key_from_secret(&secret)
+        })
+        .transpose()?;
+
+    let mut callers: Vec<Address> = inputs.transactions.iter().map(|tx| tx.caller).collect();
+    callers.sort();
+    callers.dedup();
+    let db = deployment_db(contracts, inputs);
+    etch_deployment(&node, &db, &callers, signer.as_ref().map(|key| key.address))?;
+    info!(
+        "Set code and balances of {} account(s) on {}",
+        db.accounts.len(),
+        options.rpc_url
+    );
+
+    for (i, tx) in inputs.transactions.iter().enumerate() {
+        let hash = send_transaction(&node, tx, signer.as_ref(), chain_id)
+            .context(format!("Genesis transaction {} from {:?}", i + 1, tx.caller))?;
+        info!("Genesis transaction {} from {:?}: {}", i + 1, tx.caller, hash);
+    }
+
+    for (address, account) in extra_accounts {
+        let info = &account.info;
+        let code = info.code.as_ref().map(|code| code.original_bytes());
+        node.set_account(*address, info.balance, info.nonce, code)?;
+        for (slot, value) in &account.storage {
+            node.set_storage(*address, *slot, *value)?;
+        }
+    }
+    info!(
+        "Genesis deployed to {}: {} transaction(s), {} extra account(s)",
+        options.rpc_url,
+        inputs.transactions.len(),
+        extra_accounts.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,233 @@
+//! Devnet bootstrap generator
+//!
+//! Produces everything needed to spin up a local multi-validator devnet in one step:
+//! per-validator key material (BLS consensus key + PoP, operator EOA, noise key),
+//! a filled-in `GenesisConfig`, and (when a bytecode directory is given) the genesis
+//! outputs themselves via the regular generate pipeline.
+//!
+//! Output layout:
+//!
+//! ```text
+//! <output>/genesis_config.json
+//! <output>/keys/governance_owner.json
+//! <output>/keys/validator-<i>/identity.json
+//! ```
+
+use anyhow::{Context, Result};
+use rand::{SeedableRng, rngs::StdRng};
+use revm_primitives::hex;
+use serde::Serialize;
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::{fs, io::Write, path::Path};
+use tracing::{info, warn};
+
+use crate::{
+    config_init::config_template,
+    genesis::{GenesisConfig, InitialValidator, derive_account_address_from_consensus_pubkey},
+    keys::{
+        format_network_address, generate_bls_keypair, generate_ecdsa_key, generate_noise_keypair,
+    },
+};
+
+/// Ports are allocated in blocks of this size per validator
+const PORTS_PER_VALIDATOR: u16 = 10;
+/// Offset of the fullnode port inside a validator's port block
+const FULLNODE_PORT_OFFSET: u16 = 2;
+
+#[derive(Debug, Clone)]
+pub struct DevnetOptions {
+    pub validators: usize,
+    pub chain_id: u64,
+    pub host: String,
+    pub base_port: u16,
+    pub stake_amount: String,
+    /// Deterministic key generation when set (never use for public networks)
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct ValidatorIdentity {
+    moniker: String,
+    #[serde(rename = "operatorAddress")]
+    operator_address: String,
+    #[serde(rename = "operatorPrivateKey")]
+    operator_private_key: String,
+    /// Owner and staker of the validator, kept apart from the operator key
+    #[serde(rename = "ownerAddress")]
+    owner_address: String,
+    #[serde(rename = "ownerPrivateKey")]
+    owner_private_key: String,
+    #[serde(rename = "consensusPrivateKey")]
+    consensus_private_key: String,
+    #[serde(rename = "consensusPubkey")]
+    consensus_pubkey: String,
+    #[serde(rename = "consensusPop")]
+    consensus_pop: String,
+    #[serde(rename = "accountAddress")]
+    account_address: String,
+    #[serde(rename = "networkPrivateKey")]
+    network_private_key: String,
+    #[serde(rename = "networkPublicKey")]
+    network_public_key: String,
+    #[serde(rename = "networkAddresses")]
+    network_addresses: String,
+    #[serde(rename = "fullnodeAddresses")]
+    fullnode_addresses: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AccountKey {
+    address: String,
+    #[serde(rename = "privateKey")]
+    private_key: String,
+}
+
+/// Keys and genesis config of a devnet, before anything is written
+struct Devnet {
+    governance_owner: AccountKey,
+    identities: Vec<ValidatorIdentity>,
+    config: GenesisConfig,
+}
+
+/// Generate keys and a genesis config for a devnet, returning the config path
+pub fn bootstrap_devnet(options: &DevnetOptions, output_dir: &str) -> Result<String> {
+    if let Some(seed) = options.seed {
+        warn!("Using deterministic devnet keys (seed {}) - never reuse for real networks", seed);
+    }
+    let devnet = build_devnet(options)?;
+
+    let keys_dir = Path::new(output_dir).join("keys");
+    fs::create_dir_all(&keys_dir)?;
+    write_private_json(&keys_dir.join("governance_owner.json"), &devnet.governance_owner)?;
+    for identity in &devnet.identities {
+        let validator_dir = keys_dir.join(&identity.moniker);
+        fs::create_dir_all(&validator_dir)?;
+        write_private_json(&validator_dir.join("identity.json"), identity)?;
+    }
+
+    let config_path = Path::new(output_dir).join("genesis_config.json");
+    write_json(&config_path, &devnet.config)?;
+
+    info!(
+        "Devnet bootstrap: {} validators, keys in {}, config at {}",
+        options.validators,
+        keys_dir.display(),
+        config_path.display()
+    );
+    Ok(config_path.to_string_lossy().into_owned())
+}
+
+/// Genesis config of a devnet with freshly generated (or, with a seed,
+/// deterministic) validator keys; the keys themselves are not kept
+pub fn devnet_config(options: &DevnetOptions) -> Result<GenesisConfig> {
+    Ok(build_devnet(options)?.config)
+}
+
+fn build_devnet(options: &DevnetOptions) -> Result<Devnet> {
+    if options.validators == 0 {
+        anyhow::bail!("A devnet needs at least one validator");
+    }
+    let port_span = options.validators as u64 * PORTS_PER_VALIDATOR as u64;
+    if options.base_port as u64 + port_span > u16::MAX as u64 {
+        anyhow::bail!(
+            "Port range {}..{} exceeds 65535",
+            options.base_port,
+            options.base_port as u64 + port_span
+        );
+    }
+
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let governance_owner = generate_ecdsa_key(&mut rng);
+    let mut identities = Vec::with_capacity(options.validators);
+    let mut validators = Vec::with_capacity(options.validators);
+    for i in 0..options.validators {
+        let moniker = format!("validator-{}", i + 1);
+        let operator = generate_ecdsa_key(&mut rng);
+        let owner = generate_ecdsa_key(&mut rng);
+        let consensus = generate_bls_keypair(&mut rng);
+        let noise = generate_noise_keypair(&mut rng);
+
+        let network_port = options.base_port + i as u16 * PORTS_PER_VALIDATOR;
+        let network_addresses =
+            format_network_address(&options.host, network_port, &noise.public_key);
+        let fullnode_addresses = format_network_address(
+            &options.host,
+            network_port + FULLNODE_PORT_OFFSET,
+            &noise.public_key,
+        );
+
+        let identity = ValidatorIdentity {
+            moniker: moniker.clone(),
+            operator_address: operator.address.to_checksum(None),
+            operator_private_key: format!("0x{}", hex::encode(operator.secret_key)),
+            owner_address: owner.address.to_checksum(None),
+            owner_private_key: format!("0x{}", hex::encode(owner.secret_key)),
+            consensus_private_key: format!("0x{}", hex::encode(consensus.secret_key)),
+            consensus_pubkey: consensus.public_key_hex(),
+            consensus_pop: consensus.proof_of_possession_hex(),
+            account_address: format!(
+                "0x{}",
+                hex::encode(derive_account_address_from_consensus_pubkey(&consensus.public_key))
+            ),
+            network_private_key: format!("0x{}", hex::encode(noise.secret_key)),
+            network_public_key: format!("0x{}", hex::encode(noise.public_key)),
+            network_addresses: network_addresses.clone(),
+            fullnode_addresses: fullnode_addresses.clone(),
+        };
+        validators.push(InitialValidator {
+            operator: identity.operator_address.clone(),
+            owner: identity.owner_address.clone(),
+            staker: identity.owner_address.clone(),
+            stake_amount: options.stake_amount.clone(),
+            moniker,
+            consensus_pubkey: identity.consensus_pubkey.clone(),
+            consensus_pop: identity.consensus_pop.clone(),
+            network_addresses,
+            fullnode_addresses,
+            voting_power: options.stake_amount.clone(),
+        });
+        identities.push(identity);
+    }
+
+    let mut config: GenesisConfig =
+        serde_json::from_value(config_template(options.validators, options.chain_id))
+            .context("Failed to build devnet config from template")?;
+    config.governance_owner = governance_owner.address.to_checksum(None);
+    config.validators = validators;
+
+    Ok(Devnet {
+        governance_owner: AccountKey {
+            address: governance_owner.address.to_checksum(None),
+            private_key: format!("0x{}", hex::encode(governance_owner.secret_key)),
+        },
+        identities,
+        config,
+    })
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(value)? + "\n")
+        .context(format!("Failed to write {}", path.display()))
+}
+
+/// Write a file holding private keys, readable by the owner only
+fn write_private_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options
+        .open(path)
+        .context(format!("Failed to create {}", path.display()))?;
+    // The mode only applies to new files; tighten one left by an earlier run
+    #[cfg(unix)]
+    file.set_permissions(fs::Permissions::from_mode(0o600))
+        .context(format!("Failed to restrict {}", path.display()))?;
+    file.write_all((serde_json::to_string_pretty(value)? + "\n").as_bytes())
+        .context(format!("Failed to write {}", path.display()))
+}
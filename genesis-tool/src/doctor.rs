@@ -0,0 +1,326 @@
+//! Setup diagnostics for `generate`
+//!
+//! `doctor` checks the usual causes of a failed run in one pass, without
+//! executing genesis: a config that does not parse or validate, missing or
+//! corrupt `.hex` files, creation code exported in place of runtime code, an
+//! output directory that is not writable or short on space, and an unreachable
+//! fork RPC endpoint. Problems are listed errors first, each with a hint.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tracing::{error, info, warn};
+
+use crate::{
+    bytecode::is_creation_code,
+    genesis::{load_genesis_config, GenesisConfig},
+    registry::ContractsManifest,
+    rpc::RpcClient,
+    validate::{validate_config, Severity, ValidationOptions},
+};
+
+/// Free space wanted in the output directory; outputs of a plain genesis are a
+/// few MiB, a forked state can take hundreds
+const MIN_FREE_BYTES: u64 = 512 * 1024 * 1024;
+
+#[derive(Debug, Clone, Default)]
+pub struct DoctorOptions {
+    pub byte_code_dir: String,
+    pub config_file: String,
+    pub output: Option<String>,
+    pub rpc_url: Option<String>,
+    pub contracts_manifest: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub severity: Severity,
+    pub check: &'static str,
+    pub message: String,
+    pub hint: String,
+}
+
+#[derive(Debug, Default)]
+pub struct DoctorReport {
+    pub problems: Vec<Problem>,
+    /// Checks that passed, for context
+    pub passed: Vec<String>,
+}
+
+impl DoctorReport {
+    fn error(&mut self, check: &'static str, message: impl Into<String>, hint: impl Into<String>) {
+        self.push(Severity::Error, check, message.into(), hint.into());
+    }
+
+    fn warn(&mut self, check: &'static str, message: impl Into<String>, hint: impl Into<String>) {
+        self.push(Severity::Warning, check, message.into(), hint.into());
+    }
+
+    fn push(&mut self, severity: Severity, check: &'static str, message: String, hint: String) {
+        self.problems.push(Problem {
+            severity,
+            check,
+            message,
+            hint,
+        });
+    }
+
+    fn ok(&mut self, message: impl Into<String>) {
+        self.passed.push(message.into());
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.problems
+            .iter()
+            .filter(|p| p.severity == Severity::Error)
+            .count()
+    }
+
+    /// Log passed checks, then problems with errors first
+    pub fn log(&self) {
+        for line in &self.passed {
+            info!("✅ {}", line);
+        }
+        let mut problems: Vec<&Problem> = self.problems.iter().collect();
+        problems.sort_by_key(|p| std::cmp::Reverse(p.severity));
+        for (i, problem) in problems.iter().enumerate() {
+            match problem.severity {
+                Severity::Error => error!("{}. ❌ [{}] {}", i + 1, problem.check, problem.message),
+                Severity::Warning => warn!("{}. ⚠️ [{}] {}", i + 1, problem.check, problem.message),
+                Severity::Info => info!("{}. ℹ️ [{}] {}", i + 1, problem.check, problem.message),
+            }
+            info!("   hint: {}", problem.hint);
+        }
+        if problems.is_empty() {
+            info!("✅ No problems found");
+        }
+    }
+}
+
+/// Run every check; later checks use what earlier ones loaded when available
+pub fn run_doctor(options: &DoctorOptions) -> DoctorReport {
+    let mut report = DoctorReport::default();
+    let config = check_config(&options.config_file, &mut report);
+    check_bytecode(options, &mut report);
+    if let Some(output) = &options.output {
+        check_output(output, &mut report);
+    }
+    if let Some(rpc_url) = &options.rpc_url {
+        check_rpc(rpc_url, config.as_ref(), &mut report);
+    }
+    report
+}
+
+fn check_config(config_file: &str, report: &mut DoctorReport) -> Option<GenesisConfig> {
+    if !Path::new(config_file).is_file() {
+        report.error(
+            "config",
+            format!("config file {} not found", config_file),
+            "pass --config-file, or create one with `genesis-tool config init`",
+        );
+        return None;
+    }
+    let config = match load_genesis_config(config_file) {
+        Ok(config) => config,
+        Err(e) => {
+            report.error(
+                "config",
+                format!("{:#}", e),
+                "compare the field names with `genesis-tool config init` output",
+            );
+            return None;
+        }
+    };
+    report.ok(format!(
+        "Config {} parses (chain ID {}, {} validator(s))",
+        config_file,
+        config.chain_id,
+        config.validators.len()
+    ));
+
+    let validation = validate_config(&config, &ValidationOptions::default());
+    for finding in &validation.findings {
+        let hint = match finding.severity {
+            Severity::Error => "generate rejects configs with validation errors",
+            Severity::Warning => "generate proceeds (fails with --strict), but review this before launch",
+            Severity::Info => "informational; generate proceeds",
+        };
+        report.push(
+            finding.severity,
+            "config",
+            format!("[{}] {}", finding.rule, finding.message),
+            hint.to_string(),
+        );
+    }
+    Some(config)
+}
+
+fn check_bytecode(options: &DoctorOptions, report: &mut DoctorReport) {
+    let dir = &options.byte_code_dir;
+    if !Path::new(dir).is_dir() {
+        report.error(
+            "bytecode",
+            format!("bytecode directory {} does not exist", dir),
+            "pass --byte-code-dir pointing at the directory with one <Contract>.hex per system contract",
+        );
+        return;
+    }
+    let contracts = match ContractsManifest::resolve(dir, options.contracts_manifest.as_deref()) {
+        Ok(contracts) => contracts,
+        Err(e) => {
+            report.error(
+                "bytecode",
+                format!("{:#}", e),
+                "fix or remove the contracts manifest to fall back to the built-in list",
+            );
+            return;
+        }
+    };
+
+    let mut healthy = 0;
+    for contract in &contracts.contracts {
+        let code = match contract.read_code(dir, &contracts.libraries) {
+            Ok(code) => code,
+            Err(e) => {
+                report.error(
+                    "bytecode",
+                    format!("{}: {:#}", contract.name, e),
+                    format!(
+                        "export {}/<Contract>.hex with only the hex runtime bytecode for every \
+                         registry entry, and list linked libraries under `libraries`",
+                        dir
+                    ),
+                );
+                continue;
+            }
+        };
+        if is_creation_code(&code) {
+            report.error(
+                "bytecode",
+                format!(
+                    "{}: {} holds creation code, not runtime code",
+                    contract.name,
+                    contract.hex_path(dir)
+                ),
+                "export `deployedBytecode.object` from the artifact instead of `bytecode.object`",
+            );
+            continue;
+        }
+        healthy += 1;
+    }
+    if healthy == contracts.contracts.len() {
+        report.ok(format!(
+            "All {} contract(s) in {} have runtime bytecode",
+            healthy, dir
+        ));
+    }
+}
+
+/// Nearest existing directory at or above `path`
+fn existing_ancestor(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|p| p.is_dir())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Free bytes on the filesystem holding `dir`, from `df`
+fn free_bytes(dir: &Path) -> Option<u64> {
+    let output = Command::new("df").arg("-Pk").arg(dir).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let available_kib: u64 = stdout.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kib * 1024)
+}
+
+fn check_output(output: &str, report: &mut DoctorReport) {
+    let path = Path::new(output);
+    if path.exists() && !path.is_dir() {
+        report.error(
+            "output",
+            format!("output path {} is a file", output),
+            "pass a directory to --output",
+        );
+        return;
+    }
+
+    let dir = existing_ancestor(path);
+    let probe = dir.join(".genesis-tool-doctor");
+    match fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe);
+        }
+        Err(e) => {
+            report.error(
+                "output",
+                format!("{} is not writable: {}", dir.display(), e),
+                "choose another --output or fix the directory permissions",
+            );
+            return;
+        }
+    }
+
+    match free_bytes(&dir) {
+        Some(free) if free < MIN_FREE_BYTES => report.error(
+            "output",
+            format!(
+                "only {} MiB free on the filesystem of {}",
+                free / (1024 * 1024),
+                dir.display()
+            ),
+            format!(
+                "free up space; keep at least {} MiB for the outputs",
+                MIN_FREE_BYTES / (1024 * 1024)
+            ),
+        ),
+        Some(free) => report.ok(format!(
+            "Output {} is writable with {} MiB free",
+            output,
+            free / (1024 * 1024)
+        )),
+        None => report.warn(
+            "output",
+            format!("could not determine free space for {}", dir.display()),
+            format!(
+                "make sure at least {} MiB are free",
+                MIN_FREE_BYTES / (1024 * 1024)
+            ),
+        ),
+    }
+}
+
+fn check_rpc(rpc_url: &str, config: Option<&GenesisConfig>, report: &mut DoctorReport) {
+    let client = RpcClient::new(rpc_url);
+    let result = client
+        .chain_id()
+        .and_then(|chain_id| Ok((chain_id, client.block_number()?)));
+    let (chain_id, block) = match result {
+        Ok(head) => head,
+        Err(e) => {
+            report.error(
+                "rpc",
+                format!("{} is unreachable: {:#}", rpc_url, e),
+                "check the URL and network access; the RPC is only needed for --fork-rpc",
+            );
+            return;
+        }
+    };
+    report.ok(format!(
+        "RPC {} answers (chain ID {}, block {})",
+        rpc_url, chain_id, block
+    ));
+    if let Some(config) = config.filter(|config| config.chain_id != chain_id) {
+        report.warn(
+            "rpc",
+            format!(
+                "RPC chain ID {} differs from the config chainId {}",
+                chain_id, config.chain_id
+            ),
+            "forking usually copies state from the chain the genesis is for",
+        );
+    }
+}
@@ -0,0 +1,443 @@
+//! `Genesis.initialize` calldata built from the artifact ABI
+//!
+//! The static path encodes `SolGenesisInitParams`, so a field added to
+//! `GenesisInitParams` in Solidity needs Rust struct surgery before genesis can
+//! be generated. Here the parameter layout comes from `Genesis.sol/Genesis.json`
+//! instead and is filled with [`DynSolValue`]s: fields the tool knows are taken
+//! by name from its own conversion of the config (so BCS network addresses,
+//! hashed task names etc. stay identical), and fields it does not know are read
+//! from the config's `extraInitParams`, which mirrors the struct layout:
+//!
+//! ```json
+//! "extraInitParams": {
+//!   "validatorConfig": { "newLimit": "100" },
+//!   "validators": [ { "commissionBps": 500 }, { "commissionBps": 300 } ]
+//! }
+//! ```
+//!
+//! Values are coerced to the artifact's types from JSON strings, numbers and
+//! booleans. Fields the tool knows but the artifact dropped are left out.
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_sol_types::{SolCall, SolStruct};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::{collections::HashSet, fs};
+use tracing::{info, warn};
+
+use crate::{
+    abi::{artifact_path, selector, signature},
+    abi_check::{parse_encode_type, split_array_suffix, StructDefs},
+    genesis::{convert_config_to_sol, Genesis, GenesisConfig, SolGenesisInitParams},
+};
+
+/// Config value the tool derives for a field, keyed by Solidity field names
+#[derive(Debug, Clone)]
+enum Known {
+    Leaf(DynSolValue),
+    Struct(Vec<(String, Known)>),
+    Array(Vec<Known>),
+}
+
+impl Known {
+    /// Attach field names to a decoded value of tool type `ty`
+    fn from_value(value: DynSolValue, ty: &str, defs: &StructDefs) -> Result<Self> {
+        let (base, suffix) = split_array_suffix(ty);
+        if !suffix.is_empty() {
+            let element_ty = &ty[..ty.rfind('[').unwrap_or(ty.len())];
+            let items = match value {
+                DynSolValue::Array(items) | DynSolValue::FixedArray(items) => items,
+                other => anyhow::bail!("expected an array for {}, got {:?}", ty, other),
+            };
+            return items
+                .into_iter()
+                .map(|item| Self::from_value(item, element_ty, defs))
+                .collect::<Result<_>>()
+                .map(Self::Array);
+        }
+        let Some(fields) = defs.get(base) else {
+            return Ok(Self::Leaf(value));
+        };
+        let DynSolValue::Tuple(values) = value else {
+            anyhow::bail!("expected a tuple for {}, got {:?}", base, value);
+        };
+        fields
+            .iter()
+            .zip(values)
+            .map(|((field_ty, name), value)| {
+                Ok((name.clone(), Self::from_value(value, field_ty, defs)?))
+            })
+            .collect::<Result<_>>()
+            .map(Self::Struct)
+    }
+
+    fn field(&self, name: &str) -> Option<&Known> {
+        match self {
+            Self::Struct(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, k)| k),
+            _ => None,
+        }
+    }
+}
+
+/// The tool's `GenesisInitParams` for `config`, with field names
+fn known_params(config: &GenesisConfig) -> Result<Known> {
+    let params = convert_config_to_sol(config)?;
+    let defs = parse_encode_type(&SolGenesisInitParams::eip712_encode_type());
+    let root = SolGenesisInitParams::NAME;
+    let ty = dyn_type(root, &defs)?;
+    let encoded = Genesis::initializeCall { params }.abi_encode();
+    let value = DynSolType::Tuple(vec![ty])
+        .abi_decode_params(&encoded[4..])
+        .context("Failed to decode the tool's own initialize encoding")?;
+    let DynSolValue::Tuple(mut values) = value else {
+        anyhow::bail!("initialize parameters did not decode as a tuple");
+    };
+    Known::from_value(values.remove(0), root, &defs)
+}
+
+/// [`DynSolType`] of a tool type, resolving struct names through `defs`
+fn dyn_type(ty: &str, defs: &StructDefs) -> Result<DynSolType> {
+    let (base, suffix) = split_array_suffix(ty);
+    let Some(fields) = defs.get(base) else {
+        return DynSolType::parse(ty).context(format!("Unsupported type {}", ty));
+    };
+    let tuple = DynSolType::Tuple(
+        fields
+            .iter()
+            .map(|(field_ty, _)| dyn_type(field_ty, defs))
+            .collect::<Result<_>>()?,
+    );
+    wrap_arrays(tuple, suffix)
+}
+
+/// Wrap `inner` in the array dimensions of `suffix` (`[2][]`)
+fn wrap_arrays(inner: DynSolType, suffix: &str) -> Result<DynSolType> {
+    let mut ty = inner;
+    for dim in suffix.split_inclusive(']') {
+        let size = dim.trim_start_matches('[').trim_end_matches(']');
+        ty = if size.is_empty() {
+            DynSolType::Array(Box::new(ty))
+        } else {
+            let size = size
+                .parse()
+                .context(format!("Invalid array size in {}", suffix))?;
+            DynSolType::FixedArray(Box::new(ty), size)
+        };
+    }
+    Ok(ty)
+}
+
+/// Fills artifact parameters from known values and `extraInitParams`
+struct Encoder {
+    /// `extraInitParams` paths that were encoded
+    used: HashSet<String>,
+}
+
+impl Encoder {
+    fn encode(
+        &mut self,
+        path: &str,
+        param: &Value,
+        known: Option<&Known>,
+        extra: Option<&Value>,
+    ) -> Result<DynSolValue> {
+        let ty = param
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if ty.ends_with(']') {
+            return self.encode_array(path, param, ty, known, extra);
+        }
+        if ty == "tuple" {
+            return self.encode_tuple(path, param, known, extra);
+        }
+
+        let sol_type =
+            DynSolType::parse(ty).context(format!("{}: unsupported type {}", path, ty))?;
+        match (known, extra) {
+            (Some(Known::Leaf(value)), _) => {
+                if value.as_type().as_ref() != Some(&sol_type) {
+                    anyhow::bail!(
+                        "{}: the tool encodes {:?}, the artifact expects {}",
+                        path,
+                        value.as_type(),
+                        ty
+                    );
+                }
+                Ok(value.clone())
+            }
+            (Some(_), _) => anyhow::bail!(
+                "{}: the tool encodes a struct or array, the artifact expects {}",
+                path,
+                ty
+            ),
+            (None, Some(value)) => {
+                let value = coerce_json(&sol_type, value)
+                    .context(format!("extraInitParams.{}", path))?;
+                self.used.insert(path.to_string());
+                Ok(value)
+            }
+            (None, None) => anyhow::bail!(
+                "{} ({}) is new in the artifact; set it under extraInitParams.{}",
+                path,
+                ty,
+                path
+            ),
+        }
+    }
+
+    fn encode_tuple(
+        &mut self,
+        path: &str,
+        param: &Value,
+        known: Option<&Known>,
+        extra: Option<&Value>,
+    ) -> Result<DynSolValue> {
+        if matches!(known, Some(Known::Leaf(_) | Known::Array(_))) {
+            anyhow::bail!("{}: the artifact expects a struct here", path);
+        }
+        let components = param
+            .get("components")
+            .and_then(Value::as_array)
+            .context(format!("{}: tuple without components", path))?;
+        let mut values = Vec::with_capacity(components.len());
+        for component in components {
+            let name = component
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let field_path = join(path, name);
+            values.push(self.encode(
+                &field_path,
+                component,
+                known.and_then(|k| k.field(name)),
+                extra.and_then(|e| e.get(name)),
+            )?);
+        }
+        if let Some(Known::Struct(fields)) = known {
+            for (name, _) in fields {
+                let dropped = !components
+                    .iter()
+                    .any(|c| c.get("name").and_then(Value::as_str) == Some(name));
+                if dropped {
+                    warn!(
+                        "{} is not in the artifact; its config value is ignored",
+                        join(path, name)
+                    );
+                }
+            }
+        }
+        Ok(DynSolValue::Tuple(values))
+    }
+
+    fn encode_array(
+        &mut self,
+        path: &str,
+        param: &Value,
+        ty: &str,
+        known: Option<&Known>,
+        extra: Option<&Value>,
+    ) -> Result<DynSolValue> {
+        let open = ty.rfind('[').unwrap_or_default();
+        let size = &ty[open + 1..ty.len() - 1];
+        let mut element = param.clone();
+        element["type"] = Value::String(ty[..open].to_string());
+
+        let known_items = match known {
+            Some(Known::Array(items)) => Some(items.as_slice()),
+            Some(_) => anyhow::bail!("{}: the artifact expects an array here", path),
+            None => None,
+        };
+        let extra_items = extra.and_then(Value::as_array);
+        let len = match (known_items, extra_items) {
+            (Some(items), _) => items.len(),
+            (None, Some(items)) => items.len(),
+            (None, None) => anyhow::bail!(
+                "{} ({}) is new in the artifact; set it under extraInitParams.{}",
+                path,
+                ty,
+                path
+            ),
+        };
+        let mut values = Vec::with_capacity(len);
+        for i in 0..len {
+            values.push(self.encode(
+                &format!("{}[{}]", path, i),
+                &element,
+                known_items.and_then(|items| items.get(i)),
+                extra_items.and_then(|items| items.get(i)),
+            )?);
+        }
+        if size.is_empty() {
+            Ok(DynSolValue::Array(values))
+        } else {
+            if size.parse::<usize>().ok() != Some(len) {
+                anyhow::bail!("{}: {} element(s) for {}", path, len, ty);
+            }
+            Ok(DynSolValue::FixedArray(values))
+        }
+    }
+}
+
+/// Coerce a JSON config value to `ty`: strings, numbers and booleans through
+/// [`DynSolType::coerce_str`], JSON arrays for arrays and tuples
+pub fn coerce_json(ty: &DynSolType, value: &Value) -> Result<DynSolValue> {
+    let elements = |types: &mut dyn Iterator<Item = &DynSolType>, items: &[Value]| {
+        types
+            .zip(items)
+            .map(|(ty, item)| coerce_json(ty, item))
+            .collect::<Result<Vec<_>>>()
+    };
+    match (ty, value) {
+        (DynSolType::Array(inner), Value::Array(items)) => {
+            elements(&mut std::iter::repeat(inner.as_ref()), items).map(DynSolValue::Array)
+        }
+        (DynSolType::FixedArray(inner, len), Value::Array(items)) => {
+            if items.len() != *len {
+                anyhow::bail!("expected {} element(s) for {}, got {}", len, ty, items.len());
+            }
+            elements(&mut std::iter::repeat(inner.as_ref()), items).map(DynSolValue::FixedArray)
+        }
+        (DynSolType::Tuple(types), Value::Array(items)) => {
+            if items.len() != types.len() {
+                anyhow::bail!("expected {} component(s) for {}, got {}", types.len(), ty, items.len());
+            }
+            elements(&mut types.iter(), items).map(DynSolValue::Tuple)
+        }
+        (_, Value::String(text)) => ty
+            .coerce_str(text)
+            .context(format!("invalid {} {}", ty, text)),
+        (_, Value::Number(_) | Value::Bool(_)) => ty
+            .coerce_str(&value.to_string())
+            .context(format!("invalid {} {}", ty, value)),
+        _ => anyhow::bail!("expected a {} value, got {}", ty, value),
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// Leaf paths of `extraInitParams`, in the encoder's path format
+fn leaf_paths(path: &str, value: &Value, paths: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (name, value) in map {
+                leaf_paths(&join(path, name), value, paths);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                leaf_paths(&format!("{}[{}]", path, i), item, paths);
+            }
+        }
+        _ => paths.push(path.to_string()),
+    }
+}
+
+/// Where staged callbacks go in artifacts whose `OracleInitParams` has them
+const PENDING_CALLBACKS_PATH: &str = "oracleConfig.pendingCallbacks";
+
+/// `extraInitParams` with `oracleConfig.pendingCallbacks` filled in from the
+/// config; the built-in bindings predate staged callbacks
+fn with_pending_callbacks(config: &GenesisConfig) -> Result<Option<Value>> {
+    let pending = &config.oracle_config.pending_callbacks;
+    if pending.is_empty() {
+        return Ok((!config.extra_init_params.is_null()).then(|| config.extra_init_params.clone()));
+    }
+    let mut extra = match &config.extra_init_params {
+        Value::Null => serde_json::json!({}),
+        Value::Object(_) => config.extra_init_params.clone(),
+        other => anyhow::bail!("extraInitParams must be an object, got {}", other),
+    };
+    if extra.pointer("/oracleConfig/pendingCallbacks").is_some() {
+        anyhow::bail!("set staged callbacks in oracleConfig.pendingCallbacks, not extraInitParams");
+    }
+    let oracle = extra
+        .as_object_mut()
+        .expect("checked above")
+        .entry("oracleConfig")
+        .or_insert_with(|| serde_json::json!({}));
+    if !oracle.is_object() {
+        anyhow::bail!("extraInitParams.oracleConfig must be an object, got {}", oracle);
+    }
+    oracle["pendingCallbacks"] = serde_json::to_value(pending)?;
+    Ok(Some(extra))
+}
+
+/// `Genesis.initialize` calldata for `config`, laid out by the artifact ABI in
+/// `byte_code_dir`
+pub fn encode_genesis_initialize(byte_code_dir: &str, config: &GenesisConfig) -> Result<Vec<u8>> {
+    let path = artifact_path(byte_code_dir, "Genesis");
+    let content = fs::read_to_string(&path).context(format!(
+        "Dynamic ABI encoding needs the Genesis artifact at {}",
+        path.display()
+    ))?;
+    let artifact: Value =
+        serde_json::from_str(&content).context(format!("Failed to parse {}", path.display()))?;
+    let function = artifact
+        .get("abi")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .find(|item| {
+            item.get("type").and_then(Value::as_str) == Some("function")
+                && item.get("name").and_then(Value::as_str) == Some("initialize")
+        })
+        .context(format!("{} has no initialize function", path.display()))?;
+    let inputs = function
+        .get("inputs")
+        .and_then(Value::as_array)
+        .filter(|inputs| inputs.len() == 1)
+        .context("initialize must take a single GenesisInitParams argument")?;
+    let sig = signature(function).context("initialize has no name")?;
+
+    let known = known_params(config)?;
+    let extra = with_pending_callbacks(config)?;
+    let mut encoder = Encoder {
+        used: HashSet::new(),
+    };
+    let params = encoder.encode("", &inputs[0], Some(&known), extra.as_ref())?;
+
+    let staged = encoder.used.iter().any(|p| p.starts_with(PENDING_CALLBACKS_PATH));
+    if !config.oracle_config.pending_callbacks.is_empty() && !staged {
+        anyhow::bail!(
+            "oracleConfig.pendingCallbacks is set but the Genesis artifact has no {}",
+            PENDING_CALLBACKS_PATH
+        );
+    }
+
+    let mut extra_paths = Vec::new();
+    if !config.extra_init_params.is_null() {
+        leaf_paths("", &config.extra_init_params, &mut extra_paths);
+    }
+    for unused in extra_paths.iter().filter(|p| !encoder.used.contains(*p)) {
+        warn!(
+            "extraInitParams.{} was not used: the artifact has no such field or the tool already encodes it",
+            unused
+        );
+    }
+
+    let mut call_data = selector(&sig).to_vec();
+    call_data.extend(DynSolValue::Tuple(vec![params]).abi_encode_params());
+
+    let static_call_data = Genesis::initializeCall {
+        params: convert_config_to_sol(config)?,
+    }
+    .abi_encode();
+    if call_data == static_call_data {
+        info!("Dynamic initialize calldata matches the built-in bindings");
+    } else {
+        info!(
+            "Dynamic initialize calldata follows the artifact layout {} ({} bytes, built-in bindings give {})",
+            sig,
+            call_data.len(),
+            static_call_data.len()
+        );
+    }
+    Ok(call_data)
+}
@@ -1,22 +1,80 @@
 use crate::{
-    genesis::{GenesisConfig, call_genesis_initialize, calculate_total_stake},
+    abi::AbiRegistry,
+    bytecode::{
+        artifact_initcode_len, code_size_violations, link_libraries, patch_immutables,
+    },
+    genesis::{
+        GenesisCall, GenesisConfig, call_genesis_initialize, calculate_total_stake, genesis_calls,
+        chunked_genesis_initialize_txns, genesis_initialize_txn, validator_batches,
+    },
+    footprint::{write_footprint_report, FootprintReport},
+    precompiles::PrecompileMode,
+    profile::{log_profile, profile_genesis},
+    registry::{ContractsManifest, SystemContract},
+    sourcemap::SourceMaps,
+    spec::EvmSpec,
+    trace::{format_trace, revert_origin},
     utils::{
-        CONTRACTS, GENESIS_ADDR, SYSTEM_ACCOUNT_INFO, SYSTEM_CALLER, analyze_txn_result,
-        execute_revm_sequential, read_hex_from_file,
+        GENESIS_ADDR, SYSTEM_ACCOUNT_INFO, SYSTEM_CALLER, analyze_txn_result, analyze_txn_result_at,
+        execute_revm_sequential_traced, read_hex_from_file,
     },
+    broadcast, code_cache, multisig, vesting,
 };
 
 use revm::{
-    InMemoryDB,
-    db::{BundleState, PlainAccount},
-    primitives::{AccountInfo, Env, SpecId, U256},
+    DatabaseRef, InMemoryDB,
+    db::{states::StorageSlot, AccountStatus, BundleAccount, BundleState, PlainAccount},
+    primitives::{AccountInfo, Address, Env, U256},
 };
 use revm_primitives::{Bytecode, Bytes, TxEnv, hex};
-use std::{collections::HashMap, fs::File, io::BufWriter};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::Path,
+};
 use tracing::{debug, error, info, warn};
 
+/// Key emitted account maps by EIP-55 checksummed address
+fn checksummed_keys<V>(map: &HashMap<Address, V>) -> BTreeMap<String, &V> {
+    map.iter()
+        .map(|(address, value)| (address.to_checksum(None), value))
+        .collect()
+}
+
+/// Linked runtime code of every system contract, keyed by address
+fn load_contract_code(
+    byte_code_dir: &str,
+    contracts: &ContractsManifest,
+) -> Result<BTreeMap<Address, Bytes>> {
+    let mut code = BTreeMap::new();
+    for contract in &contracts.contracts {
+        let runtime_bytecode = code_cache::runtime_code(byte_code_dir, contract, &contracts.libraries)?;
+
+        // Oversized code deploys fine through the alloc but can never be
+        // redeployed by a transaction, which blocks later upgrades
+        let initcode_len = contract
+            .immutables
+            .as_ref()
+            .and_then(|immutables| artifact_initcode_len(&immutables.artifact).ok().flatten());
+        for violation in code_size_violations(runtime_bytecode.len(), initcode_len) {
+            warn!("{} ({:?}): {}", contract.name, contract.address, violation);
+        }
+        code.insert(contract.address, Bytes::from(runtime_bytecode));
+    }
+    Ok(code)
+}
+
 /// Deploy contracts using BSC-style direct bytecode deployment
-fn deploy_bsc_style(byte_code_dir: &str, total_stake: U256) -> InMemoryDB {
+fn deploy_bsc_style(
+    contracts: &ContractsManifest,
+    runtime_code: &BTreeMap<Address, Bytes>,
+    total_stake: U256,
+    call_funding: &BTreeMap<Address, U256>,
+) -> InMemoryDB {
     let mut db = InMemoryDB::default();
 
     // Add system address with sufficient balance to fund Genesis.initialize (payable)
@@ -28,12 +86,9 @@ fn deploy_bsc_style(byte_code_dir: &str, total_stake: U256) -> InMemoryDB {
         ..AccountInfo::default()
     });
 
-    for (contract_name, target_address) in CONTRACTS {
-        let hex_path = format!("{}/{}.hex", byte_code_dir, contract_name);
-        let bytecode_hex = read_hex_from_file(&hex_path);
-
-        // For BSC style, we need to extract runtime bytecode from constructor bytecode
-        let runtime_bytecode = extract_runtime_bytecode(&bytecode_hex);
+    for contract in &contracts.contracts {
+        let (contract_name, target_address) = (contract.name.as_str(), contract.address);
+        let runtime_bytecode = runtime_code[&target_address].clone();
 
         // Set balance for Genesis contract (needs to fund validator stake pools)
         let balance = if contract_name == "Genesis" {
@@ -47,7 +102,7 @@ fn deploy_bsc_style(byte_code_dir: &str, total_stake: U256) -> InMemoryDB {
         db.insert_account_info(
             target_address,
             AccountInfo {
-                code: Some(Bytecode::new_raw(Bytes::from(runtime_bytecode))),
+                code: Some(Bytecode::new_raw(runtime_bytecode)),
                 balance,
                 ..AccountInfo::default()
             },
@@ -66,9 +121,35 @@ fn deploy_bsc_style(byte_code_dir: &str, total_stake: U256) -> InMemoryDB {
         }
     }
 
+
+    // Callers of preGenesisCalls/postGenesisCalls hold exactly the value they send
+    for (caller, amount) in call_funding {
+        let mut info = db.basic_ref(*caller).ok().flatten().unwrap_or_default();
+        info.balance += *amount;
+        db.insert_account_info(*caller, info);
+    }
+
     db
 }
 
+/// Read a contract's artifact, link its libraries and fill in its configured immutables
+pub fn load_runtime_bytecode(
+    byte_code_dir: &str,
+    contract: &SystemContract,
+    libraries: &BTreeMap<String, Address>,
+) -> Result<Vec<u8>> {
+    let bytecode_hex = read_hex_from_file(&contract.hex_path(byte_code_dir));
+    let bytecode_hex = link_libraries(&bytecode_hex, libraries)
+        .context(format!("Failed to link libraries of {}", contract.name))?;
+    // For BSC style, we need to extract runtime bytecode from constructor bytecode
+    let mut runtime_bytecode = extract_runtime_bytecode(&bytecode_hex);
+    if let Some(immutables) = &contract.immutables {
+        patch_immutables(&mut runtime_bytecode, immutables)
+            .context(format!("Failed to patch immutables of {}", contract.name))?;
+    }
+    Ok(runtime_bytecode)
+}
+
 /// Extract runtime bytecode from constructor bytecode
 /// This is a simplified implementation - the bytecode should already be runtime bytecode
 fn extract_runtime_bytecode(constructor_bytecode: &str) -> Vec<u8> {
@@ -115,17 +196,42 @@ pub fn prepare_env(chain_id: u64) -> Env {
     env
 }
 
+/// [`prepare_env`] for the config's chain, with the block fields of its `evmEnv`
+pub fn config_env(config: &GenesisConfig) -> Env {
+    let mut env = prepare_env(config.chain_id);
+    if let Some(evm_env) = &config.evm_env {
+        evm_env.apply(&mut env, config.spec_id());
+    }
+    env
+}
+
 /// Transaction builder for genesis initialization
 struct GenesisTransactionBuilder {
     transactions: Vec<TxEnv>,
 }
 
 impl GenesisTransactionBuilder {
-    fn new(config: &GenesisConfig) -> Self {
-        // Genesis.initialize is the only transaction needed
-        // It handles all contract initialization internally
-        let transactions = vec![call_genesis_initialize(GENESIS_ADDR, config)];
-        Self { transactions }
+    fn new(config: &GenesisConfig, initialize_call_data: Option<Vec<u8>>) -> Result<Self> {
+        // Genesis.initialize handles all contract initialization internally;
+        // configured hook calls run around it. A validator set split into
+        // batches goes through the chunked initializer instead.
+        let batches = validator_batches(config);
+        let initialize = match initialize_call_data {
+            _ if batches.len() > 1 => chunked_genesis_initialize_txns(GENESIS_ADDR, config, &batches)?,
+            Some(call_data) => vec![genesis_initialize_txn(GENESIS_ADDR, config, call_data)?],
+            None => vec![call_genesis_initialize(GENESIS_ADDR, config)?],
+        };
+        let hook = |(label, call): (String, &GenesisCall)| {
+            info!("{}: {} on {}", label, call.signature, call.target);
+            call.to_txn().context(label)
+        };
+        let (pre, post): (Vec<_>, Vec<_>) = genesis_calls(config)
+            .into_iter()
+            .partition(|(label, _)| label.starts_with("pre"));
+        let mut transactions = pre.into_iter().map(hook).collect::<Result<Vec<_>>>()?;
+        transactions.extend(initialize);
+        transactions.extend(post.into_iter().map(hook).collect::<Result<Vec<_>>>()?);
+        Ok(Self { transactions })
     }
 
     fn build(self) -> Vec<TxEnv> {
@@ -137,33 +243,248 @@ impl GenesisTransactionBuilder {
     }
 }
 
-/// Build genesis transactions
-fn build_genesis_transactions(config: &GenesisConfig) -> Vec<TxEnv> {
-    GenesisTransactionBuilder::new(config).build()
+/// Build genesis transactions; `initialize_call_data` replaces the calldata
+/// encoded from the built-in bindings
+fn build_genesis_transactions(
+    config: &GenesisConfig,
+    initialize_call_data: Option<Vec<u8>>,
+) -> Result<Vec<TxEnv>> {
+    Ok(GenesisTransactionBuilder::new(config, initialize_call_data)?.build())
+}
+
+/// File in the output directory that holds the executed state between stages
+pub const BUILD_STATE_FILE: &str = "genesis_state.json";
+
+/// Everything genesis execution depends on, loaded before running it
+pub struct BuildInputs {
+    pub runtime_code: BTreeMap<Address, Bytes>,
+    pub transactions: Vec<TxEnv>,
+    pub total_stake: U256,
+    /// Value each hook call caller sends, credited to it before execution
+    pub call_funding: BTreeMap<Address, U256>,
+    /// Genesis.initialize was encoded from the artifact ABI (`--dynamic-abi`)
+    pub dynamic_abi: bool,
+    /// sha256 over the code, transactions, chain ID, spec and tool version;
+    /// equal hashes execute to the same state
+    pub hash: String,
+}
+
+impl BuildInputs {
+    pub fn prepare(
+        byte_code_dir: &str,
+        contracts: &ContractsManifest,
+        config: &GenesisConfig,
+        initialize_call_data: Option<Vec<u8>>,
+    ) -> Result<Self> {
+        let dynamic_abi = initialize_call_data.is_some();
+        let runtime_code = load_contract_code(byte_code_dir, contracts)?;
+        let mut transactions =
+            multisig::deployment_txns(byte_code_dir, config).context("governanceMultisig")?;
+        if !transactions.is_empty() {
+            info!("Prepending governance multisig deployment and setup");
+        }
+        transactions.extend(
+            build_genesis_transactions(config, initialize_call_data).context("Genesis.initialize")?,
+        );
+        let vesting_txns = vesting::deployment_txns(byte_code_dir, config).context("vesting")?;
+        if !vesting_txns.is_empty() {
+            info!("Appending {} vesting contract deployment(s)", vesting_txns.len());
+        }
+        transactions.extend(vesting_txns);
+        let replay_txns = broadcast::replay_txns(config).context("replayBroadcast")?;
+        if !replay_txns.is_empty() {
+            info!("Appending {} replayed broadcast transaction(s)", replay_txns.len());
+        }
+        transactions.extend(replay_txns.iter().cloned());
+        let total_stake = calculate_total_stake(config)?;
+        let mut call_funding = BTreeMap::new();
+        for (_, call) in genesis_calls(config) {
+            let (Ok(caller), Ok(value)) = (call.caller_address(), call.value_wei()) else {
+                continue;
+            };
+            if value > U256::ZERO {
+                *call_funding.entry(caller).or_insert(U256::ZERO) += value;
+            }
+        }
+        // The vesting deployer sends each schedule's amount with its deployment
+        let vesting_total = vesting::total_vesting(config);
+        if vesting_total > U256::ZERO {
+            *call_funding.entry(vesting::VESTING_DEPLOYER).or_insert(U256::ZERO) += vesting_total;
+        }
+        // Broadcast senders hold exactly the value they send, like hook callers
+        for tx in replay_txns.iter().filter(|tx| tx.value > U256::ZERO) {
+            *call_funding.entry(tx.caller).or_insert(U256::ZERO) += tx.value;
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        hasher.update(config.chain_id.to_be_bytes());
+        hasher.update(format!("{:?}", config.spec_id()));
+        // Block fields `config_env` applies to every genesis transaction
+        hasher.update(serde_json::to_vec(&config.evm_env).expect("evmEnv serializes"));
+        hasher.update(total_stake.to_be_bytes::<32>());
+        for (address, code) in &runtime_code {
+            hasher.update(address);
+            hasher.update(code);
+        }
+        for tx in &transactions {
+            hasher.update(format!("{:?} {:?} {}", tx.caller, tx.transact_to, tx.value));
+            hasher.update(&tx.data);
+        }
+        Ok(Self {
+            runtime_code,
+            transactions,
+            total_stake,
+            call_funding,
+            dynamic_abi,
+            hash: hex::encode(hasher.finalize()),
+        })
+    }
+}
+
+/// Executed genesis persisted between `generate --build-state-only` and
+/// `assemble`, and reused by `generate --resume`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildState {
+    pub inputs_hash: String,
+    pub byte_code_dir: String,
+    pub contracts: ContractsManifest,
+    pub runtime_code: BTreeMap<Address, Bytes>,
+    pub total_stake: U256,
+    #[serde(default)]
+    pub call_funding: BTreeMap<Address, U256>,
+    /// `--evm-spec` the state was executed under, over the config's `evmSpec`
+    #[serde(default)]
+    pub evm_spec: Option<EvmSpec>,
+    #[serde(default)]
+    pub dynamic_abi: bool,
+    /// State changes of the genesis transactions, before system accounts are
+    /// cleaned up
+    pub bundle_state: BundleState,
+    /// Accounts from outside execution (forked state, extraAlloc) as info and storage
+    pub extra_accounts: HashMap<Address, (AccountInfo, HashMap<U256, U256>)>,
 }
 
+impl BuildState {
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).context(format!("Failed to open {}", path.display()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .context(format!("Failed to decode build state {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let file = File::create(path).context(format!("Failed to create {}", path.display()))?;
+        serde_json::to_writer(BufWriter::new(file), self)
+            .context(format!("Failed to write build state {}", path.display()))?;
+        info!("Build state written to {}", path.display());
+        Ok(())
+    }
+
+    pub fn set_extra_accounts(&mut self, accounts: &HashMap<Address, PlainAccount>) {
+        self.extra_accounts = accounts
+            .iter()
+            .map(|(address, account)| {
+                let storage = account.storage.iter().map(|(k, v)| (*k, *v)).collect();
+                (*address, (account.info.clone(), storage))
+            })
+            .collect();
+    }
+
+    /// The executed state as the alloc is written: each account from outside
+    /// execution replaces the executed account at its address, storage included
+    pub fn alloc_state(&self) -> BundleState {
+        let mut alloc_state = self.bundle_state.clone();
+        for (address, (info, storage)) in &self.extra_accounts {
+            if let Some(code) = &info.code {
+                alloc_state.contracts.insert(info.code_hash, code.clone());
+            }
+            let storage = storage
+                .iter()
+                .map(|(slot, value)| (*slot, StorageSlot::new_changed(U256::ZERO, *value)))
+                .collect();
+            // Destroyed and recreated: slots the override lacks read as zero
+            alloc_state.state.insert(
+                *address,
+                BundleAccount::new(None, Some(info.clone()), storage, AccountStatus::DestroyedChanged),
+            );
+        }
+        alloc_state
+    }
+
+    /// Database the system contracts were deployed into before execution
+    pub fn deployed_db(&self) -> InMemoryDB {
+        deploy_bsc_style(
+            &self.contracts,
+            &self.runtime_code,
+            self.total_stake,
+            &self.call_funding,
+        )
+    }
+}
+
+/// Run both stages without persisting the state in between
 pub fn genesis_generate(
     byte_code_dir: &str,
+    contracts: &ContractsManifest,
     output_dir: &str,
     config: &GenesisConfig,
-) -> (InMemoryDB, BundleState) {
+    extra_accounts: &HashMap<Address, PlainAccount>,
+    initialize_call_data: Option<Vec<u8>>,
+    trace: bool,
+) -> Result<AssembledGenesis> {
+    let inputs = BuildInputs::prepare(byte_code_dir, contracts, config, initialize_call_data)?;
+    let state = build_state(
+        byte_code_dir,
+        contracts,
+        output_dir,
+        config,
+        inputs,
+        extra_accounts,
+        trace,
+    )?;
+    assemble_genesis(&state, config, output_dir, true)
+}
+
+/// Deploy the system contracts and execute the genesis transactions; a failed
+/// transaction prints its decoded call trace and aborts. Writes the gas profile.
+pub fn build_state(
+    byte_code_dir: &str,
+    contracts: &ContractsManifest,
+    output_dir: &str,
+    config: &GenesisConfig,
+    inputs: BuildInputs,
+    extra_accounts: &HashMap<Address, PlainAccount>,
+    trace: bool,
+) -> Result<BuildState> {
     info!("=== Starting Genesis deployment and initialization ===");
 
     // Calculate total stake needed for Genesis contract
-    let total_stake = calculate_total_stake(config);
+    let total_stake = inputs.total_stake;
     info!("Total stake required: {} wei", total_stake);
 
-    let db = deploy_bsc_style(byte_code_dir, total_stake);
+    let db = deploy_bsc_style(
+        contracts,
+        &inputs.runtime_code,
+        total_stake,
+        &inputs.call_funding,
+    );
 
-    let env = prepare_env(config.chain_id);
+    let env = config_env(config);
 
-    let txs = build_genesis_transactions(config);
+    let txs = inputs.transactions;
 
-    let r = execute_revm_sequential(db.clone(), SpecId::LATEST, env.clone(), &txs, None);
-    let (result, mut bundle_state) = match r {
-        Ok((result, bundle_state)) => {
+    let r = execute_revm_sequential_traced(
+        &db,
+        config.spec_id(),
+        env.clone(),
+        PrecompileMode::default(),
+        &txs,
+        None,
+    );
+    let (result, bundle_state, traces) = match r {
+        Ok((result, bundle_state, traces)) => {
             info!("=== Genesis initialization successful ===");
-            (result, bundle_state)
+            (result, bundle_state, traces)
         }
         Err(e) => {
             panic!(
@@ -173,12 +494,23 @@ pub fn genesis_generate(
         }
     };
     debug!("the bundle state is {:?}", bundle_state);
-    let ret = (db, bundle_state.clone());
 
+    let abi = AbiRegistry::load(byte_code_dir, contracts);
     for (i, r) in result.iter().enumerate() {
+        if !r.is_success() || trace {
+            println!(
+                "Call trace of transaction {}:\n{}",
+                i + 1,
+                format_trace(&traces[i], &abi)
+            );
+        }
         if !r.is_success() {
             error!("=== Transaction {} failed ===", i + 1);
-            println!("Detailed analysis: {}", analyze_txn_result(r));
+            let source_maps = SourceMaps::load(byte_code_dir, contracts);
+            let location = revert_origin(&traces[i]).and_then(|frame| {
+                source_maps.locate(&frame.to?, frame.pc)
+            });
+            println!("Detailed analysis: {}", analyze_txn_result_at(r, location.as_ref()));
             panic!("Genesis transaction {} failed", i + 1);
         } else {
             info!("Detailed analysis: {}", analyze_txn_result(r));
@@ -189,19 +521,227 @@ pub fn genesis_generate(
         result.len()
     );
 
+    let total_gas_used = result.iter().map(|r| r.gas_used()).sum();
+    let profile = profile_genesis(&traces.concat(), total_gas_used, &bundle_state, &abi);
+    log_profile(&profile);
+    let path = format!("{output_dir}/genesis_profile.json");
+    serde_json::to_writer_pretty(
+        BufWriter::new(File::create(&path).context(format!("Failed to create {}", path))?),
+        &profile,
+    )
+    .context(format!("Failed to write {}", path))?;
+
+    let mut state = BuildState {
+        inputs_hash: inputs.hash,
+        byte_code_dir: byte_code_dir.to_string(),
+        contracts: contracts.clone(),
+        runtime_code: inputs.runtime_code,
+        total_stake,
+        call_funding: inputs.call_funding,
+        evm_spec: config.evm_spec,
+        dynamic_abi: inputs.dynamic_abi,
+        bundle_state,
+        extra_accounts: HashMap::new(),
+    };
+    state.set_extra_accounts(extra_accounts);
+    Ok(state)
+}
+
+/// Drop storage slots whose value is zero and accounts left with no balance,
+/// nonce, code or storage; neither is part of the state trie, so the state
+/// root is unchanged. Returns the number of slots and accounts removed.
+fn prune_genesis_state(genesis_state: &mut HashMap<Address, PlainAccount>) -> (usize, usize) {
+    let mut slots = 0;
+    for account in genesis_state.values_mut() {
+        let before = account.storage.len();
+        account.storage.retain(|_, value| !value.is_zero());
+        slots += before - account.storage.len();
+    }
+    let before = genesis_state.len();
+    genesis_state.retain(|_, account| {
+        let info = &account.info;
+        !(info.balance.is_zero()
+            && info.nonce == 0
+            && info.code.as_ref().is_none_or(|code| code.is_empty())
+            && account.storage.is_empty())
+    });
+    (slots, before - genesis_state.len())
+}
+
+/// Database with the system contracts deployed and the genesis callers funded,
+/// before any genesis transaction runs
+pub fn deployment_db(contracts: &ContractsManifest, inputs: &BuildInputs) -> InMemoryDB {
+    deploy_bsc_style(
+        contracts,
+        &inputs.runtime_code,
+        inputs.total_stake,
+        &inputs.call_funding,
+    )
+}
+
+/// Deploy the system contracts and execute the genesis transactions in memory,
+/// without traces or output files; returns the deployment database and the
+/// state changes, or the first transaction that failed
+pub fn execute_genesis(
+    contracts: &ContractsManifest,
+    config: &GenesisConfig,
+    inputs: BuildInputs,
+) -> Result<(InMemoryDB, BundleState)> {
+    let db = deployment_db(contracts, &inputs);
+    let bundle_state = execute_transactions(&db, config, &inputs.transactions)?;
+    Ok((db, bundle_state))
+}
+
+/// Execute the genesis transactions against a deployment database; returns
+/// the state changes, or the first transaction that failed
+pub fn execute_transactions(
+    db: &InMemoryDB,
+    config: &GenesisConfig,
+    transactions: &[TxEnv],
+) -> Result<BundleState> {
+    let (results, bundle_state, _) = execute_revm_sequential_traced(
+        db,
+        config.spec_id(),
+        config_env(config),
+        PrecompileMode::default(),
+        transactions,
+        None,
+    )
+    .map_err(|e| anyhow::anyhow!("{:?}", e.map_db_err(|_| "Database error".to_string())))?;
+    if let Some((i, result)) = results.iter().enumerate().find(|(_, r)| !r.is_success()) {
+        anyhow::bail!(
+            "Genesis transaction {} failed: {}",
+            i + 1,
+            analyze_txn_result(result)
+        );
+    }
+    Ok(bundle_state)
+}
+
+/// How the alloc treats an account that only exists to run genesis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaffoldingPolicy {
+    /// Funding or deployer account; dropped from the alloc
+    Remove,
+    /// Keeps its code and storage; what is left of its funding buffer is zeroed
+    ZeroBalance,
+    /// Hook call caller; its nonce is rewound by the transactions it sent
+    RewindNonce(u64),
+}
+
+/// Scaffolding accounts of genesis execution and what happens to each
+pub fn scaffolding_accounts(config: &GenesisConfig) -> BTreeMap<Address, ScaffoldingPolicy> {
+    let mut accounts = BTreeMap::new();
+    for (_, call) in genesis_calls(config) {
+        if let Ok(caller) = call.caller_address() {
+            let policy = accounts
+                .entry(caller)
+                .or_insert(ScaffoldingPolicy::RewindNonce(0));
+            if let ScaffoldingPolicy::RewindNonce(sent) = policy {
+                *sent += 1;
+            }
+        }
+    }
+    accounts.insert(GENESIS_ADDR, ScaffoldingPolicy::ZeroBalance);
+    for deployer in [SYSTEM_CALLER, multisig::MULTISIG_DEPLOYER, vesting::VESTING_DEPLOYER] {
+        accounts.insert(deployer, ScaffoldingPolicy::Remove);
+    }
+    accounts
+}
+
+/// Apply [`scaffolding_accounts`] to the executed state so no balance, nonce
+/// or account that only served execution reaches the alloc
+fn finalize_scaffolding(bundle_state: &mut BundleState, config: &GenesisConfig) {
+    for (address, policy) in scaffolding_accounts(config) {
+        match policy {
+            ScaffoldingPolicy::Remove => {
+                let removed = bundle_state.state.remove(&address);
+                if let Some(info) = removed.and_then(|account| account.info) {
+                    debug!(
+                        "Removed scaffolding account {:?} (balance {} wei, nonce {})",
+                        address, info.balance, info.nonce
+                    );
+                }
+            }
+            ScaffoldingPolicy::ZeroBalance => {
+                let info = bundle_state
+                    .state
+                    .get_mut(&address)
+                    .and_then(|account| account.info.as_mut());
+                if let Some(info) = info.filter(|info| info.balance > U256::ZERO) {
+                    // Genesis.initialize forwards the stake it is sent to the
+                    // StakePools; the funding buffer must not leak to mainnet
+                    info!(
+                        "Zeroing out scaffolding balance of {:?}: {} wei",
+                        address, info.balance
+                    );
+                    info.balance = U256::ZERO;
+                }
+            }
+            ScaffoldingPolicy::RewindNonce(sent) => {
+                let info = bundle_state
+                    .state
+                    .get_mut(&address)
+                    .and_then(|account| account.info.as_mut());
+                if let Some(info) = info {
+                    info.nonce = info.nonce.saturating_sub(sent);
+                    if info.balance > U256::ZERO {
+                        warn!(
+                            "Hook call caller {:?} keeps {} wei after genesis execution",
+                            address, info.balance
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Genesis state handed to the post-genesis checks
+pub struct AssembledGenesis {
+    /// Database the system contracts were deployed into before execution
+    pub db: InMemoryDB,
+    /// Uncleaned state changes of the genesis transactions
+    pub bundle_state: BundleState,
+    /// `bundle_state` with forked, imported and `extraAlloc` accounts in place
+    /// of the executed ones, as the alloc is written; see [`BuildState::alloc_state`]
+    pub alloc_state: BundleState,
+}
+
+/// Write the genesis outputs of an executed state, pruning zero slots and empty
+/// accounts unless `prune` is false; returns the states for post-genesis checks
+pub fn assemble_genesis(
+    state: &BuildState,
+    config: &GenesisConfig,
+    output_dir: &str,
+    prune: bool,
+) -> Result<AssembledGenesis> {
+    let contracts = &state.contracts;
+    let ret = AssembledGenesis {
+        db: state.deployed_db(),
+        bundle_state: state.bundle_state.clone(),
+        alloc_state: state.alloc_state(),
+    };
+    let mut bundle_state = state.bundle_state.clone();
+
     // Add deployed contracts to the final state
     let mut genesis_state = HashMap::new();
 
-    for (contract_name, contract_address) in CONTRACTS {
-        let hex_path = format!("{}/{}.hex", byte_code_dir, contract_name);
-        let bytecode_hex = read_hex_from_file(&hex_path);
-        let runtime_bytecode = extract_runtime_bytecode(&bytecode_hex);
+    for contract in &contracts.contracts {
+        let (contract_name, contract_address) = (contract.name.as_str(), contract.address);
+        let Some(runtime_bytecode) = state.runtime_code.get(&contract_address).cloned() else {
+            anyhow::bail!(
+                "Build state has no runtime code for {} ({:?})",
+                contract_name,
+                contract_address
+            );
+        };
 
         genesis_state.insert(
             contract_address,
             PlainAccount {
                 info: AccountInfo {
-                    code: Some(Bytecode::new_raw(Bytes::from(runtime_bytecode))),
+                    code: Some(Bytecode::new_raw(runtime_bytecode)),
                     ..AccountInfo::default()
                 },
                 storage: Default::default(),
@@ -214,32 +754,16 @@ pub fn genesis_generate(
         );
     }
 
-    // Add any state changes from the bundle_state (from the initialize transaction)
-    // Remove system accounts that should NOT carry balance into genesis:
-    // 1. SYSTEM_CALLER — funding account used only during genesis execution
-    bundle_state.state.remove(&SYSTEM_CALLER);
-
-    // 2. GENESIS_ADDR — buffer balance used during initialize() should be zeroed out.
-    //    Genesis.initialize() transfers all validator stakes to StakePools;
-    //    any remaining balance is a phantom artifact that must not leak to mainnet.
-    if let Some(genesis_account) = bundle_state.state.get_mut(&GENESIS_ADDR) {
-        if let Some(ref mut info) = genesis_account.info {
-            if info.balance > U256::ZERO {
-                warn!(
-                    "Zeroing out Genesis contract phantom balance: {} wei",
-                    info.balance
-                );
-                info.balance = U256::ZERO;
-            }
-        }
-    }
+    // Add any state changes from the bundle_state (from the initialize transaction),
+    // without what the scaffolding accounts were given to run it
+    finalize_scaffolding(&mut bundle_state, config);
 
     // Safety scan: warn about any unexpected non-zero balances in system contracts
     for (addr, account) in &bundle_state.state {
         if let Some(ref info) = account.info {
             // StakePool addresses are expected to hold stake — skip them
             // System contracts should generally have zero balance
-            let is_system_contract = CONTRACTS.iter().any(|(_, a)| a == addr);
+            let is_system_contract = contracts.contains_address(addr);
             if is_system_contract && info.balance > U256::ZERO {
                 warn!(
                     "Unexpected non-zero balance at system contract {:?}: {} wei",
@@ -250,16 +774,17 @@ pub fn genesis_generate(
     }
 
     // write bundle state into one json file named bundle_state.json
+    let path = format!("{output_dir}/bundle_state.json");
     serde_json::to_writer_pretty(
-        BufWriter::new(File::create(format!("{output_dir}/bundle_state.json")).unwrap()),
+        BufWriter::new(File::create(&path).context(format!("Failed to create {}", path))?),
         &bundle_state,
     )
-    .unwrap();
+    .context(format!("Failed to write {}", path))?;
 
     info!(
         "bundle state size is {:?}, contracts size {:?}",
         bundle_state.state.len(),
-        CONTRACTS.len()
+        contracts.contracts.len()
     );
     for (address, account) in bundle_state.state.into_iter() {
         debug!("Address: {:?}, account: {:?}", address, account);
@@ -280,11 +805,74 @@ pub fn genesis_generate(
         }
     }
 
-    serde_json::to_writer_pretty(
-        BufWriter::new(File::create(format!("{output_dir}/genesis_accounts.json")).unwrap()),
-        &genesis_state,
-    )
-    .unwrap();
+    // Accounts supplied from outside the genesis execution (e.g. forked chain state)
+    // take precedence over the freshly initialized state
+    for (address, (info, storage)) in &state.extra_accounts {
+        let account = PlainAccount {
+            info: info.clone(),
+            storage: storage.iter().map(|(k, v)| (*k, *v)).collect(),
+        };
+        if genesis_state.insert(*address, account).is_some() {
+            if contracts.contains_address(address) {
+                warn!(
+                    "Overriding system contract {:?} with external state; post-genesis checks read the override",
+                    address
+                );
+            } else {
+                info!("Overriding generated state of {:?} with external account", address);
+            }
+        }
+    }
+
+    // Overrides must not leave a system contract without code
+    for contract in &contracts.contracts {
+        let (contract_name, contract_address) = (contract.name.as_str(), contract.address);
+        let has_code = genesis_state
+            .get(&contract_address)
+            .and_then(|account| account.info.code.as_ref())
+            .is_some_and(|code| !code.is_empty());
+        if !has_code {
+            anyhow::bail!(
+                "System contract {} ({:?}) is missing from the genesis state",
+                contract_name, contract_address
+            );
+        }
+    }
+
+    // Linked code delegatecalls into its libraries, which must be deployed too
+    for (library_name, library_address) in &contracts.libraries {
+        let has_code = genesis_state
+            .get(library_address)
+            .and_then(|account| account.info.code.as_ref())
+            .is_some_and(|code| !code.is_empty());
+        if !has_code {
+            anyhow::bail!(
+                "Linked library {} ({:?}) has no code in the genesis state",
+                library_name, library_address
+            );
+        }
+    }
+
+    let mut accounts_json = serde_json::to_vec_pretty(&checksummed_keys(&genesis_state))?;
+    if prune {
+        let (slots, accounts) = prune_genesis_state(&mut genesis_state);
+        if slots > 0 || accounts > 0 {
+            let pruned = serde_json::to_vec_pretty(&checksummed_keys(&genesis_state))?;
+            info!(
+                "Pruned {} zero storage slot(s) and {} empty account(s); genesis_accounts.json {} -> {} bytes",
+                slots,
+                accounts,
+                accounts_json.len(),
+                pruned.len()
+            );
+            accounts_json = pruned;
+        }
+    }
+    let footprint = FootprintReport::new(&genesis_state, contracts, accounts_json.len());
+    let path = format!("{output_dir}/genesis_accounts.json");
+    fs::write(&path, accounts_json).context(format!("Failed to write {}", path))?;
+    footprint.log();
+    write_footprint_report(&footprint, output_dir)?;
 
     // Create contracts JSON with bytecode
     let contracts_json: HashMap<_, _> = genesis_state
@@ -298,10 +886,11 @@ pub fn genesis_generate(
         })
         .collect();
 
+    let path = format!("{output_dir}/genesis_contracts.json");
     serde_json::to_writer_pretty(
-        BufWriter::new(File::create(format!("{output_dir}/genesis_contracts.json")).unwrap()),
-        &contracts_json,
+        BufWriter::new(File::create(&path).context(format!("Failed to create {}", path))?),
+        &checksummed_keys(&contracts_json),
     )
-    .unwrap();
-    ret
+    .context(format!("Failed to write {}", path))?;
+    Ok(ret)
 }
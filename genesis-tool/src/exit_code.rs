@@ -0,0 +1,74 @@
+//! Process exit codes by failure category
+//!
+//! `generate`, `assemble` and `verify` mark the phase they are in with
+//! [`enter`]; a failure is reported with the exit code of the phase it happened
+//! in, whether it surfaced as an error returned from `main` or as a panic. An
+//! I/O error after the inputs were read counts as [`Phase::Io`], so a full disk
+//! is not mistaken for a failed verification. Other commands exit with 1.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Failure categories, with their exit codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Phase {
+    /// Not attributed to a phase
+    Other = 1,
+    /// Loading, parsing or validating the genesis config
+    Config = 2,
+    /// Contract manifest, `.hex` artifacts and ABI bindings
+    Bytecode = 3,
+    /// Executing the genesis transactions
+    Execution = 4,
+    /// Post-genesis verification, simulations and `verify`
+    Verification = 5,
+    /// Reading or writing files outside the phases above
+    Io = 6,
+}
+
+impl Phase {
+    fn from_code(code: u8) -> Self {
+        match code {
+            2 => Phase::Config,
+            3 => Phase::Bytecode,
+            4 => Phase::Execution,
+            5 => Phase::Verification,
+            6 => Phase::Io,
+            _ => Phase::Other,
+        }
+    }
+
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+static PHASE: AtomicU8 = AtomicU8::new(Phase::Other as u8);
+
+/// Attribute failures from here on to `phase`
+pub fn enter(phase: Phase) {
+    PHASE.store(phase as u8, Ordering::Relaxed);
+}
+
+pub fn current() -> Phase {
+    Phase::from_code(PHASE.load(Ordering::Relaxed))
+}
+
+/// Exit code for an error returned from `main`
+pub fn for_error(error: &anyhow::Error) -> u8 {
+    let phase = current();
+    let io = error.chain().any(|cause| cause.is::<std::io::Error>());
+    match phase {
+        Phase::Execution | Phase::Verification if io => Phase::Io.code(),
+        phase => phase.code(),
+    }
+}
+
+/// Exit a panicking process with the code of the current phase instead of 101
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        std::process::exit(current().code() as i32);
+    }));
+}
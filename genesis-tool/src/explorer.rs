@@ -0,0 +1,208 @@
+//! Source-verification payloads for block explorers
+//!
+//! `generate --verification-bundle` writes, for each system contract with a
+//! forge artifact, the solc standard JSON input that reproduces its bytecode,
+//! so Blockscout or Etherscan can verify the pre-deployed contracts at launch.
+//! The input is rebuilt from the artifact's solc metadata: compiler settings
+//! as compiled (with the registry's `libraries` linked in), and every source
+//! file read from the forge project root, the parent of `out/`, and checked
+//! against the keccak256 the metadata recorded. `index.json` lists each
+//! contract's address, fully qualified name, compiler version and payload file.
+//!
+//! System contracts are placed as runtime code, so no constructor ran and the
+//! constructor arguments are empty; contracts whose immutables the registry
+//! fills are flagged, since explorers compare those bytes too.
+
+use alloy_primitives::{keccak256, Address, B256};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeMap, fs, path::Path};
+use tracing::{info, warn};
+
+use crate::{
+    abi::read_artifact,
+    registry::{ContractsManifest, SystemContract},
+};
+
+/// Subdirectory of the output directory the bundle is written to
+pub const VERIFICATION_DIR: &str = "verification";
+
+pub const VERIFICATION_INDEX_FILE: &str = "index.json";
+
+/// Outputs requested in the standard JSON input, enough to compare bytecode
+const OUTPUT_SELECTION: [&str; 4] = ["abi", "evm.bytecode", "evm.deployedBytecode", "metadata"];
+
+/// Index entry of one contract's payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationEntry {
+    pub name: String,
+    pub address: Address,
+    /// `<source path>:<contract name>`, as explorers ask for it
+    pub contract_name: String,
+    /// solc version with the `v` prefix, e.g. `v0.8.30+commit.73712a01`
+    pub compiler_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Always `0x`: system contracts are placed as runtime code
+    pub constructor_arguments: String,
+    /// The registry patches immutables into the runtime code
+    pub patched_immutables: bool,
+    /// Standard JSON input file, relative to the bundle directory
+    pub standard_json_input: String,
+}
+
+/// solc metadata of a forge artifact: `rawMetadata`, else `metadata`
+fn artifact_metadata(artifact: &Value) -> Option<Value> {
+    match artifact.get("rawMetadata").and_then(Value::as_str) {
+        Some(raw) => serde_json::from_str(raw).ok(),
+        None => artifact.get("metadata").filter(|m| m.is_object()).cloned(),
+    }
+}
+
+/// Standard JSON input and index entry of one contract
+fn verification_payload(
+    contract: &SystemContract,
+    metadata: &Value,
+    project_root: &Path,
+    libraries: &BTreeMap<String, Address>,
+) -> Result<(Value, VerificationEntry)> {
+    let compiler_version = metadata
+        .pointer("/compiler/version")
+        .and_then(Value::as_str)
+        .context("metadata has no compiler.version")?;
+    let mut settings = metadata
+        .get("settings")
+        .and_then(Value::as_object)
+        .cloned()
+        .context("metadata has no settings")?;
+    let (source_path, contract_name) = settings
+        .remove("compilationTarget")
+        .and_then(|target| target.as_object()?.iter().next().map(|(p, n)| (p.clone(), n.clone())))
+        .and_then(|(path, name)| Some((path, name.as_str()?.to_string())))
+        .context("metadata has no compilationTarget")?;
+
+    // Metadata keys libraries as "<path>:<name>", standard JSON nests them
+    let mut linked: Map<String, Value> = Map::new();
+    let compiled = settings.remove("libraries").unwrap_or_default();
+    let compiled = compiled.as_object().into_iter().flatten().map(|(k, v)| (k.clone(), v.clone()));
+    let configured = libraries.iter().map(|(k, v)| (k.clone(), json!(v)));
+    for (qualified, address) in compiled.chain(configured) {
+        let Some((path, name)) = qualified.rsplit_once(':') else {
+            continue;
+        };
+        linked
+            .entry(path.to_string())
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .expect("library entries are objects")
+            .insert(name.to_string(), address);
+    }
+    settings.insert("libraries".to_string(), Value::Object(linked));
+    settings.insert(
+        "outputSelection".to_string(),
+        json!({ "*": { "*": OUTPUT_SELECTION } }),
+    );
+
+    let mut sources = Map::new();
+    let mut license = None;
+    for (path, source) in metadata
+        .get("sources")
+        .and_then(Value::as_object)
+        .context("metadata has no sources")?
+    {
+        let content = match source.get("content").and_then(Value::as_str) {
+            Some(content) => content.to_string(),
+            None => fs::read_to_string(project_root.join(path))
+                .context(format!("Failed to read source {}", path))?,
+        };
+        if let Some(recorded) = source.get("keccak256").and_then(Value::as_str) {
+            let actual = keccak256(content.as_bytes());
+            if recorded.parse::<B256>().ok() != Some(actual) {
+                anyhow::bail!(
+                    "{} changed since it was compiled (keccak256 {}, recorded {}); rebuild the artifacts",
+                    path,
+                    actual,
+                    recorded
+                );
+            }
+        }
+        if path == &source_path {
+            license = source.get("license").and_then(Value::as_str).map(str::to_string);
+        }
+        sources.insert(path.clone(), json!({ "content": content }));
+    }
+
+    let input = json!({
+        "language": metadata.get("language").and_then(Value::as_str).unwrap_or("Solidity"),
+        "sources": sources,
+        "settings": settings,
+    });
+    let entry = VerificationEntry {
+        name: contract.name.clone(),
+        address: contract.address,
+        contract_name: format!("{}:{}", source_path, contract_name),
+        compiler_version: format!("v{}", compiler_version.trim_start_matches('v')),
+        license,
+        constructor_arguments: "0x".to_string(),
+        patched_immutables: contract.immutables.is_some(),
+        standard_json_input: format!("{}.json", contract.name),
+    };
+    Ok((input, entry))
+}
+
+/// Write a standard JSON input per system contract and the index to
+/// `<output_dir>/verification`; contracts without an artifact or whose sources
+/// cannot be reproduced are skipped with a warning. Returns the entries written.
+pub fn write_verification_bundle(
+    byte_code_dir: &str,
+    contracts: &ContractsManifest,
+    output_dir: &str,
+) -> Result<Vec<VerificationEntry>> {
+    let bundle_dir = Path::new(output_dir).join(VERIFICATION_DIR);
+    fs::create_dir_all(&bundle_dir).context(format!("Failed to create {}", bundle_dir.display()))?;
+    let out_dir = Path::new(byte_code_dir);
+    let project_root = out_dir
+        .canonicalize()
+        .ok()
+        .and_then(|dir| dir.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| out_dir.join(".."));
+
+    let mut entries = Vec::new();
+    for contract in &contracts.contracts {
+        let payload = read_artifact(byte_code_dir, &contract.name).and_then(|artifact| {
+            let metadata = artifact_metadata(&artifact)
+                .context("artifact has no solc metadata (build with forge's default extra_output)")?;
+            verification_payload(contract, &metadata, &project_root, &contracts.libraries)
+        });
+        let (input, entry) = match payload {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!("No verification payload for {}: {:#}", contract.name, e);
+                continue;
+            }
+        };
+        if entry.patched_immutables {
+            warn!(
+                "{} has patched immutables; explorers that compare them will not match the compiled code",
+                contract.name
+            );
+        }
+        let path = bundle_dir.join(&entry.standard_json_input);
+        fs::write(&path, serde_json::to_string_pretty(&input)? + "\n")
+            .context(format!("Failed to write {}", path.display()))?;
+        entries.push(entry);
+    }
+
+    let path = bundle_dir.join(VERIFICATION_INDEX_FILE);
+    fs::write(&path, serde_json::to_string_pretty(&entries)? + "\n")
+        .context(format!("Failed to write {}", path.display()))?;
+    info!(
+        "Wrote verification payloads for {} of {} system contract(s) to {}",
+        entries.len(),
+        contracts.contracts.len(),
+        bundle_dir.display()
+    );
+    Ok(entries)
+}
@@ -0,0 +1,316 @@
+//! Genesis files for Besu and Erigon
+//!
+//! Some partners run non-reth clients against archive copies of the chain.
+//! `export --format besu|erigon` translates a generated genesis.json into the
+//! target client's genesis schema: the alloc carries over account by account, so
+//! the state root is unchanged, with nonces as hex strings and storage keys and
+//! values padded to 32 bytes as both clients require. The chain config is
+//! mapped key by key; whatever the client cannot represent (Gravity forks,
+//! `baseFeeParams`, `extraFields`, unknown keys) is dropped and listed in the
+//! compatibility report.
+//!
+//! Gravity blocks come from its own consensus, so the exported chain is a
+//! merged one from genesis: Besu gets an empty `ethash` section and a zero
+//! terminal total difficulty, the way it configures post-merge networks.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+};
+use tracing::{info, warn};
+
+use crate::spec::GRAVITY_FORKS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Besu,
+    Erigon,
+}
+
+impl ExportFormat {
+    fn client(self) -> &'static str {
+        match self {
+            ExportFormat::Besu => "Besu",
+            ExportFormat::Erigon => "Erigon",
+        }
+    }
+}
+
+/// Chain config keys both clients read as reth does
+const SHARED_CONFIG_KEYS: [&str; 23] = [
+    "chainId",
+    "homesteadBlock",
+    "daoForkBlock",
+    "eip150Block",
+    "eip155Block",
+    "eip158Block",
+    "byzantiumBlock",
+    "constantinopleBlock",
+    "petersburgBlock",
+    "istanbulBlock",
+    "muirGlacierBlock",
+    "berlinBlock",
+    "londonBlock",
+    "arrowGlacierBlock",
+    "grayGlacierBlock",
+    "mergeNetsplitBlock",
+    "shanghaiTime",
+    "cancunTime",
+    "pragueTime",
+    "osakaTime",
+    "terminalTotalDifficulty",
+    "depositContractAddress",
+    "blobSchedule",
+];
+
+/// Mainnet EIP-1559 parameters, the only ones the clients apply
+const DEFAULT_BASE_FEE_PARAMS: (u64, u64) = (8, 2);
+
+/// A genesis field the target client cannot represent, or one the export added
+#[derive(Debug, Clone, Serialize)]
+pub struct CompatibilityNote {
+    pub field: String,
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReport {
+    pub format: ExportFormat,
+    pub accounts: usize,
+    /// Fields left out of the exported genesis
+    pub dropped: Vec<CompatibilityNote>,
+    /// Fields the client needs that the reth genesis has no counterpart of
+    pub added: Vec<CompatibilityNote>,
+}
+
+impl ExportReport {
+    fn new(format: ExportFormat) -> Self {
+        ExportReport {
+            format,
+            accounts: 0,
+            dropped: Vec::new(),
+            added: Vec::new(),
+        }
+    }
+
+    fn drop_field(&mut self, field: impl Into<String>, note: impl Into<String>) {
+        self.dropped.push(CompatibilityNote {
+            field: field.into(),
+            note: note.into(),
+        });
+    }
+
+    fn add_field(&mut self, field: impl Into<String>, note: impl Into<String>) {
+        self.added.push(CompatibilityNote {
+            field: field.into(),
+            note: note.into(),
+        });
+    }
+
+    pub fn log(&self) {
+        let client = self.format.client();
+        for note in &self.dropped {
+            warn!("⚠️  {} cannot represent {}: {}", client, note.field, note.note);
+        }
+        for note in &self.added {
+            info!("Added {} for {}: {}", note.field, client, note.note);
+        }
+        if self.dropped.is_empty() {
+            info!("✅ Every genesis field has a {} equivalent", client);
+        }
+    }
+}
+
+/// Map the reth chain config onto the client's, recording what is lost
+fn export_config(config: &Map<String, Value>, format: ExportFormat, report: &mut ExportReport) -> Map<String, Value> {
+    let client = format.client();
+    let mut exported = Map::new();
+    for (key, value) in config {
+        if value.is_null() {
+            continue;
+        }
+        match key.as_str() {
+            key if SHARED_CONFIG_KEYS.contains(&key) => {
+                exported.insert(key.to_string(), value.clone());
+            }
+            "terminalTotalDifficultyPassed" => match format {
+                ExportFormat::Erigon => {
+                    exported.insert(key.clone(), value.clone());
+                }
+                // Besu only knows the difficulty itself, handled below
+                ExportFormat::Besu => {}
+            },
+            "daoForkSupport" => {
+                if value != &Value::Bool(true) {
+                    report.drop_field(
+                        "config.daoForkSupport",
+                        format!("{} has no DAO fork opt-out; daoForkBlock applies the fork", client),
+                    );
+                }
+            }
+            "baseFeeParams" => {
+                let param = |name: &str| value.get(name).and_then(Value::as_u64);
+                let params = (param("maxChangeDenominator"), param("elasticityMultiplier"));
+                if params != (Some(DEFAULT_BASE_FEE_PARAMS.0), Some(DEFAULT_BASE_FEE_PARAMS.1)) {
+                    report.drop_field(
+                        "config.baseFeeParams",
+                        format!(
+                            "{} computes base fees with maxChangeDenominator {} and elasticityMultiplier {}, not {}; post-genesis block validation diverges",
+                            client, DEFAULT_BASE_FEE_PARAMS.0, DEFAULT_BASE_FEE_PARAMS.1, value
+                        ),
+                    );
+                }
+            }
+            "extraFields" => {
+                for field in value.as_object().into_iter().flat_map(Map::keys) {
+                    report.drop_field(
+                        format!("config.extraFields.{}", field),
+                        "reth-specific chain spec field",
+                    );
+                }
+            }
+            key if GRAVITY_FORKS.contains(&key) => {
+                report.drop_field(
+                    format!("config.{}", key),
+                    "Gravity fork; behaviour it gates is not reproduced",
+                );
+            }
+            _ => report.drop_field(format!("config.{}", key), "unknown chain config key"),
+        }
+    }
+
+    if format == ExportFormat::Besu {
+        let passed = config.get("terminalTotalDifficultyPassed") == Some(&Value::Bool(true));
+        if passed && !exported.contains_key("terminalTotalDifficulty") {
+            exported.insert("terminalTotalDifficulty".to_string(), json!(0));
+            report.add_field(
+                "config.terminalTotalDifficulty",
+                "0, since terminalTotalDifficultyPassed is set",
+            );
+        }
+        let has_consensus = ["ethash", "clique", "ibft2", "qbft"]
+            .iter()
+            .any(|key| exported.contains_key(*key));
+        if !has_consensus {
+            exported.insert("ethash".to_string(), json!({}));
+            report.add_field(
+                "config.ethash",
+                "empty section Besu needs to run a merged chain; blocks come from Gravity consensus",
+            );
+        }
+    }
+    exported
+}
+
+/// `0x`-prefixed hex left-padded to 32 bytes, as storage keys and values
+fn pad_word(hex: &str) -> Result<String> {
+    let digits = hex.strip_prefix("0x").unwrap_or(hex);
+    if digits.len() > 64 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("{} is not a 32-byte hex word", hex);
+    }
+    Ok(format!("0x{:0>64}", digits.to_lowercase()))
+}
+
+/// Alloc entry as both clients read it
+fn export_account(account: &Value) -> Result<Value> {
+    let mut exported = Map::new();
+    let balance = account.get("balance").and_then(Value::as_str).unwrap_or("0x0");
+    exported.insert("balance".to_string(), json!(balance));
+    let nonce = match account.get("nonce") {
+        None | Some(Value::Null) => 0,
+        Some(Value::Number(n)) => n.as_u64().context("nonce is not a u64")?,
+        Some(Value::String(s)) => match s.strip_prefix("0x") {
+            Some(digits) => u64::from_str_radix(digits, 16),
+            None => s.parse(),
+        }
+        .context(format!("invalid nonce {}", s))?,
+        Some(other) => anyhow::bail!("invalid nonce {}", other),
+    };
+    if nonce > 0 {
+        exported.insert("nonce".to_string(), json!(format!("0x{:x}", nonce)));
+    }
+    if let Some(code) = account.get("code").and_then(Value::as_str) {
+        if !code.trim_start_matches("0x").is_empty() {
+            exported.insert("code".to_string(), json!(code));
+        }
+    }
+    if let Some(storage) = account.get("storage").and_then(Value::as_object) {
+        let mut slots = Map::new();
+        for (slot, value) in storage {
+            let value = value.as_str().context(format!("storage slot {} is not a hex string", slot))?;
+            slots.insert(pad_word(slot)?, json!(pad_word(value)?));
+        }
+        if !slots.is_empty() {
+            exported.insert("storage".to_string(), Value::Object(slots));
+        }
+    }
+    Ok(Value::Object(exported))
+}
+
+/// Translate the reth genesis at `genesis_path` for the client of `format`
+pub fn export_genesis(genesis_path: &str, format: ExportFormat) -> Result<(Value, ExportReport)> {
+    let content = fs::read_to_string(genesis_path).context(format!("Failed to read {}", genesis_path))?;
+    let genesis: Map<String, Value> =
+        serde_json::from_str(&content).context(format!("Failed to parse {}", genesis_path))?;
+
+    let mut report = ExportReport::new(format);
+    let mut exported = Map::new();
+    for (key, value) in &genesis {
+        match key.as_str() {
+            "config" => {
+                let config = value.as_object().context("genesis config is not an object")?;
+                exported.insert(
+                    key.clone(),
+                    Value::Object(export_config(config, format, &mut report)),
+                );
+            }
+            "alloc" => {
+                let alloc = value.as_object().context("genesis alloc is not an object")?;
+                let mut accounts = Map::new();
+                for (address, account) in alloc {
+                    let account = export_account(account).context(format!("alloc account {}", address))?;
+                    accounts.insert(address.to_lowercase(), account);
+                }
+                report.accounts = accounts.len();
+                exported.insert(key.clone(), Value::Object(accounts));
+            }
+            _ if value.is_null() => {}
+            _ => {
+                exported.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    if !exported.contains_key("config") {
+        anyhow::bail!("{} has no config section", genesis_path);
+    }
+    Ok((Value::Object(exported), report))
+}
+
+/// Write the exported genesis to `output`, and the report as JSON to
+/// `report_path` when given
+pub fn write_export(
+    genesis_path: &str,
+    format: ExportFormat,
+    output: &str,
+    report_path: Option<&str>,
+) -> Result<ExportReport> {
+    let (genesis, report) = export_genesis(genesis_path, format)?;
+    let file = File::create(output).context(format!("Failed to create {}", output))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), &genesis)
+        .context(format!("Failed to write {}", output))?;
+    info!(
+        "Wrote {} genesis with {} account(s) to {}",
+        format.client(),
+        report.accounts,
+        output
+    );
+    if let Some(path) = report_path {
+        fs::write(path, serde_json::to_string_pretty(&report)? + "\n")
+            .context(format!("Failed to write {}", path))?;
+    }
+    Ok(report)
+}
@@ -0,0 +1,84 @@
+//! In-process genesis state for downstream tests
+//!
+//! Integration tests in other crates, such as gravity-reth's, can build a
+//! genesis without shelling out to the CLI: [`minimal_config`] returns a valid
+//! config with `n` validators whose keys follow from a seed, and
+//! [`build_genesis`] executes it against a forge `out/` directory, returning the
+//! deployment database and bundle state `generate` would write its outputs
+//! from. Nothing is written to disk.
+
+use anyhow::Result;
+use revm::{db::BundleState, InMemoryDB};
+
+use crate::{
+    devnet::{devnet_config, DevnetOptions},
+    execute::{execute_genesis, BuildInputs},
+    genesis::GenesisConfig,
+    registry::ContractsManifest,
+    simulate::Simulator,
+};
+
+/// Chain ID of fixture configs
+pub const FIXTURE_CHAIN_ID: u64 = 1337;
+
+/// Seed of the validator keys in [`minimal_config`]
+pub const FIXTURE_SEED: u64 = 0;
+
+/// Stake and voting power of each fixture validator (100 ether)
+pub const FIXTURE_STAKE: &str = "100000000000000000000";
+
+/// An executed genesis
+#[derive(Debug, Clone)]
+pub struct GenesisFixture {
+    pub config: GenesisConfig,
+    /// Database the system contracts were deployed into before execution
+    pub db: InMemoryDB,
+    /// State changes of the genesis transactions, before system accounts are
+    /// cleaned up
+    pub bundle_state: BundleState,
+}
+
+impl GenesisFixture {
+    /// Simulator over the genesis state, for calls and block prologues
+    pub fn simulator(&self) -> Simulator {
+        Simulator::from_bundle(self.db.clone(), &self.bundle_state, self.config.chain_id)
+            .with_spec_id(self.config.spec_id())
+            .with_evm_env(self.config.evm_env.as_ref())
+    }
+}
+
+/// Default config with `validators` equally staked validators whose keys
+/// follow from [`FIXTURE_SEED`]
+pub fn minimal_config(validators: usize) -> Result<GenesisConfig> {
+    config_with_seed(validators, FIXTURE_SEED)
+}
+
+/// Like [`minimal_config`], with validator keys derived from `seed`
+pub fn config_with_seed(validators: usize, seed: u64) -> Result<GenesisConfig> {
+    devnet_config(&DevnetOptions {
+        validators,
+        chain_id: FIXTURE_CHAIN_ID,
+        host: "127.0.0.1".to_string(),
+        base_port: 6180,
+        stake_amount: FIXTURE_STAKE.to_string(),
+        seed: Some(seed),
+    })
+}
+
+/// Execute genesis for `config` with the contracts in `byte_code_dir`
+pub fn build_genesis(byte_code_dir: &str, config: GenesisConfig) -> Result<GenesisFixture> {
+    let contracts = ContractsManifest::resolve(byte_code_dir, None)?;
+    contracts.preflight(byte_code_dir)?;
+    let inputs = BuildInputs::prepare(byte_code_dir, &contracts, &config, None)?;
+    let (db, bundle_state) = execute_genesis(&contracts, &config, inputs)?;
+    Ok(GenesisFixture {
+        config,
+        db,
+        bundle_state,
+    })
+}
+
+/// [`minimal_config`] with `validators` validators, executed
+pub fn minimal_genesis(byte_code_dir: &str, validators: usize) -> Result<GenesisFixture> {
+    build_genesis(byte_code_dir, minimal_config(validators)?)
+}
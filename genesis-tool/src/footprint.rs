@@ -0,0 +1,212 @@
+//! Storage footprint of the genesis state
+//!
+//! `generate` and `assemble` write `footprint_report.json` next to the
+//! outputs: per system contract (and linked library) the runtime code size,
+//! the storage slots it starts with and an estimate of what it adds to the
+//! state trie, then the same for every other account together and the totals.
+//! Comparing the reports of two hardfork generations shows which contract
+//! grew the genesis.
+//!
+//! The trie estimate counts leaf nodes exactly as encoded (hashed key path
+//! plus RLP value) and adds [`BRANCH_BYTES_PER_LEAF`] for the branch nodes
+//! above them; extension nodes and the root are ignored. It is meant for
+//! comparing generations, not for predicting database size.
+
+use anyhow::{Context, Result};
+use revm::db::PlainAccount;
+use revm_primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path};
+use tracing::info;
+
+use crate::registry::ContractsManifest;
+
+/// File in the output directory holding the report
+pub const FOOTPRINT_REPORT_FILE: &str = "footprint_report.json";
+
+/// Leaf path (a 32-byte hashed key, hex-prefix encoded) and the leaf's list header
+const LEAF_OVERHEAD_BYTES: usize = 33 + 2;
+
+/// A full 16-way branch node is about 17 * 33 bytes; n leaves sit under about
+/// n / 15 of them, so each leaf accounts for roughly this many branch bytes
+const BRANCH_BYTES_PER_LEAF: usize = 36;
+
+/// Length of the RLP encoding of an integer given as big-endian bytes
+fn rlp_int_len(bytes: &[u8]) -> usize {
+    let trimmed = bytes.iter().skip_while(|b| **b == 0).count();
+    match trimmed {
+        0 => 1,
+        1 if bytes[bytes.len() - 1] < 0x80 => 1,
+        n => 1 + n,
+    }
+}
+
+/// Length of an RLP list header for a payload of `payload` bytes
+fn rlp_list_header_len(payload: usize) -> usize {
+    if payload < 56 {
+        1
+    } else {
+        1 + (usize::BITS - payload.leading_zeros()).div_ceil(8) as usize
+    }
+}
+
+/// Estimated trie bytes of a leaf holding an RLP value of `value_len` bytes
+fn leaf_bytes(value_len: usize) -> usize {
+    LEAF_OVERHEAD_BYTES + value_len + BRANCH_BYTES_PER_LEAF
+}
+
+/// Account trie leaf of `account`: `[nonce, balance, storageRoot, codeHash]`
+fn account_trie_bytes(account: &PlainAccount) -> usize {
+    let payload = rlp_int_len(&account.info.nonce.to_be_bytes())
+        + rlp_int_len(&account.info.balance.to_be_bytes::<32>())
+        + 2 * 33;
+    let value = rlp_list_header_len(payload) + payload;
+    // The account RLP is itself wrapped as a string inside the leaf
+    leaf_bytes(value + rlp_list_header_len(value))
+}
+
+/// Storage trie leaves of `account`; values are stored as RLP integers
+fn storage_trie_bytes(account: &PlainAccount) -> usize {
+    account
+        .storage
+        .values()
+        .filter(|value| !value.is_zero())
+        .map(|value| leaf_bytes(rlp_int_len(&value.to_be_bytes::<32>())))
+        .sum()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Footprint {
+    pub accounts: usize,
+    pub code_bytes: usize,
+    pub storage_slots: usize,
+    pub storage_trie_bytes: usize,
+    pub account_trie_bytes: usize,
+    /// Code plus the estimated trie bytes
+    pub total_bytes: usize,
+}
+
+impl Footprint {
+    fn of(account: &PlainAccount) -> Self {
+        let code_bytes = account.info.code.as_ref().map_or(0, |code| code.original_bytes().len());
+        let storage_trie_bytes = storage_trie_bytes(account);
+        let account_trie_bytes = account_trie_bytes(account);
+        Footprint {
+            accounts: 1,
+            code_bytes,
+            storage_slots: account.storage.values().filter(|value| !value.is_zero()).count(),
+            storage_trie_bytes,
+            account_trie_bytes,
+            total_bytes: code_bytes + storage_trie_bytes + account_trie_bytes,
+        }
+    }
+
+    fn add(&mut self, other: &Footprint) {
+        self.accounts += other.accounts;
+        self.code_bytes += other.code_bytes;
+        self.storage_slots += other.storage_slots;
+        self.storage_trie_bytes += other.storage_trie_bytes;
+        self.account_trie_bytes += other.account_trie_bytes;
+        self.total_bytes += other.total_bytes;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractFootprint {
+    pub name: String,
+    pub address: Address,
+    #[serde(flatten)]
+    pub footprint: Footprint,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FootprintReport {
+    /// System contracts in manifest order, then linked libraries
+    pub contracts: Vec<ContractFootprint>,
+    /// Every other account: stake pools, vesting, extra alloc, funded accounts
+    pub other_accounts: Footprint,
+    pub total: Footprint,
+    /// Size of the written `genesis_accounts.json`
+    pub genesis_accounts_json_bytes: usize,
+}
+
+impl FootprintReport {
+    pub fn new(
+        genesis_state: &HashMap<Address, PlainAccount>,
+        contracts: &ContractsManifest,
+        genesis_accounts_json_bytes: usize,
+    ) -> Self {
+        let named = contracts
+            .contracts
+            .iter()
+            .map(|contract| (contract.name.clone(), contract.address))
+            .chain(contracts.libraries.iter().map(|(name, address)| (name.clone(), *address)));
+        let contracts: Vec<ContractFootprint> = named
+            .filter_map(|(name, address)| {
+                genesis_state.get(&address).map(|account| ContractFootprint {
+                    name,
+                    address,
+                    footprint: Footprint::of(account),
+                })
+            })
+            .collect();
+
+        let mut other_accounts = Footprint::default();
+        for (address, account) in genesis_state {
+            if !contracts.iter().any(|contract| contract.address == *address) {
+                other_accounts.add(&Footprint::of(account));
+            }
+        }
+        let mut total = other_accounts.clone();
+        for contract in &contracts {
+            total.add(&contract.footprint);
+        }
+        FootprintReport {
+            contracts,
+            other_accounts,
+            total,
+            genesis_accounts_json_bytes,
+        }
+    }
+
+    pub fn log(&self) {
+        let mut contracts: Vec<&ContractFootprint> = self.contracts.iter().collect();
+        contracts.sort_by_key(|contract| std::cmp::Reverse(contract.footprint.total_bytes));
+        info!("Genesis footprint (code bytes, storage slots, estimated trie bytes):");
+        for contract in contracts {
+            let footprint = &contract.footprint;
+            info!(
+                "  {:<32} {:>7} {:>6} {:>9}",
+                contract.name,
+                footprint.code_bytes,
+                footprint.storage_slots,
+                footprint.storage_trie_bytes + footprint.account_trie_bytes
+            );
+        }
+        let other = &self.other_accounts;
+        info!(
+            "  {:<32} {:>7} {:>6} {:>9}",
+            format!("{} other account(s)", other.accounts),
+            other.code_bytes,
+            other.storage_slots,
+            other.storage_trie_bytes + other.account_trie_bytes
+        );
+        info!(
+            "Total: {} account(s), {} code bytes, {} storage slots, ~{} trie bytes; genesis_accounts.json {} bytes",
+            self.total.accounts,
+            self.total.code_bytes,
+            self.total.storage_slots,
+            self.total.storage_trie_bytes + self.total.account_trie_bytes,
+            self.genesis_accounts_json_bytes
+        );
+    }
+}
+
+pub fn write_footprint_report(report: &FootprintReport, output_dir: &str) -> Result<()> {
+    let path = Path::new(output_dir).join(FOOTPRINT_REPORT_FILE);
+    fs::write(&path, serde_json::to_string_pretty(report)? + "\n")
+        .context(format!("Failed to write {}", path.display()))
+}
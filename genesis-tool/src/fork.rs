@@ -0,0 +1,223 @@
+//! Forked-devnet support: pull live chain state into a generated genesis
+//!
+//! Code, balance, nonce and full storage of every account with code in the
+//! reserved system range `0x1625F0000`-`0x1625F5FFF` (plus the manifest
+//! contracts and any extra accounts requested) are fetched at a pinned block and
+//! merged into the generated alloc, overriding the freshly initialized state for
+//! those addresses. The range is scanned with batched `eth_getCode`, so system
+//! contracts the manifest does not know are forked too. Storage is
+//! enumerated with `debug_storageRangeAt`, so the RPC node must expose the debug
+//! namespace with storage key preimages available.
+
+use alloy_primitives::{Address, B256, U256};
+use anyhow::{Context, Result};
+use revm::db::PlainAccount;
+use revm_primitives::{hex, AccountInfo, Bytecode};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::{BTreeSet, HashMap};
+use tracing::{info, warn};
+
+use crate::{
+    registry::ContractsManifest,
+    rpc::{to_hex_quantity, RpcClient},
+    utils::{RESERVED_RANGE_END, RESERVED_RANGE_START},
+};
+
+/// Maximum slots requested per `debug_storageRangeAt` page
+const STORAGE_PAGE_SIZE: u64 = 1024;
+
+/// Addresses per `eth_getCode` batch when scanning the system range
+const CODE_SCAN_BATCH_SIZE: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct ForkOptions {
+    pub rpc_url: String,
+    /// Block to fork from; latest when unset
+    pub block: Option<u64>,
+    /// Accounts fetched in addition to the system contracts
+    pub extra_accounts: Vec<Address>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockHeader {
+    hash: B256,
+    transactions: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StorageRange {
+    storage: HashMap<B256, StorageEntry>,
+    #[serde(rename = "nextKey")]
+    next_key: Option<B256>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StorageEntry {
+    key: Option<B256>,
+    value: B256,
+}
+
+/// Fetch system contracts and extra accounts from a live chain at the fork block
+pub fn fetch_fork_state(
+    options: &ForkOptions,
+    contracts: &ContractsManifest,
+) -> Result<HashMap<Address, PlainAccount>> {
+    let client = RpcClient::new(&options.rpc_url);
+    let block_number = match options.block {
+        Some(n) => n,
+        None => client.block_number()?,
+    };
+    info!(
+        "Forking state from {} (chain id {}) at block {}",
+        client.url(),
+        client.chain_id()?,
+        block_number
+    );
+
+    let block: BlockHeader = client
+        .call(
+            "eth_getBlockByNumber",
+            json!([to_hex_quantity(block_number), false]),
+        )
+        .context(format!("Fork block {} not found", block_number))?;
+
+    let mut addresses: BTreeSet<Address> = contracts.contracts.iter().map(|c| c.address).collect();
+    for address in scan_system_range(&client, block_number)? {
+        if addresses.insert(address) {
+            warn!(
+                "{:?} has code on the forked chain but is not in the contracts manifest; forking it too",
+                address
+            );
+        }
+    }
+    addresses.extend(options.extra_accounts.iter().copied());
+
+    let mut accounts = HashMap::new();
+    for address in addresses {
+        match fetch_account(&client, address, block_number, &block)? {
+            Some(account) => {
+                info!(
+                    "Forked {:?}: {} bytes code, {} storage slots",
+                    address,
+                    account.info.code.as_ref().map_or(0, |c| c.len()),
+                    account.storage.len()
+                );
+                accounts.insert(address, account);
+            }
+            None => warn!("Account {:?} is empty on the forked chain, skipping", address),
+        }
+    }
+
+    info!("Fetched {} accounts from forked chain", accounts.len());
+    Ok(accounts)
+}
+
+/// Addresses in the reserved system range with code at `block_number`
+fn scan_system_range(client: &RpcClient, block_number: u64) -> Result<Vec<Address>> {
+    let block_tag = to_hex_quantity(block_number);
+    let start = U256::from_be_slice(RESERVED_RANGE_START.as_slice());
+    let end = U256::from_be_slice(RESERVED_RANGE_END.as_slice());
+    let range: Vec<Address> = (0..=(end - start).to::<u64>())
+        .map(|offset| Address::from_word((start + U256::from(offset)).into()))
+        .collect();
+    info!("Scanning {} system range addresses for code", range.len());
+
+    let mut with_code = Vec::new();
+    for chunk in range.chunks(CODE_SCAN_BATCH_SIZE) {
+        let params: Vec<serde_json::Value> =
+            chunk.iter().map(|address| json!([address, block_tag])).collect();
+        let codes: Vec<alloy_primitives::Bytes> = client
+            .batch_call("eth_getCode", &params)
+            .context("Failed to scan the system address range")?;
+        with_code.extend(
+            chunk
+                .iter()
+                .zip(codes)
+                .filter(|(_, code)| !code.is_empty())
+                .map(|(address, _)| *address),
+        );
+    }
+    Ok(with_code)
+}
+
+fn fetch_account(
+    client: &RpcClient,
+    address: Address,
+    block_number: u64,
+    block: &BlockHeader,
+) -> Result<Option<PlainAccount>> {
+    let block_tag = to_hex_quantity(block_number);
+    let code = client.get_code(address, &block_tag)?;
+    let balance: U256 = client.call("eth_getBalance", json!([address, block_tag]))?;
+    let nonce: U256 = client.call("eth_getTransactionCount", json!([address, block_tag]))?;
+
+    if code.is_empty() && balance.is_zero() && nonce.is_zero() {
+        return Ok(None);
+    }
+
+    let storage = if code.is_empty() {
+        HashMap::default()
+    } else {
+        fetch_storage(client, address, block)?
+    };
+
+    let bytecode = Bytecode::new_raw(code);
+    Ok(Some(PlainAccount {
+        info: AccountInfo {
+            balance,
+            nonce: nonce
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Nonce {} of {:?} exceeds u64", nonce, address))?,
+            code_hash: bytecode.hash_slow(),
+            code: Some(bytecode),
+        },
+        storage: storage.into_iter().collect(),
+    }))
+}
+
+/// Enumerate all storage of `address` as of the end of `block`
+fn fetch_storage(
+    client: &RpcClient,
+    address: Address,
+    block: &BlockHeader,
+) -> Result<HashMap<U256, U256>> {
+    let mut storage = HashMap::new();
+    let mut start = B256::ZERO;
+
+    loop {
+        let range: StorageRange = client
+            .call(
+                "debug_storageRangeAt",
+                json!([
+                    block.hash,
+                    block.transactions.len(),
+                    address,
+                    start,
+                    STORAGE_PAGE_SIZE
+                ]),
+            )
+            .context("debug_storageRangeAt is required for forking (enable the debug namespace)")?;
+
+        for (hashed_key, entry) in range.storage {
+            let key = entry.key.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Node returned no preimage for storage slot {} of {:?}; enable preimage recording",
+                    hex::encode(hashed_key),
+                    address
+                )
+            })?;
+            let value = U256::from_be_bytes(entry.value.0);
+            if !value.is_zero() {
+                storage.insert(U256::from_be_bytes(key.0), value);
+            }
+        }
+
+        match range.next_key {
+            Some(next) => start = next,
+            None => break,
+        }
+    }
+
+    Ok(storage)
+}
@@ -1,19 +1,38 @@
+use alloy_dyn_abi::{DynSolType, DynSolValue};
 use alloy_sol_macro::sol;
 use alloy_sol_types::SolCall;
-use revm_primitives::{hex, Address, Bytes, ExecutionResult, TxEnv, U256};
+use revm::db::PlainAccount;
+use revm_primitives::{
+    hex, AccountInfo, Address, BlobExcessGasAndPrice, Bytecode, Bytes, Env, ExecutionResult,
+    SpecId, TxEnv, B256, U256,
+};
 use serde::{Deserialize, Serialize};
-use tracing::{error, info};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::{error, info, warn};
 
 use crate::{
+    abi::selector,
+    dynamic_abi::coerce_json,
+    multiaddr::normalize_network_address,
+    multisig,
+    onchain_config::OnChainConsensusConfig,
     post_genesis::handle_execution_result,
+    spec::{is_ethereum_fork, spec_from_chain_config, EvmSpec},
+    validate::Severity,
     utils::{
-        new_system_call_txn, new_system_call_txn_with_value, GENESIS_ADDR, VALIDATOR_MANAGER_ADDR,
+        new_system_call_txn, new_system_call_txn_with_value, CONTRACTS, GENESIS_ADDR,
+        SYSTEM_CALLER, VALIDATOR_MANAGER_ADDR,
     },
 };
 
 /// Derive 32-byte AccountAddress from BLS consensus public key using SHA3-256
 /// This matches the derivation used in gravity-reth for validator identity
-fn derive_account_address_from_consensus_pubkey(consensus_pubkey: &[u8]) -> [u8; 32] {
+pub fn derive_account_address_from_consensus_pubkey(consensus_pubkey: &[u8]) -> [u8; 32] {
     use tiny_keccak::{Hasher, Sha3};
 
     let mut hasher = Sha3::v256();
@@ -29,10 +48,23 @@ fn derive_account_address_from_consensus_pubkey(consensus_pubkey: &[u8]) -> [u8;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct GenesisConfig {
-    /// Chain ID for the network (default: 1 = Mainnet)
+    /// Chain ID for the network (default: 1337, a local devnet)
     #[serde(rename = "chainId", default = "default_chain_id")]
     pub chain_id: u64,
 
+    /// Accept the default chain ID for a config that is not a local devnet
+    #[serde(rename = "allowDefaultChainId", default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_default_chain_id: bool,
+
+    /// Severity of owners and operators shared between validators, or owned by
+    /// their operator (default: warning, info on devnets)
+    #[serde(rename = "roleSeparation", default, skip_serializing_if = "Option::is_none")]
+    pub role_separation: Option<Severity>,
+
+    /// `chainId` was absent and [`DEFAULT_CHAIN_ID`] applies; set on load
+    #[serde(skip)]
+    pub chain_id_defaulted: bool,
+
     #[serde(rename = "validatorConfig")]
     pub validator_config: ValidatorConfigParams,
 
@@ -42,8 +74,9 @@ pub struct GenesisConfig {
     #[serde(rename = "governanceConfig")]
     pub governance_config: GovernanceConfigParams,
 
-    /// Owner address for the Governance contract (manages executors)
-    #[serde(rename = "governanceOwner")]
+    /// Owner address for the Governance contract (manages executors); defaults
+    /// to the multisig when `governanceMultisig` is set
+    #[serde(rename = "governanceOwner", default)]
     pub governance_owner: String,
 
     #[serde(rename = "epochIntervalMicros")]
@@ -52,9 +85,17 @@ pub struct GenesisConfig {
     #[serde(rename = "majorVersion")]
     pub major_version: u64,
 
-    #[serde(rename = "consensusConfig")]
+    #[serde(rename = "consensusConfig", default)]
     pub consensus_config: String, // hex bytes
 
+    /// Typed alternative to `consensusConfig`; BCS-encoded into it on load
+    #[serde(
+        rename = "consensusConfigStruct",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub consensus_config_struct: Option<OnChainConsensusConfig>,
+
     #[serde(rename = "executionConfig")]
     pub execution_config: String, // hex bytes
 
@@ -69,6 +110,11 @@ pub struct GenesisConfig {
 
     pub validators: Vec<InitialValidator>,
 
+    /// Register validators in batches of this size (`Genesis.beginInitialize`,
+    /// `addValidatorBatch`, `finishInitialize`) instead of one `initialize` call
+    #[serde(rename = "validatorBatchSize", default, skip_serializing_if = "Option::is_none")]
+    pub validator_batch_size: Option<usize>,
+
     /// Lockup expiration timestamp for initial validator stake pools (microseconds)
     #[serde(rename = "initialLockedUntilMicros")]
     pub initial_locked_until_micros: u64,
@@ -76,10 +122,706 @@ pub struct GenesisConfig {
     /// Genesis block timestamp (Unix seconds). Falls back to template default if unset.
     #[serde(rename = "genesisTimestampSecs", default)]
     pub genesis_timestamp_secs: Option<u64>,
+
+    /// Genesis block base fee (wei). Falls back to the template if unset.
+    #[serde(rename = "baseFeePerGas", default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<u64>,
+
+    /// Genesis block gas limit. Falls back to the template if unset.
+    #[serde(rename = "gasLimit", default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+
+    /// EIP-1559 base fee adjustment, written to the genesis chain config
+    #[serde(rename = "baseFeeParams", default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_params: Option<BaseFeeParams>,
+
+    /// Block fields genesis transactions and post-genesis checks execute under
+    #[serde(rename = "evmEnv", default, skip_serializing_if = "Option::is_none")]
+    pub evm_env: Option<EvmEnvConfig>,
+
+    /// Additional accounts written into the genesis alloc, keyed by address
+    #[serde(rename = "extraAlloc", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra_alloc: BTreeMap<String, ExtraAllocAccount>,
+
+    /// Treasury account, funded in the alloc; system contracts with a
+    /// `treasury()` getter are expected to point at it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub treasury: Option<FundedAccount>,
+
+    /// Fee collector account, funded in the alloc; system contracts with a
+    /// `feeCollector()` getter are expected to point at it
+    #[serde(rename = "feeCollector", default, skip_serializing_if = "Option::is_none")]
+    pub fee_collector: Option<FundedAccount>,
+
+    /// Native supply the genesis alloc must add up to, in any format accepted
+    /// by [`parse_amount`]; checked after execution when set
+    #[serde(rename = "totalSupply", default, skip_serializing_if = "Option::is_none")]
+    pub total_supply: Option<String>,
+
+    /// Reserved system-range addresses that `extraAlloc` may deliberately populate
+    #[serde(rename = "allowReservedAlloc", default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_reserved_alloc: Vec<String>,
+
+    /// Hardfork to execute genesis under; should match what greth enables at block 0
+    #[serde(rename = "evmSpec", default, skip_serializing_if = "Option::is_none")]
+    pub evm_spec: Option<EvmSpec>,
+
+    /// Fork activations written into the genesis chain config, keyed like it
+    /// (`cancunTime`, `zetaBlock`, ...): block numbers for `*Block`, Unix
+    /// seconds for `*Time`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hardforks: BTreeMap<String, u64>,
+
+    /// `GenesisInitParams` fields without a typed config entry, laid out like the
+    /// struct; only read by `generate --dynamic-abi`
+    #[serde(rename = "extraInitParams", default, skip_serializing_if = "Value::is_null")]
+    pub extra_init_params: Value,
+
+    /// Calls executed before `Genesis.initialize`, in order
+    #[serde(rename = "preGenesisCalls", default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_genesis_calls: Vec<GenesisCall>,
+
+    /// Calls executed after `Genesis.initialize`, in order
+    #[serde(rename = "postGenesisCalls", default, skip_serializing_if = "Vec::is_empty")]
+    pub post_genesis_calls: Vec<GenesisCall>,
+
+    /// Vesting contracts deployed and funded after `Genesis.initialize`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vesting: Option<VestingConfig>,
+
+    /// Safe-style multisig deployed at genesis to own Governance
+    #[serde(rename = "governanceMultisig", default, skip_serializing_if = "Option::is_none")]
+    pub governance_multisig: Option<MultisigConfig>,
+
+    /// forge script broadcast (`run-latest.json`) replayed after the vesting
+    /// deployments
+    #[serde(rename = "replayBroadcast", default, skip_serializing_if = "Option::is_none")]
+    pub replay_broadcast: Option<String>,
+}
+
+impl GenesisConfig {
+    /// revm spec for genesis execution: `evmSpec`, else the newest Ethereum
+    /// fork `hardforks` activates at genesis, else [`SpecId::LATEST`]
+    pub fn spec_id(&self) -> SpecId {
+        self.evm_spec
+            .map(EvmSpec::spec_id)
+            .or_else(|| self.scheduled_spec_id())
+            .unwrap_or(SpecId::LATEST)
+    }
+
+    /// Newest Ethereum fork `hardforks` activates at genesis, if it lists any
+    pub fn scheduled_spec_id(&self) -> Option<SpecId> {
+        if !self.hardforks.keys().any(|key| is_ethereum_fork(key)) {
+            return None;
+        }
+        let chain_config = serde_json::to_value(&self.hardforks).unwrap_or_default();
+        Some(spec_from_chain_config(
+            &chain_config,
+            self.genesis_timestamp_secs.unwrap_or(0),
+        ))
+    }
+
+    /// `treasury` and `feeCollector`, labelled with their config keys
+    pub fn funded_accounts(&self) -> Vec<(&'static str, &FundedAccount)> {
+        [("treasury", &self.treasury), ("feeCollector", &self.fee_collector)]
+            .into_iter()
+            .filter_map(|(key, account)| account.as_ref().map(|a| (key, a)))
+            .collect()
+    }
+}
+
+/// EIP-1559 parameters, named as in greth's chain spec
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseFeeParams {
+    /// Bound on the base fee change per block is `1 / maxChangeDenominator`
+    pub max_change_denominator: u64,
+
+    /// Gas target is `gasLimit / elasticityMultiplier`
+    pub elasticity_multiplier: u64,
+}
+
+/// Block environment of genesis execution. Unset fields keep revm's defaults:
+/// no block gas limit, and zero basefee, coinbase, difficulty and prevrandao.
+/// Execution never charges gas, so these only change what contracts read.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmEnvConfig {
+    /// `block.gaslimit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_gas_limit: Option<u64>,
+
+    /// `block.basefee`, in wei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub basefee: Option<u64>,
+
+    /// `block.coinbase`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+
+    /// `block.difficulty` before the merge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<U256>,
+
+    /// `block.prevrandao` from the merge on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prevrandao: Option<B256>,
+
+    /// Excess blob gas of the block (EIP-4844); `block.blobbasefee` follows
+    /// from it unless `blobBaseFee` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excess_blob_gas: Option<u64>,
+
+    /// `block.blobbasefee`, in wei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_base_fee: Option<u128>,
+}
+
+/// EIP-4844 blob base fee update fraction, and its EIP-7691 value from Prague
+const BLOB_BASE_FEE_UPDATE_FRACTION: u128 = 3_338_477;
+const BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE: u128 = 5_007_716;
+
+/// Blob base fee for `excess_blob_gas`: EIP-4844's `fake_exponential` from a
+/// 1 wei minimum
+fn blob_base_fee(excess_blob_gas: u64, spec_id: SpecId) -> u128 {
+    let fraction = if spec_id >= SpecId::PRAGUE {
+        BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE
+    } else {
+        BLOB_BASE_FEE_UPDATE_FRACTION
+    };
+    let numerator = excess_blob_gas as u128;
+    let mut output = 0u128;
+    let mut accumulator = fraction;
+    let mut i = 1u128;
+    while accumulator > 0 {
+        output = output.saturating_add(accumulator);
+        accumulator = accumulator.saturating_mul(numerator) / (fraction * i);
+        i += 1;
+    }
+    output / fraction
+}
+
+impl EvmEnvConfig {
+    /// Set the configured block fields of `env`, for execution under `spec_id`
+    pub fn apply(&self, env: &mut Env, spec_id: SpecId) {
+        if let Some(gas_limit) = self.block_gas_limit {
+            env.block.gas_limit = U256::from(gas_limit);
+        }
+        if let Some(basefee) = self.basefee {
+            env.block.basefee = U256::from(basefee);
+        }
+        if let Some(coinbase) = self.coinbase {
+            env.block.coinbase = coinbase;
+        }
+        if let Some(difficulty) = self.difficulty {
+            env.block.difficulty = difficulty;
+        }
+        if let Some(prevrandao) = self.prevrandao {
+            env.block.prevrandao = Some(prevrandao);
+        }
+        if self.excess_blob_gas.is_some() || self.blob_base_fee.is_some() {
+            let excess_blob_gas = self.excess_blob_gas.unwrap_or_default();
+            env.block.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice {
+                excess_blob_gas,
+                blob_gasprice: self
+                    .blob_base_fee
+                    .unwrap_or_else(|| blob_base_fee(excess_blob_gas, spec_id)),
+            });
+        }
+        // Genesis transactions are sent with a zero gas price and unbounded gas;
+        // a configured block must not reject them
+        env.cfg.disable_base_fee = true;
+        env.cfg.disable_block_gas_limit = true;
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FundedAccount {
+    pub address: String,
+
+    /// Initial balance, in any format accepted by [`parse_amount`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultisigConfig {
+    /// Safe artifact, read from `<name>.sol/<name>.json` in the bytecode directory
+    #[serde(default = "default_multisig_contract")]
+    pub contract: String,
+
+    pub owners: Vec<String>,
+
+    /// Owner confirmations a transaction needs
+    pub threshold: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_handler: Option<String>,
+}
+
+fn default_multisig_contract() -> String {
+    "Safe".to_string()
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct VestingConfig {
+    /// Vesting contract artifact, read from `<name>.sol/<name>.json` in the bytecode directory
+    #[serde(default = "default_vesting_contract")]
+    pub contract: String,
+
+    pub schedules: Vec<VestingSchedule>,
+}
+
+fn default_vesting_contract() -> String {
+    "VestingWallet".to_string()
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingSchedule {
+    pub beneficiary: String,
+
+    /// Amount locked in the contract, in any format accepted by [`parse_amount`]
+    pub amount: String,
+
+    /// Vesting start (Unix microseconds); defaults to `genesisTimestampSecs`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_micros: Option<u64>,
+
+    /// Nothing is released before `start + cliff`
+    #[serde(default)]
+    pub cliff_micros: u64,
+
+    pub duration_micros: u64,
+
+    #[serde(default)]
+    pub revocable: bool,
+
+    /// Address allowed to revoke a revocable schedule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoker: Option<String>,
+}
+
+impl VestingSchedule {
+    /// Start in Unix microseconds, falling back to the genesis timestamp
+    pub fn start_micros(&self, config: &GenesisConfig) -> Option<u64> {
+        self.start_micros
+            .or_else(|| config.genesis_timestamp_secs.map(|secs| secs.saturating_mul(1_000_000)))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ExtraAllocAccount {
+    /// Amount, in any format accepted by [`parse_amount`]
+    #[serde(default)]
+    pub balance: Option<String>,
+
+    #[serde(default)]
+    pub nonce: Option<u64>,
+
+    /// Runtime bytecode (hex)
+    #[serde(default)]
+    pub code: Option<String>,
+
+    /// Storage slot -> value, both 0x-prefixed hex
+    #[serde(default)]
+    pub storage: BTreeMap<String, String>,
+}
+
+/// Extra transaction executed before or after `Genesis.initialize`
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GenesisCall {
+    /// Address, or the name of a built-in system contract
+    pub target: String,
+
+    /// Function signature with parameter types only, e.g. `addTask(uint32,bytes32,bytes)`
+    pub signature: String,
+
+    /// Arguments: strings, numbers or booleans for scalars, arrays for arrays and tuples
+    #[serde(default)]
+    pub args: Vec<Value>,
+
+    /// Sender (defaults to the system caller); it is funded with `value` for the call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caller: Option<String>,
+
+    /// Amount sent, in any format accepted by [`parse_amount`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl GenesisCall {
+    pub fn target_address(&self) -> anyhow::Result<Address> {
+        if let Some((_, address)) = CONTRACTS.iter().find(|(name, _)| *name == self.target) {
+            return Ok(*address);
+        }
+        self.target
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid target {}: {}", self.target, e))
+    }
+
+    pub fn caller_address(&self) -> anyhow::Result<Address> {
+        match &self.caller {
+            Some(caller) => caller
+                .parse()
+                .map_err(|e| anyhow::anyhow!("invalid caller {}: {}", caller, e)),
+            None => Ok(SYSTEM_CALLER),
+        }
+    }
+
+    pub fn value_wei(&self) -> anyhow::Result<U256> {
+        match &self.value {
+            Some(value) => parse_amount(value).map_err(|e| anyhow::anyhow!("value: {}", e)),
+            None => Ok(U256::ZERO),
+        }
+    }
+
+    /// Selector of the canonical signature followed by the ABI-encoded arguments
+    pub fn call_data(&self) -> anyhow::Result<Vec<u8>> {
+        let (name, params) = self
+            .signature
+            .trim()
+            .split_once('(')
+            .ok_or_else(|| anyhow::anyhow!("signature {} has no parameter list", self.signature))?;
+        let params = DynSolType::parse(&format!("({}", params))
+            .map_err(|e| anyhow::anyhow!("signature {}: {}", self.signature, e))?;
+        let DynSolType::Tuple(types) = &params else {
+            anyhow::bail!("signature {} has no parameter list", self.signature);
+        };
+        if types.len() != self.args.len() {
+            anyhow::bail!(
+                "{} takes {} argument(s), {} given",
+                self.signature,
+                types.len(),
+                self.args.len()
+            );
+        }
+        let values = types
+            .iter()
+            .zip(&self.args)
+            .enumerate()
+            .map(|(i, (ty, arg))| {
+                coerce_json(ty, arg).map_err(|e| anyhow::anyhow!("argument {}: {:#}", i, e))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let canonical = format!("{}{}", name.trim(), params.sol_type_name());
+        let mut call_data = selector(&canonical).to_vec();
+        call_data.extend(DynSolValue::Tuple(values).abi_encode_params());
+        Ok(call_data)
+    }
+
+    pub fn to_txn(&self) -> anyhow::Result<TxEnv> {
+        Ok(TxEnv {
+            caller: self.caller_address()?,
+            value: self.value_wei()?,
+            ..new_system_call_txn(self.target_address()?, self.call_data()?.into())
+        })
+    }
+}
+
+/// `preGenesisCalls` then `postGenesisCalls`, labelled for messages
+pub fn genesis_calls(config: &GenesisConfig) -> Vec<(String, &GenesisCall)> {
+    let pre = config.pre_genesis_calls.iter().enumerate();
+    let post = config.post_genesis_calls.iter().enumerate();
+    pre.map(|(i, call)| (format!("preGenesisCalls[{}]", i), call))
+        .chain(post.map(|(i, call)| (format!("postGenesisCalls[{}]", i), call)))
+        .collect()
+}
+
+/// Chain ID of a config without `chainId`
+pub const DEFAULT_CHAIN_ID: u64 = 1337;
+
 fn default_chain_id() -> u64 {
-    1337
+    DEFAULT_CHAIN_ID
+}
+
+// ============================================================================
+// CONFIG LOADING - ${ENV_VAR} interpolation and "$include" directives
+// ============================================================================
+
+/// Key of the include directive: `{ "$include": "validators.json" }`
+const INCLUDE_KEY: &str = "$include";
+
+/// Include resolution state: the chain of files being loaded, to detect
+/// cycles, and every file read so far
+#[derive(Debug, Default)]
+struct ConfigSources {
+    stack: Vec<PathBuf>,
+    files: Vec<PathBuf>,
+}
+
+/// Load a GenesisConfig from disk.
+///
+/// Before deserialization the file is preprocessed:
+/// - `${ENV_VAR}` is replaced by the (JSON-escaped) value of the environment variable,
+///   `${ENV_VAR:-default}` falls back to `default` when unset, and `$${` is a literal `${`.
+///   Substitution is textual, so `"epochIntervalMicros": ${EPOCH}` works for numbers too.
+/// - Any object of the form `{ "$include": "path.json" }` is replaced by the parsed
+///   contents of that file (resolved relative to the including file, interpolated the same way).
+pub fn load_genesis_config(path: &str) -> anyhow::Result<GenesisConfig> {
+    load_genesis_config_with_overrides(path, &[])
+}
+
+/// Load a GenesisConfig like [`load_genesis_config`], then apply `--set` overrides
+/// (see [`apply_config_override`]) to the preprocessed JSON before deserializing it.
+pub fn load_genesis_config_with_overrides(
+    path: &str,
+    overrides: &[String],
+) -> anyhow::Result<GenesisConfig> {
+    let mut value = load_config_value(Path::new(path), &mut ConfigSources::default())?;
+    for spec in overrides {
+        apply_config_override(&mut value, spec)?;
+    }
+    config_from_value(value, path)
+}
+
+/// Files loading the config at `path` with `overrides` reads: the config, its
+/// `$include` fragments and, as `--set` may point it elsewhere, the
+/// `replayBroadcast` file
+pub fn config_input_files(path: &str, overrides: &[String]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut sources = ConfigSources::default();
+    let mut value = load_config_value(Path::new(path), &mut sources)?;
+    for spec in overrides {
+        apply_config_override(&mut value, spec)?;
+    }
+    let mut files = sources.files;
+    if let Some(replay) = value.get("replayBroadcast").and_then(Value::as_str) {
+        files.push(PathBuf::from(replay));
+    }
+    Ok(files)
+}
+
+/// Parse `content` as the GenesisConfig file at `path`, preprocessed like
+/// [`load_genesis_config`] with includes resolved relative to `path`
+pub fn parse_genesis_config(content: &str, path: &str) -> anyhow::Result<GenesisConfig> {
+    let canonical = fs::canonicalize(path)
+        .map_err(|e| anyhow::anyhow!("Failed to open config file {}: {}", path, e))?;
+    let value = parse_config_value(content, &canonical, &mut ConfigSources::default())?;
+    config_from_value(value, path)
+}
+
+fn config_from_value(value: Value, path: &str) -> anyhow::Result<GenesisConfig> {
+    let chain_id_defaulted = value.get("chainId").is_none();
+    let mut config: GenesisConfig = serde_json::from_value(value)
+        .map_err(|e| anyhow::anyhow!("Failed to parse genesis config {}: {}", path, e))?;
+    config.chain_id_defaulted = chain_id_defaulted;
+    if config.governance_multisig.is_some() && config.governance_owner.is_empty() {
+        config.governance_owner = multisig::multisig_address().to_checksum(None);
+    }
+    encode_typed_configs(&mut config)?;
+    Ok(config)
+}
+
+/// Replace raw config hex with the encoding of its typed counterpart, if given
+fn encode_typed_configs(config: &mut GenesisConfig) -> anyhow::Result<()> {
+    if let Some(consensus) = &config.consensus_config_struct {
+        let encoded = format!("0x{}", hex::encode(consensus.to_bcs()?));
+        if !config.consensus_config.is_empty()
+            && parse_hex_bytes("consensusConfig", &config.consensus_config).ok()
+                != parse_hex_bytes("consensusConfig", &encoded).ok()
+        {
+            warn!(
+                "consensusConfigStruct overrides a different consensusConfig ({} -> {})",
+                config.consensus_config, encoded
+            );
+        }
+        config.consensus_config = encoded;
+    } else if config.consensus_config.is_empty() {
+        anyhow::bail!("Either consensusConfig or consensusConfigStruct must be set");
+    }
+    Ok(())
+}
+
+/// Set one field of the config JSON, given as `<dotted.path>=<value>`.
+///
+/// Path segments are the config's JSON keys; a numeric segment indexes an array,
+/// as in `validators.0.stakeAmount`. The value is parsed as JSON and taken as a
+/// string when that fails, so `chainId=42069`, `consensusConfig=0x01` and
+/// `validators.0.moniker="42"` all do what they look like. Where the config
+/// already holds a string, the text stays a string, so amounts such as
+/// `validatorConfig.minimumBond=1000000000000000000` are not turned into numbers.
+/// Every segment but the last must already exist, which catches typos in the
+/// middle of a path.
+pub fn apply_config_override(value: &mut Value, spec: &str) -> anyhow::Result<()> {
+    let (path, raw) = spec
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("--set must be <dotted.path>=<value>, got {}", spec))?;
+    if path.is_empty() {
+        anyhow::bail!("--set {} has an empty path", spec);
+    }
+    let parse_value = |existing: Option<&Value>| match serde_json::from_str(raw) {
+        Ok(Value::String(text)) => Value::String(text),
+        Ok(_) if existing.is_some_and(Value::is_string) => Value::String(raw.to_string()),
+        Ok(value) => value,
+        Err(_) => Value::String(raw.to_string()),
+    };
+
+    let segments: Vec<&str> = path.split('.').collect();
+    let (last, parents) = segments.split_last().expect("split yields at least one segment");
+    let mut target = value;
+    for (i, segment) in parents.iter().enumerate() {
+        let at = segments[..=i].join(".");
+        target = match target {
+            Value::Object(map) => map
+                .get_mut(*segment)
+                .ok_or_else(|| anyhow::anyhow!("--set {}: {} is not in the config", path, at))?,
+            Value::Array(items) => {
+                let len = items.len();
+                segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| items.get_mut(index))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("--set {}: {} is not an index below {}", path, at, len)
+                    })?
+            }
+            other => anyhow::bail!("--set {}: {} is {}, not an object or array", path, at, other),
+        };
+    }
+
+    match target {
+        Value::Object(map) => {
+            let new_value = parse_value(map.get(*last));
+            match map.insert(last.to_string(), new_value.clone()) {
+                Some(old) => info!("--set {}: {} -> {}", path, old, new_value),
+                None => warn!("--set {}: {} was not in the config and is added", path, new_value),
+            }
+        }
+        Value::Array(items) => {
+            let len = items.len();
+            let Some(item) = last.parse::<usize>().ok().and_then(|index| items.get_mut(index))
+            else {
+                anyhow::bail!("--set {}: {} is not an index below {}", path, last, len);
+            };
+            let new_value = parse_value(Some(item));
+            info!("--set {}: {} -> {}", path, item, new_value);
+            *item = new_value;
+        }
+        other => anyhow::bail!(
+            "--set {}: parent of {} is {}, not an object or array",
+            path,
+            last,
+            other
+        ),
+    }
+    Ok(())
+}
+
+fn load_config_value(path: &Path, sources: &mut ConfigSources) -> anyhow::Result<Value> {
+    let canonical = fs::canonicalize(path)
+        .map_err(|e| anyhow::anyhow!("Failed to open config file {}: {}", path.display(), e))?;
+    if sources.stack.contains(&canonical) {
+        anyhow::bail!("Circular $include detected at {}", path.display());
+    }
+
+    let raw = fs::read_to_string(&canonical)?;
+    sources.files.push(canonical.clone());
+    parse_config_value(&raw, &canonical, sources)
+}
+
+/// Interpolate and parse the contents of the config file at `canonical`, then
+/// resolve its includes
+fn parse_config_value(
+    raw: &str,
+    canonical: &Path,
+    sources: &mut ConfigSources,
+) -> anyhow::Result<Value> {
+    let content = interpolate_env_vars(raw)
+        .map_err(|e| anyhow::anyhow!("{}: {}", canonical.display(), e))?;
+    let mut value: Value = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Invalid JSON in {}: {}", canonical.display(), e))?;
+
+    sources.stack.push(canonical.to_path_buf());
+    let base_dir = canonical.parent().unwrap_or_else(|| Path::new("."));
+    resolve_includes(&mut value, base_dir, sources)?;
+    sources.stack.pop();
+
+    Ok(value)
+}
+
+fn resolve_includes(
+    value: &mut Value,
+    base_dir: &Path,
+    sources: &mut ConfigSources,
+) -> anyhow::Result<()> {
+    match value {
+        Value::Object(map) => {
+            if let Some(include) = map.get(INCLUDE_KEY) {
+                let Some(include_path) = include.as_str() else {
+                    anyhow::bail!("{} must be a string path, got {}", INCLUDE_KEY, include);
+                };
+                if map.len() != 1 {
+                    anyhow::bail!(
+                        "{} object must not contain other keys (including {})",
+                        INCLUDE_KEY,
+                        include_path
+                    );
+                }
+                info!("Including config fragment: {}", include_path);
+                *value = load_config_value(&base_dir.join(include_path), sources)?;
+                return Ok(());
+            }
+            for child in map.values_mut() {
+                resolve_includes(child, base_dir, sources)?;
+            }
+        }
+        Value::Array(items) => {
+            for child in items.iter_mut() {
+                resolve_includes(child, base_dir, sources)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace `${VAR}` / `${VAR:-default}` references with environment variable values
+fn interpolate_env_vars(input: &str) -> anyhow::Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(pos) = rest.find('$') {
+        output.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        if let Some(escaped) = after.strip_prefix("${") {
+            output.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let Some(body) = after.strip_prefix('{') else {
+            output.push('$');
+            rest = after;
+            continue;
+        };
+        let end = body
+            .find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unterminated ${{...}} reference"))?;
+        let reference = &body[..end];
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            anyhow::bail!("Invalid environment variable reference: ${{{}}}", reference);
+        }
+
+        let resolved = match (std::env::var(name), default) {
+            (Ok(v), _) => v,
+            (Err(_), Some(default)) => default.to_string(),
+            (Err(_), None) => anyhow::bail!("Environment variable {} is not set", name),
+        };
+        // Escape as JSON does, control characters included, so the substitution
+        // stays valid inside JSON strings
+        let escaped = serde_json::to_string(&resolved).expect("strings serialize");
+        output.push_str(&escaped[1..escaped.len() - 1]);
+        rest = &body[end + 1..];
+    }
+    output.push_str(rest);
+
+    Ok(output)
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -160,13 +902,34 @@ pub struct OracleInitParams {
 
     pub callbacks: Vec<String>, // addresses as hex strings
 
+    /// Callbacks staged at genesis and applied by NativeOracle at the first
+    /// epoch boundary; `callbacks` are active from block 0
+    #[serde(rename = "pendingCallbacks", default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_callbacks: Vec<PendingCallback>,
+
     #[serde(default)]
     pub tasks: Vec<OracleTaskParams>,
 
+    /// Task types registered in OnDemandOracleTaskConfig
+    #[serde(rename = "onDemandTasks", default)]
+    pub on_demand_tasks: Vec<OnDemandTaskParams>,
+
+    /// Expected OracleRequestQueue state, checked after genesis
+    #[serde(rename = "requestQueue", default, skip_serializing_if = "Option::is_none")]
+    pub request_queue: Option<RequestQueueConfig>,
+
     #[serde(rename = "bridgeConfig", default)]
     pub bridge_config: BridgeConfig,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PendingCallback {
+    #[serde(rename = "sourceType")]
+    pub source_type: u32,
+
+    pub callback: String, // address
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct OracleTaskParams {
     #[serde(rename = "sourceType")]
@@ -191,6 +954,31 @@ pub struct OracleTaskParams {
     pub config: String, // The URI string
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct OnDemandTaskParams {
+    #[serde(rename = "sourceType")]
+    pub source_type: u32,
+
+    #[serde(rename = "sourceId")]
+    pub source_id: u64,
+
+    pub config: String, // encoded as its UTF-8 bytes, like task configs
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RequestQueueConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub treasury: Option<String>, // address
+
+    /// Fee per source type, in any format accepted by [`parse_amount`]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fees: BTreeMap<u32, String>,
+
+    /// Request expiration per source type, in seconds
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub expirations: BTreeMap<u32, u64>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct BridgeConfig {
     pub deploy: bool,
@@ -290,6 +1078,12 @@ sol! {
         bytes config;
     }
 
+    struct SolOnDemandTaskParams {
+        uint32 sourceType;
+        uint256 sourceId;
+        bytes config;
+    }
+
     struct SolBridgeConfig {
         bool deploy;
         address trustedBridge;
@@ -300,6 +1094,7 @@ sol! {
         uint32[] sourceTypes;
         address[] callbacks;
         SolOracleTaskParams[] tasks;
+        SolOnDemandTaskParams[] onDemandTasks;
         SolBridgeConfig bridgeConfig;
     }
 
@@ -347,6 +1142,9 @@ sol! {
 
     contract Genesis {
         function initialize(SolGenesisInitParams calldata params) external payable;
+        function beginInitialize(SolGenesisInitParams calldata params) external payable;
+        function addValidatorBatch(SolInitialValidator[] calldata validators) external payable;
+        function finishInitialize() external;
     }
 }
 
@@ -354,27 +1152,191 @@ sol! {
 // CONVERSION FUNCTIONS
 // ============================================================================
 
-fn parse_u256(s: &str) -> U256 {
-    s.parse::<U256>()
-        .expect(&format!("Invalid U256 string: {}", s))
+/// Decimals of the units accepted after an amount
+const AMOUNT_UNITS: [(&str, u32); 4] = [("wei", 0), ("gwei", 9), ("ether", 18), ("eth", 18)];
+
+/// Parse a token amount into wei.
+///
+/// Accepts plain integers (`"1000000000000000000"`), `_` separators (`"1_000"`),
+/// `0x` hex, scientific notation (`"1e18"`, `"1.5e3"`) and a trailing unit
+/// (`"1_000 ether"`, `"5 gwei"`). Fractions must resolve to whole wei.
+pub fn parse_amount(s: &str) -> Result<U256, String> {
+    let cleaned = s.trim().replace('_', "");
+    if let Some(hex_digits) = cleaned.strip_prefix("0x").or_else(|| cleaned.strip_prefix("0X")) {
+        return U256::from_str_radix(hex_digits, 16)
+            .map_err(|e| format!("invalid hex amount {:?}: {}", s, e));
+    }
+
+    let (number, unit) = match cleaned.split_once(char::is_whitespace) {
+        Some((number, unit)) => (number, unit.trim()),
+        None => (cleaned.as_str(), ""),
+    };
+    let unit_decimals = if unit.is_empty() {
+        0
+    } else {
+        AMOUNT_UNITS
+            .iter()
+            .find(|(name, _)| unit.eq_ignore_ascii_case(name))
+            .map(|(_, decimals)| *decimals)
+            .ok_or_else(|| format!("unknown unit {:?} in amount {:?}", unit, s))?
+    };
+
+    let (mantissa, exponent) = match number.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (
+            mantissa,
+            exponent
+                .parse::<i64>()
+                .map_err(|_| format!("invalid exponent in amount {:?}", s))?,
+        ),
+        None => (number, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if integer.is_empty() && fraction.is_empty()
+        || !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("invalid amount {:?}", s));
+    }
+
+    // value = digits * 10^(exponent + unit - fraction digits)
+    let digits = format!("{}{}", integer, fraction);
+    let overflow = || format!("amount {:?} overflows uint256", s);
+    let scale = i64::try_from(fraction.len())
+        .ok()
+        .and_then(|fraction_digits| {
+            exponent
+                .checked_add(unit_decimals as i64)?
+                .checked_sub(fraction_digits)
+        })
+        .ok_or_else(overflow)?;
+    let (digits, scale) = if scale < 0 {
+        let cut = digits.len().saturating_sub(scale.unsigned_abs() as usize);
+        if digits[cut..].chars().any(|c| c != '0') {
+            return Err(format!("amount {:?} is not a whole number of wei", s));
+        }
+        (&digits[..cut], 0)
+    } else {
+        (digits.as_str(), scale as u64)
+    };
+    let base = if digits.is_empty() {
+        U256::ZERO
+    } else {
+        digits
+            .parse::<U256>()
+            .map_err(|e| format!("invalid amount {:?}: {}", s, e))?
+    };
+    U256::from(10)
+        .checked_pow(U256::from(scale))
+        .and_then(|multiplier| base.checked_mul(multiplier))
+        .ok_or_else(overflow)
+}
+
+/// Render wei as ether for human review, e.g. `1000.5 ether`
+pub fn format_ether(wei: U256) -> String {
+    let one_ether = U256::from(10).pow(U256::from(18));
+    let whole = wei / one_ether;
+    let fraction = wei % one_ether;
+    if fraction.is_zero() {
+        format!("{} ether", whole)
+    } else {
+        let fraction = format!("{:0>18}", fraction.to_string());
+        format!("{}.{} ether", whole, fraction.trim_end_matches('0'))
+    }
+}
+
+fn parse_u256(field: &str, s: &str) -> anyhow::Result<U256> {
+    parse_amount(s).map_err(|e| anyhow::anyhow!("{}: {}", field, e))
 }
 
-fn parse_u128(s: &str) -> u128 {
-    s.parse::<u128>()
-        .expect(&format!("Invalid u128 string: {}", s))
+fn parse_u128(field: &str, s: &str) -> anyhow::Result<u128> {
+    u128::try_from(parse_u256(field, s)?)
+        .map_err(|_| anyhow::anyhow!("{}: amount {} does not fit in uint128", field, s))
 }
 
-fn parse_address(s: &str) -> Address {
+fn parse_address(field: &str, s: &str) -> anyhow::Result<Address> {
     s.parse::<Address>()
-        .expect(&format!("Invalid address: {}", s))
+        .map_err(|e| anyhow::anyhow!("{}: invalid address {:?}: {}", field, s, e))
 }
 
-fn parse_hex_bytes(s: &str) -> Vec<u8> {
-    let s = s.strip_prefix("0x").unwrap_or(s);
-    if s.is_empty() {
-        return Vec::new();
+fn parse_hex_bytes(field: &str, s: &str) -> anyhow::Result<Vec<u8>> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    if digits.is_empty() {
+        return Ok(Vec::new());
     }
-    hex::decode(s).expect(&format!("Invalid hex string: {}", s))
+    hex::decode(digits).map_err(|e| anyhow::anyhow!("{}: invalid hex {:?}: {}", field, s, e))
+}
+
+/// Convert `extraAlloc`, `treasury` and `feeCollector` into plain accounts for the genesis alloc
+pub fn extra_alloc_accounts(config: &GenesisConfig) -> anyhow::Result<HashMap<Address, PlainAccount>> {
+    let mut accounts = HashMap::new();
+    for (address, account) in &config.extra_alloc {
+        let parsed: Address = address
+            .parse()
+            .map_err(|e| anyhow::anyhow!("extraAlloc: invalid address {}: {}", address, e))?;
+        let balance = match &account.balance {
+            Some(balance) => parse_amount(balance)
+                .map_err(|e| anyhow::anyhow!("extraAlloc {}: balance: {}", address, e))?,
+            None => U256::ZERO,
+        };
+        let code = match &account.code {
+            Some(code) => {
+                let code = code.strip_prefix("0x").unwrap_or(code);
+                hex::decode(code)
+                    .map_err(|e| anyhow::anyhow!("extraAlloc {}: code: {}", address, e))?
+            }
+            None => Vec::new(),
+        };
+        let mut storage = HashMap::new();
+        for (slot, value) in &account.storage {
+            let parse_word = |s: &str| {
+                U256::from_str_radix(s.strip_prefix("0x").unwrap_or(s), 16)
+                    .map_err(|e| anyhow::anyhow!("extraAlloc {}: storage {}: {}", address, s, e))
+            };
+            storage.insert(parse_word(slot)?, parse_word(value)?);
+        }
+
+        let bytecode = Bytecode::new_raw(code.into());
+        accounts.insert(
+            parsed,
+            PlainAccount {
+                info: AccountInfo {
+                    balance,
+                    nonce: account.nonce.unwrap_or(0),
+                    code_hash: bytecode.hash_slow(),
+                    code: Some(bytecode),
+                },
+                storage: storage.into_iter().collect(),
+            },
+        );
+    }
+    for (key, account) in config.funded_accounts() {
+        let parsed: Address = account
+            .address
+            .parse()
+            .map_err(|e| anyhow::anyhow!("{}: invalid address {}: {}", key, account.address, e))?;
+        if accounts.contains_key(&parsed) {
+            anyhow::bail!(
+                "{} {} is already in the alloc (extraAlloc or treasury); give it one balance",
+                key,
+                account.address
+            );
+        }
+        let balance = match &account.balance {
+            Some(balance) => parse_amount(balance)
+                .map_err(|e| anyhow::anyhow!("{}: balance: {}", key, e))?,
+            None => U256::ZERO,
+        };
+        accounts.insert(
+            parsed,
+            PlainAccount {
+                info: AccountInfo {
+                    balance,
+                    ..AccountInfo::default()
+                },
+                storage: Default::default(),
+            },
+        );
+    }
+    Ok(accounts)
 }
 
 /// BCS encode a string (for network addresses)
@@ -383,30 +1345,37 @@ fn bcs_encode_string(s: &str) -> Vec<u8> {
     bcs::to_bytes(s).expect(&format!("Failed to BCS encode string: {}", s))
 }
 
-pub fn convert_config_to_sol(config: &GenesisConfig) -> SolGenesisInitParams {
+/// Validate and normalize a network address, then BCS encode it
+fn bcs_encode_network_address(s: &str) -> anyhow::Result<Vec<u8>> {
+    let normalized = normalize_network_address(s)
+        .map_err(|e| anyhow::anyhow!("Invalid network address {}: {}", s, e))?;
+    Ok(bcs_encode_string(&normalized))
+}
+
+pub fn convert_config_to_sol(config: &GenesisConfig) -> anyhow::Result<SolGenesisInitParams> {
     // Convert ValidatorConfig
     let validator_config = SolValidatorConfigParams {
-        minimumBond: parse_u256(&config.validator_config.minimum_bond),
-        maximumBond: parse_u256(&config.validator_config.maximum_bond),
+        minimumBond: parse_u256("validatorConfig.minimumBond", &config.validator_config.minimum_bond)?,
+        maximumBond: parse_u256("validatorConfig.maximumBond", &config.validator_config.maximum_bond)?,
         unbondingDelayMicros: config.validator_config.unbonding_delay_micros,
         allowValidatorSetChange: config.validator_config.allow_validator_set_change,
         votingPowerIncreaseLimitPct: config.validator_config.voting_power_increase_limit_pct,
-        maxValidatorSetSize: parse_u256(&config.validator_config.max_validator_set_size),
+        maxValidatorSetSize: parse_u256("validatorConfig.maxValidatorSetSize", &config.validator_config.max_validator_set_size)?,
         autoEvictEnabled: config.validator_config.auto_evict_enabled,
         autoEvictThresholdPct: config.validator_config.auto_evict_threshold_pct,
     };
 
     // Convert StakingConfig
     let staking_config = SolStakingConfigParams {
-        minimumStake: parse_u256(&config.staking_config.minimum_stake),
+        minimumStake: parse_u256("stakingConfig.minimumStake", &config.staking_config.minimum_stake)?,
         lockupDurationMicros: config.staking_config.lockup_duration_micros,
         unbondingDelayMicros: config.staking_config.unbonding_delay_micros,
     };
 
     // Convert GovernanceConfig
     let governance_config = SolGovernanceConfigParams {
-        minVotingThreshold: parse_u128(&config.governance_config.min_voting_threshold),
-        requiredProposerStake: parse_u256(&config.governance_config.required_proposer_stake),
+        minVotingThreshold: parse_u128("governanceConfig.minVotingThreshold", &config.governance_config.min_voting_threshold)?,
+        requiredProposerStake: parse_u256("governanceConfig.requiredProposerStake", &config.governance_config.required_proposer_stake)?,
         votingDurationMicros: config.governance_config.voting_duration_micros,
     };
 
@@ -430,8 +1399,8 @@ pub fn convert_config_to_sol(config: &GenesisConfig) -> SolGenesisInitParams {
             .oracle_config
             .callbacks
             .iter()
-            .map(|s| parse_address(s))
-            .collect(),
+            .map(|s| parse_address("oracleConfig.callbacks", s))
+            .collect::<anyhow::Result<_>>()?,
         tasks: config
             .oracle_config
             .tasks
@@ -464,17 +1433,33 @@ pub fn convert_config_to_sol(config: &GenesisConfig) -> SolGenesisInitParams {
                 }
             })
             .collect(),
+        onDemandTasks: config
+            .oracle_config
+            .on_demand_tasks
+            .iter()
+            .map(|t| SolOnDemandTaskParams {
+                sourceType: t.source_type,
+                sourceId: U256::from(t.source_id),
+                config: t.config.as_bytes().to_vec().into(),
+            })
+            .collect(),
         bridgeConfig: SolBridgeConfig {
             deploy: config.oracle_config.bridge_config.deploy,
             trustedBridge: if config.oracle_config.bridge_config.trusted_bridge.is_empty() {
                 Address::ZERO
             } else {
-                parse_address(&config.oracle_config.bridge_config.trusted_bridge)
+                parse_address(
+                    "oracleConfig.bridgeConfig.trustedBridge",
+                    &config.oracle_config.bridge_config.trusted_bridge,
+                )?
             },
             trustedSourceId: if config.oracle_config.bridge_config.trusted_source_id.is_empty() {
                 U256::ZERO
             } else {
-                parse_u256(&config.oracle_config.bridge_config.trusted_source_id)
+                parse_u256(
+                    "oracleConfig.bridgeConfig.trustedSourceId",
+                    &config.oracle_config.bridge_config.trusted_source_id,
+                )?
             },
         },
     };
@@ -485,8 +1470,8 @@ pub fn convert_config_to_sol(config: &GenesisConfig) -> SolGenesisInitParams {
             .jwk_config
             .issuers
             .iter()
-            .map(|s| parse_hex_bytes(s).into())
-            .collect(),
+            .map(|s| parse_hex_bytes("jwkConfig.issuers", s).map(Into::into))
+            .collect::<anyhow::Result<_>>()?,
         jwks: config
             .jwk_config
             .jwks
@@ -506,58 +1491,103 @@ pub fn convert_config_to_sol(config: &GenesisConfig) -> SolGenesisInitParams {
             .collect(),
     };
 
-    // Convert Validators
-    let validators: Vec<SolInitialValidator> = config
-        .validators
-        .iter()
-        .map(|v| SolInitialValidator {
-            operator: parse_address(&v.operator),
-            owner: parse_address(&v.owner),
-            staker: parse_address(&v.staker),
-            stakeAmount: parse_u256(&v.stake_amount),
-            moniker: v.moniker.clone(),
-            consensusPubkey: parse_hex_bytes(&v.consensus_pubkey).into(),
-            consensusPop: parse_hex_bytes(&v.consensus_pop).into(),
-            // BCS encode network addresses from human-readable format
-            networkAddresses: bcs_encode_string(&v.network_addresses).into(),
-            fullnodeAddresses: bcs_encode_string(&v.fullnode_addresses).into(),
-            votingPower: parse_u256(&v.voting_power),
-        })
-        .collect();
-
-    SolGenesisInitParams {
+    Ok(SolGenesisInitParams {
         validatorConfig: validator_config,
         stakingConfig: staking_config,
         governanceConfig: governance_config,
-        governanceOwner: parse_address(&config.governance_owner),
+        governanceOwner: parse_address("governanceOwner", &config.governance_owner)?,
         epochIntervalMicros: config.epoch_interval_micros,
         majorVersion: config.major_version,
-        consensusConfig: parse_hex_bytes(&config.consensus_config).into(),
-        executionConfig: parse_hex_bytes(&config.execution_config).into(),
+        consensusConfig: parse_hex_bytes("consensusConfig", &config.consensus_config)?.into(),
+        executionConfig: parse_hex_bytes("executionConfig", &config.execution_config)?.into(),
         randomnessConfig: randomness_config,
         oracleConfig: oracle_config,
         jwkConfig: jwk_config,
-        validators,
+        validators: convert_validators_to_sol(&config.validators)?,
         initialLockedUntilMicros: config.initial_locked_until_micros,
-    }
+    })
 }
 
-/// Calculate total stake amount needed for Genesis.initialize (payable)
-pub fn calculate_total_stake(config: &GenesisConfig) -> U256 {
-    config
-        .validators
+/// Validators as encoded in `GenesisInitParams.validators` and validator batches
+pub fn convert_validators_to_sol(validators: &[InitialValidator]) -> anyhow::Result<Vec<SolInitialValidator>> {
+    validators
         .iter()
-        .map(|v| parse_u256(&v.stake_amount))
-        .fold(U256::ZERO, |acc, stake| acc + stake)
+        .map(|v| {
+            Ok(SolInitialValidator {
+                operator: parse_address(&format!("validator {} operator", v.moniker), &v.operator)?,
+                owner: parse_address(&format!("validator {} owner", v.moniker), &v.owner)?,
+                staker: parse_address(&format!("validator {} staker", v.moniker), &v.staker)?,
+                stakeAmount: parse_u256(&format!("validator {} stakeAmount", v.moniker), &v.stake_amount)?,
+                moniker: v.moniker.clone(),
+                consensusPubkey: parse_hex_bytes(
+                    &format!("validator {} consensusPubkey", v.moniker),
+                    &v.consensus_pubkey,
+                )?
+                .into(),
+                consensusPop: parse_hex_bytes(&format!("validator {} consensusPop", v.moniker), &v.consensus_pop)?
+                    .into(),
+                // BCS encode network addresses from human-readable format
+                networkAddresses: bcs_encode_network_address(&v.network_addresses)?.into(),
+                fullnodeAddresses: bcs_encode_network_address(&v.fullnode_addresses)?.into(),
+                votingPower: parse_u256(&format!("validator {} votingPower", v.moniker), &v.voting_power)?,
+            })
+        })
+        .collect()
+}
+
+/// Calculate total stake amount needed for Genesis.initialize (payable)
+pub fn calculate_total_stake(config: &GenesisConfig) -> anyhow::Result<U256> {
+    validators_stake(&config.validators)
+}
+
+/// Stake the validators' pools are created with
+pub fn validators_stake(validators: &[InitialValidator]) -> anyhow::Result<U256> {
+    validators.iter().try_fold(U256::ZERO, |total, v| {
+        let stake = parse_u256(&format!("validator {} stakeAmount", v.moniker), &v.stake_amount)?;
+        total
+            .checked_add(stake)
+            .ok_or_else(|| anyhow::anyhow!("total validator stake overflows uint256"))
+    })
 }
 
-pub fn call_genesis_initialize(genesis_address: Address, config: &GenesisConfig) -> TxEnv {
-    let sol_params = convert_config_to_sol(config);
-    let total_stake = calculate_total_stake(config);
+/// Validator registration batches: the whole set unless `validatorBatchSize`
+/// splits it into more than one
+pub fn validator_batches(config: &GenesisConfig) -> Vec<&[InitialValidator]> {
+    match config.validator_batch_size {
+        Some(size) if size > 0 && config.validators.len() > size => config.validators.chunks(size).collect(),
+        _ => vec![config.validators.as_slice()],
+    }
+}
+
+pub fn call_genesis_initialize(genesis_address: Address, config: &GenesisConfig) -> anyhow::Result<TxEnv> {
+    let call_data = Genesis::initializeCall {
+        params: convert_config_to_sol(config)?,
+    }
+    .abi_encode();
+    genesis_initialize_txn(genesis_address, config, call_data)
+}
 
+/// `Genesis.initialize` transaction with prepared calldata, funded with the
+/// total initial stake
+pub fn genesis_initialize_txn(
+    genesis_address: Address,
+    config: &GenesisConfig,
+    call_data: Vec<u8>,
+) -> anyhow::Result<TxEnv> {
+    let total_stake = calculate_total_stake(config)?;
+
+    log_initialize_params(genesis_address, config);
+    info!("Total stake value: {} wei", total_stake);
+    info!("Call data length: {}", call_data.len());
+
+    // Genesis.initialize is payable - need to send total stake amount
+    Ok(new_system_call_txn_with_value(genesis_address, call_data.into(), total_stake))
+}
+
+/// Log the config `Genesis.initialize` and `beginInitialize` are called with
+fn log_initialize_params(genesis_address: Address, config: &GenesisConfig) {
     info!("=== Genesis Initialize Parameters ===");
     info!("Genesis address: {:?}", genesis_address);
-    info!("Total stake value: {} wei", total_stake);
     info!("Validator count: {}", config.validators.len());
     info!("Epoch interval: {} micros", config.epoch_interval_micros);
     info!("Major version: {}", config.major_version);
@@ -585,13 +1615,59 @@ pub fn call_genesis_initialize(genesis_address: Address, config: &GenesisConfig)
             );
         }
     }
+    if !config.oracle_config.on_demand_tasks.is_empty() {
+        info!("On-demand task types count: {}", config.oracle_config.on_demand_tasks.len());
+        for (i, task) in config.oracle_config.on_demand_tasks.iter().enumerate() {
+            info!(
+                "  On-demand task {}: sourceType={}, sourceId={}",
+                i, task.source_type, task.source_id
+            );
+        }
+    }
+}
 
-    let call_data = Genesis::initializeCall { params: sol_params }.abi_encode();
+/// Transactions of a chunked genesis: `beginInitialize` registering the first
+/// of `batches`, `addValidatorBatch` for each later one, then
+/// `finishInitialize`. Each registering call is funded with its batch's stake.
+pub fn chunked_genesis_initialize_txns(
+    genesis_address: Address,
+    config: &GenesisConfig,
+    batches: &[&[InitialValidator]],
+) -> anyhow::Result<Vec<TxEnv>> {
+    let (first, rest) = batches.split_first().expect("at least one validator batch");
+    info!(
+        "Registering {} validators in {} batches of at most {}",
+        config.validators.len(),
+        batches.len(),
+        first.len()
+    );
 
+    let mut params = convert_config_to_sol(config)?;
+    params.validators = convert_validators_to_sol(first)?;
+    let call_data = Genesis::beginInitializeCall { params }.abi_encode();
+    let first_stake = validators_stake(first)?;
+    log_initialize_params(genesis_address, config);
+    info!("Validator batch 1: {} validator(s), {} wei", first.len(), first_stake);
     info!("Call data length: {}", call_data.len());
+    let mut transactions = vec![new_system_call_txn_with_value(
+        genesis_address,
+        call_data.into(),
+        first_stake,
+    )];
+
+    for (i, batch) in rest.iter().enumerate() {
+        let stake = validators_stake(batch)?;
+        info!("Validator batch {}: {} validator(s), {} wei", i + 2, batch.len(), stake);
+        let call_data = Genesis::addValidatorBatchCall {
+            validators: convert_validators_to_sol(batch)?,
+        }
+        .abi_encode();
+        transactions.push(new_system_call_txn_with_value(genesis_address, call_data.into(), stake));
+    }
 
-    // Genesis.initialize is payable - need to send total stake amount
-    new_system_call_txn_with_value(genesis_address, call_data.into(), total_stake)
+    let call_data = Genesis::finishInitializeCall {}.abi_encode();
+    transactions.push(new_system_call_txn(genesis_address, call_data.into()));
+    Ok(transactions)
 }
 
 // ============================================================================
@@ -612,6 +1688,7 @@ sol! {
         }
 
         function getActiveValidators() external view returns (ValidatorConsensusInfo[] memory);
+        function getTotalVotingPower() external view returns (uint256);
     }
 }
 
@@ -664,3 +1741,201 @@ pub fn print_active_validators_result(result: &ExecutionResult, config: &Genesis
         );
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ether(n: u64) -> U256 {
+        U256::from(n) * U256::from(10).pow(U256::from(18))
+    }
+
+    #[test]
+    fn parse_amount_accepts_units_and_separators() {
+        assert_eq!(parse_amount("1_000 ether"), Ok(ether(1000)));
+        assert_eq!(parse_amount("5 gwei"), Ok(U256::from(5_000_000_000u64)));
+        assert_eq!(parse_amount("1 ETH"), Ok(ether(1)));
+        assert_eq!(parse_amount(" 42 "), Ok(U256::from(42)));
+        assert_eq!(parse_amount("0.5 ether"), Ok(ether(1) / U256::from(2)));
+    }
+
+    #[test]
+    fn parse_amount_accepts_scientific_notation() {
+        assert_eq!(parse_amount("1.5e18"), Ok(U256::from(1_500_000_000_000_000_000u64)));
+        assert_eq!(parse_amount("1e3 gwei"), Ok(U256::from(1_000_000_000_000u64)));
+        assert_eq!(parse_amount("2500e-3 ether"), Ok(ether(5) / U256::from(2)));
+    }
+
+    #[test]
+    fn parse_amount_accepts_hex() {
+        assert_eq!(parse_amount("0x0de0b6b3a7640000"), Ok(ether(1)));
+        assert_eq!(parse_amount("0X10"), Ok(U256::from(16)));
+        assert!(parse_amount("0xzz").is_err());
+    }
+
+    #[test]
+    fn parse_amount_rejects_fractional_wei() {
+        let err = parse_amount("1.5").unwrap_err();
+        assert!(err.contains("not a whole number of wei"), "{}", err);
+        assert!(parse_amount("1e-1").is_err());
+        assert!(parse_amount("0.1 gwei").is_ok());
+        assert!(parse_amount("0.0000000001 gwei").is_err());
+    }
+
+    #[test]
+    fn parse_amount_rejects_overflow() {
+        for amount in [
+            "1e78",
+            "1e9223372036854775807 ether",
+            "1.5e-9223372036854775808",
+            "0x1ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        ] {
+            assert!(parse_amount(amount).is_err(), "{}", amount);
+        }
+        assert!(parse_amount("1e77").is_ok());
+        let err = parse_amount("1e9223372036854775807 ether").unwrap_err();
+        assert!(err.contains("overflows uint256"), "{}", err);
+    }
+
+    #[test]
+    fn parse_amount_rejects_malformed_input() {
+        for amount in ["", "ether", "1 wei wei", "1 finney", "1.2.3", "-1", "1e", "."] {
+            assert!(parse_amount(amount).is_err(), "{:?}", amount);
+        }
+    }
+
+    #[test]
+    fn interpolate_env_vars_substitutes_and_escapes() {
+        std::env::set_var("GENESIS_TEST_EPOCH", "7200000000");
+        std::env::set_var("GENESIS_TEST_QUOTED", "a \"b\"\n");
+        std::env::remove_var("GENESIS_TEST_UNSET");
+        assert_eq!(
+            interpolate_env_vars(r#"{"epoch": ${GENESIS_TEST_EPOCH}, "s": "${GENESIS_TEST_QUOTED}"}"#).unwrap(),
+            r#"{"epoch": 7200000000, "s": "a \"b\"\n"}"#
+        );
+        assert_eq!(
+            interpolate_env_vars("${GENESIS_TEST_UNSET:-fallback} ${GENESIS_TEST_EPOCH:-0}").unwrap(),
+            "fallback 7200000000"
+        );
+        // `$${` is a literal `${`; a `$` not followed by `{` is kept
+        assert_eq!(
+            interpolate_env_vars("$${GENESIS_TEST_EPOCH} $5 $$ end$").unwrap(),
+            "${GENESIS_TEST_EPOCH} $5 $$ end$"
+        );
+    }
+
+    #[test]
+    fn interpolate_env_vars_rejects_bad_references() {
+        std::env::remove_var("GENESIS_TEST_MISSING");
+        for input in ["${GENESIS_TEST_MISSING}", "${GENESIS-TEST}", "${}", "${UNTERMINATED"] {
+            assert!(interpolate_env_vars(input).is_err(), "{}", input);
+        }
+    }
+
+    fn config_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("genesis-config-{}-{}", name, std::process::id()));
+        fs::create_dir_all(dir.join("fragments")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn nested_includes_resolve_relative_to_the_including_file() {
+        let dir = config_dir("includes");
+        std::env::set_var("GENESIS_TEST_MONIKER", "alice");
+        fs::write(
+            dir.join("main.json"),
+            r#"{"chainId": 7, "validators": {"$include": "fragments/validators.json"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("fragments/validators.json"),
+            r#"[{"$include": "one.json"}, {"moniker": "bob"}]"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("fragments/one.json"),
+            r#"{"moniker": "${GENESIS_TEST_MONIKER}", "note": "$${literal}"}"#,
+        )
+        .unwrap();
+
+        let mut sources = ConfigSources::default();
+        let value = load_config_value(&dir.join("main.json"), &mut sources).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "chainId": 7,
+                "validators": [{"moniker": "alice", "note": "${literal}"}, {"moniker": "bob"}]
+            })
+        );
+        assert_eq!(sources.files.len(), 3);
+        assert!(sources.stack.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn circular_include_is_rejected() {
+        let dir = config_dir("cycle");
+        fs::write(dir.join("a.json"), r#"{"next": {"$include": "fragments/b.json"}}"#).unwrap();
+        fs::write(dir.join("fragments/b.json"), r#"{"next": {"$include": "../a.json"}}"#).unwrap();
+        let err = load_config_value(&dir.join("a.json"), &mut ConfigSources::default()).unwrap_err();
+        assert!(err.to_string().contains("Circular $include"), "{}", err);
+        fs::write(dir.join("c.json"), r#"{"$include": "a.json", "extra": 1}"#).unwrap();
+        assert!(load_config_value(&dir.join("c.json"), &mut ConfigSources::default()).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn override_target() -> Value {
+        serde_json::json!({
+            "chainId": 1,
+            "validatorConfig": {"minimumBond": "1000", "allowValidatorSetChange": true},
+            "validators": [
+                {"moniker": "v1", "stakeAmount": "1 ether", "networkAddresses": "/ip4/1"},
+                {"moniker": "v2", "stakeAmount": "2 ether"}
+            ]
+        })
+    }
+
+    #[test]
+    fn apply_config_override_sets_dotted_paths() {
+        let mut value = override_target();
+        apply_config_override(&mut value, "chainId=42069").unwrap();
+        apply_config_override(&mut value, "validatorConfig.allowValidatorSetChange=false").unwrap();
+        // Strings stay strings, even when the text parses as a number
+        apply_config_override(&mut value, "validatorConfig.minimumBond=2000").unwrap();
+        apply_config_override(&mut value, "consensusConfig=0x01").unwrap();
+        apply_config_override(&mut value, "validatorConfig.newField={\"a\":[1]}").unwrap();
+        assert_eq!(value["chainId"], 42069);
+        assert_eq!(value["validatorConfig"]["allowValidatorSetChange"], false);
+        assert_eq!(value["validatorConfig"]["minimumBond"], "2000");
+        assert_eq!(value["consensusConfig"], "0x01");
+        assert_eq!(value["validatorConfig"]["newField"], serde_json::json!({"a": [1]}));
+    }
+
+    #[test]
+    fn apply_config_override_indexes_arrays() {
+        let mut value = override_target();
+        apply_config_override(&mut value, "validators.1.stakeAmount=5 ether").unwrap();
+        apply_config_override(&mut value, "validators.0.moniker=\"42\"").unwrap();
+        apply_config_override(&mut value, "validators.0={\"moniker\": \"v0\"}").unwrap();
+        assert_eq!(value["validators"][1]["stakeAmount"], "5 ether");
+        assert_eq!(value["validators"][0], serde_json::json!({"moniker": "v0"}));
+    }
+
+    #[test]
+    fn apply_config_override_rejects_bad_paths() {
+        for spec in [
+            "chainId",
+            "=1",
+            "validatorConfig.missing.field=1",
+            "validators.2.moniker=v3",
+            "validators.x.moniker=v3",
+            "validators.2=1",
+            "chainId.value=1",
+            "validators.0.moniker.first=x",
+        ] {
+            let mut value = override_target();
+            assert!(apply_config_override(&mut value, spec).is_err(), "{}", spec);
+            assert_eq!(value, override_target(), "{}", spec);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Golden-output comparison
+//!
+//! `generate --check-against <dir>` regenerates into the output directory and
+//! compares the result with a previously blessed output, so a release of the
+//! tool can show it still produces the same genesis for a fixed config. Every
+//! file of the golden directory must exist in the new output and match it:
+//! byte for byte, or for JSON files, value for value (key order and
+//! formatting aside). Files new in the output are reported but do not fail.
+//!
+//! Not compared: `manifest.json`, which records the tool version and input
+//! paths, the `genesis_state.json` resume cache, and the `reverts` of
+//! `bundle_state.json`, whose order follows hash map iteration and which never
+//! reach the genesis alloc.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::{error, info, warn};
+
+use crate::{execute::BUILD_STATE_FILE, provenance::MANIFEST_FILE};
+
+/// Differences listed per file; the rest are counted
+const MAX_LISTED_DIFFERENCES: usize = 20;
+
+/// Values longer than this are shortened in the diff
+const MAX_VALUE_CHARS: usize = 80;
+
+/// How one golden file differs from its regenerated counterpart
+#[derive(Debug, Clone)]
+pub struct FileDiff {
+    /// Path relative to the output directory
+    pub file: String,
+    /// One line per difference, at most [`MAX_LISTED_DIFFERENCES`]
+    pub differences: Vec<String>,
+    /// Differences found in total
+    pub total: usize,
+}
+
+/// Files below `dir`, relative to it and sorted
+fn relative_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        let path = dir.join(&relative);
+        for entry in fs::read_dir(&path).context(format!("Failed to read {}", path.display()))? {
+            let entry = entry?;
+            let child = relative.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                pending.push(child);
+            } else {
+                files.push(child);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn is_excluded(file: &Path) -> bool {
+    file == Path::new(MANIFEST_FILE) || file == Path::new(BUILD_STATE_FILE)
+}
+
+fn show(value: &Value) -> String {
+    let text = value.to_string();
+    match text.char_indices().nth(MAX_VALUE_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+/// Collect the paths at which `golden` and `actual` differ
+fn diff_values(path: &str, golden: &Value, actual: &Value, out: &mut Vec<String>) {
+    let shown_path = if path.is_empty() { "/" } else { path };
+    match (golden, actual) {
+        (Value::Object(golden), Value::Object(actual)) => {
+            for (key, golden_value) in golden {
+                let child = format!("{}/{}", path, key);
+                match actual.get(key) {
+                    Some(actual_value) => diff_values(&child, golden_value, actual_value, out),
+                    None => out.push(format!("{}: {} -> (missing)", child, show(golden_value))),
+                }
+            }
+            for (key, actual_value) in actual {
+                if !golden.contains_key(key) {
+                    out.push(format!("{}/{}: (missing) -> {}", path, key, show(actual_value)));
+                }
+            }
+        }
+        (Value::Array(golden), Value::Array(actual)) => {
+            for (i, (golden_value, actual_value)) in golden.iter().zip(actual).enumerate() {
+                diff_values(&format!("{}/{}", path, i), golden_value, actual_value, out);
+            }
+            if golden.len() != actual.len() {
+                out.push(format!(
+                    "{}: {} element(s) -> {} element(s)",
+                    shown_path,
+                    golden.len(),
+                    actual.len()
+                ));
+            }
+        }
+        _ if golden != actual => {
+            out.push(format!("{}: {} -> {}", shown_path, show(golden), show(actual)))
+        }
+        _ => {}
+    }
+}
+
+/// Differences between two versions of `file`, empty when they match
+fn diff_file(file: &Path, golden: &[u8], actual: &[u8]) -> Vec<String> {
+    if golden == actual {
+        return vec![];
+    }
+    let parsed = serde_json::from_slice::<Value>(golden)
+        .and_then(|golden| Ok((golden, serde_json::from_slice::<Value>(actual)?)));
+    match parsed {
+        Ok((mut golden, mut actual)) => {
+            if file == Path::new("bundle_state.json") {
+                for value in [&mut golden, &mut actual] {
+                    if let Some(object) = value.as_object_mut() {
+                        object.remove("reverts");
+                    }
+                }
+            }
+            let mut differences = Vec::new();
+            diff_values("", &golden, &actual, &mut differences);
+            differences
+        }
+        Err(_) => {
+            let lines = |bytes: &[u8]| bytes.split(|b| *b == b'\n').count();
+            let line = golden
+                .split(|b| *b == b'\n')
+                .zip(actual.split(|b| *b == b'\n'))
+                .position(|(golden, actual)| golden != actual)
+                .unwrap_or_else(|| lines(golden).min(lines(actual)));
+            vec![format!(
+                "contents differ from line {} ({} -> {} bytes)",
+                line + 1,
+                golden.len(),
+                actual.len()
+            )]
+        }
+    }
+}
+
+/// Compare the files of `golden_dir` with those regenerated in `output_dir`
+pub fn compare_outputs(golden_dir: &str, output_dir: &str) -> Result<Vec<FileDiff>> {
+    let (golden_dir, output_dir) = (Path::new(golden_dir), Path::new(output_dir));
+    let golden_files = relative_files(golden_dir)?;
+    let mut diffs = Vec::new();
+    for file in golden_files.iter().filter(|file| !is_excluded(file)) {
+        let golden = fs::read(golden_dir.join(file))
+            .context(format!("Failed to read {}", golden_dir.join(file).display()))?;
+        let differences = match fs::read(output_dir.join(file)) {
+            Ok(actual) => diff_file(file, &golden, &actual),
+            Err(_) => vec!["not generated".to_string()],
+        };
+        if !differences.is_empty() {
+            diffs.push(FileDiff {
+                file: file.display().to_string(),
+                total: differences.len(),
+                differences: differences.into_iter().take(MAX_LISTED_DIFFERENCES).collect(),
+            });
+        }
+    }
+    for file in relative_files(output_dir)? {
+        if !is_excluded(&file) && !golden_files.contains(&file) {
+            warn!("{} is new; {} has no golden copy", file.display(), golden_dir.display());
+        }
+    }
+    Ok(diffs)
+}
+
+/// Log each difference; returns whether the outputs matched
+pub fn log_diffs(golden_dir: &str, diffs: &[FileDiff]) -> bool {
+    if diffs.is_empty() {
+        info!("✅ Output matches the golden output in {}", golden_dir);
+        return true;
+    }
+    for diff in diffs {
+        error!("❌ {} differs from {}:", diff.file, golden_dir);
+        for difference in &diff.differences {
+            error!("    {}", difference);
+        }
+        if diff.total > diff.differences.len() {
+            error!("    … and {} more", diff.total - diff.differences.len());
+        }
+    }
+    false
+}
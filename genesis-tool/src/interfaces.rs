@@ -0,0 +1,163 @@
+//! Expected-interface manifests
+//!
+//! `verify --interfaces interfaces.json` calls every view a consumer depends on
+//! against the genesis state and checks that the deployed contracts still
+//! answer it with the layout the consumer decodes. The manifest is a list of
+//! calls in the `preGenesisCalls` format plus the expected return types:
+//!
+//! ```json
+//! [
+//!   { "target": "EpochConfig", "signature": "epochIntervalMicros()", "returns": "(uint64)" },
+//!   {
+//!     "target": "0x00000000000000000000000000000001625F4001",
+//!     "signature": "getProviderJWKs(bytes)",
+//!     "args": ["0x68747470733a2f2f6163636f756e74732e676f6f676c652e636f6d"],
+//!     "returns": "((bytes,uint64,(string,string,string,string,string)[]))"
+//!   }
+//! ]
+//! ```
+//!
+//! `returns` is written like a parameter list, so a single struct is wrapped in
+//! its own parentheses. A requirement fails when the call reverts (an empty
+//! revert is how a contract without the selector answers), returns nothing, or
+//! returns data that does not decode and re-encode to the same bytes.
+
+use alloy_dyn_abi::DynSolType;
+use anyhow::{Context, Result};
+use revm::DatabaseRef;
+use revm_primitives::{hex, Env, ExecutionResult, SpecId};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tracing::{error, info, info_span};
+
+use crate::{
+    genesis::GenesisCall,
+    spec::resolve_spec,
+    utils::execute_revm_sequential,
+    verify::{load_genesis_db, resolve_chain_id, VerifyOptions, VerifyResult},
+};
+
+/// One view a consumer requires, with the return types it decodes
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InterfaceRequirement {
+    #[serde(flatten)]
+    pub call: GenesisCall,
+
+    /// Return types as a parameter list, e.g. `(uint64,bytes)`
+    pub returns: String,
+}
+
+impl InterfaceRequirement {
+    fn describe(&self) -> String {
+        format!("{}.{}", self.call.target, self.call.signature)
+    }
+}
+
+pub fn load_interface_manifest(path: &str) -> Result<Vec<InterfaceRequirement>> {
+    let content = fs::read_to_string(path).context(format!("Failed to read {}", path))?;
+    let requirements: Vec<InterfaceRequirement> =
+        serde_json::from_str(&content).context(format!("Failed to parse {}", path))?;
+    for requirement in &requirements {
+        return_types(&requirement.returns)
+            .context(format!("{}: returns", requirement.describe()))?;
+        requirement
+            .call
+            .call_data()
+            .context(format!("{}: call", requirement.describe()))?;
+    }
+    Ok(requirements)
+}
+
+/// `returns` as a tuple; a bare type is one return value
+fn return_types(returns: &str) -> Result<DynSolType> {
+    let ty = DynSolType::parse(returns.trim()).map_err(|e| anyhow::anyhow!("{}", e))?;
+    Ok(match ty {
+        DynSolType::Tuple(_) => ty,
+        single => DynSolType::Tuple(vec![single]),
+    })
+}
+
+/// Call `requirement` and decode its output strictly; `Ok` holds the output length
+fn check_requirement(
+    db: impl DatabaseRef,
+    spec_id: SpecId,
+    env: &Env,
+    requirement: &InterfaceRequirement,
+) -> std::result::Result<usize, String> {
+    let tx = requirement.call.to_txn().map_err(|e| format!("{:#}", e))?;
+    let returns = return_types(&requirement.returns).map_err(|e| format!("{:#}", e))?;
+    let (results, _) = execute_revm_sequential(db, spec_id, env.clone(), &[tx], None)
+        .map_err(|_| "EVM execution failed".to_string())?;
+    let output = match results.into_iter().next() {
+        Some(ExecutionResult::Success { output, .. }) => output.into_data(),
+        Some(ExecutionResult::Revert { output, .. }) if output.is_empty() => {
+            return Err("reverted without data; the selector is likely unknown".to_string())
+        }
+        Some(ExecutionResult::Revert { output, .. }) => {
+            return Err(match alloy_sol_types::decode_revert_reason(&output) {
+                Some(reason) => format!("reverted: {}", reason),
+                None => format!("reverted: 0x{}", hex::encode(&output)),
+            })
+        }
+        Some(ExecutionResult::Halt { reason, .. }) => return Err(format!("halted: {:?}", reason)),
+        None => return Err("no execution result returned".to_string()),
+    };
+
+    let DynSolType::Tuple(types) = &returns else {
+        unreachable!("return_types always yields a tuple")
+    };
+    if output.is_empty() && !types.is_empty() {
+        return Err("empty output; is the contract deployed?".to_string());
+    }
+    let decoded = returns
+        .abi_decode_params(&output)
+        .map_err(|e| format!("output does not decode as {}: {}", returns.sol_type_name(), e))?;
+    if decoded.abi_encode_params() != output.as_ref() {
+        return Err(format!(
+            "output ({} bytes) does not re-encode as {}",
+            output.len(),
+            returns.sol_type_name()
+        ));
+    }
+    Ok(output.len())
+}
+
+/// Check every requirement of an interface manifest against the genesis state.
+///
+/// The genesis is loaded into memory again, as for the bytecode comparison.
+pub fn cross_check_interfaces(
+    result: &mut VerifyResult,
+    genesis_path: &str,
+    requirements: &[InterfaceRequirement],
+    options: &VerifyOptions,
+) -> Result<()> {
+    let _span = info_span!("verification", step = "interface_check").entered();
+    info!("Checking {} required interface(s)...", requirements.len());
+    let (db, _, header) = load_genesis_db(genesis_path)?;
+    let spec_id = resolve_spec(options.evm_spec, header.config.as_ref(), header.timestamp_secs()?);
+    let env = options.env(resolve_chain_id(options.chain_id, &header)?, spec_id);
+
+    let mut failures = Vec::new();
+    for requirement in requirements {
+        match check_requirement(&db, spec_id, &env, requirement) {
+            Ok(len) => info!(
+                "✅ {} returns {} ({} bytes)",
+                requirement.describe(),
+                requirement.returns,
+                len
+            ),
+            Err(e) => failures.push(format!("interface {}: {}", requirement.describe(), e)),
+        }
+    }
+
+    if failures.is_empty() {
+        info!("✅ All {} required interface(s) conform", requirements.len());
+    } else {
+        for failure in &failures {
+            error!("❌ {}", failure);
+        }
+        result.success = false;
+        result.errors.extend(failures);
+    }
+    Ok(())
+}
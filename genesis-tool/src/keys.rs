@@ -0,0 +1,155 @@
+//! Key generation helpers for validators
+//!
+//! - BLS12-381 (min-pk) consensus keys with proof-of-possession, matching the
+//!   48-byte `consensusPubkey` / 96-byte `consensusPop` format of the config
+//! - secp256k1 keys for operator/owner/staker EOAs
+//! - x25519 noise keys embedded in `networkAddresses`
+
+use alloy_primitives::Address;
+use rand::{CryptoRng, RngCore};
+use revm_primitives::hex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Domain separation tag for BLS proof-of-possession (aptos-crypto compatible)
+pub const BLS_POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+#[derive(Debug, Clone)]
+pub struct BlsKeypair {
+    pub secret_key: [u8; 32],
+    pub public_key: [u8; 48],
+    pub proof_of_possession: [u8; 96],
+}
+
+impl BlsKeypair {
+    pub fn public_key_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.public_key))
+    }
+
+    pub fn proof_of_possession_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.proof_of_possession))
+    }
+}
+
+/// Generate a BLS consensus keypair and its proof-of-possession
+pub fn generate_bls_keypair<R: RngCore + CryptoRng>(rng: &mut R) -> BlsKeypair {
+    let mut ikm = [0u8; 32];
+    rng.fill_bytes(&mut ikm);
+    bls_keypair_from_ikm(&ikm).expect("32-byte IKM is always valid for BLS key generation")
+}
+
+/// Derive a BLS keypair from input key material (at least 32 bytes)
+pub fn bls_keypair_from_ikm(ikm: &[u8]) -> anyhow::Result<BlsKeypair> {
+    let sk = blst::min_pk::SecretKey::key_gen(ikm, &[])
+        .map_err(|e| anyhow::anyhow!("BLS key generation failed: {:?}", e))?;
+    bls_keypair_from_secret(&sk.to_bytes())
+}
+
+/// Check that `pop` is a valid proof-of-possession for `public_key`
+pub fn verify_bls_pop(public_key: &[u8], pop: &[u8]) -> bool {
+    let (Ok(pk), Ok(sig)) = (
+        blst::min_pk::PublicKey::key_validate(public_key),
+        blst::min_pk::Signature::from_bytes(pop),
+    ) else {
+        return false;
+    };
+    sig.verify(true, public_key, BLS_POP_DST, &[], &pk, true) == blst::BLST_ERROR::BLST_SUCCESS
+}
+
+#[derive(Debug, Clone)]
+pub struct EcdsaKey {
+    pub secret_key: [u8; 32],
+    pub address: Address,
+}
+
+/// Generate a secp256k1 key and its Ethereum address
+pub fn generate_ecdsa_key<R: RngCore + CryptoRng>(rng: &mut R) -> EcdsaKey {
+    let signing_key = k256::ecdsa::SigningKey::random(rng);
+    ecdsa_key_from_signing_key(&signing_key)
+}
+
+/// Build an `EcdsaKey` from a raw 32-byte secret
+pub fn ecdsa_key_from_secret(secret: &[u8]) -> anyhow::Result<EcdsaKey> {
+    let signing_key = k256::ecdsa::SigningKey::from_slice(secret)
+        .map_err(|e| anyhow::anyhow!("Invalid secp256k1 secret key: {}", e))?;
+    Ok(ecdsa_key_from_signing_key(&signing_key))
+}
+
+fn ecdsa_key_from_signing_key(signing_key: &k256::ecdsa::SigningKey) -> EcdsaKey {
+    let encoded = signing_key.verifying_key().to_encoded_point(false);
+    // Skip the 0x04 uncompressed-point prefix
+    let address = Address::from_raw_public_key(&encoded.as_bytes()[1..]);
+    EcdsaKey {
+        secret_key: signing_key.to_bytes().into(),
+        address,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NoiseKeypair {
+    pub secret_key: [u8; 32],
+    pub public_key: [u8; 32],
+}
+
+/// Generate an x25519 noise keypair for validator network addresses
+pub fn generate_noise_keypair<R: RngCore + CryptoRng>(rng: &mut R) -> NoiseKeypair {
+    let secret = x25519_dalek::StaticSecret::random_from_rng(rng);
+    let public = x25519_dalek::PublicKey::from(&secret);
+    NoiseKeypair {
+        secret_key: secret.to_bytes(),
+        public_key: public.to_bytes(),
+    }
+}
+
+/// Format a validator network address in the `/ip4/.../noise-ik/.../handshake/0` form
+pub fn format_network_address(ip: &str, port: u16, noise_public_key: &[u8; 32]) -> String {
+    format!(
+        "/ip4/{}/tcp/{}/noise-ik/{}/handshake/0",
+        ip,
+        port,
+        hex::encode(noise_public_key)
+    )
+}
+
+/// Write the BLS secret key as an encrypted (scrypt + AES-128-CTR) keystore file,
+/// refusing to overwrite an existing one unless `force` is set
+pub fn write_bls_keystore<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    keystore_dir: &Path,
+    name: &str,
+    keypair: &BlsKeypair,
+    password: &str,
+    force: bool,
+) -> anyhow::Result<PathBuf> {
+    let path = keystore_dir.join(name);
+    if path.exists() && !force {
+        anyhow::bail!("{} already exists (use --force to overwrite)", path.display());
+    }
+    fs::create_dir_all(keystore_dir)?;
+    eth_keystore::encrypt_key(keystore_dir, rng, keypair.secret_key, password, Some(name))
+        .map_err(|e| anyhow::anyhow!("Failed to write keystore: {}", e))?;
+    Ok(path)
+}
+
+/// Decrypt a BLS keystore written by `write_bls_keystore`
+pub fn read_bls_keystore(path: &Path, password: &str) -> anyhow::Result<BlsKeypair> {
+    let secret = eth_keystore::decrypt_key(path, password)
+        .map_err(|e| anyhow::anyhow!("Failed to decrypt keystore {}: {}", path.display(), e))?;
+    bls_keypair_from_secret(&secret)
+}
+
+/// Rebuild a BLS keypair (and PoP) from a 32-byte secret key
+pub fn bls_keypair_from_secret(secret: &[u8]) -> anyhow::Result<BlsKeypair> {
+    let sk = blst::min_pk::SecretKey::from_bytes(secret)
+        .map_err(|e| anyhow::anyhow!("Invalid BLS secret key: {:?}", e))?;
+    let public_key = sk.sk_to_pk().compress();
+    // PoP is a signature over the compressed public key under the PoP DST
+    let pop = sk.sign(&public_key, BLS_POP_DST, &[]);
+    Ok(BlsKeypair {
+        secret_key: sk.to_bytes(),
+        public_key,
+        proof_of_possession: pop.compress(),
+    })
+}
@@ -3,4 +3,55 @@ pub mod utils;
 pub mod genesis;
 pub mod post_genesis;
 pub mod verify;
+pub mod csv_import;
+pub mod config_init;
+pub mod keys;
+pub mod devnet;
+pub mod multiaddr;
+pub mod validate;
+pub mod rpc;
+pub mod fork;
+pub mod simulate;
+pub mod upgrade;
+pub mod onchain_config;
+pub mod oidc;
+pub mod registry;
+pub mod bytecode;
+pub mod logging;
+pub mod abi;
+pub mod trace;
+pub mod profile;
+pub mod sourcemap;
+pub mod provenance;
+pub mod db;
+pub mod spec;
+pub mod precompiles;
+pub mod doctor;
+pub mod selectors;
+pub mod abi_check;
+pub mod dynamic_abi;
+pub mod exit_code;
+pub mod validator_edit;
+pub mod stake_pools;
+pub mod analyze;
+pub mod vesting;
+pub mod multisig;
+pub mod supply;
+pub mod record;
+pub mod fixtures;
+pub mod watch;
+pub mod serve;
+pub mod deploy;
+pub mod state_import;
+pub mod broadcast;
+pub mod explorer;
+pub mod export;
+pub mod bench;
+pub mod synthetic;
+pub mod footprint;
+pub mod state_diff;
+pub mod interfaces;
+pub mod golden;
+pub mod migrate;
+pub mod code_cache;
 // jwks module removed - JWK initialization is now handled in Genesis.initialize
\ No newline at end of file
@@ -0,0 +1,225 @@
+//! Logging setup
+//!
+//! Logs go to the console or to a file, as text or JSON. File output is written
+//! through a non-blocking, non-lossy worker whose guard lives in a global slot:
+//! dropping it drains every queued line to disk, so shutdown and the panic hook
+//! both flush deterministically via [`flush`]. Log files can be rotated hourly,
+//! daily or by size for long-running devnet sessions.
+
+use anyhow::{Context, Result};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use tracing::Level;
+use tracing_appender::{
+    non_blocking::{NonBlockingBuilder, WorkerGuard},
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::fmt::{format::FmtSpan, MakeWriter};
+
+/// Worker guard of the file writer; taken and dropped by [`flush`]
+static FILE_GUARD: Mutex<Option<WorkerGuard>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    Text,
+    /// One JSON object per event, plus span close events carrying timings
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogRotation {
+    Never,
+    Hourly,
+    Daily,
+    /// Roll over once the file reaches `max_bytes`
+    Size,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogOptions {
+    pub level: Level,
+    pub format: LogFormat,
+    /// Log to this file instead of stdout
+    pub file: Option<String>,
+    pub rotation: LogRotation,
+    /// Size threshold for [`LogRotation::Size`]
+    pub max_bytes: u64,
+    /// Rotated files kept besides the active one
+    pub max_files: usize,
+}
+
+/// Flushes the file writer when dropped
+pub struct LogGuard {
+    _private: (),
+}
+
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        flush();
+    }
+}
+
+/// Drain all queued log lines to the file; later file log lines are dropped
+pub fn flush() {
+    let guard = FILE_GUARD
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take();
+    // WorkerGuard::drop blocks until the worker has written everything queued
+    drop(guard);
+}
+
+/// File writer that renames `<path>` to `<path>.1` (shifting older files up)
+/// once it would grow past `max_bytes`
+struct SizeRollingWriter {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    written: u64,
+}
+
+impl SizeRollingWriter {
+    fn open(path: &Path, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            max_bytes,
+            max_files,
+            file,
+            written,
+        })
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        PathBuf::from(format!("{}.{}", self.path.display(), index))
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.max_files > 0 {
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for SizeRollingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Install the global subscriber writing to `writer` in the chosen format
+fn init_subscriber<W>(level: Level, format: LogFormat, writer: W, ansi: bool)
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(writer);
+    match format {
+        LogFormat::Text => builder.with_ansi(ansi).init(),
+        LogFormat::Json => builder
+            .json()
+            .with_span_events(FmtSpan::CLOSE)
+            .with_current_span(true)
+            .with_span_list(true)
+            .init(),
+    }
+}
+
+fn file_writer(path: &str, options: &LogOptions) -> Result<Box<dyn Write + Send>> {
+    let path = Path::new(path);
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(dir).context(format!("Failed to create log directory {}", dir.display()))?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Log file path {} has no file name", path.display()))?
+        .to_string_lossy()
+        .into_owned();
+
+    let rolling = |rotation: Rotation| {
+        RollingFileAppender::builder()
+            .rotation(rotation)
+            .filename_prefix(file_name.clone())
+            .max_log_files(options.max_files.max(1))
+            .build(dir)
+            .context(format!("Failed to open log file {}", path.display()))
+    };
+    Ok(match options.rotation {
+        LogRotation::Never => Box::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .context(format!("Failed to open log file {}", path.display()))?,
+        ),
+        LogRotation::Hourly => Box::new(rolling(Rotation::HOURLY)?),
+        LogRotation::Daily => Box::new(rolling(Rotation::DAILY)?),
+        LogRotation::Size => Box::new(
+            SizeRollingWriter::open(path, options.max_bytes, options.max_files)
+                .context(format!("Failed to open log file {}", path.display()))?,
+        ),
+    })
+}
+
+/// Set up the global subscriber and, for file logging, a panic hook that
+/// records the panic and flushes before the default hook runs
+pub fn init(options: &LogOptions) -> Result<LogGuard> {
+    let Some(path) = &options.file else {
+        init_subscriber(options.level, options.format, io::stdout, true);
+        return Ok(LogGuard { _private: () });
+    };
+
+    let (non_blocking, guard) = NonBlockingBuilder::default()
+        .lossy(false)
+        .finish(file_writer(path, options)?);
+    *FILE_GUARD
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(guard);
+    init_subscriber(options.level, options.format, non_blocking, false);
+
+    let original_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        tracing::error!("PANIC: {}", panic_info);
+        flush();
+        original_hook(panic_info);
+    }));
+
+    tracing::info!(
+        "Logging to file: {} (rotation {:?})",
+        path,
+        options.rotation
+    );
+    Ok(LogGuard { _private: () })
+}
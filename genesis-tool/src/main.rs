@@ -1,40 +1,38 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use genesis_tool::{execute, genesis::GenesisConfig, post_genesis, verify};
-use serde_json;
-use std::fs;
-use tracing::{Level, info, warn};
-
-// Custom guard to ensure proper log flushing
-struct LogGuard {
-    _guard: Option<tracing_appender::non_blocking::WorkerGuard>,
-    has_file_logging: bool,
-}
-
-impl LogGuard {
-    fn new(guard: Option<tracing_appender::non_blocking::WorkerGuard>) -> Self {
-        let has_file_logging = guard.is_some();
-        Self {
-            _guard: guard,
-            has_file_logging,
-        }
-    }
-
-    fn flush_and_wait(&self) {
-        if self.has_file_logging {
-            tracing::info!("Ensuring all logs are written to file...");
-            std::thread::sleep(std::time::Duration::from_millis(1000));
-        }
-    }
-}
-
-impl Drop for LogGuard {
-    fn drop(&mut self) {
-        if self.has_file_logging {
-            std::thread::sleep(std::time::Duration::from_millis(500));
-        }
-    }
-}
+use alloy_primitives::{Address, U256};
+use genesis_tool::{
+    abi, abi_check, analyze, bench, broadcast, code_cache, config_init, csv_import,
+    db::{DbBackend, DbOptions},
+    deploy, devnet, doctor, dynamic_abi, execute, explorer,
+    export::{self, ExportFormat},
+    exit_code::{self, Phase},
+    fork,
+    genesis::{self, GenesisConfig},
+    golden, interfaces, keys,
+    migrate::{self, ConfigLayout},
+    logging::{self, LogFormat, LogRotation},
+    multisig,
+    oidc, post_genesis,
+    precompiles::PrecompileMode,
+    provenance, record, registry::ContractsManifest, selectors, serve, simulate, stake_pools, synthetic,
+    spec::EvmSpec,
+    state_diff, state_import,
+    upgrade,
+    utils::{expand_glob, is_reserved_system_address},
+    validate,
+    validator_edit::{self, ValidatorEdit},
+    verify::{self, VerifyOptions},
+    vesting, watch,
+};
+use revm_primitives::hex;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::{Level, error, info, warn};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Gravity Genesis Tool", long_about = None)]
@@ -47,6 +45,24 @@ struct Args {
     #[arg(short, long, global = true)]
     log_file: Option<String>,
 
+    /// Log output format; `json` emits one object per event plus span close
+    /// events carrying timings
+    #[arg(long, value_enum, default_value_t = LogFormat::Text, global = true)]
+    log_format: LogFormat,
+
+    /// Log file rotation; hourly and daily files get a date suffix, size rotation
+    /// keeps <file>.1, <file>.2, ...
+    #[arg(long, value_enum, default_value_t = LogRotation::Never, global = true, requires = "log_file")]
+    log_rotation: LogRotation,
+
+    /// File size that triggers `--log-rotation size`
+    #[arg(long, default_value_t = 100 * 1024 * 1024, global = true)]
+    log_max_bytes: u64,
+
+    /// Rotated log files to keep
+    #[arg(long, default_value_t = 7, global = true)]
+    log_max_files: usize,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -66,12 +82,822 @@ enum Commands {
         /// Output directory
         #[arg(short, long)]
         output: String,
+
+        /// CSV file with initial validators, merged into the config's validators
+        #[arg(long)]
+        validators_csv: Option<String>,
+
+        /// Replace the config's validators with the CSV contents instead of merging
+        #[arg(long, requires = "validators_csv")]
+        replace_validators: bool,
+
+        /// Replace the config's validators with this many generated ones and report
+        /// where genesis outgrows gas and storage bounds (scale testing only)
+        #[arg(long, conflicts_with = "validators_csv")]
+        synthetic_validators: Option<usize>,
+
+        /// Stakes of the synthetic validators: uniform:<amount>, range:<min>..<max>
+        /// or zipf:<top>:<exponent> (defaults to the minimum bond for all)
+        #[arg(long, requires = "synthetic_validators", value_parser = synthetic::StakeDistribution::parse)]
+        stake_distribution: Option<synthetic::StakeDistribution>,
+
+        /// Seed of the synthetic validator keys and stakes
+        #[arg(long, default_value_t = 0, requires = "synthetic_validators")]
+        synthetic_seed: u64,
+
+        /// Post-genesis flow to simulate against the generated state (repeatable)
+        #[arg(long = "simulate", value_enum)]
+        simulations: Vec<simulate::SimulationFlow>,
+
+        /// How the chain precompiles (native mint, BLS PoP verify) behave during --simulate flows
+        #[arg(long, value_enum, default_value_t = PrecompileMode::Real)]
+        precompiles: PrecompileMode,
+
+        /// JSON-RPC endpoint of a live chain; its system contract state is forked into the genesis
+        #[arg(long)]
+        fork_rpc: Option<String>,
+
+        /// Block number to fork from (defaults to latest)
+        #[arg(long, requires = "fork_rpc")]
+        fork_block: Option<u64>,
+
+        /// Additional account to copy from the forked chain (repeatable)
+        #[arg(long = "fork-account", requires = "fork_rpc")]
+        fork_accounts: Vec<Address>,
+
+        /// anvil or hardhat state dump whose accounts (code, storage, balances)
+        /// are added to the alloc, over forked state and under extraAlloc
+        #[arg(long)]
+        import_state: Option<String>,
+
+        /// forge script broadcast (e.g. broadcast/Deploy.s.sol/1/run-latest.json)
+        /// to replay after system initialization (overrides the config's replayBroadcast)
+        #[arg(long)]
+        replay_broadcast: Option<String>,
+
+        /// Fetch each jwkConfig issuer's keys over HTTPS: fill empty key lists, verify configured ones
+        #[arg(long)]
+        fetch_jwks: bool,
+
+        /// Reject config addresses without an EIP-55 checksum instead of warning
+        #[arg(long)]
+        strict_checksums: bool,
+
+        /// JSON-RPC endpoint of the bridge's source chain; checks bridgeConfig.trustedBridge
+        /// has code there and trustedSourceId matches its chain ID
+        #[arg(long)]
+        bridge_rpc: Option<String>,
+
+        /// Accept the default chainId (1337) when the config omits it for validators that are not all local
+        #[arg(long)]
+        allow_default_chain_id: bool,
+
+        /// Fail config validation on warnings as well as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// JSON array of validation findings to accept, as { "rule", "contains"?, "reason"? }
+        #[arg(long)]
+        exemptions: Option<String>,
+
+        /// Severity of owners/operators shared between validators or owner == operator
+        /// (overrides the config's roleSeparation; default warning, info on devnets)
+        #[arg(long, value_enum)]
+        role_separation: Option<validate::Severity>,
+
+        /// Set a config field as <dotted.path>=<json value>, e.g.
+        /// governanceConfig.votingDurationMicros=600000000 or validators.0.stakeAmount=... (repeatable)
+        #[arg(long = "set", value_name = "PATH=VALUE")]
+        config_overrides: Vec<String>,
+
+        /// System contract registry (defaults to <byte_code_dir>/contracts_manifest.json,
+        /// then the built-in list)
+        #[arg(long)]
+        contracts_manifest: Option<String>,
+
+        /// Replace one contract's artifact as <name>=<path to .hex> (repeatable)
+        #[arg(long = "override")]
+        overrides: Vec<String>,
+
+        /// Print the call trace of genesis transactions even when they succeed
+        #[arg(long)]
+        trace: bool,
+
+        /// Hardfork to execute genesis under (overrides the config's evmSpec;
+        /// defaults to revm's latest)
+        #[arg(long, value_enum)]
+        evm_spec: Option<EvmSpec>,
+
+        /// Compare the tool's ABI bindings with the forge artifacts first and
+        /// abort on drift
+        #[arg(long)]
+        abi_check: bool,
+
+        /// Encode Genesis.initialize from the Genesis artifact ABI instead of the
+        /// built-in bindings; new fields are read from the config's extraInitParams
+        #[arg(long)]
+        dynamic_abi: bool,
+
+        /// Reuse <output>/genesis_state.json instead of executing genesis when the
+        /// bytecode, transactions and spec are unchanged since it was written
+        #[arg(long)]
+        resume: bool,
+
+        /// Stop after executing genesis and writing <output>/genesis_state.json;
+        /// finish with `assemble`
+        #[arg(long)]
+        build_state_only: bool,
+
+        /// Keep zero-valued storage slots and empty accounts in the genesis alloc
+        #[arg(long)]
+        no_prune: bool,
+
+        /// Link and patch every artifact instead of reusing runtime code cached
+        /// under ~/.cache/genesis-tool
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Write a standard JSON input per system contract to <output>/verification
+        /// for block explorer source verification
+        #[arg(long)]
+        verification_bundle: bool,
+
+        /// Regenerate whenever the bytecode directory or config file changes,
+        /// reusing the executed state while the transactions are unchanged
+        #[arg(long)]
+        watch: bool,
+
+        /// Previous output directory to compare the regenerated outputs with;
+        /// fails with a diff when any of its files changed
+        #[arg(long, conflicts_with_all = ["build_state_only", "watch"])]
+        check_against: Option<String>,
+    },
+    /// Write the genesis outputs from a state saved by `generate --build-state-only`
+    Assemble {
+        /// Genesis configuration file the state was built from
+        #[arg(short, long, default_value = "generate/new_genesis_config.json")]
+        config_file: String,
+
+        /// Output directory
+        #[arg(short, long)]
+        output: String,
+
+        /// Saved build state (defaults to <output>/genesis_state.json)
+        #[arg(long)]
+        state: Option<String>,
+
+        /// Config overrides the state was built with, as passed to `generate --set`
+        #[arg(long = "set", value_name = "PATH=VALUE")]
+        config_overrides: Vec<String>,
+
+        /// Keep zero-valued storage slots and empty accounts in the genesis alloc
+        #[arg(long)]
+        no_prune: bool,
     },
     /// Verify an existing genesis.json file for ABI compatibility
     Verify {
-        /// Path to the genesis.json file to verify
+        /// Path to the genesis.json file to verify; repeatable and may contain
+        /// `*`/`?` wildcards (quote them), in which case files are verified concurrently
+        #[arg(short, long = "genesis-file", required = true)]
+        genesis_files: Vec<String>,
+
+        /// Genesis config the genesis was generated from; enables per-field comparison
+        #[arg(long)]
+        expected_config: Option<String>,
+
+        /// Bytecode directory the genesis was generated from; enables code comparison
+        #[arg(short, long)]
+        byte_code_dir: Option<String>,
+
+        /// Accept system contract code differing from the artifacts only in solc metadata
+        #[arg(long, requires = "byte_code_dir")]
+        ignore_metadata: bool,
+
+        /// Where genesis state is held while verifying; `redb` keeps it on disk for
+        /// allocs too large for memory (needs the `redb` build feature)
+        #[arg(long, value_enum, default_value_t = DbBackend::Memory)]
+        db_backend: DbBackend,
+
+        /// Directory for on-disk databases (defaults to the system temp directory)
+        #[arg(long)]
+        db_dir: Option<PathBuf>,
+
+        /// Hardfork to execute under; defaults to the newest fork the genesis
+        /// file's config section activates at genesis
+        #[arg(long, value_enum)]
+        evm_spec: Option<EvmSpec>,
+
+        /// JSON list of views a consumer requires (target, signature, args,
+        /// returns); each must answer with data decoding as `returns`
+        #[arg(long)]
+        interfaces: Option<String>,
+
+        /// Chain id to execute under; defaults to the genesis file's
+        /// `config.chainId`
+        #[arg(long)]
+        chain_id: Option<u64>,
+    },
+    /// Check generated files against the checksums in their manifest.json
+    VerifyManifest {
+        /// Path to the manifest.json written by generate
+        #[arg(short, long)]
+        manifest: String,
+
+        /// Config file to compare with the recorded config hash
+        #[arg(short, long)]
+        config_file: Option<String>,
+
+        /// Bytecode directory to compare with the recorded git commit
+        #[arg(short, long)]
+        byte_code_dir: Option<String>,
+    },
+    /// List the function selectors each system contract dispatches on
+    Selectors {
+        /// Genesis file to read deployed code from
+        #[arg(short, long, required_unless_present = "byte_code_dir")]
+        genesis_file: Option<String>,
+
+        /// Bytecode directory; supplies code when no genesis file is given, and
+        /// ABIs from its forge artifacts for naming selectors
+        #[arg(short, long)]
+        byte_code_dir: Option<String>,
+
+        /// System contract registry (defaults to <byte_code_dir>/contracts_manifest.json,
+        /// then the built-in list)
+        #[arg(long)]
+        contracts_manifest: Option<String>,
+
+        /// Signatures to resolve selectors with: a JSON object of selector to
+        /// signature(s), or one signature per line
+        #[arg(long)]
+        signatures: Option<String>,
+
+        /// Output JSON file (prints a listing to stdout when omitted)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Record the ABI-encoded outputs of the on-chain config views of a genesis
+    RecordFixtures {
+        /// Path to the genesis.json file to record from
+        #[arg(short, long)]
+        genesis_file: String,
+
+        /// Fixture directory
+        #[arg(short, long)]
+        output: String,
+
+        /// Chain ID used for the simulated EVM
+        #[arg(long, default_value_t = 1337)]
+        chain_id: u64,
+
+        /// Compare with the fixtures already in the directory instead of
+        /// writing them; fails when an output changed
+        #[arg(long)]
+        check: bool,
+    },
+    /// Translate a genesis.json into the genesis schema of Besu or Erigon
+    Export {
+        /// Path to the genesis.json file to translate
+        #[arg(short, long)]
+        genesis_file: String,
+
+        /// Client whose genesis schema to write
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+
+        /// Output genesis file
+        #[arg(short, long)]
+        output: String,
+
+        /// Write the compatibility report as JSON to this file
+        #[arg(long)]
+        report: Option<String>,
+    },
+    /// Diff two generated bundle_state.json files per account and storage slot,
+    /// naming slots from the artifacts' storage layouts
+    DiffState {
+        /// bundle_state.json of the earlier generation
+        old: String,
+
+        /// bundle_state.json of the later generation
+        new: String,
+
+        /// Byte code directory (forge output) for contract names and storage layouts
+        #[arg(short, long)]
+        byte_code_dir: Option<String>,
+
+        /// Write the diff as JSON to this file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Time the deploy, execute, emit and verify phases of generation over
+    /// several runs, optionally failing on regressions against a baseline
+    Bench {
+        /// Byte code directory (containing .hex files for each contract)
+        #[arg(short, long)]
+        byte_code_dir: String,
+
+        /// Genesis configuration file to generate from
+        #[arg(short, long, alias = "config")]
+        config_file: String,
+
+        /// Runs to time
+        #[arg(short = 'n', long, default_value_t = 5)]
+        iterations: usize,
+
+        /// Write the results as JSON to this file, for use as a later baseline
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Earlier results to compare with; a phase whose median regressed fails the run
+        #[arg(long)]
+        baseline: Option<String>,
+
+        /// Allowed growth of a phase median over the baseline, in percent
+        #[arg(long, default_value_t = 20.0)]
+        max_regression: f64,
+
+        /// Directory the generated outputs are written to (defaults to a temporary directory)
+        #[arg(long)]
+        work_dir: Option<PathBuf>,
+    },
+    /// Deploy the system contracts and run genesis on a local anvil or hardhat node
+    Deploy {
+        /// Byte code directory
+        #[arg(short, long)]
+        byte_code_dir: String,
+
+        /// Genesis configuration file
+        #[arg(short, long, default_value = "generate/new_genesis_config.json")]
+        config_file: String,
+
+        /// JSON-RPC endpoint of the node
+        #[arg(long, default_value = "http://localhost:8545")]
+        rpc_url: String,
+
+        /// Hex private key; signs genesis transactions whose caller is its
+        /// account, the others are sent by impersonating their caller
+        #[arg(long)]
+        private_key: Option<String>,
+
+        /// Set a config field as <dotted.path>=<json value> (repeatable)
+        #[arg(long = "set", value_name = "PATH=VALUE")]
+        config_overrides: Vec<String>,
+
+        /// System contract registry (defaults to <byte_code_dir>/contracts_manifest.json,
+        /// then the built-in list)
+        #[arg(long)]
+        contracts_manifest: Option<String>,
+    },
+    /// Serve a devnet or generate output directory over HTTP(S) for node provisioning
+    Serve {
+        /// Directory to serve (devnet or generate output)
+        #[arg(short, long)]
+        output: String,
+
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// Environment variable holding the bearer token; a random token is
+        /// generated and logged when it is unset
+        #[arg(long, default_value = "GENESIS_TOOL_SERVE_TOKEN")]
+        token_env: String,
+
+        /// PEM certificate chain; serves HTTPS together with --tls-key
+        #[arg(long, requires = "tls_key")]
+        tls_cert: Option<String>,
+
+        /// PEM private key for --tls-cert
+        #[arg(long, requires = "tls_cert")]
+        tls_key: Option<String>,
+    },
+    /// Compare the tool's sol! bindings with the forge artifact ABIs
+    AbiCheck {
+        /// Byte code directory (forge `out/` with <Name>.sol/<Name>.json artifacts)
+        #[arg(short, long)]
+        byte_code_dir: String,
+    },
+    /// Diagnose a generate setup: config, bytecode files, output directory and RPC
+    Doctor {
+        /// Byte code directory (containing .hex files for each contract)
+        #[arg(short, long)]
+        byte_code_dir: String,
+
+        /// Genesis configuration file
+        #[arg(short, long, default_value = "generate/new_genesis_config.json")]
+        config_file: String,
+
+        /// Output directory to check for write access and free space
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// JSON-RPC endpoint to ping, as passed to --fork-rpc
+        #[arg(long)]
+        rpc: Option<String>,
+
+        /// System contract registry (defaults to <byte_code_dir>/contracts_manifest.json,
+        /// then the built-in list)
+        #[arg(long)]
+        contracts_manifest: Option<String>,
+    },
+    /// Bootstrap a local devnet: validator keys, genesis config and genesis outputs
+    Devnet {
+        /// Number of validators
+        #[arg(long, default_value_t = 4)]
+        validators: usize,
+
+        /// Output directory for keys, config and genesis files
+        #[arg(short, long)]
+        output: String,
+
+        /// Byte code directory; when set, genesis is generated into <output>/genesis
+        #[arg(short, long)]
+        byte_code_dir: Option<String>,
+
+        /// Chain ID of the devnet
+        #[arg(long, default_value_t = 1337)]
+        chain_id: u64,
+
+        /// Host IP used in validator network addresses
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// First port; each validator gets a block of 10 ports
+        #[arg(long, default_value_t = 6180)]
+        base_port: u16,
+
+        /// Stake amount (wei) per validator
+        #[arg(long, default_value = "100000000000000000000")]
+        stake_amount: String,
+
+        /// Seed for deterministic key generation (testing only)
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Key generation helpers for validators
+    Keygen {
+        #[command(subcommand)]
+        command: KeygenCommands,
+    },
+    /// Genesis config file helpers
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Upgrade a genesis config in an older layout to the current schema,
+    /// listing every change made
+    MigrateConfig {
+        /// Layout the config is written in
+        #[arg(long, value_enum)]
+        from: ConfigLayout,
+
+        /// Config file to migrate
+        input: String,
+
+        /// Output path of the migrated config (default: print it)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Print the StakePool address genesis will create for each validator
+    PoolAddresses {
+        /// Byte code directory (forge `out/` with StakePool.sol/StakePool.json)
+        #[arg(short, long)]
+        byte_code_dir: String,
+
+        /// Genesis configuration file
+        #[arg(short, long, default_value = "generate/new_genesis_config.json")]
+        config_file: String,
+
+        /// Config overrides, as passed to `generate --set`
+        #[arg(long = "set", value_name = "PATH=VALUE")]
+        config_overrides: Vec<String>,
+
+        /// Print the addresses as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report voting power concentration of the initial validators
+    Analyze {
+        /// Genesis configuration file
+        #[arg(short, long, default_value = "generate/new_genesis_config.json")]
+        config_file: String,
+
+        /// Config overrides, as passed to `generate --set`
+        #[arg(long = "set", value_name = "PATH=VALUE")]
+        config_overrides: Vec<String>,
+    },
+    /// Edit the validators of a genesis config, validating the result
+    Validators {
+        #[command(subcommand)]
+        command: ValidatorsCommands,
+    },
+    /// Simulate a governance proposal from creation to execution against a genesis
+    SimulateGovernance {
+        /// Path to the genesis.json file (generated or forked)
         #[arg(short, long)]
         genesis_file: String,
+
+        /// Chain ID used for the simulated EVM
+        #[arg(long, default_value_t = 1337)]
+        chain_id: u64,
+
+        /// Proposal action as <target>:<calldata hex> (repeatable); defaults to a
+        /// no-op GovernanceConfig update
+        #[arg(long = "action")]
+        actions: Vec<String>,
+
+        /// How the chain precompiles (native mint, BLS PoP verify) behave in the simulated EVM
+        #[arg(long, value_enum, default_value_t = PrecompileMode::Real)]
+        precompiles: PrecompileMode,
+
+        /// Advance the on-chain clock by this much (e.g. `6h`, `2d`) before the
+        /// simulation, applying every epoch transition on the way
+        #[arg(long, value_parser = simulate::parse_duration)]
+        advance_time: Option<Duration>,
+
+        /// Run this many epoch transitions before the simulation (after `--advance-time`)
+        #[arg(long, default_value_t = 0)]
+        advance_epochs: u64,
+    },
+    /// Build the governance proposal payload for shipping a contract upgrade
+    UpgradePayload {
+        /// Bytecode directory of the currently deployed contracts
+        #[arg(long)]
+        old_dir: String,
+
+        /// Bytecode directory of the new contracts
+        #[arg(long)]
+        new_dir: String,
+
+        /// Output JSON file (prints to stdout when omitted)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Schedule VersionConfig.majorVersion for the next epoch
+        #[arg(long)]
+        major_version: Option<u64>,
+
+        /// Append Reconfiguration.governanceReconfigure() to apply the change immediately
+        #[arg(long)]
+        reconfigure: bool,
+
+        /// Extra proposal action as <target>:<calldata hex> (repeatable)
+        #[arg(long = "action")]
+        actions: Vec<String>,
+
+        /// Stake pool proposing; enables the createProposal calldata in the output
+        #[arg(long)]
+        stake_pool: Option<Address>,
+
+        /// Proposal metadata URI
+        #[arg(long, default_value = "")]
+        metadata_uri: String,
+
+        /// Treat contracts whose code differs only in solc metadata as unchanged
+        #[arg(long)]
+        ignore_metadata: bool,
+    },
+    /// Simulate add-stake, unstake and withdrawal on a stake pool against a genesis
+    SimulateStaking {
+        /// Path to the genesis.json file (generated or forked)
+        #[arg(short, long)]
+        genesis_file: String,
+
+        /// Chain ID used for the simulated EVM
+        #[arg(long, default_value_t = 1337)]
+        chain_id: u64,
+
+        /// Stake pool to use (defaults to the first pool)
+        #[arg(long)]
+        pool: Option<Address>,
+
+        /// Amount (wei) to stake and withdraw (defaults to the minimum stake)
+        #[arg(long)]
+        amount: Option<U256>,
+
+        /// How the chain precompiles (native mint, BLS PoP verify) behave in the simulated EVM
+        #[arg(long, value_enum, default_value_t = PrecompileMode::Real)]
+        precompiles: PrecompileMode,
+
+        /// Advance the on-chain clock by this much (e.g. `6h`, `2d`) before the
+        /// simulation, applying every epoch transition on the way
+        #[arg(long, value_parser = simulate::parse_duration)]
+        advance_time: Option<Duration>,
+
+        /// Run this many epoch transitions before the simulation (after `--advance-time`)
+        #[arg(long, default_value_t = 0)]
+        advance_epochs: u64,
+    },
+    /// Make one call against a genesis from an impersonated sender
+    Call {
+        /// Path to the genesis.json file (generated or forked)
+        #[arg(short, long)]
+        genesis_file: String,
+
+        /// Contract address, or the name of a built-in system contract
+        #[arg(long)]
+        to: String,
+
+        /// Function signature with parameter types only, e.g. `getValidator(address)`
+        #[arg(long)]
+        sig: String,
+
+        /// Argument, as JSON or a plain string (repeatable, in order)
+        #[arg(long = "arg")]
+        args: Vec<String>,
+
+        /// Sender to impersonate (defaults to the system caller)
+        #[arg(long)]
+        from: Option<Address>,
+
+        /// Amount sent, in any format accepted in the config (e.g. `1 ether`)
+        #[arg(long)]
+        value: Option<String>,
+
+        /// Revert text the call is expected to fail with, e.g. `OnlySystemCaller`
+        #[arg(long)]
+        expect_revert: Option<String>,
+
+        /// Chain ID used for the simulated EVM
+        #[arg(long, default_value_t = 1337)]
+        chain_id: u64,
+
+        /// How the chain precompiles (native mint, BLS PoP verify) behave in the simulated EVM
+        #[arg(long, value_enum, default_value_t = PrecompileMode::Real)]
+        precompiles: PrecompileMode,
+
+        /// Advance the on-chain clock by this much (e.g. `6h`, `2d`) before the
+        /// simulation, applying every epoch transition on the way
+        #[arg(long, value_parser = simulate::parse_duration)]
+        advance_time: Option<Duration>,
+
+        /// Run this many epoch transitions before the simulation (after `--advance-time`)
+        #[arg(long, default_value_t = 0)]
+        advance_epochs: u64,
+    },
+    /// Run a script of calls against a genesis, stopping at the first unexpected outcome
+    Exec {
+        /// Path to the genesis.json file (generated or forked)
+        #[arg(short, long)]
+        genesis_file: String,
+
+        /// JSON array of steps: calls with optional `coinbase`, `asCoinbase`
+        /// and `expectRevert`
+        #[arg(short, long)]
+        script: String,
+
+        /// Sender of steps without their own `caller` (defaults to the system caller)
+        #[arg(long)]
+        from: Option<Address>,
+
+        /// Chain ID used for the simulated EVM
+        #[arg(long, default_value_t = 1337)]
+        chain_id: u64,
+
+        /// How the chain precompiles (native mint, BLS PoP verify) behave in the simulated EVM
+        #[arg(long, value_enum, default_value_t = PrecompileMode::Real)]
+        precompiles: PrecompileMode,
+
+        /// Advance the on-chain clock by this much (e.g. `6h`, `2d`) before the
+        /// simulation, applying every epoch transition on the way
+        #[arg(long, value_parser = simulate::parse_duration)]
+        advance_time: Option<Duration>,
+
+        /// Run this many epoch transitions before the simulation (after `--advance-time`)
+        #[arg(long, default_value_t = 0)]
+        advance_epochs: u64,
+
+        /// Directory `snapshot` steps write `<name>.snapshot.json` to (defaults
+        /// to the script's directory)
+        #[arg(long)]
+        snapshot_dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum KeygenCommands {
+    /// Generate a BLS consensus keypair with proof-of-possession
+    Bls {
+        /// Write the secret key to an encrypted keystore in this directory
+        #[arg(long)]
+        keystore_dir: Option<String>,
+
+        /// Keystore file name
+        #[arg(long, default_value = "consensus.keystore")]
+        keystore_name: String,
+
+        /// Environment variable holding the keystore password
+        #[arg(long, default_value = "GENESIS_TOOL_KEYSTORE_PASSWORD")]
+        password_env: String,
+
+        /// Overwrite the keystore file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommands {
+    /// Write a commented template genesis config with placeholder validators
+    Init {
+        /// Output path of the template config
+        #[arg(short, long, default_value = "new_genesis_config.json")]
+        output: String,
+
+        /// Number of placeholder validators
+        #[arg(long, default_value_t = 4)]
+        validators: usize,
+
+        /// Chain ID written into the template
+        #[arg(long, default_value_t = 1337)]
+        chain_id: u64,
+
+        /// Overwrite the output file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ValidatorsCommands {
+    /// Append a validator
+    Add {
+        /// Genesis configuration file to edit
+        #[arg(short, long, alias = "config")]
+        config_file: String,
+
+        #[arg(long)]
+        moniker: String,
+
+        #[arg(long)]
+        operator: String,
+
+        #[arg(long)]
+        owner: String,
+
+        /// Defaults to the owner
+        #[arg(long)]
+        staker: Option<String>,
+
+        /// Wei, or an amount with a unit such as "100 ether"
+        #[arg(long)]
+        stake_amount: String,
+
+        /// Defaults to the stake amount
+        #[arg(long)]
+        voting_power: Option<String>,
+
+        #[arg(long)]
+        consensus_pubkey: String,
+
+        #[arg(long)]
+        consensus_pop: String,
+
+        #[arg(long)]
+        network_addresses: String,
+
+        /// Defaults to the network addresses
+        #[arg(long)]
+        fullnode_addresses: Option<String>,
+
+        /// Validate and print the result without writing the config
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove a validator
+    Remove {
+        /// Genesis configuration file to edit
+        #[arg(short, long, alias = "config")]
+        config_file: String,
+
+        /// Moniker or consensus pubkey of the validator
+        validator: String,
+
+        /// Validate and print the result without writing the config
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Change fields of a validator
+    Update {
+        /// Genesis configuration file to edit
+        #[arg(short, long, alias = "config")]
+        config_file: String,
+
+        /// Moniker or consensus pubkey of the validator
+        validator: String,
+
+        /// Field to change as <field>=<json value>, e.g. stakeAmount="200 ether" (repeatable)
+        #[arg(long = "set", value_name = "FIELD=VALUE", required = true)]
+        fields: Vec<String>,
+
+        /// Validate and print the result without writing the config
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
@@ -86,73 +912,498 @@ async fn main() -> Result<()> {
         Level::INFO
     };
 
-    // Set up logging and create log guard for proper cleanup
-    let log_guard = if let Some(log_file_path) = &args.log_file {
-        // Create log file directory if it doesn't exist
-        if let Some(parent) = std::path::Path::new(log_file_path).parent() {
-            if !parent.exists() {
-                fs::create_dir_all(parent)?;
-            }
-        }
-
-        // Set up logging to file
-        let file_appender = tracing_appender::rolling::never("", log_file_path);
-        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+    // Set up logging; the guard flushes file output when main returns
+    let log_guard = logging::init(&logging::LogOptions {
+        level,
+        format: args.log_format,
+        file: args.log_file.clone(),
+        rotation: args.log_rotation,
+        max_bytes: args.log_max_bytes,
+        max_files: args.log_max_files,
+    })?;
 
-        tracing_subscriber::fmt()
-            .with_max_level(level)
-            .with_writer(non_blocking)
-            .with_ansi(false)
-            .init();
-
-        info!("Logging to file: {}", log_file_path);
-        LogGuard::new(Some(guard))
-    } else {
-        // Console-only logging
-        tracing_subscriber::fmt().with_max_level(level).init();
-        LogGuard::new(None)
-    };
-
-    // Set up panic hook to ensure logs are flushed before panic
-    let has_file_logging = log_guard.has_file_logging;
-    let original_hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |panic_info| {
-        if has_file_logging {
-            eprintln!("PANIC occurred! Ensuring all logs are written...");
-            tracing::error!("PANIC: {}", panic_info);
-            tracing::error!("Flushing logs before panic exit...");
-            std::thread::sleep(std::time::Duration::from_millis(1200));
-            eprintln!("Log flush attempt completed");
-        }
-        original_hook(panic_info);
-    }));
+    exit_code::install_panic_hook();
 
     // Run the appropriate command
     let result = match &args.command {
-        Commands::Generate { byte_code_dir, config_file, output } => {
-            run_generate(byte_code_dir, config_file, output).await
+        Commands::Generate {
+            byte_code_dir,
+            config_file,
+            output,
+            validators_csv,
+            replace_validators,
+            synthetic_validators,
+            stake_distribution,
+            synthetic_seed,
+            simulations,
+            precompiles,
+            fork_rpc,
+            fork_block,
+            fork_accounts,
+            import_state,
+            replay_broadcast,
+            fetch_jwks,
+            strict_checksums,
+            bridge_rpc,
+            allow_default_chain_id,
+            strict,
+            exemptions,
+            role_separation,
+            config_overrides,
+            contracts_manifest,
+            overrides,
+            trace,
+            evm_spec,
+            abi_check,
+            dynamic_abi,
+            resume,
+            build_state_only,
+            no_prune,
+            no_cache,
+            verification_bundle,
+            watch,
+            check_against,
+        } => {
+            if *no_cache {
+                code_cache::disable();
+            }
+            let options = GenerateOptions {
+                validators_csv: validators_csv.clone(),
+                replace_validators: *replace_validators,
+                synthetic: synthetic_validators.map(|count| synthetic::SyntheticValidators {
+                    count,
+                    seed: *synthetic_seed,
+                    distribution: stake_distribution.clone(),
+                }),
+                simulations: simulations.clone(),
+                precompiles: *precompiles,
+                fork: fork_rpc.as_ref().map(|rpc_url| fork::ForkOptions {
+                    rpc_url: rpc_url.clone(),
+                    block: *fork_block,
+                    extra_accounts: fork_accounts.clone(),
+                }),
+                import_state: import_state.clone(),
+                replay_broadcast: replay_broadcast.clone(),
+                fetch_jwks: *fetch_jwks,
+                validation: validate::ValidationOptions {
+                    strict_checksums: *strict_checksums,
+                    bridge_rpc: bridge_rpc.clone(),
+                    allow_default_chain_id: *allow_default_chain_id,
+                    strict: *strict,
+                    exemptions: exemptions
+                        .as_deref()
+                        .map(validate::load_exemptions)
+                        .transpose()?
+                        .unwrap_or_default(),
+                    role_separation: *role_separation,
+                },
+                config_overrides: config_overrides.clone(),
+                contracts_manifest: contracts_manifest.clone(),
+                overrides: overrides.clone(),
+                trace: *trace,
+                evm_spec: *evm_spec,
+                abi_check: *abi_check,
+                dynamic_abi: *dynamic_abi,
+                resume: *resume,
+                build_state_only: *build_state_only,
+                no_prune: *no_prune,
+                verification_bundle: *verification_bundle,
+                check_against: check_against.clone(),
+            };
+            if *watch {
+                let sources = watch::WatchSources {
+                    config_overrides: config_overrides.clone(),
+                    files: [
+                        validators_csv,
+                        exemptions,
+                        contracts_manifest,
+                        import_state,
+                        replay_broadcast,
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .collect(),
+                };
+                watch::watch_generate(byte_code_dir, config_file, output, &sources)
+            } else {
+                run_generate(byte_code_dir, config_file, output, &options).await
+            }
         }
-        Commands::Verify { genesis_file } => {
-            run_verify(genesis_file)
+        Commands::Verify {
+            genesis_files,
+            expected_config,
+            byte_code_dir,
+            ignore_metadata,
+            db_backend,
+            db_dir,
+            evm_spec,
+            interfaces,
+            chain_id,
+        } => run_verify(
+            genesis_files,
+            expected_config.as_deref(),
+            byte_code_dir.as_deref(),
+            *ignore_metadata,
+            interfaces.as_deref(),
+            &VerifyOptions {
+                db: DbOptions {
+                    backend: *db_backend,
+                    dir: db_dir.clone(),
+                },
+                evm_spec: *evm_spec,
+                chain_id: *chain_id,
+                evm_env: None,
+            },
+        ),
+        Commands::VerifyManifest {
+            manifest,
+            config_file,
+            byte_code_dir,
+        } => run_verify_manifest(manifest, config_file.as_deref(), byte_code_dir.as_deref()),
+        Commands::Selectors {
+            genesis_file,
+            byte_code_dir,
+            contracts_manifest,
+            signatures,
+            output,
+        } => run_selectors(
+            genesis_file.as_deref(),
+            byte_code_dir.as_deref(),
+            contracts_manifest.as_deref(),
+            signatures.as_deref(),
+            output.as_deref(),
+        ),
+        Commands::RecordFixtures {
+            genesis_file,
+            output,
+            chain_id,
+            check,
+        } => run_record_fixtures(genesis_file, output, *chain_id, *check),
+        Commands::Export {
+            genesis_file,
+            format,
+            output,
+            report,
+        } => run_export(genesis_file, *format, output, report.as_deref()),
+        Commands::DiffState {
+            old,
+            new,
+            byte_code_dir,
+            output,
+        } => run_diff_state(old, new, byte_code_dir.as_deref(), output.as_deref()),
+        Commands::Bench {
+            byte_code_dir,
+            config_file,
+            iterations,
+            output,
+            baseline,
+            max_regression,
+            work_dir,
+        } => run_bench(
+            byte_code_dir,
+            config_file,
+            *iterations,
+            output.as_deref(),
+            baseline.as_deref(),
+            *max_regression,
+            work_dir.as_deref(),
+        ),
+        Commands::Deploy {
+            byte_code_dir,
+            config_file,
+            rpc_url,
+            private_key,
+            config_overrides,
+            contracts_manifest,
+        } => run_deploy(
+            byte_code_dir,
+            config_file,
+            config_overrides,
+            contracts_manifest.as_deref(),
+            deploy::DeployOptions {
+                rpc_url: rpc_url.clone(),
+                private_key: private_key.clone(),
+            },
+        ),
+        Commands::Serve {
+            output,
+            host,
+            port,
+            token_env,
+            tls_cert,
+            tls_key,
+        } => run_serve(output, host, *port, token_env, tls_cert.as_deref(), tls_key.as_deref()),
+        Commands::Assemble {
+            config_file,
+            output,
+            state,
+            config_overrides,
+            no_prune,
+        } => run_assemble(
+            config_file,
+            output,
+            state.as_deref(),
+            config_overrides,
+            *no_prune,
+        ),
+        Commands::AbiCheck { byte_code_dir } => run_abi_check(byte_code_dir),
+        Commands::Doctor {
+            byte_code_dir,
+            config_file,
+            output,
+            rpc,
+            contracts_manifest,
+        } => run_doctor(&doctor::DoctorOptions {
+            byte_code_dir: byte_code_dir.clone(),
+            config_file: config_file.clone(),
+            output: output.clone(),
+            rpc_url: rpc.clone(),
+            contracts_manifest: contracts_manifest.clone(),
+        }),
+        Commands::Devnet {
+            validators,
+            output,
+            byte_code_dir,
+            chain_id,
+            host,
+            base_port,
+            stake_amount,
+            seed,
+        } => {
+            let options = devnet::DevnetOptions {
+                validators: *validators,
+                chain_id: *chain_id,
+                host: host.clone(),
+                base_port: *base_port,
+                stake_amount: stake_amount.clone(),
+                seed: *seed,
+            };
+            run_devnet(&options, output, byte_code_dir.as_deref()).await
         }
+        Commands::Keygen {
+            command:
+                KeygenCommands::Bls {
+                    keystore_dir,
+                    keystore_name,
+                    password_env,
+                    force,
+                },
+        } => run_keygen_bls(keystore_dir.as_deref(), keystore_name, password_env, *force),
+        Commands::Config {
+            command:
+                ConfigCommands::Init {
+                    output,
+                    validators,
+                    chain_id,
+                    force,
+                },
+        } => config_init::write_config_template(output, *validators, *chain_id, *force),
+        Commands::MigrateConfig { from, input, output } => {
+            migrate::run_migrate_config(input, *from, output.as_deref())
+        }
+        Commands::PoolAddresses {
+            byte_code_dir,
+            config_file,
+            config_overrides,
+            json,
+        } => run_pool_addresses(byte_code_dir, config_file, config_overrides, *json),
+        Commands::Analyze {
+            config_file,
+            config_overrides,
+        } => run_analyze(config_file, config_overrides),
+        Commands::Validators { command } => run_validators(command),
+        Commands::SimulateGovernance {
+            genesis_file,
+            chain_id,
+            actions,
+            precompiles,
+            advance_time,
+            advance_epochs,
+        } => run_simulate_governance(
+            genesis_file,
+            *chain_id,
+            actions,
+            *precompiles,
+            &simulate::TimeTravel {
+                advance_time: *advance_time,
+                advance_epochs: *advance_epochs,
+            },
+        ),
+        Commands::UpgradePayload {
+            old_dir,
+            new_dir,
+            output,
+            major_version,
+            reconfigure,
+            actions,
+            stake_pool,
+            metadata_uri,
+            ignore_metadata,
+        } => actions
+            .iter()
+            .map(|action| parse_proposal_action(action))
+            .collect::<Result<Vec<_>>>()
+            .and_then(|extra_actions| {
+                let options = upgrade::UpgradeOptions {
+                    major_version: *major_version,
+                    reconfigure: *reconfigure,
+                    extra_actions,
+                    stake_pool: *stake_pool,
+                    metadata_uri: metadata_uri.clone(),
+                    ignore_metadata: *ignore_metadata,
+                };
+                run_upgrade_payload(old_dir, new_dir, output.as_deref(), &options)
+            }),
+        Commands::SimulateStaking {
+            genesis_file,
+            chain_id,
+            pool,
+            amount,
+            precompiles,
+            advance_time,
+            advance_epochs,
+        } => run_simulate_staking(
+            genesis_file,
+            *chain_id,
+            *pool,
+            *amount,
+            *precompiles,
+            &simulate::TimeTravel {
+                advance_time: *advance_time,
+                advance_epochs: *advance_epochs,
+            },
+        ),
+        Commands::Call {
+            genesis_file,
+            to,
+            sig,
+            args,
+            from,
+            value,
+            expect_revert,
+            chain_id,
+            precompiles,
+            advance_time,
+            advance_epochs,
+        } => {
+            let call = genesis::GenesisCall {
+                target: to.clone(),
+                signature: sig.clone(),
+                args: args.iter().map(|arg| parse_cli_arg(arg)).collect(),
+                caller: None,
+                value: value.clone(),
+            };
+            let step = simulate::script::CallStep {
+                expect_revert: expect_revert.clone(),
+                ..simulate::script::CallStep::new(call)
+            };
+            run_script_steps(
+                genesis_file,
+                &[simulate::script::ScriptStep::Call(step)],
+                &simulate::script::ScriptOptions {
+                    from: *from,
+                    time_travel: simulate::TimeTravel {
+                        advance_time: *advance_time,
+                        advance_epochs: *advance_epochs,
+                    },
+                    ..Default::default()
+                },
+                *chain_id,
+                *precompiles,
+            )
+        }
+        Commands::Exec {
+            genesis_file,
+            script,
+            from,
+            chain_id,
+            precompiles,
+            advance_time,
+            advance_epochs,
+            snapshot_dir,
+        } => run_exec(
+            genesis_file,
+            script,
+            &simulate::script::ScriptOptions {
+                from: *from,
+                time_travel: simulate::TimeTravel {
+                    advance_time: *advance_time,
+                    advance_epochs: *advance_epochs,
+                },
+                snapshot_dir: snapshot_dir.clone().unwrap_or_else(|| {
+                    Path::new(script).parent().map(Path::to_path_buf).unwrap_or_default()
+                }),
+            },
+            *chain_id,
+            *precompiles,
+        ),
     };
 
-    // Ensure logs are flushed before exiting
     info!("Main execution completed");
-    log_guard.flush_and_wait();
+    drop(log_guard);
+
+    if let Err(e) = result {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(exit_code::for_error(&e) as i32);
+    }
+    Ok(())
+}
 
-    result
+/// Optional inputs to `generate` beyond the config file
+#[derive(Debug, Default)]
+struct GenerateOptions {
+    validators_csv: Option<String>,
+    replace_validators: bool,
+    synthetic: Option<synthetic::SyntheticValidators>,
+    simulations: Vec<simulate::SimulationFlow>,
+    precompiles: PrecompileMode,
+    fork: Option<fork::ForkOptions>,
+    import_state: Option<String>,
+    replay_broadcast: Option<String>,
+    fetch_jwks: bool,
+    validation: validate::ValidationOptions,
+    config_overrides: Vec<String>,
+    contracts_manifest: Option<String>,
+    overrides: Vec<String>,
+    trace: bool,
+    evm_spec: Option<EvmSpec>,
+    abi_check: bool,
+    dynamic_abi: bool,
+    resume: bool,
+    build_state_only: bool,
+    no_prune: bool,
+    verification_bundle: bool,
+    check_against: Option<String>,
 }
 
-async fn run_generate(byte_code_dir: &str, config_file: &str, output: &str) -> Result<()> {
+async fn run_generate(
+    byte_code_dir: &str,
+    config_file: &str,
+    output: &str,
+    options: &GenerateOptions,
+) -> Result<()> {
     info!("Starting Gravity Genesis Generate");
     info!("Reading Genesis configuration from: {}", config_file);
-    
-    let config_content = fs::read_to_string(config_file)?;
-    let config: GenesisConfig = serde_json::from_str(&config_content)?;
-    
+    exit_code::enter(Phase::Config);
+
+    let mut config: GenesisConfig =
+        genesis::load_genesis_config_with_overrides(config_file, &options.config_overrides)?;
+    if let Some(csv_path) = &options.validators_csv {
+        csv_import::apply_validators_csv(&mut config, csv_path, options.replace_validators)?;
+    }
+    if let Some(synthetic) = &options.synthetic {
+        synthetic::apply_synthetic_validators(&mut config, synthetic)?;
+    }
+    if options.fetch_jwks {
+        oidc::fetch_jwks(&mut config.jwk_config)?;
+    }
+
+    check_config(&config, &options.validation)?;
+
     info!("Genesis configuration loaded successfully");
     info!("Validator count: {}", config.validators.len());
+    analyze::StakeAnalysis::new(&config).log();
     info!("Epoch interval: {} micros", config.epoch_interval_micros);
     info!("Major version: {}", config.major_version);
 
@@ -168,37 +1419,924 @@ async fn run_generate(byte_code_dir: &str, config_file: &str, output: &str) -> R
         }
     }
 
+    if let Some(evm_spec) = options.evm_spec {
+        config.evm_spec = Some(evm_spec);
+    }
+    info!("EVM spec: {:?}", config.spec_id());
+    if let Some(path) = &options.replay_broadcast {
+        config.replay_broadcast = Some(path.clone());
+    }
+    broadcast::replay_txns(&config)?;
+
+    exit_code::enter(Phase::Bytecode);
+    let mut contracts =
+        ContractsManifest::resolve(byte_code_dir, options.contracts_manifest.as_deref())?;
+    contracts.apply_overrides(&options.overrides)?;
+    contracts.preflight(byte_code_dir)?;
+    if config.vesting.is_some() {
+        vesting::deployment_txns(byte_code_dir, &config)?;
+    }
+    if config.governance_multisig.is_some() {
+        multisig::deployment_txns(byte_code_dir, &config)?;
+    }
+    if options.abi_check {
+        check_abi_drift(byte_code_dir)?;
+    }
+
+    exit_code::enter(Phase::Io);
     if !fs::metadata(output).is_ok() {
-        fs::create_dir_all(output).unwrap();
+        fs::create_dir_all(output)?;
     }
     info!("Output directory: {}", output);
 
-    let (db, bundle_state) = execute::genesis_generate(
-        byte_code_dir,
+    let mut extra_accounts = match &options.fork {
+        Some(fork_options) => {
+            for address in &fork_options.extra_accounts {
+                if is_reserved_system_address(*address)
+                    && !config.allow_reserved_alloc.iter().any(|a| a.parse() == Ok(*address))
+                {
+                    anyhow::bail!(
+                        "--fork-account {:?} is in the reserved system range; list it in allowReservedAlloc if intended",
+                        address
+                    );
+                }
+            }
+            fork::fetch_fork_state(fork_options, &contracts)?
+        }
+        None => HashMap::new(),
+    };
+    // Explicitly configured accounts take precedence over imported and forked state
+    exit_code::enter(Phase::Config);
+    if let Some(path) = &options.import_state {
+        extra_accounts.extend(state_import::import_state_dump(path, &config)?);
+    }
+    extra_accounts.extend(genesis::extra_alloc_accounts(&config)?);
+
+    if !options.dynamic_abi && !config.oracle_config.pending_callbacks.is_empty() {
+        anyhow::bail!(
+            "oracleConfig.pendingCallbacks needs --dynamic-abi: the built-in Genesis bindings have no staged callbacks"
+        );
+    }
+    if options.dynamic_abi && genesis::validator_batches(&config).len() > 1 {
+        anyhow::bail!(
+            "validatorBatchSize cannot be combined with --dynamic-abi: the chunked initializer is encoded from the built-in bindings"
+        );
+    }
+    exit_code::enter(Phase::Bytecode);
+    let initialize_call_data = options
+        .dynamic_abi
+        .then(|| dynamic_abi::encode_genesis_initialize(byte_code_dir, &config))
+        .transpose()?;
+
+    exit_code::enter(Phase::Execution);
+    if let Some(synthetic) = &options.synthetic {
+        let report = synthetic::scale_report(byte_code_dir, &contracts, &config, synthetic.seed, |config| {
+            options
+                .dynamic_abi
+                .then(|| dynamic_abi::encode_genesis_initialize(byte_code_dir, config))
+                .transpose()
+        })?;
+        report.log();
+        synthetic::write_scale_report(&report, output)?;
+    }
+    let inputs =
+        execute::BuildInputs::prepare(byte_code_dir, &contracts, &config, initialize_call_data)?;
+    let state_path = Path::new(output).join(execute::BUILD_STATE_FILE);
+    let saved = match options.resume {
+        true => match execute::BuildState::load(&state_path) {
+            Ok(state) if state.inputs_hash == inputs.hash => Some(state),
+            Ok(_) => {
+                info!("Inputs changed since {} was written; executing genesis", state_path.display());
+                None
+            }
+            Err(e) => {
+                info!("No reusable build state ({:#}); executing genesis", e);
+                None
+            }
+        },
+        false => None,
+    };
+    let state = match saved {
+        Some(mut state) => {
+            info!("Inputs unchanged since {}; skipping genesis execution", state_path.display());
+            state.set_extra_accounts(&extra_accounts);
+            state
+        }
+        None => {
+            let state = execute::build_state(
+                byte_code_dir,
+                &contracts,
+                output,
+                &config,
+                inputs,
+                &extra_accounts,
+                options.trace,
+            )?;
+            state.save(&state_path)?;
+            state
+        }
+    };
+    if options.build_state_only {
+        info!(
+            "Genesis state built; write the outputs with: genesis-tool assemble -c {} -o {}{}",
+            config_file,
+            output,
+            options
+                .config_overrides
+                .iter()
+                .map(|spec| format!(" --set '{}'", spec))
+                .collect::<String>()
+        );
+        return Ok(());
+    }
+
+    exit_code::enter(Phase::Io);
+    let genesis = execute::assemble_genesis(&state, &config, output, !options.no_prune)?;
+    if options.verification_bundle {
+        explorer::write_verification_bundle(byte_code_dir, &contracts, output)?;
+    }
+    finish_generate(genesis, &config, byte_code_dir, config_file, output, options)?;
+
+    if let Some(golden_dir) = &options.check_against {
+        exit_code::enter(Phase::Verification);
+        let diffs = golden::compare_outputs(golden_dir, output)?;
+        if !golden::log_diffs(golden_dir, &diffs) {
+            anyhow::bail!(
+                "Generated output differs from {} in {} file(s)",
+                golden_dir,
+                diffs.len()
+            );
+        }
+    }
+
+    info!("Gravity Genesis Generate completed successfully");
+    Ok(())
+}
+
+/// Config validation shared by `generate` and `assemble`
+fn check_config(config: &GenesisConfig, options: &validate::ValidationOptions) -> Result<()> {
+    let report = validate::validate_config(config, options);
+    report.log();
+    if report.has_errors() {
+        anyhow::bail!(
+            "Genesis config validation failed with {} error(s)",
+            report.error_count()
+        );
+    }
+    if options.strict && report.failure_count(true) > 0 {
+        anyhow::bail!(
+            "Genesis config validation failed with {} warning(s) under --strict",
+            report.failure_count(true)
+        );
+    }
+    Ok(())
+}
+
+/// Post-genesis checks, simulations and the provenance manifest, shared by
+/// `generate` and `assemble`
+fn finish_generate(
+    genesis: execute::AssembledGenesis,
+    config: &GenesisConfig,
+    byte_code_dir: &str,
+    config_file: &str,
+    output: &str,
+    options: &GenerateOptions,
+) -> Result<()> {
+    let simulator = (!options.simulations.is_empty())
+        .then(|| {
+            simulate::Simulator::from_bundle(genesis.db.clone(), &genesis.alloc_state, config.chain_id)
+                .with_spec_id(config.spec_id())
+                .with_evm_env(config.evm_env.as_ref())
+                .with_precompiles(options.precompiles)
+        });
+
+    exit_code::enter(Phase::Verification);
+    let pools = post_genesis::genesis_stake_pools(&genesis.db, &genesis.bundle_state, config)
+        .map_err(|e| anyhow::anyhow!("Failed to read genesis stake pools: {}", e))?;
+    if abi::artifact_path(byte_code_dir, "StakePool").is_file() {
+        let predicted =
+            stake_pools::predict_pools(config, &stake_pools::load_creation_code(byte_code_dir)?)?;
+        stake_pools::check_predictions(&predicted, &pools);
+    }
+    post_genesis::verify_result(&genesis, config);
+
+    if let Some(mut sim) = simulator {
+        for flow in &options.simulations {
+            info!("Simulating {:?} flow against generated state", flow);
+            let report = simulate::run_flow(&mut sim, *flow)?;
+            report.log();
+            if let Some(step) = report.failure() {
+                anyhow::bail!(
+                    "{:?} simulation stuck at '{}': {}",
+                    flow,
+                    step.name,
+                    step.detail
+                );
+            }
+        }
+    }
+
+    exit_code::enter(Phase::Io);
+    provenance::write_manifest(
         output,
-        &config,
+        byte_code_dir,
+        config_file,
+        &options.config_overrides,
+        config.genesis_timestamp_secs,
+        pools,
+    )?;
+    Ok(())
+}
+
+fn run_assemble(
+    config_file: &str,
+    output: &str,
+    state: Option<&str>,
+    config_overrides: &[String],
+    no_prune: bool,
+) -> Result<()> {
+    info!("Starting Gravity Genesis Assemble");
+
+    exit_code::enter(Phase::Config);
+    let mut config = genesis::load_genesis_config_with_overrides(config_file, config_overrides)?;
+    check_config(&config, &validate::ValidationOptions::default())?;
+    exit_code::enter(Phase::Io);
+    let state_path = match state {
+        Some(path) => PathBuf::from(path),
+        None => Path::new(output).join(execute::BUILD_STATE_FILE),
+    };
+    let state = execute::BuildState::load(&state_path)?;
+    info!(
+        "Loaded build state {} ({} changed accounts)",
+        state_path.display(),
+        state.bundle_state.state.len()
     );
+    if let Some(evm_spec) = state.evm_spec {
+        config.evm_spec = Some(evm_spec);
+    }
 
-    post_genesis::verify_result(
-        db,
-        bundle_state,
+    // The state must have been executed from this config, or the alloc and
+    // the checks below describe different chains
+    exit_code::enter(Phase::Config);
+    let initialize_call_data = state
+        .dynamic_abi
+        .then(|| dynamic_abi::encode_genesis_initialize(&state.byte_code_dir, &config))
+        .transpose()?;
+    let inputs = execute::BuildInputs::prepare(
+        &state.byte_code_dir,
+        &state.contracts,
         &config,
-    );
+        initialize_call_data,
+    )?;
+    if inputs.hash != state.inputs_hash {
+        anyhow::bail!(
+            "{} was not built from {}{}: inputs hash {} differs from {}",
+            state_path.display(),
+            config_file,
+            config_overrides
+                .iter()
+                .map(|spec| format!(" --set '{}'", spec))
+                .collect::<String>(),
+            inputs.hash,
+            state.inputs_hash
+        );
+    }
+    exit_code::enter(Phase::Io);
+    fs::create_dir_all(output)?;
 
-    info!("Gravity Genesis Generate completed successfully");
+    let genesis = execute::assemble_genesis(&state, &config, output, !no_prune)?;
+    finish_generate(
+        genesis,
+        &config,
+        &state.byte_code_dir,
+        config_file,
+        output,
+        &GenerateOptions {
+            config_overrides: config_overrides.to_vec(),
+            ..Default::default()
+        },
+    )?;
+
+    info!("Gravity Genesis Assemble completed successfully");
+    Ok(())
+}
+
+fn run_pool_addresses(
+    byte_code_dir: &str,
+    config_file: &str,
+    config_overrides: &[String],
+    json: bool,
+) -> Result<()> {
+    let config = genesis::load_genesis_config_with_overrides(config_file, config_overrides)?;
+    let creation_code = stake_pools::load_creation_code(byte_code_dir)?;
+    let pools = stake_pools::predict_pools(&config, &creation_code)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&pools)?);
+    } else {
+        stake_pools::print_pools(&pools);
+    }
+    Ok(())
+}
+
+fn run_analyze(config_file: &str, config_overrides: &[String]) -> Result<()> {
+    let config = genesis::load_genesis_config_with_overrides(config_file, config_overrides)?;
+    let analysis = analyze::StakeAnalysis::new(&config);
+    let mut report = validate::ValidationReport::default();
+    validate::check_stake_concentration(&analysis, &mut report);
+    report.log();
+    analyze::print_stake_distribution(&config);
+    analyze::print_analysis(&analysis);
+    Ok(())
+}
+
+fn run_validators(command: &ValidatorsCommands) -> Result<()> {
+    let (config_file, edit, dry_run) = match command {
+        ValidatorsCommands::Add {
+            config_file,
+            moniker,
+            operator,
+            owner,
+            staker,
+            stake_amount,
+            voting_power,
+            consensus_pubkey,
+            consensus_pop,
+            network_addresses,
+            fullnode_addresses,
+            dry_run,
+        } => {
+            let validator = genesis::InitialValidator {
+                operator: operator.clone(),
+                owner: owner.clone(),
+                staker: staker.clone().unwrap_or_else(|| owner.clone()),
+                stake_amount: stake_amount.clone(),
+                moniker: moniker.clone(),
+                consensus_pubkey: consensus_pubkey.clone(),
+                consensus_pop: consensus_pop.clone(),
+                network_addresses: network_addresses.clone(),
+                fullnode_addresses: fullnode_addresses
+                    .clone()
+                    .unwrap_or_else(|| network_addresses.clone()),
+                voting_power: voting_power.clone().unwrap_or_else(|| stake_amount.clone()),
+            };
+            (config_file, ValidatorEdit::Add(validator), *dry_run)
+        }
+        ValidatorsCommands::Remove {
+            config_file,
+            validator,
+            dry_run,
+        } => (config_file, ValidatorEdit::Remove(validator.clone()), *dry_run),
+        ValidatorsCommands::Update {
+            config_file,
+            validator,
+            fields,
+            dry_run,
+        } => (
+            config_file,
+            ValidatorEdit::Update(validator.clone(), fields.clone()),
+            *dry_run,
+        ),
+    };
+    validator_edit::edit_validators(config_file, &edit, &validate::ValidationOptions::default(), dry_run)
+}
+
+fn run_verify_manifest(
+    manifest: &str,
+    config_file: Option<&str>,
+    byte_code_dir: Option<&str>,
+) -> Result<()> {
+    info!("Starting Gravity Manifest Verify");
+
+    let mismatches = provenance::verify_manifest(manifest, config_file, byte_code_dir)?;
+    if mismatches.is_empty() {
+        info!("Gravity Manifest Verify completed successfully");
+        return Ok(());
+    }
+    for mismatch in &mismatches {
+        warn!("Mismatch: {}", mismatch);
+    }
+    Err(anyhow::anyhow!(
+        "Manifest verification failed with {} mismatch(es)",
+        mismatches.len()
+    ))
+}
+
+fn run_selectors(
+    genesis_file: Option<&str>,
+    byte_code_dir: Option<&str>,
+    contracts_manifest: Option<&str>,
+    signatures: Option<&str>,
+    output: Option<&str>,
+) -> Result<()> {
+    info!("Starting Gravity Selector Inventory");
+
+    let inventories =
+        selectors::collect_selectors(genesis_file, byte_code_dir, contracts_manifest, signatures)?;
+    match output {
+        Some(path) => {
+            fs::write(path, serde_json::to_string_pretty(&inventories)? + "\n")?;
+            info!("Selector inventory written to {}", path);
+        }
+        None => print!("{}", selectors::format_inventories(&inventories)),
+    }
+    let unresolved: usize = inventories.iter().map(|c| c.unresolved()).sum();
+    if unresolved > 0 {
+        warn!(
+            "{} selector(s) could not be named; pass --byte-code-dir with artifacts or --signatures",
+            unresolved
+        );
+    }
+    Ok(())
+}
+
+fn run_export(genesis_file: &str, format: ExportFormat, output: &str, report: Option<&str>) -> Result<()> {
+    info!("Exporting {} as a {:?} genesis", genesis_file, format);
+    let compatibility = export::write_export(genesis_file, format, output, report)?;
+    compatibility.log();
+    if !compatibility.dropped.is_empty() {
+        warn!(
+            "{} field(s) of {} were not carried over; see the compatibility report above",
+            compatibility.dropped.len(),
+            genesis_file
+        );
+    }
+    Ok(())
+}
+
+fn run_diff_state(old: &str, new: &str, byte_code_dir: Option<&str>, output: Option<&str>) -> Result<()> {
+    info!("Diffing genesis state {} -> {}", old, new);
+    let diff = state_diff::diff_states(old, new, byte_code_dir)?;
+    diff.log();
+    if let Some(path) = output {
+        state_diff::write_diff(&diff, path)?;
+        info!("Wrote state diff to {}", path);
+    }
+    Ok(())
+}
+
+fn run_bench(
+    byte_code_dir: &str,
+    config_file: &str,
+    iterations: usize,
+    output: Option<&str>,
+    baseline: Option<&str>,
+    max_regression: f64,
+    work_dir: Option<&Path>,
+) -> Result<()> {
+    let work_dir = work_dir.map_or_else(|| std::env::temp_dir().join("genesis-tool-bench"), Path::to_path_buf);
+    info!("Benchmarking {} over {} iteration(s), writing outputs to {}", config_file, iterations, work_dir.display());
+    let report = bench::run_bench(byte_code_dir, config_file, iterations, &work_dir)?;
+    report.log();
+    if let Some(path) = output {
+        bench::write_report(&report, path)?;
+        info!("Benchmark results written to {}", path);
+    }
+    let Some(baseline_path) = baseline else {
+        return Ok(());
+    };
+    let regressions = report.regressions(&bench::load_report(baseline_path)?, max_regression);
+    if !regressions.is_empty() {
+        for regression in &regressions {
+            error!("❌ Regression {}", regression);
+        }
+        anyhow::bail!("{} phase(s) regressed against {}", regressions.len(), baseline_path);
+    }
+    info!("✅ No phase regressed by more than {}% against {}", max_regression, baseline_path);
+    Ok(())
+}
+
+fn run_record_fixtures(genesis_file: &str, output: &str, chain_id: u64, check: bool) -> Result<()> {
+    if !check {
+        return record::write_fixtures(genesis_file, chain_id, output);
+    }
+    let differences = record::check_fixtures(genesis_file, chain_id, output)?;
+    if differences.is_empty() {
+        info!("Fixtures in {} match {}", output, genesis_file);
+        return Ok(());
+    }
+    for difference in &differences {
+        warn!("Fixture mismatch: {}", difference);
+    }
+    Err(anyhow::anyhow!(
+        "{} fixture(s) in {} differ from {}",
+        differences.len(),
+        output,
+        genesis_file
+    ))
+}
+
+/// Log the binding comparison and fail if any binding drifted
+fn check_abi_drift(byte_code_dir: &str) -> Result<()> {
+    let report = abi_check::check_bindings(byte_code_dir)?;
+    report.log();
+    if !report.is_clean() {
+        anyhow::bail!(
+            "{} ABI binding(s) drifted from the artifacts in {}",
+            report.drifts.len(),
+            byte_code_dir
+        );
+    }
+    Ok(())
+}
+
+fn run_abi_check(byte_code_dir: &str) -> Result<()> {
+    info!("Starting Gravity ABI Check");
+    check_abi_drift(byte_code_dir)?;
+    info!("Gravity ABI Check completed");
     Ok(())
 }
 
-fn run_verify(genesis_file: &str) -> Result<()> {
+fn run_doctor(options: &doctor::DoctorOptions) -> Result<()> {
+    info!("Starting Gravity Doctor");
+
+    let report = doctor::run_doctor(options);
+    report.log();
+    match report.error_count() {
+        0 => {
+            info!("Gravity Doctor completed: setup looks ready for generate");
+            Ok(())
+        }
+        errors => Err(anyhow::anyhow!(
+            "Doctor found {} problem(s) that would break generate",
+            errors
+        )),
+    }
+}
+
+async fn run_devnet(
+    options: &devnet::DevnetOptions,
+    output: &str,
+    byte_code_dir: Option<&str>,
+) -> Result<()> {
+    info!("Starting Gravity Devnet Bootstrap");
+
+    let config_path = devnet::bootstrap_devnet(options, output)?;
+
+    match byte_code_dir {
+        Some(byte_code_dir) => {
+            let genesis_output = format!("{}/genesis", output);
+            run_generate(
+                byte_code_dir,
+                &config_path,
+                &genesis_output,
+                &GenerateOptions::default(),
+            )
+            .await?;
+        }
+        None => {
+            warn!("No --byte-code-dir given; skipping genesis generation");
+            info!(
+                "Generate later with: genesis-tool generate --byte-code-dir <dir> --config-file {} --output {}/genesis",
+                config_path, output
+            );
+        }
+    }
+
+    info!("Gravity Devnet Bootstrap completed successfully");
+    Ok(())
+}
+
+fn run_deploy(
+    byte_code_dir: &str,
+    config_file: &str,
+    config_overrides: &[String],
+    contracts_manifest: Option<&str>,
+    options: deploy::DeployOptions,
+) -> Result<()> {
+    let config = genesis::load_genesis_config_with_overrides(config_file, config_overrides)?;
+    // A local node is a devnet whatever chain ID the config keeps
+    let validation = validate::ValidationOptions {
+        allow_default_chain_id: true,
+        ..Default::default()
+    };
+    let report = validate::validate_config(&config, &validation);
+    report.log();
+    if report.has_errors() {
+        anyhow::bail!(
+            "Genesis config validation failed with {} error(s)",
+            report.error_count()
+        );
+    }
+    let contracts = ContractsManifest::resolve(byte_code_dir, contracts_manifest)?;
+    contracts.preflight(byte_code_dir)?;
+    let extra_accounts = genesis::extra_alloc_accounts(&config)?;
+    let inputs = execute::BuildInputs::prepare(byte_code_dir, &contracts, &config, None)?;
+    deploy::deploy_genesis(&options, &contracts, &config, &inputs, &extra_accounts)
+}
+
+fn run_serve(
+    output: &str,
+    host: &str,
+    port: u16,
+    token_env: &str,
+    tls_cert: Option<&str>,
+    tls_key: Option<&str>,
+) -> Result<()> {
+    let token = match std::env::var(token_env) {
+        Ok(token) if !token.is_empty() => token,
+        _ => {
+            let mut bytes = [0u8; 16];
+            rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut bytes);
+            let token = hex::encode(bytes);
+            info!("{} is not set; requests must send Authorization: Bearer {}", token_env, token);
+            token
+        }
+    };
+    serve::serve(serve::ServeOptions {
+        output_dir: PathBuf::from(output),
+        host: host.to_string(),
+        port,
+        token,
+        tls: tls_cert.zip(tls_key).map(|(cert, key)| (cert.to_string(), key.to_string())),
+    })
+}
+
+fn run_keygen_bls(
+    keystore_dir: Option<&str>,
+    keystore_name: &str,
+    password_env: &str,
+    force: bool,
+) -> Result<()> {
+    let mut rng = rand::rngs::OsRng;
+    let keypair = keys::generate_bls_keypair(&mut rng);
+    let account_address = genesis::derive_account_address_from_consensus_pubkey(&keypair.public_key);
+
+    let mut output = serde_json::json!({
+        "consensusPubkey": keypair.public_key_hex(),
+        "consensusPop": keypair.proof_of_possession_hex(),
+        "accountAddress": format!("0x{}", hex::encode(account_address)),
+    });
+
+    match keystore_dir {
+        Some(dir) => {
+            let password = std::env::var(password_env).map_err(|_| {
+                anyhow::anyhow!("Keystore password must be provided via ${}", password_env)
+            })?;
+            let path = keys::write_bls_keystore(
+                &mut rng,
+                std::path::Path::new(dir),
+                keystore_name,
+                &keypair,
+                &password,
+                force,
+            )?;
+            info!("Encrypted consensus key written to {}", path.display());
+            output["keystore"] = serde_json::Value::String(path.display().to_string());
+        }
+        None => {
+            warn!("No --keystore-dir given; printing the secret key in plaintext");
+            output["consensusPrivateKey"] =
+                serde_json::Value::String(format!("0x{}", hex::encode(keypair.secret_key)));
+        }
+    }
+
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+fn run_simulate_governance(
+    genesis_file: &str,
+    chain_id: u64,
+    actions: &[String],
+    precompiles: PrecompileMode,
+    time_travel: &simulate::TimeTravel,
+) -> Result<()> {
+    info!("Starting Gravity Governance Simulation");
+
+    let actions = if actions.is_empty() {
+        None
+    } else {
+        Some(
+            actions
+                .iter()
+                .map(|action| parse_proposal_action(action))
+                .collect::<Result<Vec<_>>>()?,
+        )
+    };
+
+    let mut sim = simulate::Simulator::from_genesis_file(genesis_file, chain_id)?
+        .with_precompiles(precompiles);
+    time_travel.apply(&mut sim)?;
+    let report = simulate::governance::simulate_governance(&mut sim, actions)?;
+    report.log();
+
+    match report.failure() {
+        None => {
+            info!("Governance simulation completed: proposal lifecycle succeeded");
+            Ok(())
+        }
+        Some(step) => Err(anyhow::anyhow!(
+            "Governance simulation stuck at '{}': {}",
+            step.name,
+            step.detail
+        )),
+    }
+}
+
+fn run_simulate_staking(
+    genesis_file: &str,
+    chain_id: u64,
+    pool: Option<Address>,
+    amount: Option<U256>,
+    precompiles: PrecompileMode,
+    time_travel: &simulate::TimeTravel,
+) -> Result<()> {
+    info!("Starting Gravity Staking Simulation");
+
+    let mut sim = simulate::Simulator::from_genesis_file(genesis_file, chain_id)?
+        .with_precompiles(precompiles);
+    time_travel.apply(&mut sim)?;
+    let report = simulate::staking::simulate_staking(&mut sim, pool, amount)?;
+    report.log();
+
+    match report.failure() {
+        None => {
+            info!("Staking simulation completed: stake withdrawn in full");
+            Ok(())
+        }
+        Some(step) => Err(anyhow::anyhow!(
+            "Staking simulation stuck at '{}': {}",
+            step.name,
+            step.detail
+        )),
+    }
+}
+
+fn run_upgrade_payload(
+    old_dir: &str,
+    new_dir: &str,
+    output: Option<&str>,
+    options: &upgrade::UpgradeOptions,
+) -> Result<()> {
+    info!("Building governance upgrade payload");
+
+    let payload = upgrade::build_upgrade_payload(old_dir, new_dir, options)?;
+    let json = serde_json::to_string_pretty(&payload)?;
+    match output {
+        Some(path) => {
+            fs::write(path, json + "\n")?;
+            info!("Upgrade payload written to {}", path);
+        }
+        None => println!("{}", json),
+    }
+
+    let actions: Vec<String> = payload
+        .targets
+        .iter()
+        .zip(&payload.datas)
+        .map(|(target, data)| format!("--action {:?}:{}", target, hex::encode(data)))
+        .collect();
+    info!(
+        "Dry-run against a genesis with: genesis-tool simulate-governance --genesis-file <genesis.json> {}",
+        actions.join(" ")
+    );
+    Ok(())
+}
+
+fn parse_proposal_action(action: &str) -> Result<simulate::governance::ProposalAction> {
+    let (target, data) = action
+        .split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("Action must be <target>:<calldata hex>, got {}", action))?;
+    Ok(simulate::governance::ProposalAction {
+        target: target
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid action target {}: {}", target, e))?,
+        data: hex::decode(data)
+            .map_err(|e| anyhow::anyhow!("Invalid action calldata {}: {}", data, e))?
+            .into(),
+    })
+}
+
+fn run_verify(
+    genesis_files: &[String],
+    expected_config: Option<&str>,
+    byte_code_dir: Option<&str>,
+    ignore_metadata: bool,
+    interfaces: Option<&str>,
+    options: &VerifyOptions,
+) -> Result<()> {
     info!("Starting Gravity Genesis Verify");
     
-    let result = verify::verify_genesis_file(genesis_file)?;
-    verify::print_verify_summary(&result);
-    
-    if result.success {
+    exit_code::enter(Phase::Io);
+    let mut files = Vec::new();
+    for pattern in genesis_files {
+        for path in expand_glob(pattern)? {
+            files.push(path.display().to_string());
+        }
+    }
+    exit_code::enter(Phase::Config);
+    let config = expected_config
+        .map(genesis::load_genesis_config)
+        .transpose()?;
+    let requirements = interfaces
+        .map(interfaces::load_interface_manifest)
+        .transpose()?;
+    // Views execute under the block fields genesis was generated with
+    let mut options = options.clone();
+    if let Some(evm_env) = config.as_ref().and_then(|config| config.evm_env.clone()) {
+        info!("Executing views under the evmEnv of {}", expected_config.unwrap_or_default());
+        options.evm_env = Some(evm_env);
+    }
+    let options = &options;
+    exit_code::enter(Phase::Verification);
+
+    if let [genesis_file] = files.as_slice() {
+        let mut result = verify::verify_genesis_file_with(genesis_file, options)?;
+        if let Some(config) = &config {
+            verify::cross_check_with_config(&mut result, config);
+        }
+        if let Some(byte_code_dir) = byte_code_dir {
+            verify::cross_check_bytecode(&mut result, genesis_file, byte_code_dir, ignore_metadata)?;
+        }
+        if let Some(requirements) = &requirements {
+            interfaces::cross_check_interfaces(&mut result, genesis_file, requirements, options)?;
+        }
+        verify::print_verify_summary(&result);
+
+        return if result.success {
+            info!("Gravity Genesis Verify completed successfully");
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Genesis verification failed"))
+        };
+    }
+
+    info!("Verifying {} genesis files concurrently", files.len());
+    let results = verify::verify_genesis_files(
+        &files,
+        config.as_ref(),
+        byte_code_dir,
+        ignore_metadata,
+        requirements.as_deref(),
+        options,
+    );
+    for file in &results {
+        if let Ok(result) = &file.result {
+            println!("{}:", file.genesis_file);
+            verify::print_verify_summary(result);
+        }
+    }
+    verify::print_aggregate_summary(&results);
+
+    let failed = results
+        .iter()
+        .filter(|r| !r.result.as_ref().is_ok_and(|r| r.success))
+        .count();
+    if failed == 0 {
         info!("Gravity Genesis Verify completed successfully");
         Ok(())
     } else {
-        Err(anyhow::anyhow!("Genesis verification failed"))
+        Err(anyhow::anyhow!(
+            "Genesis verification failed for {} of {} files",
+            failed,
+            results.len()
+        ))
+    }
+}
+
+/// `--arg` value: arrays (also for tuples), quoted strings and booleans as JSON,
+/// anything else (addresses, numbers of any size, bytes) as the string itself
+fn parse_cli_arg(arg: &str) -> serde_json::Value {
+    let json = arg.starts_with(['[', '{', '"']) || arg == "true" || arg == "false";
+    json.then(|| serde_json::from_str(arg).ok())
+        .flatten()
+        .unwrap_or_else(|| serde_json::Value::String(arg.to_string()))
+}
+
+fn run_exec(
+    genesis_file: &str,
+    script: &str,
+    options: &simulate::script::ScriptOptions,
+    chain_id: u64,
+    precompiles: PrecompileMode,
+) -> Result<()> {
+    let steps = simulate::script::load_script(script)?;
+    info!("Running {} step(s) of {} against {}", steps.len(), script, genesis_file);
+    run_script_steps(genesis_file, &steps, options, chain_id, precompiles)
+}
+
+fn run_script_steps(
+    genesis_file: &str,
+    steps: &[simulate::script::ScriptStep],
+    options: &simulate::script::ScriptOptions,
+    chain_id: u64,
+    precompiles: PrecompileMode,
+) -> Result<()> {
+    let mut sim = simulate::Simulator::from_genesis_file(genesis_file, chain_id)?
+        .with_precompiles(precompiles);
+    options.time_travel.apply(&mut sim)?;
+    let report = simulate::script::run_script(&mut sim, steps, options)?;
+    report.log();
+
+    match report.failure() {
+        None => {
+            info!("Script completed: {} step(s) as expected", report.steps.len());
+            Ok(())
+        }
+        Some(step) => Err(anyhow::anyhow!(
+            "Script stopped at '{}': {}",
+            step.name,
+            step.detail
+        )),
     }
 }
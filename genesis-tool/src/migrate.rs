@@ -0,0 +1,319 @@
+//! `migrate-config`: upgrade older genesis config layouts to the current schema
+//!
+//! Only one older layout exists, `v1`, the flat config that predates the
+//! nested `validatorConfig`, `stakingConfig` and `governanceConfig` objects
+//! and the randomness, oracle and JWK configs:
+//!
+//! ```json
+//! {
+//!   "minimumBond": "...", "maximumBond": "...", "unbondingDelayMicros": 604800000000,
+//!   "allowValidatorSetChange": true, "votingPowerIncreaseLimitPct": 20,
+//!   "maxValidatorSetSize": "100",
+//!   "minimumStake": "...", "lockupDurationMicros": 86400000000,
+//!   "minVotingThreshold": "...", "requiredProposerStake": "...",
+//!   "votingDurationMicros": 604800000000,
+//!   "epochIntervalMicros": 7200000000, "consensusConfig": "0x...",
+//!   "validators": [
+//!     { "address": "0x...", "stakeAmount": "...", "moniker": "...",
+//!       "consensusPubkey": "0x...", "networkAddresses": "/ip4/..." }
+//!   ]
+//! }
+//! ```
+//!
+//! The flat parameters move into their config objects; v1 had a single
+//! unbonding delay, which both `validatorConfig` and `stakingConfig` receive.
+//! Fields v1 did not have are filled with the defaults in [`DEFAULTS`], which
+//! keep the behavior of a v1 chain: randomness off, no oracle sources, no JWK
+//! issuers, no auto-eviction. Per validator, `address` becomes the operator,
+//! owner and staker that are not set, `votingPower` defaults to `stakeAmount`
+//! and `fullnodeAddresses` to `networkAddresses`.
+//!
+//! Two fields cannot be derived and are flagged for review: a missing
+//! `consensusPop` gets the all-zero placeholder of `config init`, to be
+//! replaced with the proof of possession of the consensus key, and
+//! `initialLockedUntilMicros` is `genesisTimestampSecs` plus the lockup
+//! duration when the timestamp is set, and 0 otherwise.
+//!
+//! Keys already in the current layout are kept, so a partly migrated file
+//! migrates too. The file is migrated as written: `${ENV_VAR}` references
+//! must stay inside strings, and `$include` files are not migrated with it.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use std::fs;
+use tracing::{info, warn};
+
+use crate::genesis::GenesisConfig;
+
+/// Config layouts `migrate-config` upgrades from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ConfigLayout {
+    /// Flat parameters, before the nested config structs
+    V1,
+}
+
+/// Flat v1 keys, by the config object they move into
+const FLAT_KEYS: [(&str, &[&str]); 3] = [
+    (
+        "validatorConfig",
+        &[
+            "minimumBond",
+            "maximumBond",
+            "unbondingDelayMicros",
+            "allowValidatorSetChange",
+            "votingPowerIncreaseLimitPct",
+            "maxValidatorSetSize",
+        ],
+    ),
+    ("stakingConfig", &["minimumStake", "lockupDurationMicros"]),
+    (
+        "governanceConfig",
+        &["minVotingThreshold", "requiredProposerStake", "votingDurationMicros"],
+    ),
+];
+
+/// Defaults for fields v1 did not have, as `(dotted path, JSON value)`
+pub const DEFAULTS: [(&str, &str); 7] = [
+    ("validatorConfig.autoEvictEnabled", "false"),
+    ("validatorConfig.autoEvictThresholdPct", "0"),
+    ("majorVersion", "1"),
+    ("executionConfig", "\"0x00\""),
+    (
+        "randomnessConfig",
+        r#"{"variant":0,"configV2":{"secrecyThreshold":0,"reconstructionThreshold":0,"fastPathSecrecyThreshold":0}}"#,
+    ),
+    ("oracleConfig", r#"{"sourceTypes":[],"callbacks":[]}"#),
+    ("jwkConfig", r#"{"issuers":[],"jwks":[]}"#),
+];
+
+/// Proof of possession placeholder, as written by `config init`
+fn placeholder_pop() -> String {
+    format!("0x{}", "0".repeat(192))
+}
+
+/// One change made by the migration
+#[derive(Debug, Clone)]
+pub struct MigrationStep {
+    /// Dotted path of the field changed
+    pub path: String,
+    pub description: String,
+    /// The value is a placeholder the operator has to replace
+    pub needs_review: bool,
+}
+
+impl MigrationStep {
+    fn new(path: impl Into<String>, description: impl Into<String>) -> Self {
+        MigrationStep {
+            path: path.into(),
+            description: description.into(),
+            needs_review: false,
+        }
+    }
+
+    fn review(path: impl Into<String>, description: impl Into<String>) -> Self {
+        MigrationStep {
+            needs_review: true,
+            ..Self::new(path, description)
+        }
+    }
+}
+
+/// A migrated config and the changes that produced it
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub config: Value,
+    pub steps: Vec<MigrationStep>,
+}
+
+/// Object at `key` of `map`, created empty when absent
+fn object_at<'a>(map: &'a mut Map<String, Value>, key: &str) -> Result<&'a mut Map<String, Value>> {
+    map.entry(key.to_string())
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("{} is not an object", key))
+}
+
+/// Set `path` to `value` unless it is already set; returns whether it was set
+fn fill(root: &mut Map<String, Value>, path: &str, value: Value) -> Result<bool> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (Some(parent), key),
+        None => (None, path),
+    };
+    let target = match parent {
+        Some(parent) => object_at(root, parent)?,
+        None => root,
+    };
+    if target.contains_key(key) {
+        return Ok(false);
+    }
+    target.insert(key.to_string(), value);
+    Ok(true)
+}
+
+/// Upgrade a config in the `from` layout to the current schema
+pub fn migrate_config(value: Value, from: ConfigLayout) -> Result<Migration> {
+    match from {
+        ConfigLayout::V1 => migrate_v1(value),
+    }
+}
+
+fn migrate_v1(value: Value) -> Result<Migration> {
+    let Value::Object(mut root) = value else {
+        anyhow::bail!("A genesis config must be a JSON object");
+    };
+    let mut steps = Vec::new();
+
+    let shared_unbonding = root.get("unbondingDelayMicros").cloned();
+    for (section, keys) in FLAT_KEYS {
+        for key in keys {
+            let Some(value) = root.remove(*key) else {
+                continue;
+            };
+            let target = object_at(&mut root, section)?;
+            if target.contains_key(*key) {
+                anyhow::bail!("{} is set both flat and in {}", key, section);
+            }
+            target.insert(key.to_string(), value);
+            steps.push(MigrationStep::new(
+                format!("{}.{}", section, key),
+                format!("moved from {}", key),
+            ));
+        }
+    }
+    if let Some(delay) = shared_unbonding {
+        if fill(&mut root, "stakingConfig.unbondingDelayMicros", delay.clone())? {
+            steps.push(MigrationStep::new(
+                "stakingConfig.unbondingDelayMicros",
+                format!("copied from the shared unbondingDelayMicros ({})", delay),
+            ));
+        }
+    }
+
+    for (path, default) in DEFAULTS {
+        let default: Value = serde_json::from_str(default).expect("defaults are valid JSON");
+        let shown = default.to_string();
+        if fill(&mut root, path, default)? {
+            steps.push(MigrationStep::new(path, format!("added with default {}", shown)));
+        }
+    }
+
+    if !root.contains_key("initialLockedUntilMicros") {
+        let timestamp = root.get("genesisTimestampSecs").and_then(Value::as_u64);
+        let lockup = root
+            .get("stakingConfig")
+            .and_then(|staking| staking.get("lockupDurationMicros"))
+            .and_then(Value::as_u64);
+        match (timestamp, lockup) {
+            (Some(secs), Some(lockup)) => {
+                let until = secs.saturating_mul(1_000_000).saturating_add(lockup);
+                root.insert("initialLockedUntilMicros".to_string(), json!(until));
+                steps.push(MigrationStep::new(
+                    "initialLockedUntilMicros",
+                    format!("added as genesisTimestampSecs + lockupDurationMicros ({})", until),
+                ));
+            }
+            _ => {
+                root.insert("initialLockedUntilMicros".to_string(), json!(0));
+                steps.push(MigrationStep::review(
+                    "initialLockedUntilMicros",
+                    "added as 0 (no lockup); set it to the launch time in microseconds plus the lockup",
+                ));
+            }
+        }
+    }
+
+    let validators = root
+        .get_mut("validators")
+        .and_then(Value::as_array_mut)
+        .ok_or_else(|| anyhow::anyhow!("validators must be an array"))?;
+    for (i, validator) in validators.iter_mut().enumerate() {
+        let validator = validator
+            .as_object_mut()
+            .ok_or_else(|| anyhow::anyhow!("validators.{} is not an object", i))?;
+        let prefix = format!("validators.{}", i);
+
+        if let Some(address) = validator.remove("address") {
+            let mut roles = Vec::new();
+            for role in ["operator", "owner", "staker"] {
+                if !validator.contains_key(role) {
+                    validator.insert(role.to_string(), address.clone());
+                    roles.push(role);
+                }
+            }
+            steps.push(MigrationStep::new(
+                format!("{}.address", prefix),
+                format!("replaced by {} ({})", roles.join(", "), address),
+            ));
+        }
+        for (field, source) in [
+            ("votingPower", "stakeAmount"),
+            ("fullnodeAddresses", "networkAddresses"),
+        ] {
+            if validator.contains_key(field) {
+                continue;
+            }
+            if let Some(value) = validator.get(source).cloned() {
+                validator.insert(field.to_string(), value);
+                steps.push(MigrationStep::new(
+                    format!("{}.{}", prefix, field),
+                    format!("copied from {}", source),
+                ));
+            }
+        }
+        if !validator.contains_key("moniker") {
+            let moniker = format!("validator-{}", i + 1);
+            steps.push(MigrationStep::new(
+                format!("{}.moniker", prefix),
+                format!("added as {:?}", moniker),
+            ));
+            validator.insert("moniker".to_string(), json!(moniker));
+        }
+        if !validator.contains_key("consensusPop") {
+            validator.insert("consensusPop".to_string(), json!(placeholder_pop()));
+            steps.push(MigrationStep::review(
+                format!("{}.consensusPop", prefix),
+                "added as an all-zero placeholder; replace it with the proof of possession of consensusPubkey",
+            ));
+        }
+    }
+    let config = Value::Object(root);
+    serde_json::from_value::<GenesisConfig>(config.clone())
+        .context("Migrated config does not match the current schema")?;
+    Ok(Migration { config, steps })
+}
+
+/// Migrate the config file at `input`; writes to `output`, or prints the
+/// migrated config when it is `None`
+pub fn run_migrate_config(input: &str, from: ConfigLayout, output: Option<&str>) -> Result<()> {
+    let content = fs::read_to_string(input).context(format!("Failed to read {}", input))?;
+    let value: Value =
+        serde_json::from_str(&content).context(format!("Invalid JSON in {}", input))?;
+    let migration = migrate_config(value, from)?;
+
+    if migration.steps.is_empty() {
+        info!("{} is already in the current layout", input);
+    } else {
+        info!("Migrated {} with {} change(s):", input, migration.steps.len());
+    }
+    for step in &migration.steps {
+        if step.needs_review {
+            warn!("  ⚠️  {}: {}", step.path, step.description);
+        } else {
+            info!("  {}: {}", step.path, step.description);
+        }
+    }
+    let review = migration.steps.iter().filter(|step| step.needs_review).count();
+    if review > 0 {
+        warn!("{} value(s) are placeholders and must be reviewed before genesis", review);
+    }
+
+    let json = serde_json::to_string_pretty(&migration.config)? + "\n";
+    match output {
+        Some(path) => {
+            fs::write(path, json).context(format!("Failed to write {}", path))?;
+            info!("Migrated config written to {}", path);
+        }
+        None => print!("{}", json),
+    }
+    Ok(())
+}
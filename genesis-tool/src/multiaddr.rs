@@ -0,0 +1,245 @@
+//! Validator network address (multiaddr) parsing
+//!
+//! `networkAddresses` / `fullnodeAddresses` are BCS-encoded as strings and parsed by
+//! gravity-reth as Aptos-style network addresses:
+//!
+//! ```text
+//! /ip4/<ip>/tcp/<port>/noise-ik/<x25519 pubkey hex>/handshake/<version>
+//! ```
+//!
+//! `/ip6/<ip>`, `/dns/<host>`, `/dns4/<host>` and `/dns6/<host>` are accepted as the host part.
+
+use std::{
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use revm_primitives::hex;
+
+/// Length of an x25519 noise public key in bytes
+pub const NOISE_KEY_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Host {
+    Ip4(Ipv4Addr),
+    Ip6(Ipv6Addr),
+    Dns {
+        protocol: &'static str,
+        name: String,
+    },
+}
+
+impl Host {
+    /// Whether the address only reaches the local machine
+    pub fn is_localhost(&self) -> bool {
+        match self {
+            Host::Ip4(ip) => ip.is_loopback() || ip.is_unspecified(),
+            Host::Ip6(ip) => ip.is_loopback() || ip.is_unspecified(),
+            Host::Dns { name, .. } => name.eq_ignore_ascii_case("localhost"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkAddress {
+    pub host: Host,
+    pub port: u16,
+    pub noise_public_key: [u8; NOISE_KEY_LEN],
+    pub handshake_version: u8,
+}
+
+impl fmt::Display for NetworkAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.host {
+            Host::Ip4(ip) => write!(f, "/ip4/{}", ip)?,
+            Host::Ip6(ip) => write!(f, "/ip6/{}", ip)?,
+            Host::Dns { protocol, name } => write!(f, "/{}/{}", protocol, name)?,
+        }
+        write!(
+            f,
+            "/tcp/{}/noise-ik/{}/handshake/{}",
+            self.port,
+            hex::encode(self.noise_public_key),
+            self.handshake_version
+        )
+    }
+}
+
+/// Parse and validate a network address string
+pub fn parse_network_address(input: &str) -> Result<NetworkAddress, String> {
+    let trimmed = input.trim().trim_end_matches('/');
+    let Some(rest) = trimmed.strip_prefix('/') else {
+        return Err(format!("address must start with '/': {:?}", input));
+    };
+    let parts: Vec<&str> = rest.split('/').collect();
+    if parts.len() != 8 {
+        return Err(format!(
+            "expected /<host-proto>/<host>/tcp/<port>/noise-ik/<key>/handshake/<version>, got {} components in {:?}",
+            parts.len(),
+            input
+        ));
+    }
+
+    let host = match parts[0] {
+        "ip4" => Host::Ip4(
+            parts[1]
+                .parse()
+                .map_err(|_| format!("invalid IPv4 address {:?}", parts[1]))?,
+        ),
+        "ip6" => Host::Ip6(
+            parts[1]
+                .parse()
+                .map_err(|_| format!("invalid IPv6 address {:?}", parts[1]))?,
+        ),
+        proto @ ("dns" | "dns4" | "dns6") => {
+            let name = parts[1].to_ascii_lowercase();
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+            {
+                return Err(format!("invalid DNS name {:?}", parts[1]));
+            }
+            let protocol = match proto {
+                "dns" => "dns",
+                "dns4" => "dns4",
+                _ => "dns6",
+            };
+            Host::Dns { protocol, name }
+        }
+        other => return Err(format!("unsupported host protocol {:?}", other)),
+    };
+
+    if parts[2] != "tcp" {
+        return Err(format!("expected 'tcp' after host, got {:?}", parts[2]));
+    }
+    let port: u16 = parts[3]
+        .parse()
+        .map_err(|_| format!("invalid TCP port {:?}", parts[3]))?;
+    if port == 0 {
+        return Err("TCP port must be non-zero".to_string());
+    }
+
+    if parts[4] != "noise-ik" {
+        return Err(format!(
+            "expected 'noise-ik' after port, got {:?}",
+            parts[4]
+        ));
+    }
+    let key_hex = parts[5].strip_prefix("0x").unwrap_or(parts[5]);
+    let key_bytes =
+        hex::decode(key_hex).map_err(|e| format!("invalid noise key hex {:?}: {}", parts[5], e))?;
+    let noise_public_key: [u8; NOISE_KEY_LEN] = key_bytes.as_slice().try_into().map_err(|_| {
+        format!(
+            "noise key must be {} bytes, got {}",
+            NOISE_KEY_LEN,
+            key_bytes.len()
+        )
+    })?;
+
+    if parts[6] != "handshake" {
+        return Err(format!(
+            "expected 'handshake' after noise key, got {:?}",
+            parts[6]
+        ));
+    }
+    let handshake_version: u8 = parts[7]
+        .parse()
+        .map_err(|_| format!("invalid handshake version {:?}", parts[7]))?;
+
+    Ok(NetworkAddress {
+        host,
+        port,
+        noise_public_key,
+        handshake_version,
+    })
+}
+
+/// Parse and re-render an address in canonical form (lowercase key, no `0x`, no trailing `/`)
+pub fn normalize_network_address(input: &str) -> Result<String, String> {
+    parse_network_address(input).map(|addr| addr.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f9";
+
+    fn address(host: &str) -> String {
+        format!("{}/tcp/6180/noise-ik/{}/handshake/0", host, KEY)
+    }
+
+    #[test]
+    fn parses_ip4() {
+        let addr = parse_network_address(&address("/ip4/10.0.0.1")).unwrap();
+        assert_eq!(addr.host, Host::Ip4(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(addr.port, 6180);
+        assert_eq!(hex::encode(addr.noise_public_key), KEY);
+        assert_eq!(addr.handshake_version, 0);
+        assert!(!addr.host.is_localhost());
+    }
+
+    #[test]
+    fn parses_ip6() {
+        let addr = parse_network_address(&address("/ip6/::1")).unwrap();
+        assert_eq!(addr.host, Host::Ip6(Ipv6Addr::LOCALHOST));
+        assert!(addr.host.is_localhost());
+        assert_eq!(
+            normalize_network_address(&address("/ip6/2001:DB8:0:0::1")).unwrap(),
+            address("/ip6/2001:db8::1")
+        );
+        assert!(parse_network_address(&address("/ip6/2001:db8::g")).is_err());
+        assert!(parse_network_address(&address("/ip6/10.0.0.1")).is_err());
+    }
+
+    #[test]
+    fn parses_dns() {
+        for proto in ["dns", "dns4", "dns6"] {
+            let addr = parse_network_address(&address(&format!("/{}/Node-1.Example.com", proto))).unwrap();
+            assert_eq!(
+                addr.host,
+                Host::Dns {
+                    protocol: proto,
+                    name: "node-1.example.com".to_string()
+                }
+            );
+        }
+        assert!(parse_network_address(&address("/dns/localhost")).unwrap().host.is_localhost());
+        assert!(parse_network_address(&address("/dns/bad_name")).is_err());
+        assert!(parse_network_address(&address("/dnsaddr/example.com")).is_err());
+    }
+
+    #[test]
+    fn normalizes_key_prefix_case_and_trailing_slash() {
+        let messy = format!(
+            "  /ip4/10.0.0.1/tcp/6180/noise-ik/0x{}/handshake/0/ ",
+            KEY.to_uppercase()
+        );
+        assert_eq!(normalize_network_address(&messy).unwrap(), address("/ip4/10.0.0.1"));
+    }
+
+    #[test]
+    fn rejects_trailing_p2p() {
+        let err = parse_network_address(&format!("{}/p2p/12D3KooWabc", address("/ip4/10.0.0.1")))
+            .unwrap_err();
+        assert!(err.contains("got 10 components"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_malformed_components() {
+        for input in [
+            "ip4/10.0.0.1/tcp/6180".to_string(),
+            address("/ip4/10.0.0.256"),
+            address("/ip4/10.0.0.1").replace("/tcp/6180", "/udp/6180"),
+            address("/ip4/10.0.0.1").replace("/tcp/6180", "/tcp/0"),
+            address("/ip4/10.0.0.1").replace("/tcp/6180", "/tcp/65536"),
+            address("/ip4/10.0.0.1").replace("noise-ik", "noise-xx"),
+            address("/ip4/10.0.0.1").replace(KEY, &KEY[2..]),
+            address("/ip4/10.0.0.1").replace(KEY, "zz"),
+            address("/ip4/10.0.0.1").replace("handshake/0", "handshake/256"),
+        ] {
+            assert!(parse_network_address(&input).is_err(), "{}", input);
+        }
+    }
+}
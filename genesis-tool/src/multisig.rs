@@ -0,0 +1,149 @@
+//! Safe-style governance multisig deployed at genesis
+//!
+//! A Safe singleton cannot be set up (its constructor locks it), and genesis
+//! has no proxy factory, so the Safe's runtime code is installed directly: the
+//! first genesis transactions create it from [`MULTISIG_DEPLOYER`] with an
+//! init code that only returns the artifact's `deployedBytecode`, then call
+//! `setup(owners, threshold, ...)` on it, which the uninitialized storage
+//! (threshold zero) allows exactly once. The multisig thus exists before
+//! `preGenesisCalls` run, at `MULTISIG_DEPLOYER.create(0)`, and a config that
+//! sets `governanceMultisig` without `governanceOwner` hands Governance to it.
+
+use alloy_primitives::address;
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolCall;
+use anyhow::{Context, Result};
+use revm_primitives::{hex, Address, Bytes, TxEnv, TxKind, U256};
+use serde_json::Value;
+
+use crate::{
+    abi::{read_artifact, signature},
+    genesis::{GenesisConfig, MultisigConfig},
+};
+
+sol! {
+    interface ISafe {
+        function setup(
+            address[] calldata _owners,
+            uint256 _threshold,
+            address to,
+            bytes calldata data,
+            address fallbackHandler,
+            address paymentToken,
+            uint256 payment,
+            address paymentReceiver
+        ) external;
+        function getOwners() external view returns (address[] memory);
+        function getThreshold() external view returns (uint256);
+    }
+}
+
+/// Sender of the multisig deployment and setup
+pub const MULTISIG_DEPLOYER: Address = address!("000000000000000000000000000000000005AFE0");
+
+/// Address the multisig is deployed at
+pub fn multisig_address() -> Address {
+    MULTISIG_DEPLOYER.create(0)
+}
+
+/// Init code returning `runtime` as the deployed code: `CODECOPY` the bytes
+/// after this 12-byte prefix to memory and `RETURN` them
+fn install_code(runtime: &[u8]) -> Result<Vec<u8>> {
+    let len = u16::try_from(runtime.len()).context("runtime code over 64 KiB")?;
+    let [hi, lo] = len.to_be_bytes();
+    let prefix = [
+        0x61, hi, lo, // PUSH2 len
+        0x80, // DUP1
+        0x60, 0x0c, // PUSH1 12
+        0x60, 0x00, // PUSH1 0
+        0x39, // CODECOPY
+        0x60, 0x00, // PUSH1 0
+        0xf3, // RETURN
+    ];
+    Ok([prefix.as_slice(), runtime].concat())
+}
+
+/// Runtime code of a Safe artifact, checked for the `setup` it is configured with
+fn safe_runtime_code(byte_code_dir: &str, contract: &str) -> Result<Vec<u8>> {
+    let artifact = read_artifact(byte_code_dir, contract)?;
+    let has_setup = artifact
+        .get("abi")
+        .and_then(Value::as_array)
+        .is_some_and(|abi| abi.iter().any(|item| signature(item).as_deref() == Some(ISafe::setupCall::SIGNATURE)));
+    if !has_setup {
+        anyhow::bail!(
+            "{} is not a Safe-style multisig: its ABI has no {}",
+            contract,
+            ISafe::setupCall::SIGNATURE
+        );
+    }
+    let deployed = artifact
+        .get("deployedBytecode")
+        .context(format!("Artifact of {} has no deployedBytecode", contract))?;
+    if deployed
+        .get("immutableReferences")
+        .and_then(Value::as_object)
+        .is_some_and(|refs| !refs.is_empty())
+    {
+        anyhow::bail!("{} has immutables, which only its constructor can set", contract);
+    }
+    let object = deployed
+        .get("object")
+        .and_then(Value::as_str)
+        .context(format!("Artifact of {} has no deployedBytecode", contract))?;
+    if object.contains("__") {
+        anyhow::bail!("{} runtime code has unlinked library placeholders", contract);
+    }
+    hex::decode(object.trim_start_matches("0x"))
+        .context(format!("Invalid {} runtime code", contract))
+}
+
+/// Owners of a multisig config, parsed
+pub fn owner_addresses(multisig: &MultisigConfig) -> Result<Vec<Address>> {
+    multisig
+        .owners
+        .iter()
+        .map(|owner| {
+            owner
+                .parse()
+                .context(format!("governanceMultisig: invalid owner {}", owner))
+        })
+        .collect()
+}
+
+/// Deployment and `setup` of the multisig, to run before any other genesis transaction
+pub fn deployment_txns(byte_code_dir: &str, config: &GenesisConfig) -> Result<Vec<TxEnv>> {
+    let Some(multisig) = &config.governance_multisig else {
+        return Ok(Vec::new());
+    };
+    let runtime = safe_runtime_code(byte_code_dir, &multisig.contract)?;
+    let fallback_handler = match &multisig.fallback_handler {
+        Some(handler) => handler
+            .parse()
+            .context(format!("governanceMultisig: invalid fallbackHandler {}", handler))?,
+        None => Address::ZERO,
+    };
+    let setup = ISafe::setupCall {
+        _owners: owner_addresses(multisig)?,
+        _threshold: U256::from(multisig.threshold),
+        to: Address::ZERO,
+        data: Bytes::new(),
+        fallbackHandler: fallback_handler,
+        paymentToken: Address::ZERO,
+        payment: U256::ZERO,
+        paymentReceiver: Address::ZERO,
+    };
+    let txn = |transact_to: TxKind, data: Vec<u8>| TxEnv {
+        caller: MULTISIG_DEPLOYER,
+        gas_limit: u64::MAX,
+        gas_price: U256::ZERO,
+        transact_to,
+        value: U256::ZERO,
+        data: data.into(),
+        ..Default::default()
+    };
+    Ok(vec![
+        txn(TxKind::Create, install_code(&runtime)?),
+        txn(TxKind::Call(multisig_address()), setup.abi_encode()),
+    ])
+}
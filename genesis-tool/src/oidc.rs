@@ -0,0 +1,179 @@
+//! JWK fetching from OIDC issuers
+//!
+//! For each issuer in `jwkConfig`, reads `/.well-known/openid-configuration`,
+//! follows its `jwks_uri` and converts the published RSA keys into
+//! [`RSA_JWK_Json`]. Issuers without configured keys are filled in; issuers with
+//! configured keys are checked against what the issuer currently serves.
+
+use anyhow::{Context, Result};
+use revm_primitives::hex;
+use serde::Deserialize;
+use std::{collections::HashMap, time::Duration};
+use tracing::{info, warn};
+
+use crate::genesis::{JWKInitParams, RSA_JWK_Json};
+
+/// Algorithm assumed for RSA keys that omit `alg`
+const DEFAULT_RSA_ALG: &str = "RS256";
+
+#[derive(Debug, Deserialize)]
+struct OpenIdConfiguration {
+    jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwkSet {
+    keys: Vec<RawJwk>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawJwk {
+    kty: String,
+    kid: Option<String>,
+    alg: Option<String>,
+    #[serde(rename = "use")]
+    key_use: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+}
+
+fn require_https(url: &str) -> Result<()> {
+    if !url.starts_with("https://") {
+        anyhow::bail!("Refusing to fetch JWKs over non-HTTPS URL {}", url);
+    }
+    Ok(())
+}
+
+/// Fetch the RSA signing keys currently published by `issuer`
+pub fn fetch_issuer_jwks(agent: &ureq::Agent, issuer: &str) -> Result<Vec<RSA_JWK_Json>> {
+    let discovery_url = format!(
+        "{}/.well-known/openid-configuration",
+        issuer.trim_end_matches('/')
+    );
+    require_https(&discovery_url)?;
+    let discovery: OpenIdConfiguration = agent
+        .get(&discovery_url)
+        .call()
+        .context(format!("Failed to fetch {}", discovery_url))?
+        .into_json()
+        .context(format!("Invalid OpenID configuration at {}", discovery_url))?;
+
+    require_https(&discovery.jwks_uri)?;
+    let jwk_set: JwkSet = agent
+        .get(&discovery.jwks_uri)
+        .call()
+        .context(format!("Failed to fetch {}", discovery.jwks_uri))?
+        .into_json()
+        .context(format!("Invalid JWK set at {}", discovery.jwks_uri))?;
+
+    let mut jwks = Vec::new();
+    for key in jwk_set.keys {
+        if key.kty != "RSA" {
+            warn!(
+                "Skipping {} key {:?} from {}: only RSA keys are supported",
+                key.kty, key.kid, issuer
+            );
+            continue;
+        }
+        if key.key_use.as_deref().is_some_and(|u| u != "sig") {
+            continue;
+        }
+        let (Some(kid), Some(n), Some(e)) = (key.kid, key.n, key.e) else {
+            anyhow::bail!("RSA key from {} is missing kid, n or e", discovery.jwks_uri);
+        };
+        jwks.push(RSA_JWK_Json {
+            kid,
+            kty: key.kty,
+            alg: key.alg.unwrap_or_else(|| DEFAULT_RSA_ALG.to_string()),
+            e,
+            n,
+        });
+    }
+    if jwks.is_empty() {
+        anyhow::bail!("{} publishes no RSA signing keys", discovery.jwks_uri);
+    }
+    Ok(jwks)
+}
+
+/// Compare configured keys against the fetched ones, keyed by `kid`
+fn check_configured_jwks(
+    issuer: &str,
+    configured: &[RSA_JWK_Json],
+    fetched: &[RSA_JWK_Json],
+) -> Result<()> {
+    let fetched_by_kid: HashMap<&str, &RSA_JWK_Json> =
+        fetched.iter().map(|jwk| (jwk.kid.as_str(), jwk)).collect();
+
+    let mut mismatches = Vec::new();
+    for jwk in configured {
+        match fetched_by_kid.get(jwk.kid.as_str()) {
+            None => mismatches.push(format!("kid {} is not published by the issuer", jwk.kid)),
+            Some(remote) if remote.n != jwk.n || remote.e != jwk.e => mismatches.push(format!(
+                "kid {}: n/e differ from the published key",
+                jwk.kid
+            )),
+            Some(remote) if remote.alg != jwk.alg => mismatches.push(format!(
+                "kid {}: alg {} but the issuer publishes {}",
+                jwk.kid, jwk.alg, remote.alg
+            )),
+            Some(_) => {}
+        }
+    }
+    if !mismatches.is_empty() {
+        anyhow::bail!(
+            "Configured JWKs for {} do not match the issuer:\n  {}",
+            issuer,
+            mismatches.join("\n  ")
+        );
+    }
+
+    for jwk in fetched {
+        if !configured.iter().any(|c| c.kid == jwk.kid) {
+            warn!(
+                "{} publishes kid {} which is not in the config",
+                issuer, jwk.kid
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Fill empty issuer key lists from the issuers and verify non-empty ones
+pub fn fetch_jwks(jwk_config: &mut JWKInitParams) -> Result<()> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(30))
+        .build();
+    if jwk_config.jwks.len() > jwk_config.issuers.len() {
+        anyhow::bail!(
+            "jwkConfig has {} JWK lists for {} issuers",
+            jwk_config.jwks.len(),
+            jwk_config.issuers.len()
+        );
+    }
+    jwk_config
+        .jwks
+        .resize_with(jwk_config.issuers.len(), Vec::new);
+
+    for (issuer_hex, jwks) in jwk_config.issuers.iter().zip(jwk_config.jwks.iter_mut()) {
+        let issuer_bytes = hex::decode(issuer_hex.strip_prefix("0x").unwrap_or(issuer_hex))
+            .context(format!("jwkConfig issuer {} is not valid hex", issuer_hex))?;
+        let issuer = String::from_utf8(issuer_bytes).context(format!(
+            "jwkConfig issuer {} is not a UTF-8 URL",
+            issuer_hex
+        ))?;
+
+        let fetched = fetch_issuer_jwks(&agent, &issuer)?;
+        if jwks.is_empty() {
+            info!("Fetched {} JWK(s) for {}", fetched.len(), issuer);
+            *jwks = fetched;
+        } else {
+            check_configured_jwks(&issuer, jwks, &fetched)?;
+            info!(
+                "{} configured JWK(s) for {} match the issuer",
+                jwks.len(),
+                issuer
+            );
+        }
+    }
+    Ok(())
+}
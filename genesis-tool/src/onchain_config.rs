@@ -0,0 +1,127 @@
+//! Typed on-chain configs
+//!
+//! `consensusConfig` is stored on chain as the BCS encoding of the node's
+//! `OnChainConsensusConfig`. These types mirror that enum layout (variant order
+//! is part of the encoding) so genesis configs can describe it in JSON via
+//! `consensusConfigStruct` instead of pasting hex.
+//!
+//! `executionConfig` has no typed counterpart here; its bytes are passed through
+//! as given.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OnChainConsensusConfig {
+    V1(ConsensusConfigV1),
+    V2(ConsensusConfigV1),
+    #[serde(rename_all = "camelCase")]
+    V3 {
+        alg: ConsensusAlgorithmConfig,
+        vtxn: ValidatorTxnConfig,
+    },
+    #[serde(rename_all = "camelCase")]
+    V4 {
+        alg: ConsensusAlgorithmConfig,
+        vtxn: ValidatorTxnConfig,
+        window_size: Option<u64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    V5 {
+        alg: ConsensusAlgorithmConfig,
+        vtxn: ValidatorTxnConfig,
+        window_size: Option<u64>,
+        rand_check_enabled: bool,
+    },
+}
+
+impl OnChainConsensusConfig {
+    pub fn to_bcs(&self) -> anyhow::Result<Vec<u8>> {
+        bcs::to_bytes(self)
+            .map_err(|e| anyhow::anyhow!("Failed to BCS encode consensus config: {}", e))
+    }
+
+    /// Decode the way the node reads the config at epoch start; trailing bytes are rejected
+    pub fn from_bcs(bytes: &[u8]) -> anyhow::Result<Self> {
+        bcs::from_bytes(bytes).map_err(|e| anyhow::anyhow!("{}", e))
+    }
+
+    pub fn proposer_election_type(&self) -> &ProposerElectionType {
+        let alg = match self {
+            Self::V1(main) | Self::V2(main) => return &main.proposer_election_type,
+            Self::V3 { alg, .. } | Self::V4 { alg, .. } | Self::V5 { alg, .. } => alg,
+        };
+        match alg {
+            ConsensusAlgorithmConfig::Jolteon { main, .. }
+            | ConsensusAlgorithmConfig::JolteonV2 { main, .. } => &main.proposer_election_type,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConsensusAlgorithmConfig {
+    #[serde(rename_all = "camelCase")]
+    Jolteon {
+        main: ConsensusConfigV1,
+        quorum_store_enabled: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    JolteonV2 {
+        main: ConsensusConfigV1,
+        quorum_store_enabled: bool,
+        order_vote_enabled: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsensusConfigV1 {
+    pub decoupled_execution: bool,
+    pub back_pressure_limit: u64,
+    pub exclude_round: u64,
+    pub proposer_election_type: ProposerElectionType,
+    pub max_failed_authors_to_store: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProposerElectionType {
+    /// Contiguous rounds per proposer
+    FixedProposer(u32),
+    /// Contiguous rounds per proposer
+    RotatingProposer(u32),
+    LeaderReputation(LeaderReputationType),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LeaderReputationType {
+    ProposerAndVoter(ProposerAndVoterConfig),
+    ProposerAndVoterV2(ProposerAndVoterConfig),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposerAndVoterConfig {
+    pub active_weight: u64,
+    pub inactive_weight: u64,
+    pub failed_weight: u64,
+    pub failure_threshold_percent: u32,
+    pub proposer_window_num_validators_multiplier: u64,
+    pub voter_window_num_validators_multiplier: u64,
+    pub weight_by_voting_power: bool,
+    pub use_history_from_previous_epoch_max_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValidatorTxnConfig {
+    /// Validator transactions disabled
+    V0,
+    #[serde(rename_all = "camelCase")]
+    V1 {
+        per_block_limit_txn_count: u64,
+        per_block_limit_total_bytes: u64,
+    },
+}
@@ -1,15 +1,191 @@
+use alloy_sol_macro::sol;
+use alloy_sol_types::{SolCall, SolError};
+use rayon::prelude::*;
 use revm::{DatabaseRef, InMemoryDB, db::BundleState};
-use revm_primitives::{ExecutionResult, SpecId, TxEnv, hex};
-use tracing::{error, info};
+use revm_primitives::{AccountInfo, Address, B256, Bytecode, ExecutionResult, TxEnv, U256, hex};
+use std::collections::HashMap;
+use tracing::{error, info, info_span, warn};
 
 use crate::{
-    execute::prepare_env,
+    broadcast,
+    execute::{config_env, AssembledGenesis},
     genesis::{
-        GenesisConfig, call_get_active_validators, print_active_validators_result,
+        GenesisConfig, IValidatorManagement, call_get_active_validators, convert_validators_to_sol, parse_amount,
+        print_active_validators_result, validator_batches,
+    },
+    stake_pools::StakePoolEntry,
+    supply::SupplyAudit,
+    multisig::{self, ISafe},
+    selectors::dispatcher_selectors,
+    vesting,
+    utils::{
+        CONSENSUS_CONFIG_ADDR, CONTRACTS, DKG_ADDR, EPOCH_CONFIG_ADDR, EXECUTION_CONFIG_ADDR, GENESIS_ADDR, GOVERNANCE_ADDR, GOVERNANCE_CONFIG_ADDR,
+        JWK_MANAGER_ADDR, NATIVE_ORACLE_ADDR, ON_DEMAND_ORACLE_TASK_CONFIG_ADDR, ORACLE_REQUEST_QUEUE_ADDR,
+        PERFORMANCE_TRACKER_ADDR, RANDOMNESS_CONFIG_ADDR, RECONFIGURATION_ADDR, STAKE_CONFIG_ADDR, TIMESTAMP_ADDR,
+        VALIDATOR_CONFIG_ADDR, VALIDATOR_MANAGER_ADDR, VERSION_CONFIG_ADDR, execute_revm_sequential, new_system_call_txn,
     },
-    utils::execute_revm_sequential,
 };
 
+sol! {
+    interface INativeOracleQueries {
+        function getDefaultCallback(uint32 sourceType) external view returns (address callback);
+        function getPendingCallback(uint32 sourceType) external view returns (address callback);
+    }
+}
+
+/// Epoch `Reconfiguration.initialize()` opens at genesis
+const GENESIS_EPOCH: u64 = 1;
+
+sol! {
+    interface IDKGQueries {
+        struct ConfigV2Data {
+            uint128 secrecyThreshold;
+            uint128 reconstructionThreshold;
+            uint128 fastPathSecrecyThreshold;
+        }
+
+        struct RandomnessConfigData {
+            uint8 variant;
+            ConfigV2Data configV2;
+        }
+
+        struct ValidatorConsensusInfo {
+            address validator;
+            bytes consensusPubkey;
+            bytes consensusPop;
+            uint256 votingPower;
+            uint64 validatorIndex;
+            bytes networkAddresses;
+            bytes fullnodeAddresses;
+        }
+
+        struct DKGSessionMetadata {
+            uint64 dealerEpoch;
+            RandomnessConfigData randomnessConfig;
+            ValidatorConsensusInfo[] dealerValidatorSet;
+            ValidatorConsensusInfo[] targetValidatorSet;
+        }
+
+        struct DKGSessionInfo {
+            DKGSessionMetadata metadata;
+            uint64 startTimeUs;
+            bytes transcript;
+        }
+
+        function isInProgress() external view returns (bool);
+        function getLastCompletedSession() external view returns (bool hasSession, DKGSessionInfo memory info);
+    }
+
+    interface IReconfigurationQueries {
+        function currentEpoch() external view returns (uint64);
+        function lastReconfigurationTime() external view returns (uint64);
+        function isTransitionInProgress() external view returns (bool);
+    }
+
+    interface ITimestampQueries {
+        function nowMicroseconds() external view returns (uint64);
+        function nowSeconds() external view returns (uint64);
+    }
+}
+
+sol! {
+    interface IReconfigurationGated {
+        error NotAllowed(address caller, address allowed);
+
+        function isInitialized() external view returns (bool);
+        function getTransitionState() external view returns (uint8);
+        function applyPendingConfig() external;
+        function tryClearIncompleteSession() external;
+        function evictUnderperformingValidators() external;
+        function onNewEpoch(uint256 activeValidatorCount) external;
+    }
+}
+
+sol! {
+    interface IBridgeReceiverQueries {
+        function trustedBridge() external view returns (address);
+        function trustedSourceId() external view returns (uint256);
+    }
+}
+
+sol! {
+    interface IOnDemandOracleTaskConfigQueries {
+        struct OnDemandTaskType {
+            bytes config;
+            uint64 updatedAt;
+        }
+
+        function getTaskType(uint32 sourceType, uint256 sourceId) external view returns (OnDemandTaskType memory taskType);
+    }
+}
+
+sol! {
+    interface IOracleRequestQueueQueries {
+        struct OracleRequest {
+            uint32 sourceType;
+            uint256 sourceId;
+            address requester;
+            bytes requestData;
+            uint256 fee;
+            uint64 requestedAt;
+            uint64 expiresAt;
+            bool fulfilled;
+            bool refunded;
+        }
+
+        function getRequest(uint256 requestId) external view returns (OracleRequest memory);
+        function getFee(uint32 sourceType) external view returns (uint256);
+        function getExpiration(uint32 sourceType) external view returns (uint64);
+        function treasury() external view returns (address);
+        function taskConfig() external view returns (address);
+        function nextRequestId() external view returns (uint256);
+    }
+}
+
+sol! {
+    interface IFundedAccountHolder {
+        function treasury() external view returns (address);
+        function feeCollector() external view returns (address);
+    }
+}
+
+sol! {
+    interface IGovernanceOwnerQueries {
+        function owner() external view returns (address);
+        function pendingOwner() external view returns (address);
+    }
+}
+
+sol! {
+    interface IVestingWalletQueries {
+        function start() external view returns (uint256);
+        function duration() external view returns (uint256);
+        function beneficiary() external view returns (address);
+        function owner() external view returns (address);
+        function released() external view returns (uint256);
+    }
+}
+
+sol! {
+    interface IJWKManagerQueries {
+        struct RSA_JWK {
+            string kid;
+            string kty;
+            string alg;
+            string e;
+            string n;
+        }
+
+        struct ProviderJWKs {
+            bytes issuer;
+            uint64 version;
+            RSA_JWK[] jwks;
+        }
+
+        function getProviderJWKs(bytes calldata issuer) external view returns (ProviderJWKs memory providerJwks);
+    }
+}
+
 /// Generic template for handling execution results
 ///
 /// This function provides a common structure for all print_* functions,
@@ -48,20 +224,57 @@ where
     }
 }
 
+/// Post-genesis state as the checks read it: the executed bundle over the
+/// deployment database. It borrows both, so every view call shares one copy
+/// of the state and checks can run on several threads at once.
+#[derive(Clone, Copy)]
+pub struct GenesisState<'a> {
+    pub db: &'a InMemoryDB,
+    pub bundle_state: &'a BundleState,
+}
+
+impl DatabaseRef for GenesisState<'_> {
+    type Error = <InMemoryDB as DatabaseRef>::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        match self.bundle_state.account(&address) {
+            Some(account) => Ok(account.account_info()),
+            None => self.db.basic_ref(address),
+        }
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.bundle_state.contracts.get(&code_hash) {
+            Some(code) => Ok(code.clone()),
+            None => self.db.code_by_hash_ref(code_hash),
+        }
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        match self.bundle_state.account(&address).and_then(|account| account.storage_slot(index)) {
+            Some(value) => Ok(value),
+            None => self.db.storage_ref(address, index),
+        }
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.db.block_hash_ref(number)
+    }
+}
+
 /// Generic template for verification functions
 fn execute_verification<F>(
-    db: impl DatabaseRef,
-    bundle_state: BundleState,
+    state: GenesisState,
     transaction: TxEnv,
     verification_name: &str,
-    chain_id: u64,
+    config: &GenesisConfig,
     result_handler: F,
 ) -> Result<(), String>
 where
     F: FnOnce(&ExecutionResult) -> Result<(), String>,
 {
-    let env = prepare_env(chain_id);
-    let r = execute_revm_sequential(db, SpecId::LATEST, env, &[transaction], Some(bundle_state));
+    let env = config_env(config);
+    let r = execute_revm_sequential(state, config.spec_id(), env, &[transaction], None);
     
     match r {
         Ok((result, _)) => {
@@ -78,14 +291,13 @@ where
     }
 }
 
-fn verify_active_validators(db: impl DatabaseRef, bundle_state: BundleState, config: &GenesisConfig) -> Result<(), String> {
+fn verify_active_validators(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
     let get_validators_txn = call_get_active_validators();
     execute_verification(
-        db,
-        bundle_state,
+        GenesisState { db, bundle_state },
         get_validators_txn,
         "active validators",
-        config.chain_id,
+        config,
         |result| {
             print_active_validators_result(result, config);
             Ok(())
@@ -93,16 +305,934 @@ fn verify_active_validators(db: impl DatabaseRef, bundle_state: BundleState, con
     )
 }
 
-pub fn verify_result(
-    db: InMemoryDB,
-    bundle_state: BundleState,
+/// Active validator set against the config: every validator in config order
+/// with its index, key and voting power, and the total voting power their sum.
+/// When `validatorBatchSize` split registration this confirms the batches
+/// joined up into the set a single `initialize` produces.
+fn verify_validator_set(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    let call = IValidatorManagement::getActiveValidatorsCall {};
+    let active = view(db, bundle_state, config, VALIDATOR_MANAGER_ADDR, call)?._0;
+    if active.len() != config.validators.len() {
+        return Err(format!(
+            "{} active validators, config has {}",
+            active.len(),
+            config.validators.len()
+        ));
+    }
+
+    let batch_size = validator_batches(config)[0].len().max(1);
+    let expected = convert_validators_to_sol(&config.validators).map_err(|e| format!("{:#}", e))?;
+    let mut total = U256::ZERO;
+    for (i, (validator, info)) in expected.iter().zip(&active).enumerate() {
+        let at = format!("validator {} ({}, batch {})", i, validator.moniker, i / batch_size + 1);
+        if info.validatorIndex != i as u64 {
+            return Err(format!("{} has index {}", at, info.validatorIndex));
+        }
+        if info.consensusPubkey != validator.consensusPubkey {
+            return Err(format!(
+                "{} has consensus key 0x{}, expected 0x{}",
+                at,
+                hex::encode(&info.consensusPubkey),
+                hex::encode(&validator.consensusPubkey)
+            ));
+        }
+        if info.votingPower != validator.votingPower {
+            return Err(format!(
+                "{} has voting power {}, expected {}",
+                at, info.votingPower, validator.votingPower
+            ));
+        }
+        total += validator.votingPower;
+    }
+
+    let call = IValidatorManagement::getTotalVotingPowerCall {};
+    let total_voting_power = view(db, bundle_state, config, VALIDATOR_MANAGER_ADDR, call)?._0;
+    if total_voting_power != total {
+        return Err(format!(
+            "total voting power is {}, the validators sum to {}",
+            total_voting_power, total
+        ));
+    }
+    info!(
+        "✓ Validator set matches the config: {} validator(s) across {} batch(es), total voting power {}",
+        active.len(),
+        validator_batches(config).len(),
+        total
+    );
+    Ok(())
+}
+
+/// A post-genesis check: tracing step name, description, and the check
+type Check = (&'static str, &'static str, fn(&InMemoryDB, &BundleState, &GenesisConfig) -> Result<(), String>);
+
+/// Checks only read the genesis state, so they are independent of each other.
+/// These read the alloc as written, forked, imported and `extraAlloc` accounts
+/// included.
+const STATE_CHECKS: [Check; 13] = [
+    ("active_validators", "active validators check", verify_active_validators),
+    ("validator_set", "validator set consistency check", verify_validator_set),
+    ("jwks", "JWK check", verify_jwks),
+    ("timestamp", "Timestamp check", verify_timestamp),
+    ("reconfiguration", "Reconfiguration check", verify_reconfiguration),
+    ("dkg", "DKG check", verify_dkg),
+    ("oracle_callbacks", "oracle callback check", verify_oracle_callbacks),
+    ("bridge", "bridge check", verify_bridge),
+    ("on_demand_tasks", "on-demand task check", verify_on_demand_tasks),
+    ("request_queue", "OracleRequestQueue check", verify_request_queue),
+    ("governance_multisig", "governance multisig check", verify_governance_multisig),
+    ("treasury", "treasury/fee collector check", verify_funded_account_holders),
+    ("vesting", "vesting check", verify_vesting),
+];
+
+/// Checks of what execution itself did, on the executed state alone
+const EXECUTION_CHECKS: [Check; 3] = [
+    ("broadcast", "broadcast replay check", |_, bundle_state, config| verify_broadcast(bundle_state, config)),
+    ("genesis_balance", "Genesis balance check", |db, bundle_state, _| verify_genesis_balance(db, bundle_state)),
+    ("supply", "supply audit", verify_supply),
+];
+
+/// Run every post-genesis check in parallel against the shared state and
+/// panic listing all that failed, in check order
+pub fn verify_result(genesis: &AssembledGenesis, config: &GenesisConfig) {
+    let checks: Vec<(&Check, &BundleState)> = STATE_CHECKS
+        .iter()
+        .map(|check| (check, &genesis.alloc_state))
+        .chain(EXECUTION_CHECKS.iter().map(|check| (check, &genesis.bundle_state)))
+        .collect();
+    let failures: Vec<String> = checks
+        .par_iter()
+        .filter_map(|((step, description, check), bundle_state)| {
+            info_span!("verification", step = *step)
+                .in_scope(|| check(&genesis.db, bundle_state, config))
+                .err()
+                .map(|e| format!("{} FAILED: {}", description, e))
+        })
+        .collect();
+    if failures.is_empty() {
+        info!("✅ All {} post-genesis checks passed", checks.len());
+        return;
+    }
+    for failure in &failures {
+        error!("Genesis verification: {}", failure);
+    }
+    panic!("Genesis verification: {}", failures.join("; "));
+}
+
+/// Stake pools of the initial validators, from the active validator set
+pub fn genesis_stake_pools(
+    db: &InMemoryDB,
+    bundle_state: &BundleState,
     config: &GenesisConfig,
-) {
-    verify_active_validators(db.clone(), bundle_state.clone(), config)
-        .expect("Genesis verification: active validators check FAILED");
-    // Add more verification steps as needed:
-    // - verify_jwks()
-    // - verify_epoch_config()
-    // - verify_randomness_config()
-    // etc.
+) -> Result<Vec<StakePoolEntry>, String> {
+    let call = IValidatorManagement::getActiveValidatorsCall {};
+    let active = view(db, bundle_state, config, VALIDATOR_MANAGER_ADDR, call)?._0;
+    config
+        .validators
+        .iter()
+        .zip(&active)
+        .map(|(v, info)| {
+            let owner = v
+                .owner
+                .parse::<Address>()
+                .map_err(|e| format!("validator {}: invalid owner {}: {}", v.moniker, v.owner, e))?;
+            Ok(StakePoolEntry { moniker: v.moniker.clone(), owner, pool: info.validator })
+        })
+        .collect()
+}
+
+/// Run a view call against the genesis state and decode its return value
+fn view<C: SolCall>(
+    db: &InMemoryDB,
+    bundle_state: &BundleState,
+    config: &GenesisConfig,
+    to: Address,
+    call: C,
+) -> Result<C::Return, String> {
+    let txn = new_system_call_txn(to, call.abi_encode().into());
+    let mut decoded = Err(format!("{} returned nothing", C::SIGNATURE));
+    execute_verification(
+        GenesisState { db, bundle_state },
+        txn,
+        C::SIGNATURE,
+        config,
+        |result| {
+            handle_execution_result(result, C::SIGNATURE, |output_bytes| {
+                decoded = C::abi_decode_returns(output_bytes, false)
+                    .map_err(|e| format!("Failed to decode {} result: {}", C::SIGNATURE, e));
+            })
+        },
+    )?;
+    decoded
+}
+
+/// Check the active callback of every configured source type, and the staged
+/// entries when NativeOracle supports staging. A staged callback must not be
+/// active yet: the active mapping keeps its configured value (or none) until
+/// the first epoch boundary.
+fn verify_oracle_callbacks(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    let oracle = &config.oracle_config;
+    let parse = |address: &str| {
+        address
+            .parse::<Address>()
+            .map_err(|e| format!("Invalid callback address {}: {}", address, e))
+    };
+    let active_of = |source_type: u32| -> Result<Address, String> {
+        let call = INativeOracleQueries::getDefaultCallbackCall { sourceType: source_type };
+        Ok(view(db, bundle_state, config, NATIVE_ORACLE_ADDR, call)?.callback)
+    };
+
+    for (source_type, callback) in oracle.source_types.iter().zip(&oracle.callbacks) {
+        let expected = parse(callback)?;
+        let actual = active_of(*source_type)?;
+        if actual != expected {
+            return Err(format!(
+                "sourceType {}: active callback {:?}, configured {:?}",
+                source_type, actual, expected
+            ));
+        }
+    }
+    info!("✅ {} active oracle callback(s) match config", oracle.callbacks.len());
+
+    let staging = dispatcher_selectors(&deployed_code(db, NATIVE_ORACLE_ADDR))
+        .contains(&INativeOracleQueries::getPendingCallbackCall::SELECTOR);
+    if !staging {
+        if !oracle.pending_callbacks.is_empty() {
+            return Err(format!(
+                "{} pending callback(s) configured but NativeOracle has no getPendingCallback",
+                oracle.pending_callbacks.len()
+            ));
+        }
+        return Ok(());
+    }
+
+    for pending in &oracle.pending_callbacks {
+        let expected = parse(&pending.callback)?;
+        let call = INativeOracleQueries::getPendingCallbackCall { sourceType: pending.source_type };
+        let staged = view(db, bundle_state, config, NATIVE_ORACLE_ADDR, call)?.callback;
+        if staged != expected {
+            return Err(format!(
+                "sourceType {}: staged callback {:?}, configured {:?}",
+                pending.source_type, staged, expected
+            ));
+        }
+        // Source types with an active callback were checked above
+        if !oracle.source_types.contains(&pending.source_type) {
+            let active = active_of(pending.source_type)?;
+            if active != Address::ZERO {
+                return Err(format!(
+                    "sourceType {}: staged callback already active as {:?}",
+                    pending.source_type, active
+                ));
+            }
+        }
+    }
+    info!(
+        "✅ {} staged oracle callback(s) match config and are not active yet",
+        oracle.pending_callbacks.len()
+    );
+    Ok(())
+}
+
+/// Check the on-chain clock the first block will advance. `Timestamp` only moves
+/// forward, so a genesis clock at or past the first block's time makes every
+/// block prologue revert with `TimestampMustAdvance`.
+fn verify_timestamp(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    let micros = view(db, bundle_state, config, TIMESTAMP_ADDR, ITimestampQueries::nowMicrosecondsCall {})?._0;
+    let secs = view(db, bundle_state, config, TIMESTAMP_ADDR, ITimestampQueries::nowSecondsCall {})?._0;
+    if secs != micros / 1_000_000 {
+        return Err(format!("Timestamp reads {}us but {}s", micros, secs));
+    }
+    if let Some(genesis_secs) = config.genesis_timestamp_secs {
+        let genesis_micros = genesis_secs.saturating_mul(1_000_000);
+        if micros >= genesis_micros && micros > 0 {
+            return Err(format!(
+                "Timestamp reads {}us, not before the genesis timestamp {}us; the first block cannot advance it",
+                micros, genesis_micros
+            ));
+        }
+    }
+    if micros == 0 {
+        info!("✅ Timestamp unset; the first block prologue starts the clock");
+    } else {
+        info!("✅ Timestamp at {}us", micros);
+    }
+    Ok(())
+}
+
+/// Run a call against the genesis state as the system caller and return its raw result
+fn probe(
+    db: &InMemoryDB,
+    bundle_state: &BundleState,
+    config: &GenesisConfig,
+    to: Address,
+    data: Vec<u8>,
+    name: &str,
+) -> Result<ExecutionResult, String> {
+    let txn = new_system_call_txn(to, data.into());
+    let mut outcome = None;
+    execute_verification(GenesisState { db, bundle_state }, txn, name, config, |result| {
+        outcome = Some(result.clone());
+        Ok(())
+    })?;
+    outcome.ok_or_else(|| format!("{} returned nothing", name))
+}
+
+/// Functions that only Reconfiguration may call during an epoch transition
+fn reconfiguration_gated_calls() -> Vec<(&'static str, Address, Vec<u8>)> {
+    let apply = IReconfigurationGated::applyPendingConfigCall {}.abi_encode();
+    vec![
+        ("StakingConfig.applyPendingConfig", STAKE_CONFIG_ADDR, apply.clone()),
+        ("ValidatorConfig.applyPendingConfig", VALIDATOR_CONFIG_ADDR, apply.clone()),
+        ("RandomnessConfig.applyPendingConfig", RANDOMNESS_CONFIG_ADDR, apply.clone()),
+        ("GovernanceConfig.applyPendingConfig", GOVERNANCE_CONFIG_ADDR, apply.clone()),
+        ("EpochConfig.applyPendingConfig", EPOCH_CONFIG_ADDR, apply.clone()),
+        ("VersionConfig.applyPendingConfig", VERSION_CONFIG_ADDR, apply.clone()),
+        ("ConsensusConfig.applyPendingConfig", CONSENSUS_CONFIG_ADDR, apply.clone()),
+        ("ExecutionConfig.applyPendingConfig", EXECUTION_CONFIG_ADDR, apply),
+        (
+            "DKG.tryClearIncompleteSession",
+            DKG_ADDR,
+            IReconfigurationGated::tryClearIncompleteSessionCall {}.abi_encode(),
+        ),
+        (
+            "ValidatorManagement.evictUnderperformingValidators",
+            VALIDATOR_MANAGER_ADDR,
+            IReconfigurationGated::evictUnderperformingValidatorsCall {}.abi_encode(),
+        ),
+        (
+            "ValidatorPerformanceTracker.onNewEpoch",
+            PERFORMANCE_TRACKER_ADDR,
+            IReconfigurationGated::onNewEpochCall { activeValidatorCount: U256::ZERO }.abi_encode(),
+        ),
+    ]
+}
+
+/// Check Reconfiguration was initialized into the genesis epoch with its epoch
+/// clock consistent with the genesis timestamp, and that the contracts it
+/// drives at an epoch boundary only accept it as caller.
+///
+/// `Reconfiguration.initialize()` announces the genesis epoch as epoch 0 in
+/// `EpochTransitioned(0, ..)` and stores the number of the epoch it opens,
+/// [`GENESIS_EPOCH`]. The authorization probes call each gated function as the
+/// system caller: the `NotAllowed` revert names the address the deployed code
+/// expects, so code built against a different `SystemAddresses` shows up here
+/// instead of as a chain that can never leave its first epoch.
+fn verify_reconfiguration(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    if !view(db, bundle_state, config, RECONFIGURATION_ADDR, IReconfigurationGated::isInitializedCall {})?._0 {
+        return Err("Reconfiguration is not initialized".to_string());
+    }
+    let epoch = view(db, bundle_state, config, RECONFIGURATION_ADDR, IReconfigurationQueries::currentEpochCall {})?._0;
+    if epoch != GENESIS_EPOCH {
+        return Err(format!("Reconfiguration is at epoch {}, expected {}", epoch, GENESIS_EPOCH));
+    }
+    let state = view(db, bundle_state, config, RECONFIGURATION_ADDR, IReconfigurationGated::getTransitionStateCall {})?._0;
+    if state != 0 {
+        return Err(format!("Reconfiguration transition state is {}, expected Idle", state));
+    }
+
+    let last_reconfiguration =
+        view(db, bundle_state, config, RECONFIGURATION_ADDR, IReconfigurationQueries::lastReconfigurationTimeCall {})?._0;
+    let now = view(db, bundle_state, config, TIMESTAMP_ADDR, ITimestampQueries::nowMicrosecondsCall {})?._0;
+    if last_reconfiguration != now {
+        return Err(format!(
+            "epoch {} started at {}us but the genesis clock reads {}us",
+            epoch, last_reconfiguration, now
+        ));
+    }
+    if let Some(secs) = config.genesis_timestamp_secs {
+        let genesis_micros = secs.saturating_mul(1_000_000);
+        if last_reconfiguration > genesis_micros {
+            return Err(format!(
+                "epoch clock starts at {}us, after the genesis timestamp {}us",
+                last_reconfiguration, genesis_micros
+            ));
+        }
+        if last_reconfiguration == 0 {
+            info!("Epoch clock is unset until the first block; the genesis epoch ends with it");
+        } else if genesis_micros - last_reconfiguration >= config.epoch_interval_micros {
+            warn!(
+                "Epoch clock starts at {}us, {}us before the genesis timestamp; the first block will end the genesis epoch",
+                last_reconfiguration,
+                genesis_micros - last_reconfiguration
+            );
+        }
+    }
+
+    for (name, address, data) in reconfiguration_gated_calls() {
+        let result = probe(db, bundle_state, config, address, data, name)?;
+        let allowed = match &result {
+            ExecutionResult::Revert { output, .. } => IReconfigurationGated::NotAllowed::abi_decode(output, true)
+                .map(|error| error.allowed)
+                .map_err(|_| format!("{} reverted without NotAllowed: 0x{}", name, hex::encode(output)))?,
+            ExecutionResult::Success { .. } => {
+                return Err(format!("{} accepted a call from the system caller", name));
+            }
+            ExecutionResult::Halt { reason, .. } => return Err(format!("{} halted: {:?}", name, reason)),
+        };
+        if allowed != RECONFIGURATION_ADDR {
+            return Err(format!("{} only accepts {:?}, not Reconfiguration {:?}", name, allowed, RECONFIGURATION_ADDR));
+        }
+    }
+    info!(
+        "✅ Reconfiguration at epoch {} since {}us; {} epoch-boundary functions gated to it",
+        epoch,
+        last_reconfiguration,
+        reconfiguration_gated_calls().len()
+    );
+    Ok(())
+}
+
+/// Check DKG starts without a session and agrees with Reconfiguration that no
+/// transition is in progress. A completed session can only come from state
+/// carried over from another chain, and must then belong to an earlier epoch.
+fn verify_dkg(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    if deployed_code(db, DKG_ADDR).is_empty() {
+        return Err(format!("DKG {:?} has no code", DKG_ADDR));
+    }
+    if view(db, bundle_state, config, DKG_ADDR, IDKGQueries::isInProgressCall {})?._0 {
+        return Err("a DKG session is in progress at genesis".to_string());
+    }
+    if view(db, bundle_state, config, RECONFIGURATION_ADDR, IReconfigurationQueries::isTransitionInProgressCall {})?._0 {
+        return Err("Reconfiguration has an epoch transition in progress at genesis".to_string());
+    }
+
+    let epoch = view(db, bundle_state, config, RECONFIGURATION_ADDR, IReconfigurationQueries::currentEpochCall {})?._0;
+    let completed = view(db, bundle_state, config, DKG_ADDR, IDKGQueries::getLastCompletedSessionCall {})?;
+    if completed.hasSession {
+        let dealer_epoch = completed.info.metadata.dealerEpoch;
+        if dealer_epoch >= epoch {
+            return Err(format!(
+                "completed DKG session of dealer epoch {} is not before epoch {}",
+                dealer_epoch, epoch
+            ));
+        }
+        warn!("DKG has a completed session of dealer epoch {} at genesis", dealer_epoch);
+    }
+    info!("✅ DKG idle at epoch {}", epoch);
+    Ok(())
+}
+
+/// Check the bridge receiver Genesis deployed has code, is the blockchain
+/// events callback, and trusts the configured bridge and source chain
+fn verify_bridge(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    let bridge = &config.oracle_config.bridge_config;
+    if !bridge.deploy {
+        return Ok(());
+    }
+    let call = INativeOracleQueries::getDefaultCallbackCall { sourceType: 0 };
+    let receiver = view(db, bundle_state, config, NATIVE_ORACLE_ADDR, call)?.callback;
+    if receiver == Address::ZERO {
+        return Err("bridgeConfig.deploy is set but no callback is registered for sourceType 0".to_string());
+    }
+    let has_code = bundle_state
+        .state
+        .get(&receiver)
+        .and_then(|account| account.info.as_ref())
+        .and_then(|info| info.code.as_ref())
+        .is_some_and(|code| !code.is_empty());
+    if !has_code {
+        return Err(format!("bridge receiver {:?} has no code", receiver));
+    }
+
+    let expected_bridge = match bridge.trusted_bridge.as_str() {
+        "" => Address::ZERO,
+        address => address
+            .parse()
+            .map_err(|e| format!("Invalid trustedBridge {}: {}", address, e))?,
+    };
+    let expected_source_id = match bridge.trusted_source_id.as_str() {
+        "" => U256::ZERO,
+        id => parse_amount(id).map_err(|e| format!("Invalid trustedSourceId {}: {}", id, e))?,
+    };
+    let trusted_bridge = view(db, bundle_state, config, receiver, IBridgeReceiverQueries::trustedBridgeCall {})?._0;
+    if trusted_bridge != expected_bridge {
+        return Err(format!(
+            "bridge receiver {:?} trusts bridge {:?}, configured {:?}",
+            receiver, trusted_bridge, expected_bridge
+        ));
+    }
+    let trusted_source_id = view(db, bundle_state, config, receiver, IBridgeReceiverQueries::trustedSourceIdCall {})?._0;
+    if trusted_source_id != expected_source_id {
+        return Err(format!(
+            "bridge receiver {:?} trusts source {}, configured {}",
+            receiver, trusted_source_id, expected_source_id
+        ));
+    }
+    info!(
+        "✅ Bridge receiver {:?} deployed, trusting {:?} on source {}",
+        receiver, trusted_bridge, trusted_source_id
+    );
+    Ok(())
+}
+
+/// Check every configured on-demand task type is registered with its config;
+/// for a repeated key the last entry wins
+fn verify_on_demand_tasks(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    let mut expected = HashMap::new();
+    for task in &config.oracle_config.on_demand_tasks {
+        expected.insert((task.source_type, task.source_id), task.config.as_bytes());
+    }
+    for ((source_type, source_id), expected_config) in &expected {
+        let call = IOnDemandOracleTaskConfigQueries::getTaskTypeCall {
+            sourceType: *source_type,
+            sourceId: U256::from(*source_id),
+        };
+        let txn = new_system_call_txn(ON_DEMAND_ORACLE_TASK_CONFIG_ADDR, call.abi_encode().into());
+
+        execute_verification(
+            GenesisState { db, bundle_state },
+            txn,
+            "on-demand tasks",
+            config,
+            |result| {
+                let mut outcome = Ok(());
+                handle_execution_result(result, "getTaskType", |output_bytes| {
+                    outcome = IOnDemandOracleTaskConfigQueries::getTaskTypeCall::abi_decode_returns(output_bytes, false)
+                        .map_err(|e| format!("Failed to decode getTaskType result: {}", e))
+                        .and_then(|decoded| {
+                            if decoded.taskType.config.as_ref() != *expected_config {
+                                return Err(format!(
+                                    "sourceType {} sourceId {}: registered config 0x{}, configured 0x{}",
+                                    source_type,
+                                    source_id,
+                                    hex::encode(&decoded.taskType.config),
+                                    hex::encode(expected_config)
+                                ));
+                            }
+                            Ok(())
+                        });
+                })?;
+                outcome
+            },
+        )?;
+    }
+    if !expected.is_empty() {
+        info!("✅ {} on-demand task type(s) registered as configured", expected.len());
+    }
+    Ok(())
+}
+
+/// Check a deployed OracleRequestQueue starts empty, points at
+/// OnDemandOracleTaskConfig, pays a treasury, and has the configured fees and
+/// expirations. Its constructor does not run for alloc-deployed code, so a
+/// zero `nextRequestId` means that state was never set.
+fn verify_request_queue(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    use IOracleRequestQueueQueries as Q;
+
+    let expected = config.oracle_config.request_queue.as_ref();
+    if deployed_code(db, ORACLE_REQUEST_QUEUE_ADDR).is_empty() {
+        if expected.is_some() {
+            return Err(format!(
+                "oracleConfig.requestQueue is set but no OracleRequestQueue is deployed at {:?}",
+                ORACLE_REQUEST_QUEUE_ADDR
+            ));
+        }
+        info!("OracleRequestQueue not deployed; skipped");
+        return Ok(());
+    }
+    let queue = ORACLE_REQUEST_QUEUE_ADDR;
+    let mut problems = Vec::new();
+
+    let next_request_id = view(db, bundle_state, config, queue, Q::nextRequestIdCall {})?._0;
+    if next_request_id == U256::ZERO {
+        problems.push(
+            "nextRequestId is 0: the constructor state (nextRequestId, taskConfig, treasury) was never set".to_string(),
+        );
+    } else if next_request_id != U256::from(1) {
+        problems.push(format!("queue is not empty: nextRequestId is {}", next_request_id));
+    } else {
+        let first_request = Q::getRequestCall { requestId: U256::from(1) };
+        let first = view(db, bundle_state, config, queue, first_request)?._0;
+        if first.requester != Address::ZERO {
+            problems.push(format!("request 1 already exists (requester {:?})", first.requester));
+        }
+    }
+
+    let task_config = view(db, bundle_state, config, queue, Q::taskConfigCall {})?._0;
+    if task_config != ON_DEMAND_ORACLE_TASK_CONFIG_ADDR {
+        problems.push(format!(
+            "taskConfig is {:?}, expected OnDemandOracleTaskConfig {:?}",
+            task_config, ON_DEMAND_ORACLE_TASK_CONFIG_ADDR
+        ));
+    }
+    let treasury = view(db, bundle_state, config, queue, Q::treasuryCall {})?._0;
+    let configured = expected
+        .and_then(|e| e.treasury.as_deref())
+        .or(config.treasury.as_ref().map(|t| t.address.as_str()));
+    match configured {
+        Some(want) => {
+            let want: Address = want
+                .parse()
+                .map_err(|e| format!("Invalid requestQueue.treasury {}: {}", want, e))?;
+            if treasury != want {
+                problems.push(format!("treasury is {:?}, configured {:?}", treasury, want));
+            }
+        }
+        None if treasury == Address::ZERO => {
+            problems.push("treasury is unset; fees would be sent to address(0)".to_string())
+        }
+        None => {}
+    }
+
+    if let Some(expected) = expected {
+        for (source_type, fee) in &expected.fees {
+            let want = parse_amount(fee)
+                .map_err(|e| format!("requestQueue.fees.{}: {}", source_type, e))?;
+            let fee_call = Q::getFeeCall { sourceType: *source_type };
+            let actual = view(db, bundle_state, config, queue, fee_call)?._0;
+            if actual != want {
+                problems.push(format!(
+                    "sourceType {}: fee {} wei, configured {} wei",
+                    source_type, actual, want
+                ));
+            }
+        }
+    }
+    for task in &config.oracle_config.on_demand_tasks {
+        let expiration = Q::getExpirationCall { sourceType: task.source_type };
+        let actual = view(db, bundle_state, config, queue, expiration)?._0;
+        match expected.and_then(|e| e.expirations.get(&task.source_type)) {
+            Some(want) if actual != *want => problems.push(format!(
+                "sourceType {}: expiration {}s, configured {}s",
+                task.source_type, actual, want
+            )),
+            None if actual == 0 => warn!(
+                "OracleRequestQueue has no expiration for on-demand sourceType {}; requests revert until governance sets one",
+                task.source_type
+            ),
+            _ => {}
+        }
+    }
+
+    if problems.is_empty() {
+        info!("✅ OracleRequestQueue is empty and configured as expected");
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+/// Runtime code deployed at `address` before execution
+fn deployed_code(db: &InMemoryDB, address: Address) -> Vec<u8> {
+    db.basic_ref(address)
+        .ok()
+        .flatten()
+        .and_then(|info| info.code)
+        .map(|code| code.original_bytes().to_vec())
+        .unwrap_or_default()
+}
+
+/// Result of a view the contract may not implement; `None` when it reverts
+fn optional_view<C: SolCall>(
+    db: &InMemoryDB,
+    bundle_state: &BundleState,
+    config: &GenesisConfig,
+    to: Address,
+    call: C,
+) -> Result<Option<C::Return>, String> {
+    let result = probe(db, bundle_state, config, to, call.abi_encode(), C::SIGNATURE)?;
+    Ok(match result {
+        ExecutionResult::Success { output, .. } => C::abi_decode_returns(output.data(), false).ok(),
+        _ => None,
+    })
+}
+
+/// Check every system contract with a `treasury()` or `feeCollector()` getter
+/// was initialized with the configured account
+fn verify_funded_account_holders(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    let mut problems = Vec::new();
+    for (key, account) in config.funded_accounts() {
+        let want: Address = account
+            .address
+            .parse()
+            .map_err(|e| format!("Invalid {} {}: {}", key, account.address, e))?;
+        let mut holders = Vec::new();
+        for (name, address) in CONTRACTS {
+            if deployed_code(db, address).is_empty() {
+                continue;
+            }
+            let held = match key {
+                "treasury" => optional_view(db, bundle_state, config, address, IFundedAccountHolder::treasuryCall {})?
+                    .map(|r| r._0),
+                _ => optional_view(db, bundle_state, config, address, IFundedAccountHolder::feeCollectorCall {})?
+                    .map(|r| r._0),
+            };
+            match held {
+                Some(held) if held != want => {
+                    problems.push(format!("{} holds {} {:?}, configured {:?}", name, key, held, want))
+                }
+                Some(_) => holders.push(name),
+                None => {}
+            }
+        }
+        if holders.is_empty() {
+            info!("No system contract exposes {}(); {:?} is only funded", key, want);
+        } else {
+            info!("✅ {} {:?} set in {}", key, want, holders.join(", "));
+        }
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+/// Check the multisig holds the configured owners and threshold, and that
+/// Governance is owned by the configured owner with no transfer pending
+fn verify_governance_multisig(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    let Some(multisig) = &config.governance_multisig else {
+        return Ok(());
+    };
+    let address = multisig::multisig_address();
+    let has_code = bundle_state
+        .state
+        .get(&address)
+        .and_then(|a| a.info.as_ref())
+        .and_then(|i| i.code.as_ref())
+        .is_some_and(|code| !code.is_empty());
+    if !has_code {
+        return Err(format!("no {} deployed at {:?}", multisig.contract, address));
+    }
+    let mut problems = Vec::new();
+
+    let mut expected = multisig::owner_addresses(multisig).map_err(|e| format!("{:#}", e))?;
+    let mut owners = view(db, bundle_state, config, address, ISafe::getOwnersCall {})?._0;
+    expected.sort();
+    owners.sort();
+    if owners != expected {
+        problems.push(format!("owners are {:?}, configured {:?}", owners, expected));
+    }
+    let threshold = view(db, bundle_state, config, address, ISafe::getThresholdCall {})?._0;
+    if threshold != U256::from(multisig.threshold) {
+        problems.push(format!("threshold is {}, configured {}", threshold, multisig.threshold));
+    }
+
+    let want: Address = config
+        .governance_owner
+        .parse()
+        .map_err(|e| format!("Invalid governanceOwner {}: {}", config.governance_owner, e))?;
+    let owner = view(db, bundle_state, config, GOVERNANCE_ADDR, IGovernanceOwnerQueries::ownerCall {})?._0;
+    if owner != want {
+        problems.push(format!("Governance is owned by {:?}, configured {:?}", owner, want));
+    }
+    let pending = view(db, bundle_state, config, GOVERNANCE_ADDR, IGovernanceOwnerQueries::pendingOwnerCall {})?._0;
+    if pending != Address::ZERO {
+        problems.push(format!("Governance has a pending owner {:?}", pending));
+    }
+
+    if problems.is_empty() {
+        info!(
+            "✅ {}-of-{} governance multisig at {:?}{}",
+            multisig.threshold,
+            multisig.owners.len(),
+            address,
+            if owner == address { " owns Governance" } else { "" }
+        );
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+/// Check each vesting contract was deployed holding its amount and, for the
+/// getters the contract has (OpenZeppelin `VestingWallet` names), that it
+/// vests the configured schedule and has released nothing
+fn verify_vesting(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    use IVestingWalletQueries as Q;
+
+    let Some(vesting) = &config.vesting else {
+        return Ok(());
+    };
+    let mut problems = Vec::new();
+    for (i, (schedule, address)) in vesting
+        .schedules
+        .iter()
+        .zip(vesting::vesting_addresses(config))
+        .enumerate()
+    {
+        let label = format!("vesting.schedules[{}] at {:?}", i, address);
+        let account = bundle_state.state.get(&address).and_then(|a| a.info.as_ref());
+        if account.and_then(|info| info.code.as_ref()).is_none_or(|code| code.is_empty()) {
+            problems.push(format!("{}: no {} deployed", label, vesting.contract));
+            continue;
+        }
+        let amount = parse_amount(&schedule.amount).map_err(|e| format!("{}: {}", label, e))?;
+        let balance = account.map(|info| info.balance).unwrap_or_default();
+        if balance != amount {
+            problems.push(format!("{}: balance {}, configured {}", label, balance, amount));
+        }
+
+        let start_secs = schedule.start_micros(config).unwrap_or_default() / 1_000_000;
+        let duration_secs = schedule.duration_micros / 1_000_000;
+        if let Some(start) = optional_view(db, bundle_state, config, address, Q::startCall {})? {
+            if start._0 != U256::from(start_secs) {
+                problems.push(format!("{}: start {}, configured {}", label, start._0, start_secs));
+            }
+        }
+        if let Some(duration) = optional_view(db, bundle_state, config, address, Q::durationCall {})? {
+            if duration._0 != U256::from(duration_secs) {
+                problems.push(format!(
+                    "{}: duration {}, configured {}",
+                    label, duration._0, duration_secs
+                ));
+            }
+        }
+        let beneficiary = match optional_view(db, bundle_state, config, address, Q::beneficiaryCall {})? {
+            Some(b) => Some(b._0),
+            None => optional_view(db, bundle_state, config, address, Q::ownerCall {})?.map(|o| o._0),
+        };
+        if let (Some(actual), Ok(want)) = (beneficiary, schedule.beneficiary.parse::<Address>()) {
+            if actual != want {
+                problems.push(format!("{}: beneficiary {:?}, configured {:?}", label, actual, want));
+            }
+        }
+        if let Some(released) = optional_view(db, bundle_state, config, address, Q::releasedCall {})? {
+            if released._0 != U256::ZERO {
+                problems.push(format!("{}: {} already released at genesis", label, released._0));
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        info!(
+            "✅ {} vesting contract(s) deployed and funded as configured",
+            vesting.schedules.len()
+        );
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+/// Check every contract the replayed broadcast recorded has code in genesis
+fn verify_broadcast(bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    let contracts = broadcast::replayed_contracts(config).map_err(|e| format!("{:#}", e))?;
+    if contracts.is_empty() {
+        return Ok(());
+    }
+    let missing: Vec<String> = contracts
+        .iter()
+        .filter(|(_, address)| {
+            bundle_state
+                .state
+                .get(address)
+                .and_then(|a| a.info.as_ref())
+                .and_then(|info| info.code.as_ref())
+                .is_none_or(|code| code.is_empty())
+        })
+        .map(|(name, address)| format!("{} at {:?}: no code", name, address))
+        .collect();
+    if missing.is_empty() {
+        info!("✅ {} contract(s) from the replayed broadcast deployed", contracts.len());
+        Ok(())
+    } else {
+        Err(missing.join("; "))
+    }
+}
+
+/// `Genesis` is funded with a buffer and forwards the stake it is sent to the
+/// pools, so its balance must end where it started; the alloc drops whatever
+/// it holds, so a difference is stake that never reached a pool or pool stake
+/// paid from a buffer that does not exist on chain
+fn verify_genesis_balance(db: &InMemoryDB, bundle_state: &BundleState) -> Result<(), String> {
+    let funded = db
+        .basic_ref(GENESIS_ADDR)
+        .ok()
+        .flatten()
+        .map(|info| info.balance)
+        .unwrap_or_default();
+    let balance = bundle_state
+        .state
+        .get(&GENESIS_ADDR)
+        .and_then(|account| account.info.as_ref())
+        .map_or(funded, |info| info.balance);
+    if balance > funded {
+        Err(format!(
+            "Genesis holds {} wei more than its funding buffer after initialization; value sent to it was not forwarded and is dropped from the alloc",
+            balance - funded
+        ))
+    } else if balance < funded {
+        Err(format!(
+            "Genesis spent {} wei of its funding buffer during initialization; that balance does not exist on chain",
+            funded - balance
+        ))
+    } else {
+        info!("✅ Genesis balance is back at its funding buffer; nothing it was sent stayed behind");
+        Ok(())
+    }
+}
+
+/// Check the balances execution hands out add up to the configured supply and
+/// log where it sits
+fn verify_supply(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    let pools: Vec<Address> = genesis_stake_pools(db, bundle_state, config)?
+        .into_iter()
+        .map(|entry| entry.pool)
+        .collect();
+    let audit = SupplyAudit::new(db, bundle_state, config, &pools)?;
+    audit.log();
+    let problems = audit.problems(config)?;
+    if problems.is_empty() {
+        info!("✅ Genesis supply matches stake, hook call values, vesting and alloc balances");
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+/// Check every configured issuer's JWKs were stored as encoded
+fn verify_jwks(db: &InMemoryDB, bundle_state: &BundleState, config: &GenesisConfig) -> Result<(), String> {
+    for (issuer, jwks) in config.jwk_config.issuers.iter().zip(&config.jwk_config.jwks) {
+        let issuer_bytes = hex::decode(issuer.strip_prefix("0x").unwrap_or(issuer))
+            .map_err(|e| format!("Invalid issuer hex {}: {}", issuer, e))?;
+        let call = IJWKManagerQueries::getProviderJWKsCall { issuer: issuer_bytes.into() };
+        let txn = new_system_call_txn(JWK_MANAGER_ADDR, call.abi_encode().into());
+
+        execute_verification(
+            GenesisState { db, bundle_state },
+            txn,
+            "JWKs",
+            config,
+            |result| {
+                let mut outcome = Ok(());
+                handle_execution_result(result, "getProviderJWKs", |output_bytes| {
+                    outcome = IJWKManagerQueries::getProviderJWKsCall::abi_decode_returns(output_bytes, false)
+                        .map_err(|e| format!("Failed to decode getProviderJWKs result: {}", e))
+                        .and_then(|decoded| {
+                            // Stored JWKs are sorted by kid, so compare by kid
+                            let stored: HashMap<&str, &IJWKManagerQueries::RSA_JWK> = decoded
+                                .providerJwks
+                                .jwks
+                                .iter()
+                                .map(|jwk| (jwk.kid.as_str(), jwk))
+                                .collect();
+                            if stored.len() != jwks.len() {
+                                return Err(format!(
+                                    "issuer {}: {} JWKs stored, {} configured",
+                                    issuer,
+                                    stored.len(),
+                                    jwks.len()
+                                ));
+                            }
+                            for jwk in jwks {
+                                let matches = stored.get(jwk.kid.as_str()).is_some_and(|s| {
+                                    s.kty == jwk.kty && s.alg == jwk.alg && s.e == jwk.e && s.n == jwk.n
+                                });
+                                if !matches {
+                                    return Err(format!(
+                                        "issuer {}: JWK {} not stored as configured",
+                                        issuer, jwk.kid
+                                    ));
+                                }
+                            }
+                            info!("✅ Issuer {}: {} JWK(s) match config", issuer, jwks.len());
+                            Ok(())
+                        });
+                })?;
+                outcome
+            },
+        )?;
+    }
+    Ok(())
 }
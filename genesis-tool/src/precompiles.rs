@@ -0,0 +1,156 @@
+//! Chain precompiles in the 0x1625F5xxx range
+//!
+//! greth implements these natively, so plain revm sees empty accounts there: a
+//! mint "succeeds" without crediting anything and the BLS PoP check returns no
+//! data, which makes `ValidatorManagement` reject every join. [`handle_register`]
+//! installs Rust implementations on top of revm's standard precompiles so bridge,
+//! reward and validator onboarding paths behave in simulations as they do on
+//! chain.
+
+use alloy_primitives::{Address, Bytes, U256};
+use revm::{
+    handler::register::{EvmHandler, HandleRegister},
+    precompile::{Precompile, PrecompileError, PrecompileErrors, PrecompileOutput, PrecompileResult},
+    ContextPrecompile, ContextStatefulPrecompile, Database, InnerEvmContext,
+};
+use std::sync::Arc;
+
+use crate::{
+    keys::verify_bls_pop,
+    utils::{BLS_POP_VERIFY_PRECOMPILE_ADDR, NATIVE_MINT_PRECOMPILE_ADDR},
+};
+
+/// Nominal gas charged per call; greth's costs are not mirrored exactly
+const NATIVE_MINT_GAS: u64 = 9_000;
+const BLS_POP_VERIFY_GAS: u64 = 45_000;
+
+/// `0x01 || recipient (20) || amount (32)`, as packed by `GBridgeReceiver`
+const MINT_OP: u8 = 0x01;
+const MINT_INPUT_LEN: usize = 1 + 20 + 32;
+
+/// `pubkey (48) || pop (96)`, as packed by `ValidatorManagement`
+const BLS_PUBKEY_LEN: usize = 48;
+const BLS_POP_INPUT_LEN: usize = BLS_PUBKEY_LEN + 96;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PrecompileMode {
+    /// Mint credits balances and PoPs are verified with blst (default)
+    #[default]
+    Real,
+    /// Mint credits balances but every well-formed PoP is accepted, so
+    /// placeholder consensus keys can join
+    Stub,
+    /// Leave the range empty, as plain revm does
+    Off,
+}
+
+/// Handler register installing the chain precompiles for `mode`
+pub fn handle_register<EXT, DB: Database>(mode: PrecompileMode) -> HandleRegister<EXT, DB> {
+    match mode {
+        PrecompileMode::Real => register::<EXT, DB, true>,
+        PrecompileMode::Stub => register::<EXT, DB, false>,
+        PrecompileMode::Off => |_| {},
+    }
+}
+
+fn register<EXT, DB: Database, const VERIFY_POP: bool>(handler: &mut EvmHandler<'_, EXT, DB>) {
+    let load_standard = handler.pre_execution.load_precompiles.clone();
+    handler.pre_execution.load_precompiles = Arc::new(move || {
+        let mut precompiles = load_standard();
+        let bls_pop_verify = if VERIFY_POP {
+            bls_pop_verify
+        } else {
+            bls_pop_accept
+        };
+        precompiles.extend([
+            (
+                NATIVE_MINT_PRECOMPILE_ADDR,
+                ContextPrecompile::ContextStateful(Arc::new(NativeMint)),
+            ),
+            (
+                BLS_POP_VERIFY_PRECOMPILE_ADDR,
+                ContextPrecompile::Ordinary(Precompile::Standard(bls_pop_verify)),
+            ),
+        ]);
+        precompiles
+    });
+}
+
+fn invalid_input(message: &str) -> PrecompileErrors {
+    PrecompileErrors::Error(PrecompileError::Other(message.to_string()))
+}
+
+/// Credits native tokens to a recipient
+struct NativeMint;
+
+impl<DB: Database> ContextStatefulPrecompile<DB> for NativeMint {
+    fn call(
+        &self,
+        input: &Bytes,
+        gas_limit: u64,
+        evmctx: &mut InnerEvmContext<DB>,
+    ) -> PrecompileResult {
+        if gas_limit < NATIVE_MINT_GAS {
+            return Err(PrecompileError::OutOfGas.into());
+        }
+        if input.len() != MINT_INPUT_LEN || input[0] != MINT_OP {
+            return Err(invalid_input("native mint: expected 0x01 || recipient || amount"));
+        }
+        let recipient = Address::from_slice(&input[1..21]);
+        let amount = U256::from_be_slice(&input[21..]);
+
+        // Top up the precompile account and transfer out of it, so the credit is
+        // journaled and undone if the calling frame reverts
+        let fatal = |_| PrecompileErrors::Fatal {
+            msg: "native mint: failed to load account".to_string(),
+        };
+        let journal = &mut evmctx.journaled_state;
+        journal
+            .load_account(NATIVE_MINT_PRECOMPILE_ADDR, &mut evmctx.db)
+            .map_err(fatal)?;
+        let reserve = journal
+            .state
+            .get_mut(&NATIVE_MINT_PRECOMPILE_ADDR)
+            .expect("account was just loaded");
+        reserve.info.balance = reserve
+            .info
+            .balance
+            .checked_add(amount)
+            .ok_or_else(|| invalid_input("native mint: amount overflows"))?;
+        if journal
+            .transfer(&NATIVE_MINT_PRECOMPILE_ADDR, &recipient, amount, &mut evmctx.db)
+            .map_err(fatal)?
+            .is_some()
+        {
+            return Err(invalid_input("native mint: recipient balance overflows"));
+        }
+        Ok(PrecompileOutput::new(NATIVE_MINT_GAS, Bytes::new()))
+    }
+}
+
+fn check_bls_pop_input(input: &Bytes, gas_limit: u64) -> Result<(), PrecompileErrors> {
+    if gas_limit < BLS_POP_VERIFY_GAS {
+        return Err(PrecompileError::OutOfGas.into());
+    }
+    if input.len() != BLS_POP_INPUT_LEN {
+        return Err(invalid_input("BLS PoP verify: expected pubkey (48) || pop (96)"));
+    }
+    Ok(())
+}
+
+/// ABI-encoded uint256 1 or 0
+fn bool_word(value: bool) -> Bytes {
+    U256::from(value as u8).to_be_bytes::<32>().to_vec().into()
+}
+
+fn bls_pop_verify(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    check_bls_pop_input(input, gas_limit)?;
+    let (public_key, pop) = input.split_at(BLS_PUBKEY_LEN);
+    let valid = verify_bls_pop(public_key, pop);
+    Ok(PrecompileOutput::new(BLS_POP_VERIFY_GAS, bool_word(valid)))
+}
+
+fn bls_pop_accept(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    check_bls_pop_input(input, gas_limit)?;
+    Ok(PrecompileOutput::new(BLS_POP_VERIFY_GAS, bool_word(true)))
+}
@@ -0,0 +1,152 @@
+//! Gas and storage profile of genesis initialization
+//!
+//! Breaks the `Genesis.initialize` call tree down per contract: gas spent in
+//! the contract's own code (excluding subcalls), how often it was entered, and
+//! how many storage slots it ended up writing, relative to a block-sized budget.
+
+use alloy_primitives::Address;
+use revm::db::BundleState;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+use crate::{abi::AbiRegistry, trace::CallFrame};
+
+/// Gas of a full block, which initialization should stay well within
+pub const GAS_BUDGET: u64 = 30_000_000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractProfile {
+    pub name: String,
+    pub address: Address,
+    pub calls: usize,
+    /// Gas spent in this contract's code, excluding its subcalls
+    pub self_gas: u64,
+    /// Share of [`GAS_BUDGET`], in percent
+    pub budget_percent: f64,
+    pub storage_slots_written: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasProfile {
+    pub total_gas_used: u64,
+    /// Gas charged outside of any frame (intrinsic cost, refunds)
+    pub intrinsic_gas: i128,
+    pub gas_budget: u64,
+    /// Sorted by self gas, highest first
+    pub contracts: Vec<ContractProfile>,
+}
+
+fn percent_of_budget(gas: u64) -> f64 {
+    gas as f64 * 100.0 / GAS_BUDGET as f64
+}
+
+/// Attribute the frames of the genesis transactions and their state changes
+/// to contracts
+pub fn profile_genesis(
+    frames: &[CallFrame],
+    total_gas_used: u64,
+    bundle_state: &BundleState,
+    abi: &AbiRegistry,
+) -> GasProfile {
+    // Self gas: each frame's gas minus that of its direct children
+    let mut self_gas: Vec<i128> = frames.iter().map(|f| f.gas_used as i128).collect();
+    let mut open: Vec<usize> = Vec::new();
+    for (i, frame) in frames.iter().enumerate() {
+        while open.last().is_some_and(|&p| frames[p].depth >= frame.depth) {
+            open.pop();
+        }
+        if let Some(&parent) = open.last() {
+            self_gas[parent] -= frame.gas_used as i128;
+        }
+        open.push(i);
+    }
+
+    let created: HashSet<Address> = frames
+        .iter()
+        .filter(|f| f.kind.starts_with("CREATE"))
+        .filter_map(|f| f.to)
+        .collect();
+    let mut by_address: HashMap<Address, (usize, i128)> = HashMap::new();
+    for (frame, gas) in frames.iter().zip(&self_gas) {
+        if let Some(address) = frame.to {
+            let entry = by_address.entry(address).or_default();
+            entry.0 += 1;
+            entry.1 += gas;
+        }
+    }
+    for (address, account) in &bundle_state.state {
+        if account
+            .storage
+            .values()
+            .any(|slot| slot.is_changed())
+        {
+            by_address.entry(*address).or_default();
+        }
+    }
+
+    let mut contracts: Vec<ContractProfile> = by_address
+        .into_iter()
+        .map(|(address, (calls, gas))| {
+            let gas = gas.max(0) as u64;
+            let storage_slots_written = bundle_state.state.get(&address).map_or(0, |account| {
+                account
+                    .storage
+                    .values()
+                    .filter(|slot| slot.is_changed())
+                    .count()
+            });
+            let name = match abi.name_of(&address) {
+                Some(name) => name.to_string(),
+                None if created.contains(&address) => "(created)".to_string(),
+                None => format!("{:?}", address),
+            };
+            ContractProfile {
+                name,
+                address,
+                calls,
+                self_gas: gas,
+                budget_percent: percent_of_budget(gas),
+                storage_slots_written,
+            }
+        })
+        .collect();
+    contracts.sort_by(|a, b| b.self_gas.cmp(&a.self_gas).then(a.name.cmp(&b.name)));
+
+    let framed_gas: i128 = frames
+        .iter()
+        .filter(|f| f.depth == 0)
+        .map(|f| f.gas_used as i128)
+        .sum();
+    GasProfile {
+        total_gas_used,
+        intrinsic_gas: total_gas_used as i128 - framed_gas,
+        gas_budget: GAS_BUDGET,
+        contracts,
+    }
+}
+
+pub fn log_profile(profile: &GasProfile) {
+    info!(
+        "Genesis initialization used {} gas ({:.2}% of a {} gas block)",
+        profile.total_gas_used,
+        percent_of_budget(profile.total_gas_used),
+        profile.gas_budget
+    );
+    info!(
+        "{:<32} {:>6} {:>12} {:>8} {:>8}",
+        "contract", "calls", "self gas", "budget", "slots"
+    );
+    for contract in &profile.contracts {
+        info!(
+            "{:<32} {:>6} {:>12} {:>7.2}% {:>8}",
+            contract.name,
+            contract.calls,
+            contract.self_gas,
+            contract.budget_percent,
+            contract.storage_slots_written
+        );
+    }
+}
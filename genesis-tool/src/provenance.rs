@@ -0,0 +1,199 @@
+//! Build provenance of generated genesis outputs
+//!
+//! `generate` finishes by writing `manifest.json` into its output directory:
+//! the sha256 of every file there, the tool version, the git commit of the
+//! bytecode directory, the sha256 of the config and the genesis timestamp.
+//! [`verify_manifest`] recomputes the hashes so a deployed genesis can be tied
+//! back to the exact inputs it was generated from.
+
+use anyhow::{Context, Result};
+use revm_primitives::hex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fs, path::Path, process::Command};
+use tracing::{info, warn};
+
+use crate::stake_pools::StakePoolEntry;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSource {
+    pub commit: String,
+    /// Uncommitted changes in the working tree at generation time
+    pub dirty: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputManifest {
+    pub tool_version: String,
+    pub byte_code_dir: String,
+    /// Git commit of the bytecode directory, when it is inside a repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_code_git: Option<GitSource>,
+    pub config_file: String,
+    pub config_sha256: String,
+    /// `--set` overrides applied on top of the config file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub config_overrides: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_timestamp_secs: Option<u64>,
+    /// Stake pool created for each initial validator
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stake_pools: Vec<StakePoolEntry>,
+    /// sha256 of each output file, keyed by name relative to the manifest
+    pub files: BTreeMap<String, String>,
+}
+
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let content = fs::read(path).context(format!("Failed to read {}", path.display()))?;
+    Ok(hex::encode(Sha256::digest(&content)))
+}
+
+/// Commit and dirtiness of the repository containing `dir`; `None` outside git
+pub fn git_source(dir: &str) -> Option<GitSource> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let commit = git(&["rev-parse", "HEAD"])?;
+    let dirty = git(&["status", "--porcelain", "--", "."]).is_some_and(|s| !s.is_empty());
+    Some(GitSource { commit, dirty })
+}
+
+/// Hash every file in `output_dir` (except an earlier manifest) and write
+/// `manifest.json` next to them
+pub fn write_manifest(
+    output_dir: &str,
+    byte_code_dir: &str,
+    config_file: &str,
+    config_overrides: &[String],
+    genesis_timestamp_secs: Option<u64>,
+    stake_pools: Vec<StakePoolEntry>,
+) -> Result<OutputManifest> {
+    let mut files = BTreeMap::new();
+    for entry in fs::read_dir(output_dir).context(format!("Failed to read {}", output_dir))? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        if path.is_file() && name != MANIFEST_FILE {
+            files.insert(name, sha256_file(&path)?);
+        }
+    }
+
+    let byte_code_git = git_source(byte_code_dir);
+    match &byte_code_git {
+        Some(git) if git.dirty => warn!(
+            "Bytecode directory {} has uncommitted changes on top of {}",
+            byte_code_dir, git.commit
+        ),
+        Some(_) => {}
+        None => warn!(
+            "Bytecode directory {} is not in a git repository; provenance has no commit",
+            byte_code_dir
+        ),
+    }
+
+    let manifest = OutputManifest {
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        byte_code_dir: byte_code_dir.to_string(),
+        byte_code_git,
+        config_file: config_file.to_string(),
+        config_sha256: sha256_file(Path::new(config_file))?,
+        config_overrides: config_overrides.to_vec(),
+        genesis_timestamp_secs,
+        stake_pools,
+        files,
+    };
+    let path = Path::new(output_dir).join(MANIFEST_FILE);
+    fs::write(&path, serde_json::to_string_pretty(&manifest)? + "\n")
+        .context(format!("Failed to write {}", path.display()))?;
+    info!(
+        "Wrote {} with {} file hashes",
+        path.display(),
+        manifest.files.len()
+    );
+    Ok(manifest)
+}
+
+/// Recompute the hashes recorded in a manifest, and optionally those of the
+/// config and bytecode commit; returns one line per mismatch
+pub fn verify_manifest(
+    manifest_path: &str,
+    config_file: Option<&str>,
+    byte_code_dir: Option<&str>,
+) -> Result<Vec<String>> {
+    let content = fs::read_to_string(manifest_path)
+        .context(format!("Failed to read manifest {}", manifest_path))?;
+    let manifest: OutputManifest = serde_json::from_str(&content)
+        .context(format!("Failed to parse manifest {}", manifest_path))?;
+    let dir = Path::new(manifest_path)
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    let mut mismatches = Vec::new();
+    for (name, expected) in &manifest.files {
+        let path = dir.join(name);
+        if !path.is_file() {
+            mismatches.push(format!("{}: missing", name));
+            continue;
+        }
+        let actual = sha256_file(&path)?;
+        if &actual != expected {
+            mismatches.push(format!("{}: sha256 {} != {}", name, actual, expected));
+        }
+    }
+
+    if let Some(config_file) = config_file {
+        let actual = sha256_file(Path::new(config_file))?;
+        if actual != manifest.config_sha256 {
+            mismatches.push(format!(
+                "config {}: sha256 {} != {}",
+                config_file, actual, manifest.config_sha256
+            ));
+        }
+    }
+
+    if let Some(byte_code_dir) = byte_code_dir {
+        match (git_source(byte_code_dir), &manifest.byte_code_git) {
+            (Some(actual), Some(expected)) if actual.commit != expected.commit => {
+                mismatches.push(format!(
+                    "bytecode {}: commit {} != {}",
+                    byte_code_dir, actual.commit, expected.commit
+                ));
+            }
+            (Some(actual), Some(_)) if actual.dirty => {
+                warn!(
+                    "Bytecode directory {} has uncommitted changes",
+                    byte_code_dir
+                )
+            }
+            (_, None) => warn!("Manifest records no bytecode commit to compare against"),
+            (None, Some(_)) => mismatches.push(format!(
+                "bytecode {}: not in a git repository",
+                byte_code_dir
+            )),
+            _ => {}
+        }
+    }
+
+    info!(
+        "Checked {} file(s) from {} (generated by genesis-tool {})",
+        manifest.files.len(),
+        manifest_path,
+        manifest.tool_version
+    );
+    Ok(mismatches)
+}
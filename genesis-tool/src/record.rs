@@ -0,0 +1,173 @@
+//! Recorded outputs of the on-chain config views
+//!
+//! `record-fixtures` loads a genesis.json, calls the views the node reads its
+//! configuration from (validator set, epoch, randomness, consensus and
+//! execution config, JWKs) and writes each call's calldata and exact
+//! ABI-encoded output to its own file, plus an `index.json` with the format
+//! version and the sha256 of the genesis it came from. Decoder tests can then
+//! pin against outputs of a real genesis, and `--check` re-records and fails
+//! when the outputs no longer match the files.
+
+use alloy_primitives::{keccak256, Address, Bytes};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+use tracing::{info, warn};
+
+use crate::{
+    provenance::sha256_file,
+    simulate::Simulator,
+    utils::{
+        CONSENSUS_CONFIG_ADDR, EPOCH_CONFIG_ADDR, EXECUTION_CONFIG_ADDR, JWK_MANAGER_ADDR,
+        RANDOMNESS_CONFIG_ADDR, RECONFIGURATION_ADDR, VALIDATOR_MANAGER_ADDR,
+    },
+};
+
+/// Bumped when the layout of the fixture files changes
+pub const FIXTURE_FORMAT_VERSION: u32 = 1;
+
+pub const FIXTURE_INDEX_FILE: &str = "index.json";
+
+/// Views recorded, as (fixture name, contract, address, signature)
+const RECORDED_VIEWS: [(&str, &str, Address, &str); 12] = [
+    ("active_validators", "ValidatorManagement", VALIDATOR_MANAGER_ADDR, "getActiveValidators()"),
+    ("active_validator_count", "ValidatorManagement", VALIDATOR_MANAGER_ADDR, "getActiveValidatorCount()"),
+    ("total_voting_power", "ValidatorManagement", VALIDATOR_MANAGER_ADDR, "getTotalVotingPower()"),
+    ("cur_validator_consensus_infos", "ValidatorManagement", VALIDATOR_MANAGER_ADDR, "getCurValidatorConsensusInfos()"),
+    ("current_epoch", "Reconfiguration", RECONFIGURATION_ADDR, "currentEpoch()"),
+    ("last_reconfiguration_time", "Reconfiguration", RECONFIGURATION_ADDR, "lastReconfigurationTime()"),
+    ("epoch_interval", "EpochConfig", EPOCH_CONFIG_ADDR, "epochIntervalMicros()"),
+    ("randomness_config", "RandomnessConfig", RANDOMNESS_CONFIG_ADDR, "getCurrentConfig()"),
+    ("consensus_config", "ConsensusConfig", CONSENSUS_CONFIG_ADDR, "getCurrentConfig()"),
+    ("execution_config", "ExecutionConfig", EXECUTION_CONFIG_ADDR, "getCurrentConfig()"),
+    ("observed_jwks", "JWKManager", JWK_MANAGER_ADDR, "getObservedJWKs()"),
+    ("patched_jwks", "JWKManager", JWK_MANAGER_ADDR, "getPatchedJWKs()"),
+];
+
+/// One recorded view call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewFixture {
+    pub name: String,
+    pub contract: String,
+    pub address: Address,
+    pub signature: String,
+    pub calldata: Bytes,
+    pub output: Bytes,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureIndex {
+    pub format_version: u32,
+    pub tool_version: String,
+    pub genesis_sha256: String,
+    pub chain_id: u64,
+    /// Fixture names; each is stored in `<name>.json`
+    pub views: Vec<String>,
+}
+
+/// Call every recorded view against the genesis at `genesis_path`
+pub fn record_views(genesis_path: &str, chain_id: u64) -> Result<Vec<ViewFixture>> {
+    let mut sim = Simulator::from_genesis_file(genesis_path, chain_id)?;
+    RECORDED_VIEWS
+        .iter()
+        .map(|(name, contract, address, signature)| {
+            let calldata = Bytes::copy_from_slice(&keccak256(signature.as_bytes())[..4]);
+            let output = sim
+                .view_raw(*address, calldata.clone())
+                .context(format!("{}.{}", contract, signature))?;
+            Ok(ViewFixture {
+                name: name.to_string(),
+                contract: contract.to_string(),
+                address: *address,
+                signature: signature.to_string(),
+                calldata,
+                output,
+            })
+        })
+        .collect()
+}
+
+/// Record the views of `genesis_path` into `output_dir`
+pub fn write_fixtures(genesis_path: &str, chain_id: u64, output_dir: &str) -> Result<()> {
+    let fixtures = record_views(genesis_path, chain_id)?;
+    fs::create_dir_all(output_dir).context(format!("Failed to create {}", output_dir))?;
+    let dir = Path::new(output_dir);
+    for fixture in &fixtures {
+        let path = dir.join(format!("{}.json", fixture.name));
+        fs::write(&path, serde_json::to_string_pretty(fixture)? + "\n")
+            .context(format!("Failed to write {}", path.display()))?;
+    }
+    let index = FixtureIndex {
+        format_version: FIXTURE_FORMAT_VERSION,
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        genesis_sha256: sha256_file(Path::new(genesis_path))?,
+        chain_id,
+        views: fixtures.iter().map(|f| f.name.clone()).collect(),
+    };
+    let path = dir.join(FIXTURE_INDEX_FILE);
+    fs::write(&path, serde_json::to_string_pretty(&index)? + "\n")
+        .context(format!("Failed to write {}", path.display()))?;
+    info!("Recorded {} view fixture(s) to {}", fixtures.len(), output_dir);
+    Ok(())
+}
+
+/// Re-record the views of `genesis_path` and compare them with the fixtures in
+/// `fixture_dir`; returns one line per difference
+pub fn check_fixtures(genesis_path: &str, chain_id: u64, fixture_dir: &str) -> Result<Vec<String>> {
+    let dir = Path::new(fixture_dir);
+    let path = dir.join(FIXTURE_INDEX_FILE);
+    let index: FixtureIndex = serde_json::from_str(
+        &fs::read_to_string(&path).context(format!("Failed to read {}", path.display()))?,
+    )
+    .context(format!("Failed to decode {}", path.display()))?;
+    if index.format_version != FIXTURE_FORMAT_VERSION {
+        anyhow::bail!(
+            "{} has fixture format {}, this tool records format {}; re-record them",
+            path.display(),
+            index.format_version,
+            FIXTURE_FORMAT_VERSION
+        );
+    }
+    let genesis_sha256 = sha256_file(Path::new(genesis_path))?;
+    if index.genesis_sha256 != genesis_sha256 {
+        warn!(
+            "Fixtures were recorded from a different genesis (sha256 {}, this one {})",
+            index.genesis_sha256, genesis_sha256
+        );
+    }
+
+    let mut differences = Vec::new();
+    let recorded = record_views(genesis_path, chain_id)?;
+    for name in &index.views {
+        if !recorded.iter().any(|f| &f.name == name) {
+            differences.push(format!("{}: no longer recorded", name));
+        }
+    }
+    for fixture in &recorded {
+        let path = dir.join(format!("{}.json", fixture.name));
+        if !path.is_file() {
+            differences.push(format!("{}: no fixture file", fixture.name));
+            continue;
+        }
+        let stored: ViewFixture = serde_json::from_str(
+            &fs::read_to_string(&path).context(format!("Failed to read {}", path.display()))?,
+        )
+        .context(format!("Failed to decode {}", path.display()))?;
+        if stored.calldata != fixture.calldata || stored.address != fixture.address {
+            differences.push(format!(
+                "{}: recorded for {:?} {}, now {:?} {}",
+                fixture.name, stored.address, stored.calldata, fixture.address, fixture.calldata
+            ));
+        } else if stored.output != fixture.output {
+            differences.push(format!(
+                "{}: output changed ({} -> {} bytes)",
+                fixture.name,
+                stored.output.len(),
+                fixture.output.len()
+            ));
+        }
+    }
+    Ok(differences)
+}
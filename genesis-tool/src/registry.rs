@@ -0,0 +1,261 @@
+//! System contract registry
+//!
+//! The list of contracts deployed at genesis (name of the `<name>.hex` artifact
+//! and the address it is placed at) is read from a `contracts_manifest.json`,
+//! so a new system contract can ship with its artifacts instead of a tool
+//! rebuild. Without a manifest the built-in [`CONTRACTS`] list is used.
+//! An entry may set `path` to take its artifact from outside the bytecode
+//! directory; `generate --override <name>=<path>` sets it from the command line.
+//! `immutables` fills the contract's immutable slots, and `libraries` maps
+//! fully qualified library names to the addresses linked into unlinked
+//! artifacts (see [`crate::bytecode`]).
+//!
+//! [`ContractsManifest::preflight`] reads every artifact before any EVM work,
+//! so all missing or corrupt files are reported together instead of a panic on
+//! the first one midway through deployment.
+//!
+//! ```json
+//! {
+//!   "contracts": [
+//!     { "name": "Genesis", "address": "0x00000000000000000000000000000001625F0001" }
+//!   ],
+//!   "libraries": {}
+//! }
+//! ```
+
+use alloy_primitives::{hex, Address};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::Path,
+};
+use tracing::{info, warn};
+
+use crate::{
+    bytecode::{link_libraries, ImmutablesConfig},
+    utils::{CONTRACTS, GENESIS_ADDR},
+};
+
+/// Manifest file name looked up in the bytecode directory
+pub const CONTRACTS_MANIFEST_FILE: &str = "contracts_manifest.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemContract {
+    /// Artifact name; bytecode is read from `<name>.hex`
+    pub name: String,
+    pub address: Address,
+    /// Artifact file used instead of `<byte_code_dir>/<name>.hex`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub immutables: Option<ImmutablesConfig>,
+}
+
+impl SystemContract {
+    pub fn hex_path(&self, byte_code_dir: &str) -> String {
+        match &self.path {
+            Some(path) => path.clone(),
+            None => format!("{}/{}.hex", byte_code_dir, self.name),
+        }
+    }
+
+    /// Read and decode the contract's artifact, linking libraries first
+    pub fn read_code(
+        &self,
+        byte_code_dir: &str,
+        libraries: &BTreeMap<String, Address>,
+    ) -> Result<Vec<u8>> {
+        let path = self.hex_path(byte_code_dir);
+        let content = fs::read_to_string(&path).context(format!("cannot read {}", path))?;
+        let linked = link_libraries(&content, libraries)?;
+        let code = hex::decode(linked.trim()).context(format!("{} is not valid hex", path))?;
+        if code.is_empty() {
+            anyhow::bail!("{} is empty", path);
+        }
+        Ok(code)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractsManifest {
+    pub contracts: Vec<SystemContract>,
+    /// Library address per fully qualified name (`path/File.sol:Name`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub libraries: BTreeMap<String, Address>,
+}
+
+impl Default for ContractsManifest {
+    fn default() -> Self {
+        Self {
+            contracts: CONTRACTS
+                .iter()
+                .map(|(name, address)| SystemContract {
+                    name: name.to_string(),
+                    address: *address,
+                    path: None,
+                    immutables: None,
+                })
+                .collect(),
+            libraries: BTreeMap::new(),
+        }
+    }
+}
+
+impl ContractsManifest {
+    pub fn from_file(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .context(format!("Failed to read contracts manifest {}", path))?;
+        let manifest: Self = serde_json::from_str(&content)
+            .context(format!("Failed to parse contracts manifest {}", path))?;
+        manifest
+            .check()
+            .context(format!("Invalid contracts manifest {}", path))?;
+        Ok(manifest)
+    }
+
+    /// Resolve the registry for a bytecode directory: an explicit manifest wins,
+    /// then `<byte_code_dir>/contracts_manifest.json`, then the built-in list
+    pub fn resolve(byte_code_dir: &str, manifest_path: Option<&str>) -> Result<Self> {
+        let bundled = Path::new(byte_code_dir).join(CONTRACTS_MANIFEST_FILE);
+        let path = match manifest_path {
+            Some(path) => path.to_string(),
+            None if bundled.exists() => bundled.to_string_lossy().into_owned(),
+            None => return Ok(Self::default()),
+        };
+        let manifest = Self::from_file(&path)?;
+        info!(
+            "Loaded {} system contract(s) from {}",
+            manifest.contracts.len(),
+            path
+        );
+        manifest.log_differences();
+        Ok(manifest)
+    }
+
+    fn check(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        let mut addresses = HashSet::new();
+        for contract in &self.contracts {
+            if contract.name.is_empty()
+                || !contract
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                anyhow::bail!(
+                    "Contract name {:?} must be a non-empty alphanumeric artifact name",
+                    contract.name
+                );
+            }
+            if !names.insert(contract.name.as_str()) {
+                anyhow::bail!("Contract {} is listed more than once", contract.name);
+            }
+            if !addresses.insert(contract.address) {
+                anyhow::bail!(
+                    "Address {:?} is assigned to more than one contract",
+                    contract.address
+                );
+            }
+        }
+        // Genesis.initialize is called at the fixed GENESIS_ADDR
+        if self.address_of("Genesis") != Some(GENESIS_ADDR) {
+            anyhow::bail!("Genesis must be listed at {:?}", GENESIS_ADDR);
+        }
+        Ok(())
+    }
+
+    /// The rest of the tool addresses built-in contracts by their fixed constants
+    fn log_differences(&self) {
+        for (name, address) in CONTRACTS {
+            match self.address_of(name) {
+                None => warn!("Built-in system contract {} is not in the manifest", name),
+                Some(a) if a != address => warn!(
+                    "Manifest moves {} from {:?} to {:?}; post-genesis checks still use the built-in address",
+                    name, address, a
+                ),
+                Some(_) => {}
+            }
+        }
+        for contract in &self.contracts {
+            if !CONTRACTS.iter().any(|(name, _)| *name == contract.name) {
+                info!(
+                    "Additional system contract {} at {:?}",
+                    contract.name, contract.address
+                );
+            }
+        }
+    }
+
+    /// Swap individual artifacts, each given as `<name>=<path to .hex>`
+    pub fn apply_overrides(&mut self, overrides: &[String]) -> Result<()> {
+        for spec in overrides {
+            let (name, path) = spec.split_once('=').ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Override must be <contract name>=<path to .hex>, got {}",
+                    spec
+                )
+            })?;
+            let Some(contract) = self.contracts.iter_mut().find(|c| c.name == name) else {
+                anyhow::bail!(
+                    "Override targets unknown system contract {}; known contracts: {}",
+                    name,
+                    self.contracts
+                        .iter()
+                        .map(|c| c.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            };
+            if !Path::new(path).is_file() {
+                anyhow::bail!("Override artifact {} for {} does not exist", path, name);
+            }
+            warn!(
+                "Overriding {} ({:?}) bytecode with {}",
+                name, contract.address, path
+            );
+            contract.path = Some(path.to_string());
+        }
+        Ok(())
+    }
+
+    /// Check that every contract has a readable, non-empty, hex-decodable
+    /// artifact, reporting all broken ones at once
+    pub fn preflight(&self, byte_code_dir: &str) -> Result<()> {
+        let problems: Vec<String> = self
+            .contracts
+            .iter()
+            .filter_map(|contract| {
+                let e = contract.read_code(byte_code_dir, &self.libraries).err()?;
+                Some(format!("{}: {:#}", contract.name, e))
+            })
+            .collect();
+        if problems.is_empty() {
+            info!(
+                "All {} system contract artifact(s) are readable",
+                self.contracts.len()
+            );
+            return Ok(());
+        }
+        anyhow::bail!(
+            "{} of {} system contract artifact(s) are missing or corrupt; each needs \
+             {}/<ContractName>.hex (or the manifest entry's `path`) holding hex runtime bytecode:\n  {}",
+            problems.len(),
+            self.contracts.len(),
+            byte_code_dir,
+            problems.join("\n  ")
+        )
+    }
+
+    pub fn address_of(&self, name: &str) -> Option<Address> {
+        self.contracts
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| c.address)
+    }
+
+    pub fn contains_address(&self, address: &Address) -> bool {
+        self.contracts.iter().any(|c| c.address == *address)
+    }
+}
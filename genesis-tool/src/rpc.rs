@@ -0,0 +1,131 @@
+//! Minimal blocking JSON-RPC client for talking to live nodes
+
+use alloy_primitives::{Address, Bytes};
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .build();
+        Self {
+            url: url.to_string(),
+            agent,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Call `method` with `params` and deserialize the `result` field
+    pub fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .context(format!("RPC request {} to {} failed", method, self.url))?
+            .into_json()
+            .context(format!("Invalid JSON-RPC response for {}", method))?;
+
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("RPC {} returned error: {}", method, error);
+        }
+        let result = response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("RPC {} response has no result", method))?;
+        serde_json::from_value(result).context(format!("Unexpected result shape for {}", method))
+    }
+
+    /// Call `method` once per entry of `params` in a single JSON-RPC batch;
+    /// results keep the order of `params`
+    pub fn batch_call<T: DeserializeOwned>(&self, method: &str, params: &[Value]) -> Result<Vec<T>> {
+        let requests: Vec<Value> = params
+            .iter()
+            .enumerate()
+            .map(|(id, params)| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": method,
+                    "params": params,
+                })
+            })
+            .collect();
+        let responses: Vec<Value> = self
+            .agent
+            .post(&self.url)
+            .send_json(Value::Array(requests))
+            .context(format!("RPC batch {} to {} failed", method, self.url))?
+            .into_json()
+            .context(format!("Invalid JSON-RPC batch response for {}", method))?;
+
+        let mut results: Vec<Option<T>> = params.iter().map(|_| None).collect();
+        for response in responses {
+            if let Some(error) = response.get("error") {
+                anyhow::bail!("RPC {} returned error: {}", method, error);
+            }
+            let id = response
+                .get("id")
+                .and_then(Value::as_u64)
+                .and_then(|id| usize::try_from(id).ok())
+                .filter(|id| *id < params.len())
+                .ok_or_else(|| anyhow::anyhow!("RPC batch {} response has an unknown id", method))?;
+            let result = response
+                .get("result")
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("RPC {} response has no result", method))?;
+            results[id] = Some(
+                serde_json::from_value(result)
+                    .context(format!("Unexpected result shape for {}", method))?,
+            );
+        }
+        results
+            .into_iter()
+            .enumerate()
+            .map(|(id, result)| {
+                result.ok_or_else(|| anyhow::anyhow!("RPC batch {} has no response for request {}", method, id))
+            })
+            .collect()
+    }
+
+    /// Current block number
+    pub fn block_number(&self) -> Result<u64> {
+        let hex_number: String = self.call("eth_blockNumber", json!([]))?;
+        parse_hex_u64(&hex_number)
+    }
+
+    pub fn chain_id(&self) -> Result<u64> {
+        let hex_id: String = self.call("eth_chainId", json!([]))?;
+        parse_hex_u64(&hex_id)
+    }
+
+    /// Code of `address` at `block_tag` (a hex block number or a tag like `latest`)
+    pub fn get_code(&self, address: Address, block_tag: &str) -> Result<Bytes> {
+        self.call("eth_getCode", json!([address, block_tag]))
+    }
+}
+
+pub fn parse_hex_u64(s: &str) -> Result<u64> {
+    u64::from_str_radix(s.strip_prefix("0x").unwrap_or(s), 16)
+        .context(format!("Invalid hex quantity: {}", s))
+}
+
+pub fn to_hex_quantity(n: u64) -> String {
+    format!("0x{:x}", n)
+}
@@ -0,0 +1,208 @@
+//! Function selector inventories of deployed system contracts
+//!
+//! solc dispatchers compare the calldata selector against each external
+//! function with `PUSH4 <selector> EQ` (or `PUSH4 <selector> DUP2 EQ` under
+//! via-IR), so the selectors a binary answers to can be read off its runtime
+//! code without an ABI. Names come from the forge artifacts next to the `.hex`
+//! files and optionally a signature file. Comparing inventories shows which
+//! contract version a genesis or artifact directory was really built from.
+//!
+//! ERC-165 interface ids compared in `supportsInterface` match the same pattern
+//! and show up as unresolved entries.
+
+use alloy_primitives::{hex, Address};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+};
+use tracing::{info, warn};
+
+use crate::{
+    abi::{selector, AbiRegistry},
+    bytecode::strip_metadata,
+    registry::ContractsManifest,
+    verify::stream_genesis_alloc,
+};
+
+const PUSH0: u8 = 0x5f;
+const PUSH4: u8 = 0x63;
+const PUSH32: u8 = 0x7f;
+const EQ: u8 = 0x14;
+const DUP2: u8 = 0x81;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectorEntry {
+    pub selector: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractSelectors {
+    pub name: String,
+    pub address: Address,
+    pub selectors: Vec<SelectorEntry>,
+}
+
+impl ContractSelectors {
+    pub fn unresolved(&self) -> usize {
+        self.selectors.iter().filter(|s| s.signature.is_none()).count()
+    }
+}
+
+/// Selectors a runtime code dispatches on, in ascending order
+pub fn dispatcher_selectors(code: &[u8]) -> Vec<[u8; 4]> {
+    let code = strip_metadata(code);
+    let mut selectors = BTreeSet::new();
+    let mut pc = 0;
+    while pc < code.len() {
+        let op = code[pc];
+        let push_len = match op {
+            PUSH0..=PUSH32 => (op - PUSH0) as usize,
+            _ => 0,
+        };
+        if op == PUSH4 && pc + 5 < code.len() {
+            let next = &code[pc + 5..];
+            let compared = next.first() == Some(&EQ) || next.starts_with(&[DUP2, EQ]);
+            if compared {
+                let mut sel = [0u8; 4];
+                sel.copy_from_slice(&code[pc + 1..pc + 5]);
+                selectors.insert(sel);
+            }
+        }
+        pc += 1 + push_len;
+    }
+    selectors.into_iter().collect()
+}
+
+/// Load a signature file: a JSON object of selector to signature (or list of
+/// signatures, as 4byte.directory exports them), or one signature per line
+pub fn load_signature_file(path: &str) -> Result<HashMap<[u8; 4], String>> {
+    let content =
+        fs::read_to_string(path).context(format!("Failed to read signature file {}", path))?;
+    let mut signatures = HashMap::new();
+    if let Ok(Value::Object(map)) = serde_json::from_str::<Value>(&content) {
+        for (key, value) in map {
+            let bytes = hex::decode(&key).context(format!("Invalid selector {} in {}", key, path))?;
+            let sel: [u8; 4] = bytes
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Selector {} in {} is not 4 bytes", key, path))?;
+            let signature = match value {
+                Value::String(sig) => sig,
+                Value::Array(sigs) => sigs
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" | "),
+                other => anyhow::bail!("Unexpected signature {} for {} in {}", other, key, path),
+            };
+            signatures.insert(sel, signature);
+        }
+    } else {
+        for line in content.lines().map(str::trim) {
+            if !line.is_empty() && !line.starts_with('#') {
+                signatures.insert(selector(line), line.to_string());
+            }
+        }
+    }
+    info!("Loaded {} signature(s) from {}", signatures.len(), path);
+    Ok(signatures)
+}
+
+/// Runtime code of every manifest contract, from a genesis alloc or else from
+/// the `.hex` files of the bytecode directory
+fn contract_code(
+    contracts: &ContractsManifest,
+    genesis_file: Option<&str>,
+    byte_code_dir: Option<&str>,
+) -> Result<HashMap<Address, Vec<u8>>> {
+    let mut codes = HashMap::new();
+    if let Some(genesis_file) = genesis_file {
+        stream_genesis_alloc(genesis_file, |address, entry| {
+            if let (Some(code), true) = (&entry.code, contracts.contains_address(&address)) {
+                let code = hex::decode(code).context(format!("Invalid code at {:?}", address))?;
+                codes.insert(address, code);
+            }
+            Ok(())
+        })?;
+    } else if let Some(dir) = byte_code_dir {
+        contracts.preflight(dir)?;
+        for contract in &contracts.contracts {
+            codes.insert(contract.address, contract.read_code(dir, &contracts.libraries)?);
+        }
+    } else {
+        anyhow::bail!("Either a genesis file or a bytecode directory is required");
+    }
+    Ok(codes)
+}
+
+/// Selector inventory of each system contract with code
+pub fn collect_selectors(
+    genesis_file: Option<&str>,
+    byte_code_dir: Option<&str>,
+    contracts_manifest: Option<&str>,
+    signature_file: Option<&str>,
+) -> Result<Vec<ContractSelectors>> {
+    let contracts = match byte_code_dir {
+        Some(dir) => ContractsManifest::resolve(dir, contracts_manifest)?,
+        None => match contracts_manifest {
+            Some(path) => ContractsManifest::from_file(path)?,
+            None => ContractsManifest::default(),
+        },
+    };
+    let abis = byte_code_dir
+        .map(|dir| AbiRegistry::load(dir, &contracts))
+        .unwrap_or_default();
+    let signatures = signature_file.map(load_signature_file).transpose()?.unwrap_or_default();
+    let codes = contract_code(&contracts, genesis_file, byte_code_dir)?;
+
+    let mut inventories = Vec::new();
+    for contract in &contracts.contracts {
+        let Some(code) = codes.get(&contract.address) else {
+            warn!("{} ({:?}) has no code; skipped", contract.name, contract.address);
+            continue;
+        };
+        let selectors = dispatcher_selectors(code)
+            .into_iter()
+            .map(|sel| SelectorEntry {
+                selector: format!("0x{}", hex::encode(sel)),
+                signature: abis
+                    .functions
+                    .get(&sel)
+                    .or_else(|| signatures.get(&sel))
+                    .cloned(),
+            })
+            .collect();
+        inventories.push(ContractSelectors {
+            name: contract.name.clone(),
+            address: contract.address,
+            selectors,
+        });
+    }
+    Ok(inventories)
+}
+
+/// Human-readable inventory, one block per contract
+pub fn format_inventories(inventories: &[ContractSelectors]) -> String {
+    let mut out = String::new();
+    for contract in inventories {
+        out.push_str(&format!(
+            "{} ({:?}): {} selector(s), {} unresolved\n",
+            contract.name,
+            contract.address,
+            contract.selectors.len(),
+            contract.unresolved()
+        ));
+        for entry in &contract.selectors {
+            out.push_str(&format!(
+                "  {}  {}\n",
+                entry.selector,
+                entry.signature.as_deref().unwrap_or("?")
+            ));
+        }
+    }
+    out
+}
@@ -0,0 +1,308 @@
+//! `serve`: devnet artifacts over HTTP(S)
+//!
+//! Serves a devnet or generate output directory read-only so provisioning
+//! scripts on other machines can pull what each node needs:
+//!
+//! ```text
+//! GET /                       index of the served files and validators
+//! GET /genesis.json           <dir>/genesis.json or <dir>/genesis/genesis.json
+//! GET /manifest.json          <dir>/manifest.json or <dir>/genesis/manifest.json
+//! GET /validators/<moniker>   <dir>/keys/<moniker>/identity.json
+//! GET /files/<path>           any file under <dir>
+//! ```
+//!
+//! Key bundles hold private keys, so every request needs `Authorization:
+//! Bearer <token>`. Connections are handled one thread each and closed after
+//! one response; with a certificate and key the listener speaks TLS.
+
+use anyhow::{Context, Result};
+use rustls::{
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    ServerConfig, ServerConnection, StreamOwned,
+};
+use serde_json::json;
+use std::{
+    fs,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+use tracing::{debug, info, warn};
+
+/// Largest request head read before the connection is dropped
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    pub output_dir: PathBuf,
+    pub host: String,
+    pub port: u16,
+    /// Bearer token every request must present
+    pub token: String,
+    /// PEM certificate chain and private key; plain HTTP without them
+    pub tls: Option<(String, String)>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+}
+
+struct Response {
+    status: u16,
+    reason: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn json(status: u16, reason: &'static str, value: serde_json::Value) -> Self {
+        let body = serde_json::to_vec_pretty(&value).unwrap_or_default();
+        Response { status, reason, content_type: "application/json", body }
+    }
+
+    fn error(status: u16, reason: &'static str) -> Self {
+        Self::json(status, reason, json!({ "error": reason }))
+    }
+
+    fn file(path: &Path) -> Self {
+        match fs::read(path) {
+            Ok(body) => Response {
+                status: 200,
+                reason: "OK",
+                content_type: match path.extension().and_then(|e| e.to_str()) {
+                    Some("json") => "application/json",
+                    Some("csv") | Some("txt") | Some("md") => "text/plain; charset=utf-8",
+                    _ => "application/octet-stream",
+                },
+                body,
+            },
+            Err(_) => Self::error(404, "Not Found"),
+        }
+    }
+}
+
+fn load_tls_config(cert: &str, key: &str) -> Result<Arc<ServerConfig>> {
+    let certs = CertificateDer::pem_file_iter(cert)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| anyhow::anyhow!("Failed to read certificate {}: {:?}", cert, e))?;
+    let key = PrivateKeyDer::from_pem_file(key)
+        .map_err(|e| anyhow::anyhow!("Failed to read private key {}: {:?}", key, e))?;
+    let config = ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()?
+    .with_no_client_auth()
+    .with_single_cert(certs, key)
+    .context("Invalid TLS certificate or key")?;
+    Ok(Arc::new(config))
+}
+
+/// Serve `options.output_dir` until the process is stopped
+pub fn serve(options: ServeOptions) -> Result<()> {
+    if !options.output_dir.is_dir() {
+        anyhow::bail!("{} is not a directory", options.output_dir.display());
+    }
+    let tls = options
+        .tls
+        .as_ref()
+        .map(|(cert, key)| load_tls_config(cert, key))
+        .transpose()?;
+    let listener = TcpListener::bind((options.host.as_str(), options.port))
+        .context(format!("Failed to listen on {}:{}", options.host, options.port))?;
+    info!(
+        "Serving {} on {}://{}:{}",
+        options.output_dir.display(),
+        if tls.is_some() { "https" } else { "http" },
+        options.host,
+        options.port
+    );
+
+    let options = Arc::new(options);
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Failed to accept a connection: {}", e);
+                continue;
+            }
+        };
+        let options = options.clone();
+        let tls = tls.clone();
+        thread::spawn(move || {
+            let peer = stream.peer_addr().ok();
+            if let Err(e) = handle_connection(stream, &options, tls) {
+                debug!("Connection from {:?} failed: {:#}", peer, e);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    options: &ServeOptions,
+    tls: Option<Arc<ServerConfig>>,
+) -> Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    match tls {
+        Some(config) => {
+            let connection = ServerConnection::new(config)?;
+            let mut stream = StreamOwned::new(connection, stream);
+            respond(&mut stream, options)?;
+            stream.conn.send_close_notify();
+            stream.flush()?;
+            Ok(())
+        }
+        None => {
+            let mut stream = stream;
+            respond(&mut stream, options)
+        }
+    }
+}
+
+fn read_request(stream: &mut impl Read) -> Result<Request> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            anyhow::bail!("connection closed before the request head ended");
+        }
+        head.extend_from_slice(&buf[..n]);
+        if head.len() > MAX_REQUEST_HEAD {
+            anyhow::bail!("request head over {} bytes", MAX_REQUEST_HEAD);
+        }
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default();
+    let path = target.split(['?', '#']).next().unwrap_or_default().to_string();
+    let authorization = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .map(|(_, value)| value.trim().to_string());
+    Ok(Request { method, path, authorization })
+}
+
+fn respond(stream: &mut (impl Read + Write), options: &ServeOptions) -> Result<()> {
+    let request = read_request(stream)?;
+    let response = route(&request, options);
+    info!("{} {} -> {}", request.method, request.path, response.status);
+
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.reason,
+        response.content_type,
+        response.body.len()
+    );
+    if response.status == 401 {
+        head.push_str("WWW-Authenticate: Bearer\r\n");
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    if request.method != "HEAD" {
+        stream.write_all(&response.body)?;
+    }
+    stream.flush()?;
+    Ok(())
+}
+
+/// Compare in time independent of where the strings first differ
+fn token_matches(presented: &str, token: &str) -> bool {
+    presented.len() == token.len()
+        && presented
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// `path` below `root`, or `None` when it would leave it
+fn resolve(root: &Path, path: &str) -> Option<PathBuf> {
+    let relative = Path::new(path);
+    relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then(|| root.join(relative))
+}
+
+/// First of `<dir>/<name>` and `<dir>/genesis/<name>` that exists
+fn find_artifact(root: &Path, name: &str) -> Option<PathBuf> {
+    [root.join(name), root.join("genesis").join(name)]
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+fn list_files(root: &Path, dir: &Path, files: &mut Vec<String>) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            list_files(root, &path, files);
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            files.push(relative.to_string_lossy().into_owned());
+        }
+    }
+}
+
+fn validator_monikers(root: &Path) -> Vec<String> {
+    let mut monikers: Vec<String> = fs::read_dir(root.join("keys"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().join("identity.json").is_file())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    monikers.sort();
+    monikers
+}
+
+fn route(request: &Request, options: &ServeOptions) -> Response {
+    if request.method != "GET" && request.method != "HEAD" {
+        return Response::error(405, "Method Not Allowed");
+    }
+    let authorized = request
+        .authorization
+        .as_deref()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|presented| token_matches(presented.trim(), &options.token));
+    if !authorized {
+        return Response::error(401, "Unauthorized");
+    }
+
+    let root = options.output_dir.as_path();
+    let path = request.path.trim_start_matches('/');
+    let found = match path {
+        "" => {
+            let mut files = Vec::new();
+            list_files(root, root, &mut files);
+            files.sort();
+            return Response::json(
+                200,
+                "OK",
+                json!({ "files": files, "validators": validator_monikers(root) }),
+            );
+        }
+        "genesis.json" | "manifest.json" => find_artifact(root, path),
+        _ => match path.split_once('/') {
+            Some(("validators", moniker)) => resolve(&root.join("keys"), moniker)
+                .map(|dir| dir.join("identity.json"))
+                .filter(|path| path.is_file()),
+            Some(("files", file)) => resolve(root, file).filter(|path| path.is_file()),
+            _ => None,
+        },
+    };
+    match found {
+        Some(path) => Response::file(&path),
+        None => Response::error(404, "Not Found"),
+    }
+}
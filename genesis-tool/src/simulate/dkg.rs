@@ -0,0 +1,214 @@
+//! First DKG round simulation
+//!
+//! Advances the clock to the first epoch boundary and runs the block prologue,
+//! which asks `Reconfiguration` to start the epoch transition. With randomness
+//! enabled this opens a DKG session through `DKG.start()`, so a V2 randomness
+//! config, validator set or clock that genesis left in a state the contracts
+//! reject shows up as a reverted prologue instead of a stalled chain. The
+//! opened session is checked against the dealer epoch and the randomness
+//! config, then closed the way the consensus engine would once DKG completes.
+
+use alloy_primitives::Bytes;
+use alloy_sol_macro::sol;
+use anyhow::Result;
+
+use super::{finishTransitionCall, isTransitionInProgressCall, SimulationReport, Simulator};
+use crate::utils::{
+    analyze_txn_result, DKG_ADDR, RANDOMNESS_CONFIG_ADDR, RECONFIGURATION_ADDR, SYSTEM_CALLER,
+};
+
+sol! {
+    interface IDKGSession {
+        struct ConfigV2Data {
+            uint128 secrecyThreshold;
+            uint128 reconstructionThreshold;
+            uint128 fastPathSecrecyThreshold;
+        }
+
+        struct RandomnessConfigData {
+            uint8 variant;
+            ConfigV2Data configV2;
+        }
+
+        struct ValidatorConsensusInfo {
+            address validator;
+            bytes consensusPubkey;
+            bytes consensusPop;
+            uint256 votingPower;
+            uint64 validatorIndex;
+            bytes networkAddresses;
+            bytes fullnodeAddresses;
+        }
+
+        struct DKGSessionMetadata {
+            uint64 dealerEpoch;
+            RandomnessConfigData randomnessConfig;
+            ValidatorConsensusInfo[] dealerValidatorSet;
+            ValidatorConsensusInfo[] targetValidatorSet;
+        }
+
+        struct DKGSessionInfo {
+            DKGSessionMetadata metadata;
+            uint64 startTimeUs;
+            bytes transcript;
+        }
+
+        function getCurrentConfig() external view returns (RandomnessConfigData memory);
+        function isInProgress() external view returns (bool);
+        function getIncompleteSession() external view returns (bool hasSession, DKGSessionInfo memory info);
+    }
+}
+
+/// `RandomnessConfig.ConfigVariant.Off`
+const VARIANT_OFF: u8 = 0;
+
+fn same_config(
+    a: &IDKGSession::RandomnessConfigData,
+    b: &IDKGSession::RandomnessConfigData,
+) -> bool {
+    a.variant == b.variant
+        && a.configV2.secrecyThreshold == b.configV2.secrecyThreshold
+        && a.configV2.reconstructionThreshold == b.configV2.reconstructionThreshold
+        && a.configV2.fastPathSecrecyThreshold == b.configV2.fastPathSecrecyThreshold
+}
+
+/// Start the first epoch transition and check the DKG session it opens
+pub fn simulate_dkg_start(sim: &mut Simulator) -> Result<SimulationReport> {
+    let mut report = SimulationReport::default();
+    sim.start_clock()?;
+
+    let config = sim
+        .view(
+            RANDOMNESS_CONFIG_ADDR,
+            &IDKGSession::getCurrentConfigCall {},
+        )?
+        ._0;
+    let v2 = &config.configV2;
+    report.ok(
+        "randomness config",
+        if config.variant == VARIANT_OFF {
+            "Off, epoch transitions skip DKG".to_string()
+        } else {
+            format!(
+                "V2, secrecy {} reconstruction {} fast path {}",
+                v2.secrecyThreshold, v2.reconstructionThreshold, v2.fastPathSecrecyThreshold
+            )
+        },
+    );
+
+    if sim.view(DKG_ADDR, &IDKGSession::isInProgressCall {})?._0 {
+        report.fail(
+            "start transition",
+            "a DKG session is already in progress at genesis",
+        );
+        return Ok(report);
+    }
+
+    let epoch = sim.current_epoch()?;
+    let at = sim.next_epoch_time()?;
+    let result = sim.run_block_prologue(at)?;
+    if !result.is_success() {
+        report.fail("start transition", analyze_txn_result(&result));
+        return Ok(report);
+    }
+
+    if config.variant == VARIANT_OFF {
+        let new_epoch = sim.current_epoch()?;
+        if new_epoch == epoch + 1 {
+            report.ok(
+                "start transition",
+                format!("epoch {} -> {} without DKG", epoch, new_epoch),
+            );
+        } else {
+            report.fail(
+                "start transition",
+                format!(
+                    "epoch {} after the boundary at {}, expected {}",
+                    new_epoch,
+                    at,
+                    epoch + 1
+                ),
+            );
+        }
+        return Ok(report);
+    }
+
+    if !sim
+        .view(RECONFIGURATION_ADDR, &isTransitionInProgressCall {})?
+        ._0
+    {
+        report.fail(
+            "start transition",
+            format!("no transition started at {}", at),
+        );
+        return Ok(report);
+    }
+    report.ok(
+        "start transition",
+        format!("epoch {} transition started at {}", epoch, at),
+    );
+
+    let session = sim.view(DKG_ADDR, &IDKGSession::getIncompleteSessionCall {})?;
+    let metadata = &session.info.metadata;
+    let problem = if !session.hasSession {
+        Some("no session in progress".to_string())
+    } else if metadata.dealerEpoch != epoch {
+        Some(format!(
+            "dealer epoch {}, expected {}",
+            metadata.dealerEpoch, epoch
+        ))
+    } else if !same_config(&metadata.randomnessConfig, &config) {
+        Some("randomness config differs from RandomnessConfig.getCurrentConfig()".to_string())
+    } else if metadata.dealerValidatorSet.is_empty() {
+        Some("empty dealer validator set".to_string())
+    } else if metadata.targetValidatorSet.is_empty() {
+        Some("empty target validator set".to_string())
+    } else if session.info.startTimeUs != at {
+        Some(format!(
+            "started at {}, expected {}",
+            session.info.startTimeUs, at
+        ))
+    } else {
+        None
+    };
+    if let Some(problem) = problem {
+        report.fail("DKG session", problem);
+        return Ok(report);
+    }
+    report.ok(
+        "DKG session",
+        format!(
+            "dealer epoch {}, {} dealer(s), {} target(s)",
+            metadata.dealerEpoch,
+            metadata.dealerValidatorSet.len(),
+            metadata.targetValidatorSet.len()
+        ),
+    );
+
+    let finish = finishTransitionCall {
+        dkgResult: Bytes::new(),
+    };
+    if let Err(e) = sim.call(SYSTEM_CALLER, RECONFIGURATION_ADDR, &finish)? {
+        report.fail("finish transition", e);
+        return Ok(report);
+    }
+    let new_epoch = sim.current_epoch()?;
+    let cleared = !sim.view(DKG_ADDR, &IDKGSession::isInProgressCall {})?._0;
+    if new_epoch == epoch + 1 && cleared {
+        report.ok(
+            "finish transition",
+            format!("epoch {} -> {}, session cleared", epoch, new_epoch),
+        );
+    } else {
+        report.fail(
+            "finish transition",
+            format!(
+                "epoch {} (expected {}), session cleared: {}",
+                new_epoch,
+                epoch + 1,
+                cleared
+            ),
+        );
+    }
+    Ok(report)
+}
@@ -0,0 +1,274 @@
+//! Governance proposal lifecycle simulation
+//!
+//! Drives a proposal end to end against genesis state: the strongest initial pool
+//! proposes, every pool's voter votes yes with its full power, time is advanced
+//! past the voting window, and the proposal is resolved and executed. Parameter
+//! combinations that deadlock (quorum above total stake, lockups ending before
+//! the voting window closes, no executor) show up as the first failing step.
+
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolCall;
+use anyhow::Result;
+
+use super::{SimulationReport, Simulator};
+use crate::utils::{GOVERNANCE_ADDR, GOVERNANCE_CONFIG_ADDR, STAKING_ADDR};
+
+sol! {
+    interface IGovernanceConfig {
+        function minVotingThreshold() external view returns (uint128);
+        function requiredProposerStake() external view returns (uint256);
+        function votingDurationMicros() external view returns (uint64);
+        function setForNextEpoch(uint128 minVotingThreshold, uint256 requiredProposerStake, uint64 votingDurationMicros) external;
+    }
+
+    interface IStaking {
+        function getAllPools() external view returns (address[] memory);
+        function getPoolVoter(address pool) external view returns (address);
+        function getPoolVotingPower(address pool, uint64 atTime) external view returns (uint256);
+    }
+
+    interface IGovernance {
+        function owner() external view returns (address);
+        function getExecutors() external view returns (address[] memory);
+        function addExecutor(address executor) external;
+        function createProposal(address stakePool, address[] targets, bytes[] datas, string metadataUri) external returns (uint64 proposalId);
+        function vote(address stakePool, uint64 proposalId, uint128 votingPower, bool support) external;
+        function resolve(uint64 proposalId) external;
+        function getProposalState(uint64 proposalId) external view returns (uint8);
+        function execute(uint64 proposalId, address[] targets, bytes[] datas) external;
+    }
+}
+
+/// `ProposalState.SUCCEEDED` in Types.sol
+const PROPOSAL_SUCCEEDED: u8 = 1;
+const PROPOSAL_STATE_NAMES: [&str; 5] = ["PENDING", "SUCCEEDED", "FAILED", "EXECUTED", "CANCELLED"];
+
+/// A single call executed by the proposal
+#[derive(Debug, Clone)]
+pub struct ProposalAction {
+    pub target: Address,
+    pub data: Bytes,
+}
+
+/// Run the full proposal lifecycle; `actions` defaults to a no-op governance config update
+pub fn simulate_governance(
+    sim: &mut Simulator,
+    actions: Option<Vec<ProposalAction>>,
+) -> Result<SimulationReport> {
+    let mut report = SimulationReport::default();
+
+    let min_threshold = sim
+        .view(
+            GOVERNANCE_CONFIG_ADDR,
+            &IGovernanceConfig::minVotingThresholdCall {},
+        )?
+        ._0;
+    let required_stake = sim
+        .view(
+            GOVERNANCE_CONFIG_ADDR,
+            &IGovernanceConfig::requiredProposerStakeCall {},
+        )?
+        ._0;
+    let voting_duration = sim
+        .view(
+            GOVERNANCE_CONFIG_ADDR,
+            &IGovernanceConfig::votingDurationMicrosCall {},
+        )?
+        ._0;
+    report.ok(
+        "governance config",
+        format!(
+            "minVotingThreshold={}, requiredProposerStake={}, votingDurationMicros={}",
+            min_threshold, required_stake, voting_duration
+        ),
+    );
+
+    let actions = actions.unwrap_or_else(|| {
+        vec![ProposalAction {
+            target: GOVERNANCE_CONFIG_ADDR,
+            data: IGovernanceConfig::setForNextEpochCall {
+                minVotingThreshold: min_threshold,
+                requiredProposerStake: required_stake,
+                votingDurationMicros: voting_duration,
+            }
+            .abi_encode()
+            .into(),
+        }]
+    });
+    let targets: Vec<Address> = actions.iter().map(|a| a.target).collect();
+    let datas: Vec<Bytes> = actions.iter().map(|a| a.data.clone()).collect();
+
+    let now = match sim.start_clock() {
+        Ok(now) => now,
+        Err(e) => {
+            report.fail("start clock", e.to_string());
+            return Ok(report);
+        }
+    };
+    let expiration = now.saturating_add(voting_duration);
+
+    // Voting power is measured at the proposal's expiration time
+    let pools = sim.view(STAKING_ADDR, &IStaking::getAllPoolsCall {})?._0;
+    let mut voters = Vec::with_capacity(pools.len());
+    let mut total_power = U256::ZERO;
+    for pool in &pools {
+        let voter = sim
+            .view(STAKING_ADDR, &IStaking::getPoolVoterCall { pool: *pool })?
+            ._0;
+        let power = sim
+            .view(
+                STAKING_ADDR,
+                &IStaking::getPoolVotingPowerCall {
+                    pool: *pool,
+                    atTime: expiration,
+                },
+            )?
+            ._0;
+        total_power += power;
+        voters.push((*pool, voter, power));
+    }
+    let detail = format!(
+        "{} pools, {} total voting power at expiration {} (quorum {})",
+        pools.len(),
+        total_power,
+        expiration,
+        min_threshold
+    );
+    if total_power < U256::from(min_threshold) || total_power.is_zero() {
+        report.fail(
+            "voting power",
+            format!(
+                "{}; quorum is unreachable (check lockup duration vs voting duration)",
+                detail
+            ),
+        );
+        return Ok(report);
+    }
+    report.ok("voting power", detail);
+
+    let Some(&(proposer_pool, proposer, proposer_power)) =
+        voters.iter().max_by_key(|(_, _, power)| *power)
+    else {
+        report.fail("select proposer", "no stake pools exist");
+        return Ok(report);
+    };
+    if proposer_power < required_stake {
+        report.fail(
+            "select proposer",
+            format!(
+                "largest pool {:?} has {} voting power, below requiredProposerStake {}",
+                proposer_pool, proposer_power, required_stake
+            ),
+        );
+        return Ok(report);
+    }
+    report.ok(
+        "select proposer",
+        format!("pool {:?} (voter {:?})", proposer_pool, proposer),
+    );
+
+    let executors = sim
+        .view(GOVERNANCE_ADDR, &IGovernance::getExecutorsCall {})?
+        ._0;
+    let executor = match executors.first() {
+        Some(executor) => *executor,
+        None => {
+            let owner = sim.view(GOVERNANCE_ADDR, &IGovernance::ownerCall {})?._0;
+            let add = IGovernance::addExecutorCall { executor: owner };
+            if let Err(e) = sim.call(owner, GOVERNANCE_ADDR, &add)? {
+                report.fail("add executor", format!("owner {:?}: {}", owner, e));
+                return Ok(report);
+            }
+            report.ok(
+                "add executor",
+                format!(
+                    "no executors at genesis; owner {:?} must add one before any proposal can execute",
+                    owner
+                ),
+            );
+            owner
+        }
+    };
+
+    let create = IGovernance::createProposalCall {
+        stakePool: proposer_pool,
+        targets: targets.clone(),
+        datas: datas.clone(),
+        metadataUri: "genesis-tool simulation".to_string(),
+    };
+    let proposal_id = match sim.call(proposer, GOVERNANCE_ADDR, &create)? {
+        Ok(ret) => ret.proposalId,
+        Err(e) => {
+            report.fail("create proposal", e);
+            return Ok(report);
+        }
+    };
+    report.ok(
+        "create proposal",
+        format!("proposal {} with {} action(s)", proposal_id, actions.len()),
+    );
+
+    for (pool, voter, power) in &voters {
+        if power.is_zero() {
+            continue;
+        }
+        let vote = IGovernance::voteCall {
+            stakePool: *pool,
+            proposalId: proposal_id,
+            votingPower: u128::MAX,
+            support: true,
+        };
+        match sim.call(*voter, GOVERNANCE_ADDR, &vote)? {
+            Ok(_) => report.ok("vote", format!("pool {:?} voted yes with {}", pool, power)),
+            Err(e) => {
+                report.fail("vote", format!("pool {:?}: {}", pool, e));
+                return Ok(report);
+            }
+        }
+    }
+
+    // Resolution must happen strictly after the voting window and the last vote
+    if let Err(e) = sim.advance_time_to(expiration + 1) {
+        report.fail("advance time", e.to_string());
+        return Ok(report);
+    }
+    report.ok("advance time", format!("clock at {}", expiration + 1));
+
+    let resolve = IGovernance::resolveCall {
+        proposalId: proposal_id,
+    };
+    if let Err(e) = sim.call(proposer, GOVERNANCE_ADDR, &resolve)? {
+        report.fail("resolve", e);
+        return Ok(report);
+    }
+    let state = sim
+        .view(
+            GOVERNANCE_ADDR,
+            &IGovernance::getProposalStateCall {
+                proposalId: proposal_id,
+            },
+        )?
+        ._0;
+    let state_name = PROPOSAL_STATE_NAMES
+        .get(state as usize)
+        .copied()
+        .unwrap_or("UNKNOWN");
+    if state != PROPOSAL_SUCCEEDED {
+        report.fail("resolve", format!("proposal resolved as {}", state_name));
+        return Ok(report);
+    }
+    report.ok("resolve", format!("proposal resolved as {}", state_name));
+
+    let execute = IGovernance::executeCall {
+        proposalId: proposal_id,
+        targets,
+        datas,
+    };
+    match sim.call(executor, GOVERNANCE_ADDR, &execute)? {
+        Ok(_) => report.ok("execute", format!("executed by {:?}", executor)),
+        Err(e) => report.fail("execute", e),
+    }
+
+    Ok(report)
+}
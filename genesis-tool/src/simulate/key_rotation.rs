@@ -0,0 +1,228 @@
+//! Consensus key rotation simulation
+//!
+//! Rotates one active validator's BLS key as its operator and checks the
+//! pending/active semantics of `ValidatorManagement`: the new key is staged as
+//! pending, `getActiveValidators()` keeps returning the old key until the next
+//! epoch boundary, and afterwards reports the new key with the pending slot
+//! cleared. Dispatcher or storage-layout regressions surface as reverts or as
+//! stale/garbled keys.
+
+use alloy_primitives::{hex, Address, Bytes};
+use alloy_sol_macro::sol;
+use anyhow::Result;
+use rand::rngs::OsRng;
+
+use super::{SimulationReport, Simulator};
+use crate::{
+    keys::generate_bls_keypair,
+    utils::{STAKING_ADDR, VALIDATOR_MANAGER_ADDR},
+};
+
+sol! {
+    interface IValidatorKeys {
+        struct ValidatorConsensusInfo {
+            address validator;
+            bytes consensusPubkey;
+            bytes consensusPop;
+            uint256 votingPower;
+            uint64 validatorIndex;
+            bytes networkAddresses;
+            bytes fullnodeAddresses;
+        }
+
+        struct ValidatorRecord {
+            address validator;
+            string moniker;
+            uint8 status;
+            uint256 bond;
+            bytes consensusPubkey;
+            bytes consensusPop;
+            bytes networkAddresses;
+            bytes fullnodeAddresses;
+            address feeRecipient;
+            address pendingFeeRecipient;
+            address stakingPool;
+            uint64 validatorIndex;
+            bytes pendingConsensusPubkey;
+            bytes pendingConsensusPop;
+        }
+
+        function getActiveValidators() external view returns (ValidatorConsensusInfo[] memory);
+        function getValidator(address stakePool) external view returns (ValidatorRecord memory);
+        function rotateConsensusKey(address stakePool, bytes newPubkey, bytes newPop) external;
+    }
+
+    interface IStakingOperators {
+        function getPoolOperator(address pool) external view returns (address);
+    }
+}
+
+/// Caller that is not the operator of any pool
+const UNAUTHORIZED_CALLER: Address = Address::repeat_byte(0x42);
+
+fn active_key(sim: &mut Simulator, pool: Address) -> Result<Option<Bytes>> {
+    let validators = sim
+        .view(
+            VALIDATOR_MANAGER_ADDR,
+            &IValidatorKeys::getActiveValidatorsCall {},
+        )?
+        ._0;
+    Ok(validators
+        .into_iter()
+        .find(|v| v.validator == pool)
+        .map(|v| v.consensusPubkey))
+}
+
+/// Rotate the consensus key of `pool` (first active validator by default)
+pub fn simulate_key_rotation(
+    sim: &mut Simulator,
+    pool: Option<Address>,
+) -> Result<SimulationReport> {
+    let mut report = SimulationReport::default();
+
+    if let Err(e) = sim.start_clock() {
+        report.fail("start clock", e.to_string());
+        return Ok(report);
+    }
+
+    let pool = match pool {
+        Some(pool) => pool,
+        None => match sim
+            .view(
+                VALIDATOR_MANAGER_ADDR,
+                &IValidatorKeys::getActiveValidatorsCall {},
+            )?
+            ._0
+            .first()
+        {
+            Some(v) => v.validator,
+            None => {
+                report.fail("select validator", "no active validators");
+                return Ok(report);
+            }
+        },
+    };
+    let Some(old_key) = active_key(sim, pool)? else {
+        report.fail(
+            "select validator",
+            format!("{:?} is not an active validator", pool),
+        );
+        return Ok(report);
+    };
+    let operator = sim
+        .view(
+            STAKING_ADDR,
+            &IStakingOperators::getPoolOperatorCall { pool },
+        )?
+        ._0;
+    report.ok(
+        "select validator",
+        format!("pool {:?} (operator {:?})", pool, operator),
+    );
+
+    let new_key = generate_bls_keypair(&mut OsRng);
+    let rotate = IValidatorKeys::rotateConsensusKeyCall {
+        stakePool: pool,
+        newPubkey: new_key.public_key.to_vec().into(),
+        newPop: new_key.proof_of_possession.to_vec().into(),
+    };
+
+    // Only the operator may rotate
+    match sim.call(UNAUTHORIZED_CALLER, VALIDATOR_MANAGER_ADDR, &rotate)? {
+        Ok(_) => {
+            report.fail(
+                "unauthorized rotation",
+                format!(
+                    "rotation by non-operator {:?} succeeded",
+                    UNAUTHORIZED_CALLER
+                ),
+            );
+            return Ok(report);
+        }
+        Err(_) => report.ok("unauthorized rotation", "rejected for non-operator"),
+    }
+
+    if let Err(e) = sim.call(operator, VALIDATOR_MANAGER_ADDR, &rotate)? {
+        report.fail("rotate key", e);
+        return Ok(report);
+    }
+    report.ok(
+        "rotate key",
+        format!("new key 0x{}", new_key.public_key_hex()),
+    );
+
+    // Before the epoch boundary: old key active, new key pending
+    let record = sim
+        .view(
+            VALIDATOR_MANAGER_ADDR,
+            &IValidatorKeys::getValidatorCall { stakePool: pool },
+        )?
+        ._0;
+    if record.pendingConsensusPubkey.as_ref() != new_key.public_key.as_slice()
+        || record.pendingConsensusPop.as_ref() != new_key.proof_of_possession.as_slice()
+    {
+        report.fail(
+            "pending key",
+            format!(
+                "pending key is 0x{}, expected the rotated key",
+                hex::encode(&record.pendingConsensusPubkey)
+            ),
+        );
+        return Ok(report);
+    }
+    if active_key(sim, pool)?.as_ref() != Some(&old_key) {
+        report.fail(
+            "pending key",
+            "active key changed before the epoch boundary",
+        );
+        return Ok(report);
+    }
+    report.ok("pending key", "staged as pending, active key unchanged");
+
+    // After the epoch boundary: new key active, pending cleared
+    let epoch = match sim.advance_to_next_epoch() {
+        Ok(epoch) => epoch,
+        Err(e) => {
+            report.fail("epoch transition", e.to_string());
+            return Ok(report);
+        }
+    };
+    report.ok("epoch transition", format!("now in epoch {}", epoch));
+
+    match active_key(sim, pool)? {
+        Some(key) if key.as_ref() == new_key.public_key.as_slice() => {}
+        Some(key) => {
+            report.fail(
+                "key applied",
+                format!(
+                    "active key is 0x{} after the epoch boundary, expected the rotated key",
+                    hex::encode(&key)
+                ),
+            );
+            return Ok(report);
+        }
+        None => {
+            report.fail(
+                "key applied",
+                format!("{:?} dropped out of the active set", pool),
+            );
+            return Ok(report);
+        }
+    }
+    let record = sim
+        .view(
+            VALIDATOR_MANAGER_ADDR,
+            &IValidatorKeys::getValidatorCall { stakePool: pool },
+        )?
+        ._0;
+    if !record.pendingConsensusPubkey.is_empty() {
+        report.fail(
+            "key applied",
+            "pending key not cleared after the epoch boundary",
+        );
+        return Ok(report);
+    }
+    report.ok("key applied", "rotated key active, pending slot cleared");
+
+    Ok(report)
+}
@@ -0,0 +1,554 @@
+//! Post-genesis flow simulation
+//!
+//! A [`Simulator`] wraps an in-memory EVM loaded with a genesis alloc and lets
+//! flows be driven step by step: impersonated calls from arbitrary accounts,
+//! read-only queries, and advancing time through the block prologue. Each flow
+//! records a [`SimulationReport`] of the steps it ran, so the first step that
+//! reverts points at the misconfiguration.
+
+pub mod dkg;
+pub mod governance;
+pub mod key_rotation;
+pub mod oracle;
+pub mod script;
+pub mod staking;
+
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolCall;
+use anyhow::{anyhow, Context, Result};
+use revm::{
+    db::{states::StorageSlot, AccountState, AccountStatus, BundleAccount, BundleState},
+    DatabaseRef, EvmBuilder, InMemoryDB,
+};
+use revm_primitives::{BlockEnv, Env, ExecutionResult, SpecId, TxEnv, TxKind};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+    time::Duration,
+};
+use tracing::{error, info};
+
+use crate::{
+    execute::prepare_env,
+    genesis::EvmEnvConfig,
+    precompiles::{handle_register, PrecompileMode},
+    spec::resolve_spec,
+    utils::{
+        analyze_txn_result, override_coinbase, BLOCK_ADDR, EPOCH_CONFIG_ADDR, RECONFIGURATION_ADDR, SYSTEM_CALLER,
+        TIMESTAMP_ADDR,
+    },
+    verify::load_genesis_db,
+};
+
+/// Active validator index used as the proposer of simulated blocks
+const SIMULATED_PROPOSER_INDEX: u64 = 0;
+
+const MICROS_PER_SECOND: u64 = 1_000_000;
+
+sol! {
+    function onBlockStart(uint64 proposerIndex, uint64[] calldata failedProposerIndices, uint64 timestampMicros) external;
+    function nowMicroseconds() external view returns (uint64);
+    function isTransitionInProgress() external view returns (bool);
+    function finishTransition(bytes calldata dkgResult) external;
+    function lastReconfigurationTime() external view returns (uint64);
+    function currentEpoch() external view returns (uint64);
+    function epochIntervalMicros() external view returns (uint64);
+}
+
+/// Flows that can be simulated against generated or existing genesis state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SimulationFlow {
+    Governance,
+    Staking,
+    KeyRotation,
+    /// Record a sample blockchain event and check its callback (the bridge, if deployed)
+    OracleRecord,
+    /// Start the first epoch transition and check the DKG session it opens
+    DkgStart,
+}
+
+/// Run one flow with its default parameters
+pub fn run_flow(sim: &mut Simulator, flow: SimulationFlow) -> Result<SimulationReport> {
+    match flow {
+        SimulationFlow::Governance => governance::simulate_governance(sim, None),
+        SimulationFlow::Staking => staking::simulate_staking(sim, None, None),
+        SimulationFlow::KeyRotation => key_rotation::simulate_key_rotation(sim, None),
+        SimulationFlow::OracleRecord => {
+            oracle::simulate_oracle_record(sim, oracle::BLOCKCHAIN_SOURCE_TYPE)
+        }
+        SimulationFlow::DkgStart => dkg::simulate_dkg_start(sim),
+    }
+}
+
+/// A simulator's state and block fields, restored with [`Simulator::restore`].
+/// The state is every account of the simulated EVM as a [`BundleState`], in
+/// the format of `bundle_state.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatorSnapshot {
+    pub block: BlockEnv,
+    pub state: BundleState,
+}
+
+impl SimulatorSnapshot {
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let file = File::create(path).context(format!("Failed to create {}", path.display()))?;
+        serde_json::to_writer(BufWriter::new(file), self)
+            .context(format!("Failed to write snapshot {}", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).context(format!("Failed to open {}", path.display()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .context(format!("Failed to decode snapshot {}", path.display()))
+    }
+}
+
+pub struct Simulator {
+    db: InMemoryDB,
+    env: Env,
+    spec_id: SpecId,
+    precompiles: PrecompileMode,
+}
+
+impl Simulator {
+    pub fn new(db: InMemoryDB, chain_id: u64) -> Self {
+        Self {
+            db,
+            env: prepare_env(chain_id),
+            spec_id: SpecId::LATEST,
+            precompiles: PrecompileMode::default(),
+        }
+    }
+
+    /// Execute under `spec_id` instead of [`SpecId::LATEST`]
+    pub fn with_spec_id(mut self, spec_id: SpecId) -> Self {
+        self.spec_id = spec_id;
+        self
+    }
+
+    /// Execute under the block fields of a config's `evmEnv`; call after
+    /// [`Self::with_spec_id`], which decides the blob base fee
+    pub fn with_evm_env(mut self, evm_env: Option<&EvmEnvConfig>) -> Self {
+        if let Some(evm_env) = evm_env {
+            evm_env.apply(&mut self.env, self.spec_id);
+        }
+        self
+    }
+
+    /// Choose how the chain precompiles (native mint, BLS PoP verify) behave
+    pub fn with_precompiles(mut self, precompiles: PrecompileMode) -> Self {
+        self.precompiles = precompiles;
+        self
+    }
+
+    /// Load the alloc of a genesis.json and start the clock at its timestamp,
+    /// executing under the spec its fork schedule activates at genesis
+    pub fn from_genesis_file(genesis_path: &str, chain_id: u64) -> Result<Self> {
+        let (db, _, header) = load_genesis_db(genesis_path)?;
+        let secs = header.timestamp_secs()?;
+        let mut simulator = Self::new(db, chain_id)
+            .with_spec_id(resolve_spec(None, header.config.as_ref(), secs));
+        if secs > 0 {
+            simulator.env.block.timestamp = U256::from(secs);
+        }
+        Ok(simulator)
+    }
+
+    /// Build from the deployment database plus the state produced by genesis execution
+    pub fn from_bundle(mut db: InMemoryDB, bundle_state: &BundleState, chain_id: u64) -> Self {
+        for (address, account) in &bundle_state.state {
+            let Some(info) = &account.info else {
+                continue;
+            };
+            db.insert_account_info(*address, info.clone());
+            // Accounts replaced at assembly keep none of their deployed storage
+            if account.status.was_destroyed() {
+                db.replace_account_storage(*address, Default::default())
+                    .expect("in-memory storage insert cannot fail");
+            }
+            for (slot, value) in &account.storage {
+                db.insert_account_storage(*address, *slot, value.present_value())
+                    .expect("in-memory storage insert cannot fail");
+            }
+        }
+        Self::new(db, chain_id)
+    }
+
+    /// Current balance of `address`
+    pub fn balance(&self, address: Address) -> U256 {
+        self.db
+            .basic_ref(address)
+            .ok()
+            .flatten()
+            .map_or(U256::ZERO, |info| info.balance)
+    }
+
+    /// Credit `amount` to `address` out of thin air
+    pub fn fund(&mut self, address: Address, amount: U256) {
+        let account = self
+            .db
+            .load_account(address)
+            .expect("in-memory account load cannot fail");
+        // A fresh account is cached as not existing, which would hide the credit
+        if matches!(account.account_state, AccountState::NotExisting) {
+            account.account_state = AccountState::None;
+        }
+        account.info.balance += amount;
+    }
+
+    /// Execute a call from `from` and commit its state changes
+    pub fn transact(
+        &mut self,
+        from: Address,
+        to: Address,
+        data: Bytes,
+        value: U256,
+    ) -> Result<ExecutionResult> {
+        let tx = TxEnv {
+            caller: from,
+            gas_limit: self.env.tx.gas_limit,
+            gas_price: U256::ZERO,
+            transact_to: TxKind::Call(to),
+            value,
+            data,
+            nonce: None,
+            ..Default::default()
+        };
+        self.transact_txn(tx)
+    }
+
+    /// Execute a prepared transaction as is and commit its state changes
+    pub fn transact_txn(&mut self, tx: TxEnv) -> Result<ExecutionResult> {
+        let to = match tx.transact_to {
+            TxKind::Call(to) => to,
+            TxKind::Create => Address::ZERO,
+        };
+        // Gas is free (zero gas price), so impersonated callers need no funding
+        let mut env = self.env.clone();
+        env.tx = tx;
+
+        let mut evm = EvmBuilder::default()
+            .with_db(&mut self.db)
+            .with_spec_id(self.spec_id)
+            .with_env(Box::new(env))
+            .append_handler_register(handle_register(self.precompiles))
+            .build();
+        evm.transact_commit()
+            .map_err(|e| anyhow!("EVM error calling {:?}: {:?}", to, e))
+    }
+
+    /// Capture the current state, clock and block number
+    pub fn snapshot(&self) -> SimulatorSnapshot {
+        // Accounts cached as not existing read the same from the empty backend
+        let state = self
+            .db
+            .accounts
+            .iter()
+            .filter_map(|(address, account)| {
+                let storage = account
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| (*slot, StorageSlot::new(*value)))
+                    .collect();
+                let account = BundleAccount::new(None, account.info(), storage, AccountStatus::Loaded);
+                account.info.is_some().then_some((*address, account))
+            })
+            .collect();
+        let mut bundle_state = BundleState {
+            state,
+            contracts: self.db.contracts.clone(),
+            ..Default::default()
+        };
+        bundle_state.state_size = bundle_state.state.values().map(|account| 1 + account.storage.len()).sum();
+        SimulatorSnapshot {
+            block: self.env.block.clone(),
+            state: bundle_state,
+        }
+    }
+
+    /// Return to a state captured by [`Self::snapshot`]
+    pub fn restore(&mut self, snapshot: SimulatorSnapshot) {
+        let mut db = InMemoryDB::default();
+        for (address, account) in snapshot.state.state {
+            let Some(mut info) = account.info else {
+                continue;
+            };
+            if info.code.is_none() {
+                info.code = snapshot.state.contracts.get(&info.code_hash).cloned();
+            }
+            db.insert_account_info(address, info);
+            let storage = account
+                .storage
+                .into_iter()
+                .map(|(slot, value)| (slot, value.present_value()))
+                .collect();
+            db.replace_account_storage(address, storage)
+                .expect("in-memory storage insert cannot fail");
+        }
+        self.db = db;
+        self.env.block = snapshot.block;
+    }
+
+    /// Block coinbase of the calls executed next
+    pub fn coinbase(&self) -> Address {
+        self.env.block.coinbase
+    }
+
+    /// Set the block coinbase of the calls executed next, returning the previous one
+    pub fn set_coinbase(&mut self, coinbase: Address) -> Address {
+        override_coinbase(&mut self.env, coinbase)
+    }
+
+    /// Typed call; returns the decoded output or the revert description
+    pub fn call<C: SolCall>(
+        &mut self,
+        from: Address,
+        to: Address,
+        call: &C,
+    ) -> Result<std::result::Result<C::Return, String>> {
+        let result = self.transact(from, to, call.abi_encode().into(), U256::ZERO)?;
+        Ok(match &result {
+            ExecutionResult::Success { output, .. } => C::abi_decode_returns(output.data(), true)
+                .map_err(|e| format!("Failed to decode {} output: {}", C::SIGNATURE, e)),
+            _ => Err(analyze_txn_result(&result)),
+        })
+    }
+
+    /// Untyped read-only call that must succeed; returns the raw output
+    pub fn view_raw(&mut self, to: Address, data: Bytes) -> Result<Bytes> {
+        let result = self.transact(Address::ZERO, to, data, U256::ZERO)?;
+        match &result {
+            ExecutionResult::Success { output, .. } => Ok(output.data().clone()),
+            _ => Err(anyhow!("call on {:?} failed: {}", to, analyze_txn_result(&result))),
+        }
+    }
+
+    /// Read-only typed call that must succeed
+    pub fn view<C: SolCall>(&mut self, to: Address, call: &C) -> Result<C::Return> {
+        self.call(Address::ZERO, to, call)?
+            .map_err(|e| anyhow!("{} on {:?} failed: {}", C::SIGNATURE, to, e))
+    }
+
+    /// Current on-chain time in microseconds
+    pub fn now_micros(&mut self) -> Result<u64> {
+        Ok(self.view(TIMESTAMP_ADDR, &nowMicrosecondsCall {})?._0)
+    }
+
+    /// Move the on-chain clock forward to `micros` by running a block prologue.
+    ///
+    /// A due epoch transition is started by the prologue; if it waits on DKG it is
+    /// finished immediately, as the consensus engine would once DKG completes.
+    pub fn advance_time_to(&mut self, micros: u64) -> Result<()> {
+        let result = self.run_block_prologue(micros)?;
+        if !result.is_success() {
+            return Err(anyhow!(
+                "Failed to advance time to {}: {}",
+                micros,
+                analyze_txn_result(&result)
+            ));
+        }
+
+        if self
+            .view(RECONFIGURATION_ADDR, &isTransitionInProgressCall {})?
+            ._0
+        {
+            let finish = finishTransitionCall {
+                dkgResult: Bytes::new(),
+            };
+            self.call(SYSTEM_CALLER, RECONFIGURATION_ADDR, &finish)?
+                .map_err(|e| anyhow!("Failed to finish epoch transition: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Run the block prologue of a new block at `micros`, leaving any epoch
+    /// transition it starts in progress; the block only advances on success
+    pub fn run_block_prologue(&mut self, micros: u64) -> Result<ExecutionResult> {
+        let call = onBlockStartCall {
+            proposerIndex: SIMULATED_PROPOSER_INDEX,
+            failedProposerIndices: vec![],
+            timestampMicros: micros,
+        };
+        let result = self.transact(SYSTEM_CALLER, BLOCK_ADDR, call.abi_encode().into(), U256::ZERO)?;
+        if result.is_success() {
+            self.env.block.timestamp = U256::from(micros / MICROS_PER_SECOND);
+            self.env.block.number += U256::from(1);
+        }
+        Ok(result)
+    }
+
+    /// Earliest on-chain time at which the next epoch transition is due
+    pub fn next_epoch_time(&mut self) -> Result<u64> {
+        let last = self
+            .view(RECONFIGURATION_ADDR, &lastReconfigurationTimeCall {})?
+            ._0;
+        let interval = self
+            .view(EPOCH_CONFIG_ADDR, &epochIntervalMicrosCall {})?
+            ._0;
+        Ok(last.saturating_add(interval).max(self.now_micros()? + 1))
+    }
+
+    /// Current epoch number
+    pub fn current_epoch(&mut self) -> Result<u64> {
+        Ok(self.view(RECONFIGURATION_ADDR, &currentEpochCall {})?._0)
+    }
+
+    /// Advance time until the next epoch transition has been applied, returning the new epoch
+    pub fn advance_to_next_epoch(&mut self) -> Result<u64> {
+        let epoch = self.current_epoch()?;
+        let target = self.next_epoch_time()?;
+        self.advance_time_to(target)?;
+
+        let new_epoch = self.current_epoch()?;
+        if new_epoch <= epoch {
+            return Err(anyhow!(
+                "No epoch transition at {} (still epoch {})",
+                target,
+                new_epoch
+            ));
+        }
+        Ok(new_epoch)
+    }
+
+    /// Advance the on-chain clock by `duration`, running a block at every epoch
+    /// transition due on the way so each reconfiguration is applied; returns
+    /// the number of epochs crossed
+    pub fn advance_time(&mut self, duration: Duration) -> Result<u64> {
+        let micros = u64::try_from(duration.as_micros())
+            .map_err(|_| anyhow!("Cannot advance time by {:?}", duration))?;
+        let target = self.start_clock()?.saturating_add(micros);
+        let mut crossed = 0;
+        while self.next_epoch_time()? <= target {
+            self.advance_to_next_epoch()?;
+            crossed += 1;
+        }
+        if self.now_micros()? < target {
+            self.advance_time_to(target)?;
+        }
+        Ok(crossed)
+    }
+
+    /// Advance through `epochs` epoch transitions, returning the new epoch
+    pub fn advance_epochs(&mut self, epochs: u64) -> Result<u64> {
+        self.start_clock()?;
+        let mut epoch = self.current_epoch()?;
+        for _ in 0..epochs {
+            epoch = self.advance_to_next_epoch()?;
+        }
+        Ok(epoch)
+    }
+
+    /// Seed the on-chain clock from the block timestamp if it has never been set
+    pub fn start_clock(&mut self) -> Result<u64> {
+        let now = self.now_micros()?;
+        if now > 0 {
+            return Ok(now);
+        }
+        let start = self.env.block.timestamp.to::<u64>() * MICROS_PER_SECOND;
+        self.advance_time_to(start)?;
+        Ok(start)
+    }
+}
+
+/// `1d`, `6h`, `30m`, `90s`, `500ms` or plain seconds
+pub fn parse_duration(text: &str) -> std::result::Result<Duration, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (amount, unit) = text.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration {:?}: expected e.g. 90s, 30m, 6h or 2d", text))?;
+    let secs = match unit.trim() {
+        "ms" => return Ok(Duration::from_millis(amount)),
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86_400,
+        "w" => 604_800,
+        other => return Err(format!("unknown duration unit {:?} in {:?}", other, text)),
+    };
+    amount
+        .checked_mul(secs)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration {:?} is too long", text))
+}
+
+/// Time travel applied before a simulation's own steps
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimeTravel {
+    /// Advance the clock by this much, applying every epoch transition on the way
+    pub advance_time: Option<Duration>,
+    /// Then run this many more epoch transitions
+    pub advance_epochs: u64,
+}
+
+impl TimeTravel {
+    pub fn apply(&self, sim: &mut Simulator) -> Result<()> {
+        if let Some(duration) = self.advance_time {
+            let crossed = sim.advance_time(duration)?;
+            info!(
+                "Advanced time by {:?} to {} micros, crossing {} epoch transition(s)",
+                duration,
+                sim.now_micros()?,
+                crossed
+            );
+        }
+        if self.advance_epochs > 0 {
+            let epoch = sim.advance_epochs(self.advance_epochs)?;
+            info!("Advanced {} epoch(s) to epoch {}", self.advance_epochs, epoch);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct SimulationStep {
+    pub name: String,
+    pub success: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Default)]
+pub struct SimulationReport {
+    pub steps: Vec<SimulationStep>,
+}
+
+impl SimulationReport {
+    pub fn ok(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.steps.push(SimulationStep {
+            name: name.into(),
+            success: true,
+            detail: detail.into(),
+        });
+    }
+
+    pub fn fail(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.steps.push(SimulationStep {
+            name: name.into(),
+            success: false,
+            detail: detail.into(),
+        });
+    }
+
+    /// First failed step, i.e. where the flow got stuck
+    pub fn failure(&self) -> Option<&SimulationStep> {
+        self.steps.iter().find(|step| !step.success)
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.failure().is_none()
+    }
+
+    pub fn log(&self) {
+        for (i, step) in self.steps.iter().enumerate() {
+            if step.success {
+                info!("✅ [{}] {}: {}", i + 1, step.name, step.detail);
+            } else {
+                error!("❌ [{}] {}: {}", i + 1, step.name, step.detail);
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! NativeOracle record simulation
+//!
+//! Records one sample cross-chain event through `NativeOracle.record()` as the
+//! consensus caller and checks that the callback registered for the source
+//! type was invoked, from the `CallbackSuccess`/`CallbackFailed` events. When
+//! the callback is the genesis-deployed `GBridgeReceiver`, the sample is a
+//! bridge deposit from its trusted bridge and the minted balance is checked
+//! too, covering the wiring from oracle to native mint.
+
+use alloy_primitives::{hex, Address, Bytes, Log, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::{SolCall, SolEvent, SolValue};
+use anyhow::Result;
+use revm_primitives::ExecutionResult;
+
+use super::{SimulationReport, Simulator};
+use crate::utils::{analyze_txn_result, NATIVE_ORACLE_ADDR, SYSTEM_CALLER};
+
+sol! {
+    interface INativeOracleRecord {
+        event CallbackSuccess(uint32 indexed sourceType, uint256 indexed sourceId, uint128 nonce, address callback);
+        event CallbackFailed(uint32 indexed sourceType, uint256 indexed sourceId, uint128 nonce, address callback, bytes reason);
+        event CallbackSkipped(uint32 indexed sourceType, uint256 indexed sourceId, uint128 nonce, address callback);
+
+        function record(uint32 sourceType, uint256 sourceId, uint128 nonce, uint256 blockNumber, bytes calldata payload, uint256 callbackGasLimit) external;
+        function getCallback(uint32 sourceType, uint256 sourceId) external view returns (address callback);
+        function getDefaultCallback(uint32 sourceType) external view returns (address callback);
+        function getLatestNonce(uint32 sourceType, uint256 sourceId) external view returns (uint128 nonce);
+    }
+
+    interface IBridgeReceiverInfo {
+        function trustedBridge() external view returns (address);
+        function trustedSourceId() external view returns (uint256);
+    }
+}
+
+/// Source type of blockchain events, where the bridge receiver is registered
+pub const BLOCKCHAIN_SOURCE_TYPE: u32 = 0;
+
+/// Source ID used when the callback does not name a trusted one
+const DEFAULT_SOURCE_ID: u64 = 1;
+
+/// Gas the consensus engine grants callbacks in the simulated record
+const CALLBACK_GAS_LIMIT: u64 = 1_000_000;
+
+/// Sender on the source chain when the callback does not name a trusted bridge
+const SAMPLE_SENDER: Address = Address::repeat_byte(0x5e);
+
+/// Recipient of the sample bridge deposit
+const SAMPLE_RECIPIENT: Address = Address::repeat_byte(0x7e);
+
+/// Amount of the sample bridge deposit, 1 ether
+const SAMPLE_AMOUNT: u64 = 1_000_000_000_000_000_000;
+
+/// Portal payload: sender (20 bytes) || message nonce (16 bytes) || message
+fn portal_payload(sender: Address, message_nonce: u128, message: &[u8]) -> Bytes {
+    let mut payload = sender.to_vec();
+    payload.extend_from_slice(&message_nonce.to_be_bytes());
+    payload.extend_from_slice(message);
+    payload.into()
+}
+
+/// Decoded NativeOracle event of `result` for `T`, if emitted
+fn oracle_event<T: SolEvent>(result: &ExecutionResult) -> Option<T> {
+    result
+        .logs()
+        .iter()
+        .filter(|log: &&Log| log.address == NATIVE_ORACLE_ADDR)
+        .find_map(|log| T::decode_log_data(&log.data, true).ok())
+}
+
+/// Record a sample event for `source_type` and check its callback ran
+pub fn simulate_oracle_record(sim: &mut Simulator, source_type: u32) -> Result<SimulationReport> {
+    let mut report = SimulationReport::default();
+
+    let callback = sim
+        .view(
+            NATIVE_ORACLE_ADDR,
+            &INativeOracleRecord::getDefaultCallbackCall {
+                sourceType: source_type,
+            },
+        )?
+        .callback;
+    if callback == Address::ZERO {
+        report.fail(
+            "resolve callback",
+            format!("no callback registered for sourceType {}", source_type),
+        );
+        return Ok(report);
+    }
+
+    // A bridge receiver only accepts its trusted source and sender
+    let trusted_source_id = sim
+        .call(
+            Address::ZERO,
+            callback,
+            &IBridgeReceiverInfo::trustedSourceIdCall {},
+        )?
+        .ok();
+    let trusted_bridge = sim
+        .call(
+            Address::ZERO,
+            callback,
+            &IBridgeReceiverInfo::trustedBridgeCall {},
+        )?
+        .ok();
+    let bridge = trusted_source_id
+        .zip(trusted_bridge)
+        .map(|(source_id, sender)| (source_id._0, sender._0));
+    let (source_id, sender) = bridge.unwrap_or((U256::from(DEFAULT_SOURCE_ID), SAMPLE_SENDER));
+
+    let resolved = sim
+        .view(
+            NATIVE_ORACLE_ADDR,
+            &INativeOracleRecord::getCallbackCall {
+                sourceType: source_type,
+                sourceId: source_id,
+            },
+        )?
+        .callback;
+    report.ok(
+        "resolve callback",
+        format!(
+            "sourceType {} sourceId {} -> {:?}{}",
+            source_type,
+            source_id,
+            resolved,
+            if bridge.is_some() {
+                " (bridge receiver)"
+            } else {
+                ""
+            }
+        ),
+    );
+
+    let nonce = sim
+        .view(
+            NATIVE_ORACLE_ADDR,
+            &INativeOracleRecord::getLatestNonceCall {
+                sourceType: source_type,
+                sourceId: source_id,
+            },
+        )?
+        .nonce
+        + 1;
+    let message = (U256::from(SAMPLE_AMOUNT), SAMPLE_RECIPIENT).abi_encode_params();
+    let record = INativeOracleRecord::recordCall {
+        sourceType: source_type,
+        sourceId: source_id,
+        nonce,
+        blockNumber: U256::from(1),
+        payload: portal_payload(sender, nonce, &message),
+        callbackGasLimit: U256::from(CALLBACK_GAS_LIMIT),
+    };
+    let balance_before = sim.balance(SAMPLE_RECIPIENT);
+    let result = sim.transact(
+        SYSTEM_CALLER,
+        NATIVE_ORACLE_ADDR,
+        record.abi_encode().into(),
+        U256::ZERO,
+    )?;
+    if !result.is_success() {
+        report.fail("record", analyze_txn_result(&result));
+        return Ok(report);
+    }
+    report.ok(
+        "record",
+        format!("nonce {} recorded as the system caller", nonce),
+    );
+
+    if let Some(failed) = oracle_event::<INativeOracleRecord::CallbackFailed>(&result) {
+        report.fail(
+            "callback",
+            format!(
+                "{:?} reverted: 0x{}",
+                failed.callback,
+                hex::encode(&failed.reason)
+            ),
+        );
+        return Ok(report);
+    }
+    match oracle_event::<INativeOracleRecord::CallbackSuccess>(&result) {
+        Some(success) if success.callback == resolved => {
+            report.ok("callback", format!("{:?} invoked", success.callback))
+        }
+        Some(success) => {
+            report.fail(
+                "callback",
+                format!(
+                    "{:?} invoked, expected the registered {:?}",
+                    success.callback, resolved
+                ),
+            );
+            return Ok(report);
+        }
+        None => {
+            let detail = match oracle_event::<INativeOracleRecord::CallbackSkipped>(&result) {
+                Some(_) => "callback skipped".to_string(),
+                None => "no callback event emitted".to_string(),
+            };
+            report.fail("callback", detail);
+            return Ok(report);
+        }
+    }
+
+    if bridge.is_some() {
+        let minted = sim.balance(SAMPLE_RECIPIENT) - balance_before;
+        if minted == U256::from(SAMPLE_AMOUNT) {
+            report.ok(
+                "bridge mint",
+                format!("{} wei minted to {:?}", minted, SAMPLE_RECIPIENT),
+            );
+        } else {
+            report.fail(
+                "bridge mint",
+                format!(
+                    "{} wei minted to {:?}, expected {}",
+                    minted, SAMPLE_RECIPIENT, SAMPLE_AMOUNT
+                ),
+            );
+        }
+    }
+    Ok(report)
+}
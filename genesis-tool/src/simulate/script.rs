@@ -0,0 +1,284 @@
+//! Scripted calls against genesis state
+//!
+//! `exec --script <file>` runs a JSON array of steps in order, each committed
+//! before the next. A step is a call written like `postGenesisCalls` entries
+//! (`target`, `signature`, `args`, `caller`, `value`) plus how to execute it:
+//! `coinbase` sets the block coinbase for the step, and `asCoinbase` sends it
+//! from the coinbase with a zero gas price, which is what `onlyCoinbase` and
+//! `onlyZeroGasPrice` guards of Blocker-style system flows check. A step with
+//! `expectRevert` succeeds when the call reverts with a description containing
+//! that text, so access control can be exercised as well.
+//!
+//! A step may instead move time between calls: `{ "advanceTime": "6h" }`
+//! runs a block at every epoch transition due in that span, and
+//! `{ "advanceEpochs": 2 }` runs that many transitions, so multi-epoch
+//! scenarios play out as the chain would; both may be combined, time first.
+//!
+//! `{ "snapshot": "name" }` serializes the state to `name.snapshot.json` in
+//! [`ScriptOptions::snapshot_dir`] and `{ "revert": "name" }` restores it, as
+//! anvil's `evm_snapshot` and `evm_revert` do: the snapshot and every one taken
+//! after it are consumed, so a scenario that reverts repeatedly takes the
+//! snapshot again each time. Consumed snapshot files are left on disk.
+//!
+//! Steps without a `caller` are sent from [`ScriptOptions::from`], else the
+//! system caller. Every sender is impersonated: before its first step it is
+//! credited [`IMPERSONATION_FUNDING_ETHER`] in the ephemeral state, so calls that
+//! check or move the sender's balance behave as for a funded account.
+
+use alloy_primitives::{hex, Address, U256};
+use anyhow::{Context, Result};
+use revm_primitives::{ExecutionResult, TxEnv};
+use serde::{Deserialize, Deserializer};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::info;
+
+use super::{parse_duration, SimulationReport, Simulator, SimulatorSnapshot, TimeTravel};
+use crate::{
+    genesis::GenesisCall,
+    utils::{analyze_txn_result, new_coinbase_call_txn},
+};
+
+/// Ether credited to each sender before its first step
+pub const IMPERSONATION_FUNDING_ETHER: u64 = 1000;
+
+#[derive(Debug, Clone, Default)]
+pub struct ScriptOptions {
+    /// Sender of steps without their own `caller`
+    pub from: Option<Address>,
+    /// Applied before the first step
+    pub time_travel: TimeTravel,
+    /// Directory `snapshot` steps write their files to
+    pub snapshot_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ScriptStep {
+    Advance(AdvanceStep),
+    Snapshot(SnapshotStep),
+    Revert(RevertStep),
+    Call(CallStep),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotStep {
+    pub snapshot: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RevertStep {
+    pub revert: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AdvanceStep {
+    /// Duration such as `90s`, `30m`, `6h` or `2d`
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub advance_time: Option<Duration>,
+
+    #[serde(default)]
+    pub advance_epochs: u64,
+}
+
+fn deserialize_duration<'de, D>(deserializer: D) -> std::result::Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+    parse_duration(&text).map(Some).map_err(serde::de::Error::custom)
+}
+
+impl AdvanceStep {
+    fn time_travel(&self) -> TimeTravel {
+        TimeTravel {
+            advance_time: self.advance_time,
+            advance_epochs: self.advance_epochs,
+        }
+    }
+
+    fn label(&self) -> String {
+        match (self.advance_time, self.advance_epochs) {
+            (Some(duration), 0) => format!("advance {:?}", duration),
+            (None, epochs) => format!("advance {} epoch(s)", epochs),
+            (Some(duration), epochs) => format!("advance {:?} and {} epoch(s)", duration, epochs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallStep {
+    /// Label in the report (defaults to the signature)
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(flatten)]
+    pub call: GenesisCall,
+
+    /// Block coinbase while the step executes
+    #[serde(default)]
+    pub coinbase: Option<Address>,
+
+    /// Send from the block coinbase instead of `caller`
+    #[serde(default)]
+    pub as_coinbase: bool,
+
+    /// Text the revert description must contain, e.g. `OnlyCoinbase`
+    #[serde(default)]
+    pub expect_revert: Option<String>,
+}
+
+impl CallStep {
+    /// Step making `call` with no execution options
+    pub fn new(call: GenesisCall) -> Self {
+        CallStep {
+            name: None,
+            call,
+            coinbase: None,
+            as_coinbase: false,
+            expect_revert: None,
+        }
+    }
+
+    fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.call.signature.clone())
+    }
+
+    /// Transaction of the step, for a block whose coinbase is `coinbase`
+    fn to_txn(&self, coinbase: Address, from: Option<Address>) -> Result<TxEnv> {
+        if !self.as_coinbase {
+            let tx = self.call.to_txn()?;
+            return Ok(match from.filter(|_| self.call.caller.is_none()) {
+                Some(from) => TxEnv { caller: from, ..tx },
+                None => tx,
+            });
+        }
+        if self.call.caller.is_some() {
+            anyhow::bail!("caller and asCoinbase are exclusive");
+        }
+        Ok(TxEnv {
+            value: self.call.value_wei()?,
+            ..new_coinbase_call_txn(
+                coinbase,
+                self.call.target_address()?,
+                self.call.call_data()?.into(),
+            )
+        })
+    }
+}
+
+pub fn load_script(path: &str) -> Result<Vec<ScriptStep>> {
+    let content = fs::read_to_string(path).context(format!("Failed to read script {}", path))?;
+    serde_json::from_str(&content).context(format!("Failed to decode script {}", path))
+}
+
+fn execute_call(
+    sim: &mut Simulator,
+    step: &CallStep,
+    options: &ScriptOptions,
+    funded: &mut HashSet<Address>,
+) -> Result<ExecutionResult> {
+    let previous = step.coinbase.map(|coinbase| sim.set_coinbase(coinbase));
+    let result = step.to_txn(sim.coinbase(), options.from).and_then(|tx| {
+        if funded.insert(tx.caller) {
+            let funding = U256::from(IMPERSONATION_FUNDING_ETHER) * U256::from(10).pow(U256::from(18));
+            sim.fund(tx.caller, funding);
+            info!("Impersonating {:?}, credited {} ether", tx.caller, IMPERSONATION_FUNDING_ETHER);
+        }
+        if tx.value > U256::ZERO {
+            sim.fund(tx.caller, tx.value);
+        }
+        sim.transact_txn(tx)
+    });
+    if let Some(previous) = previous {
+        sim.set_coinbase(previous);
+    }
+    result
+}
+
+/// Run `steps` in order, stopping at the first one that does not go as expected
+pub fn run_script(
+    sim: &mut Simulator,
+    steps: &[ScriptStep],
+    options: &ScriptOptions,
+) -> Result<SimulationReport> {
+    let mut report = SimulationReport::default();
+    let mut funded = HashSet::new();
+    // Oldest first; the funded senders are saved too, since their credit is state
+    let mut snapshots: Vec<(String, PathBuf, HashSet<Address>)> = Vec::new();
+    for step in steps {
+        let step = match step {
+            ScriptStep::Snapshot(SnapshotStep { snapshot: name }) => {
+                if name.is_empty() || Path::new(name).file_name() != Some(name.as_ref()) {
+                    report.fail(format!("snapshot {}", name), "snapshot names must be plain file names");
+                    break;
+                }
+                let path = options.snapshot_dir.join(format!("{}.snapshot.json", name));
+                sim.snapshot()
+                    .save(&path)
+                    .context(format!("snapshot {}", name))?;
+                snapshots.retain(|(taken, ..)| taken != name);
+                snapshots.push((name.clone(), path.clone(), funded.clone()));
+                report.ok(
+                    format!("snapshot {}", name),
+                    format!("saved to {}, {} snapshot(s) held", path.display(), snapshots.len()),
+                );
+                continue;
+            }
+            ScriptStep::Revert(RevertStep { revert: name }) => {
+                let Some(index) = snapshots.iter().position(|(taken, ..)| taken == name) else {
+                    report.fail(format!("revert {}", name), "no such snapshot, or it was already reverted to");
+                    break;
+                };
+                let discarded = snapshots.len() - index - 1;
+                let (_, path, saved) = snapshots.drain(index..).next().expect("index is in range");
+                sim.restore(SimulatorSnapshot::load(&path).context(format!("revert {}", name))?);
+                funded = saved;
+                report.ok(
+                    format!("revert {}", name),
+                    format!("restored, {} later snapshot(s) discarded", discarded),
+                );
+                continue;
+            }
+            ScriptStep::Advance(advance) => {
+                match advance.time_travel().apply(sim) {
+                    Ok(()) => report.ok(advance.label(), format!("now {} micros", sim.now_micros()?)),
+                    Err(e) => report.fail(advance.label(), format!("{:#}", e)),
+                }
+                if !report.succeeded() {
+                    break;
+                }
+                continue;
+            }
+            ScriptStep::Call(call) => call,
+        };
+        let result = execute_call(sim, step, options, &mut funded)
+            .map_err(|e| anyhow::anyhow!("{}: {:#}", step.label(), e))?;
+        let reason = analyze_txn_result(&result);
+        match (&result, &step.expect_revert) {
+            (ExecutionResult::Success { output, gas_used, .. }, None) => report.ok(
+                step.label(),
+                format!("0x{} (gas {})", hex::encode(output.data()), gas_used),
+            ),
+            (ExecutionResult::Success { .. }, Some(expected)) => {
+                report.fail(step.label(), format!("succeeded, expected a revert with {}", expected))
+            }
+            (_, Some(expected)) if reason.contains(expected.as_str()) => {
+                report.ok(step.label(), format!("reverted as expected: {}", reason))
+            }
+            _ => report.fail(step.label(), reason),
+        }
+        if !report.succeeded() {
+            break;
+        }
+    }
+    Ok(report)
+}
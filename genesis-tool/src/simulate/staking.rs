@@ -0,0 +1,242 @@
+//! Staking flow simulation
+//!
+//! Adds stake to an initial validator's StakePool as its staker, unstakes it,
+//! fast-forwards past the pool lockup and `unbondingDelayMicros`, then withdraws
+//! and checks the staker got the full amount back. Genesis parameter combinations
+//! that make stake unwithdrawable fail at the step where funds get stuck.
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolCall;
+use anyhow::Result;
+
+use super::{SimulationReport, Simulator};
+use crate::utils::{analyze_txn_result, STAKE_CONFIG_ADDR, STAKING_ADDR};
+
+sol! {
+    interface IStakingConfig {
+        function minimumStake() external view returns (uint256);
+        function lockupDurationMicros() external view returns (uint64);
+        function unbondingDelayMicros() external view returns (uint64);
+    }
+
+    interface IStakingPools {
+        function getAllPools() external view returns (address[] memory);
+        function getPoolStaker(address pool) external view returns (address);
+    }
+
+    interface IStakePool {
+        function getActiveStake() external view returns (uint256);
+        function getTotalPending() external view returns (uint256);
+        function getLockedUntil() external view returns (uint64);
+        function getClaimableAmount() external view returns (uint256);
+        function addStake() external payable;
+        function unstake(uint256 amount) external;
+        function withdrawAvailable(address recipient) external returns (uint256 amount);
+    }
+}
+
+/// Stake added when `minimumStake` is zero
+const DEFAULT_STAKE_AMOUNT: u128 = 1_000_000_000_000_000_000;
+
+/// Run add-stake → unstake → unlock → withdraw on `pool` (first pool by default);
+/// `amount` defaults to the configured minimum stake
+pub fn simulate_staking(
+    sim: &mut Simulator,
+    pool: Option<Address>,
+    amount: Option<U256>,
+) -> Result<SimulationReport> {
+    let mut report = SimulationReport::default();
+
+    let minimum_stake = sim
+        .view(STAKE_CONFIG_ADDR, &IStakingConfig::minimumStakeCall {})?
+        ._0;
+    let lockup = sim
+        .view(
+            STAKE_CONFIG_ADDR,
+            &IStakingConfig::lockupDurationMicrosCall {},
+        )?
+        ._0;
+    let unbonding = sim
+        .view(
+            STAKE_CONFIG_ADDR,
+            &IStakingConfig::unbondingDelayMicrosCall {},
+        )?
+        ._0;
+    report.ok(
+        "staking config",
+        format!(
+            "minimumStake={}, lockupDurationMicros={}, unbondingDelayMicros={}",
+            minimum_stake, lockup, unbonding
+        ),
+    );
+
+    let pool = match pool {
+        Some(pool) => pool,
+        None => match sim
+            .view(STAKING_ADDR, &IStakingPools::getAllPoolsCall {})?
+            ._0
+            .first()
+        {
+            Some(pool) => *pool,
+            None => {
+                report.fail("select pool", "no stake pools exist at genesis");
+                return Ok(report);
+            }
+        },
+    };
+    let staker = match sim.call(
+        Address::ZERO,
+        STAKING_ADDR,
+        &IStakingPools::getPoolStakerCall { pool },
+    )? {
+        Ok(ret) => ret._0,
+        Err(e) => {
+            report.fail("select pool", format!("{:?}: {}", pool, e));
+            return Ok(report);
+        }
+    };
+    report.ok(
+        "select pool",
+        format!("pool {:?} (staker {:?})", pool, staker),
+    );
+
+    let amount = amount.unwrap_or(if minimum_stake.is_zero() {
+        U256::from(DEFAULT_STAKE_AMOUNT)
+    } else {
+        minimum_stake
+    });
+
+    let now = match sim.start_clock() {
+        Ok(now) => now,
+        Err(e) => {
+            report.fail("start clock", e.to_string());
+            return Ok(report);
+        }
+    };
+
+    // Add stake
+    let active_before = sim.view(pool, &IStakePool::getActiveStakeCall {})?._0;
+    sim.fund(staker, amount);
+    let result = sim.transact(
+        staker,
+        pool,
+        IStakePool::addStakeCall {}.abi_encode().into(),
+        amount,
+    )?;
+    if !result.is_success() {
+        report.fail("add stake", analyze_txn_result(&result));
+        return Ok(report);
+    }
+    let active_after = sim.view(pool, &IStakePool::getActiveStakeCall {})?._0;
+    let locked_until = sim.view(pool, &IStakePool::getLockedUntilCall {})?._0;
+    if active_after != active_before + amount {
+        report.fail(
+            "add stake",
+            format!(
+                "active stake went from {} to {}, expected +{}",
+                active_before, active_after, amount
+            ),
+        );
+        return Ok(report);
+    }
+    if locked_until < now.saturating_add(lockup) {
+        report.fail(
+            "add stake",
+            format!(
+                "lockedUntil {} is before now + lockupDurationMicros ({})",
+                locked_until,
+                now.saturating_add(lockup)
+            ),
+        );
+        return Ok(report);
+    }
+    report.ok(
+        "add stake",
+        format!("added {}, pool locked until {}", amount, locked_until),
+    );
+
+    // Unstake into a pending bucket
+    let pending_before = sim.view(pool, &IStakePool::getTotalPendingCall {})?._0;
+    if let Err(e) = sim.call(staker, pool, &IStakePool::unstakeCall { amount })? {
+        report.fail("unstake", e);
+        return Ok(report);
+    }
+    let pending_after = sim.view(pool, &IStakePool::getTotalPendingCall {})?._0;
+    if pending_after != pending_before + amount {
+        report.fail(
+            "unstake",
+            format!(
+                "pending went from {} to {}, expected +{}",
+                pending_before, pending_after, amount
+            ),
+        );
+        return Ok(report);
+    }
+    report.ok("unstake", format!("{} pending until unlock", amount));
+
+    // Nothing may be claimable while the lockup holds
+    let claimable_early = sim.view(pool, &IStakePool::getClaimableAmountCall {})?._0;
+    if !claimable_early.is_zero() {
+        report.fail(
+            "early withdrawal",
+            format!("{} claimable before lockup expiry", claimable_early),
+        );
+        return Ok(report);
+    }
+    report.ok("early withdrawal", "nothing claimable before unlock");
+
+    // Claimable once now > lockedUntil + unbondingDelay
+    let Some(unlock_at) = locked_until
+        .checked_add(unbonding)
+        .and_then(|t| t.checked_add(1))
+    else {
+        report.fail(
+            "advance time",
+            format!(
+                "lockedUntil {} + unbondingDelayMicros {} overflows uint64; stake can never be withdrawn",
+                locked_until, unbonding
+            ),
+        );
+        return Ok(report);
+    };
+    if let Err(e) = sim.advance_time_to(unlock_at) {
+        report.fail("advance time", e.to_string());
+        return Ok(report);
+    }
+    report.ok("advance time", format!("clock at {}", unlock_at));
+
+    let claimable = sim.view(pool, &IStakePool::getClaimableAmountCall {})?._0;
+    if claimable < amount {
+        report.fail(
+            "withdraw",
+            format!("only {} of {} claimable after unbonding", claimable, amount),
+        );
+        return Ok(report);
+    }
+
+    let balance_before = sim.balance(staker);
+    match sim.call(
+        staker,
+        pool,
+        &IStakePool::withdrawAvailableCall { recipient: staker },
+    )? {
+        Ok(ret) => {
+            let received = sim.balance(staker) - balance_before;
+            if ret.amount != claimable || received != claimable {
+                report.fail(
+                    "withdraw",
+                    format!(
+                        "withdrew {} but staker balance changed by {} (claimable {})",
+                        ret.amount, received, claimable
+                    ),
+                );
+            } else {
+                report.ok("withdraw", format!("{} returned to staker", received));
+            }
+        }
+        Err(e) => report.fail("withdraw", e),
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,207 @@
+//! Source-level locations from forge source maps
+//!
+//! Forge artifacts carry `deployedBytecode.sourceMap`, which maps each
+//! instruction of the runtime code to a byte range in a source file identified
+//! by its compiler source id. Every artifact also records its own source id
+//! (`id`) and path (`ast.absolutePath`), so scanning the bytecode directory
+//! yields the id-to-file table. Paths are relative to the forge project root,
+//! the parent of `out/`, where the sources are read to turn offsets into lines.
+
+use alloy_primitives::Address;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+use crate::{abi::artifact_path, registry::ContractsManifest};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Path relative to the project root, e.g. `src/staking/Staking.sol`
+    pub file: String,
+    /// Byte offset of the mapped range in the file
+    pub offset: usize,
+    /// 1-based line and column, when the source file could be read
+    pub line: Option<(usize, usize)>,
+    /// Trimmed text of that line
+    pub snippet: Option<String>,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some((line, column)) => write!(f, "{}:{}:{}", self.file, line, column)?,
+            None => write!(f, "{} (byte offset {})", self.file, self.offset)?,
+        }
+        if let Some(snippet) = &self.snippet {
+            write!(f, "\n    {}", snippet)?;
+        }
+        Ok(())
+    }
+}
+
+/// Start offset and source id of a source map entry; length, jump type and
+/// modifier depth are not needed to point at a line
+#[derive(Debug, Clone, Copy, Default)]
+struct Entry {
+    offset: usize,
+    /// -1 for instructions without a source (compiler generated)
+    file: i64,
+}
+
+/// Decode the compressed `s:l:f:j:m;...` format, where empty fields repeat
+/// the previous entry's value
+fn parse_source_map(map: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let mut last = Entry::default();
+    for item in map.split(';') {
+        let mut fields = item.split(':');
+        if let Some(s) = fields.next().and_then(|v| v.parse().ok()) {
+            last.offset = s;
+        }
+        fields.next();
+        if let Some(f) = fields.next().and_then(|v| v.parse().ok()) {
+            last.file = f;
+        }
+        entries.push(last);
+    }
+    entries
+}
+
+/// Instruction index of each byte offset of hex code, skipping PUSH data.
+/// Link placeholders only occur inside PUSH20 data, so they never need decoding.
+fn instruction_indices(code_hex: &str) -> Vec<usize> {
+    let code = code_hex.trim_start_matches("0x").as_bytes();
+    let len = code.len() / 2;
+    let mut indices = vec![0; len];
+    let (mut pc, mut index) = (0, 0);
+    while pc < len {
+        let opcode = std::str::from_utf8(&code[2 * pc..2 * pc + 2])
+            .ok()
+            .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+            .unwrap_or(0);
+        let push_len = match opcode {
+            0x60..=0x7f => (opcode - 0x5f) as usize,
+            _ => 0,
+        };
+        for slot in indices.iter_mut().skip(pc).take(push_len + 1) {
+            *slot = index;
+        }
+        pc += push_len + 1;
+        index += 1;
+    }
+    indices
+}
+
+#[derive(Debug, Default)]
+struct ContractMap {
+    instructions: Vec<usize>,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Default)]
+pub struct SourceMaps {
+    /// Forge project root that source paths are relative to
+    root: PathBuf,
+    sources: HashMap<i64, String>,
+    contracts: HashMap<Address, ContractMap>,
+}
+
+impl SourceMaps {
+    /// Load source maps of the manifest contracts whose artifacts have one
+    pub fn load(byte_code_dir: &str, contracts: &ContractsManifest) -> Self {
+        let dir = Path::new(byte_code_dir);
+        let mut maps = Self {
+            root: dir
+                .parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or(Path::new("."))
+                .to_path_buf(),
+            ..Self::default()
+        };
+
+        for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+            for file in fs::read_dir(entry.path()).into_iter().flatten().flatten() {
+                let Some(artifact) = read_artifact(&file.path()) else {
+                    continue;
+                };
+                let id = artifact.get("id").and_then(Value::as_i64);
+                let path = artifact
+                    .pointer("/ast/absolutePath")
+                    .and_then(Value::as_str);
+                if let (Some(id), Some(path)) = (id, path) {
+                    maps.sources.insert(id, path.to_string());
+                }
+            }
+        }
+
+        for contract in &contracts.contracts {
+            let path = artifact_path(byte_code_dir, &contract.name);
+            let Some(artifact) = read_artifact(&path) else {
+                continue;
+            };
+            let deployed = artifact
+                .get("deployedBytecode")
+                .or_else(|| artifact.pointer("/evm/deployedBytecode"));
+            let map = deployed
+                .and_then(|d| d.get("sourceMap"))
+                .and_then(Value::as_str);
+            let object = deployed
+                .and_then(|d| d.get("object"))
+                .and_then(Value::as_str);
+            match (map, object) {
+                (Some(map), Some(object)) if !map.is_empty() => {
+                    maps.contracts.insert(
+                        contract.address,
+                        ContractMap {
+                            instructions: instruction_indices(object),
+                            entries: parse_source_map(map),
+                        },
+                    );
+                }
+                _ => debug!("No source map for {} at {}", contract.name, path.display()),
+            }
+        }
+        maps
+    }
+
+    /// Source location of the instruction at `pc` in the code at `address`
+    pub fn locate(&self, address: &Address, pc: usize) -> Option<SourceLocation> {
+        let contract = self.contracts.get(address)?;
+        let entry = contract.entries.get(*contract.instructions.get(pc)?)?;
+        let file = self.sources.get(&entry.file)?.clone();
+        let source = fs::read_to_string(self.root.join(&file)).ok();
+        let (line, snippet) = match source {
+            Some(source) if entry.offset <= source.len() => {
+                let before = &source.as_bytes()[..entry.offset];
+                let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+                let line_start = before
+                    .iter()
+                    .rposition(|&b| b == b'\n')
+                    .map_or(0, |i| i + 1);
+                let snippet = source[line_start..]
+                    .lines()
+                    .next()
+                    .map(|text| text.trim().to_string());
+                (Some((line, entry.offset - line_start + 1)), snippet)
+            }
+            _ => (None, None),
+        };
+        Some(SourceLocation {
+            file,
+            offset: entry.offset,
+            line,
+            snippet,
+        })
+    }
+}
+
+fn read_artifact(path: &Path) -> Option<Value> {
+    if path.extension().is_none_or(|ext| ext != "json") {
+        return None;
+    }
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
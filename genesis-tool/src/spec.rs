@@ -0,0 +1,134 @@
+//! EVM hardfork selection
+//!
+//! Genesis execution and verification run under one revm [`SpecId`]. It comes
+//! from `--evm-spec`, the config's `evmSpec`, or for `verify` the fork schedule
+//! in the genesis file's `config` section, so the EVM matches what greth
+//! enables at block 0 instead of whatever revm calls `LATEST`.
+
+use revm::primitives::SpecId;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::info;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum EvmSpec {
+    Homestead,
+    Byzantium,
+    Constantinople,
+    Petersburg,
+    Istanbul,
+    Berlin,
+    London,
+    /// Paris
+    Merge,
+    Shanghai,
+    Cancun,
+    Prague,
+    /// Newest fork revm supports
+    Latest,
+}
+
+impl EvmSpec {
+    pub fn spec_id(self) -> SpecId {
+        match self {
+            EvmSpec::Homestead => SpecId::HOMESTEAD,
+            EvmSpec::Byzantium => SpecId::BYZANTIUM,
+            EvmSpec::Constantinople => SpecId::CONSTANTINOPLE,
+            EvmSpec::Petersburg => SpecId::PETERSBURG,
+            EvmSpec::Istanbul => SpecId::ISTANBUL,
+            EvmSpec::Berlin => SpecId::BERLIN,
+            EvmSpec::London => SpecId::LONDON,
+            EvmSpec::Merge => SpecId::MERGE,
+            EvmSpec::Shanghai => SpecId::SHANGHAI,
+            EvmSpec::Cancun => SpecId::CANCUN,
+            EvmSpec::Prague => SpecId::PRAGUE,
+            EvmSpec::Latest => SpecId::LATEST,
+        }
+    }
+}
+
+/// Block-activated forks of a geth-style chain config, oldest first
+const BLOCK_FORKS: [(&str, SpecId); 9] = [
+    ("homesteadBlock", SpecId::HOMESTEAD),
+    ("eip150Block", SpecId::TANGERINE),
+    ("eip158Block", SpecId::SPURIOUS_DRAGON),
+    ("byzantiumBlock", SpecId::BYZANTIUM),
+    ("constantinopleBlock", SpecId::CONSTANTINOPLE),
+    ("petersburgBlock", SpecId::PETERSBURG),
+    ("istanbulBlock", SpecId::ISTANBUL),
+    ("berlinBlock", SpecId::BERLIN),
+    ("londonBlock", SpecId::LONDON),
+];
+
+/// Timestamp-activated forks, oldest first
+const TIME_FORKS: [(&str, SpecId); 3] = [
+    ("shanghaiTime", SpecId::SHANGHAI),
+    ("cancunTime", SpecId::CANCUN),
+    ("pragueTime", SpecId::PRAGUE),
+];
+
+/// Gravity's own forks, named after Greek letters and activated in alphabet order
+pub const GRAVITY_FORKS: [&str; 24] = [
+    "alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta", "theta", "iota", "kappa",
+    "lambda", "mu", "nu", "xi", "omicron", "pi", "rho", "sigma", "tau", "upsilon", "phi", "chi",
+    "psi", "omega",
+];
+
+/// Chain config keys of the Ethereum forks the tool knows, in activation order
+pub fn ethereum_fork_keys() -> impl Iterator<Item = &'static str> {
+    BLOCK_FORKS
+        .iter()
+        .map(|(key, _)| *key)
+        .chain(TIME_FORKS.iter().map(|(key, _)| *key))
+}
+
+pub fn is_ethereum_fork(key: &str) -> bool {
+    ethereum_fork_keys().any(|k| k == key)
+}
+
+/// Newest fork a genesis `config` section activates at block 0, given the
+/// genesis timestamp in seconds
+pub fn spec_from_chain_config(config: &Value, genesis_timestamp: u64) -> SpecId {
+    let activation = |key: &str| config.get(key).and_then(Value::as_u64);
+    let mut spec = SpecId::FRONTIER;
+    for (key, fork) in BLOCK_FORKS {
+        if activation(key) == Some(0) {
+            spec = fork;
+        }
+    }
+    let merged = config
+        .get("terminalTotalDifficultyPassed")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+        || activation("terminalTotalDifficulty") == Some(0)
+        || activation("mergeNetsplitBlock") == Some(0);
+    if merged {
+        spec = SpecId::MERGE;
+    }
+    for (key, fork) in TIME_FORKS {
+        if activation(key).is_some_and(|time| time <= genesis_timestamp) {
+            spec = fork;
+        }
+    }
+    spec
+}
+
+/// Spec for execution: an explicit choice wins, then the genesis fork
+/// schedule, then [`SpecId::LATEST`]
+pub fn resolve_spec(
+    explicit: Option<EvmSpec>,
+    chain_config: Option<&Value>,
+    genesis_timestamp: u64,
+) -> SpecId {
+    let (spec, source) = match (explicit, chain_config) {
+        (Some(spec), _) => (spec.spec_id(), "explicit setting"),
+        (None, Some(config)) => (
+            spec_from_chain_config(config, genesis_timestamp),
+            "genesis fork schedule",
+        ),
+        (None, None) => (SpecId::LATEST, "default"),
+    };
+    info!("EVM spec: {:?} (from {})", spec, source);
+    spec
+}
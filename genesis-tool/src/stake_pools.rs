@@ -0,0 +1,111 @@
+//! StakePool address prediction for the initial validators
+//!
+//! `Genesis.initialize` creates one pool per validator, in config order, through
+//! `Staking.createPool`, which deploys `StakePool` with CREATE2 from the Staking
+//! address. The salt is the factory's `poolNonce`, starting at 0, and the init
+//! code is the `StakePool` creation code followed by its constructor arguments
+//! `(owner, staker, operator, voter = owner, initialLockedUntilMicros)`. So the
+//! addresses follow from the config and the `StakePool` artifact alone, as long
+//! as no `preGenesisCalls` entry creates a pool first. `generate` checks the
+//! prediction against the executed genesis.
+
+use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_sol_types::SolValue;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{
+    abi::{creation_code, read_artifact},
+    genesis::GenesisConfig,
+    utils::STAKING_ADDR,
+};
+
+/// The pool of one initial validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakePoolEntry {
+    pub moniker: String,
+    pub owner: Address,
+    pub pool: Address,
+}
+
+/// `bytecode.object` of the `StakePool` artifact in a forge `out/` directory
+pub fn load_creation_code(byte_code_dir: &str) -> Result<Vec<u8>> {
+    let artifact = read_artifact(byte_code_dir, "StakePool")
+        .context("pool addresses need the StakePool artifact")?;
+    creation_code(&artifact, "StakePool")
+}
+
+/// Addresses `Genesis.initialize` will create the validators' pools at
+pub fn predict_pools(config: &GenesisConfig, creation_code: &[u8]) -> Result<Vec<StakePoolEntry>> {
+    config
+        .validators
+        .iter()
+        .enumerate()
+        .map(|(nonce, v)| {
+            let parse = |field: &str, value: &str| {
+                value.parse::<Address>().context(format!(
+                    "validator {}: invalid {} {}",
+                    v.moniker, field, value
+                ))
+            };
+            let owner = parse("owner", &v.owner)?;
+            let staker = parse("staker", &v.staker)?;
+            let operator = parse("operator", &v.operator)?;
+            let args = (owner, staker, operator, owner, config.initial_locked_until_micros)
+                .abi_encode_params();
+            let init_code = [creation_code, &args].concat();
+            let salt = B256::from(U256::from(nonce));
+            Ok(StakePoolEntry {
+                moniker: v.moniker.clone(),
+                owner,
+                pool: STAKING_ADDR.create2(salt, keccak256(&init_code)),
+            })
+        })
+        .collect()
+}
+
+/// Compare predicted pools with the ones genesis created; returns whether all match
+pub fn check_predictions(predicted: &[StakePoolEntry], actual: &[StakePoolEntry]) -> bool {
+    let mut matched = predicted.len() == actual.len();
+    if !matched {
+        warn!(
+            "Predicted {} stake pools, genesis created {}",
+            predicted.len(),
+            actual.len()
+        );
+    }
+    for (predicted, actual) in predicted.iter().zip(actual) {
+        if predicted.pool != actual.pool {
+            warn!(
+                "Stake pool of {} is {:?}, predicted {:?}; pool-addresses output does not match this bytecode",
+                actual.moniker, actual.pool, predicted.pool
+            );
+            matched = false;
+        }
+    }
+    if matched {
+        info!("All {} predicted stake pool addresses match genesis", actual.len());
+    }
+    matched
+}
+
+/// Print one line per validator: moniker, owner and pool address
+pub fn print_pools(pools: &[StakePoolEntry]) {
+    let width = pools
+        .iter()
+        .map(|p| p.moniker.len())
+        .chain(["moniker".len()])
+        .max()
+        .unwrap_or_default();
+    println!("{:<width$}  {:<42}  {}", "moniker", "owner", "stake pool");
+    for p in pools {
+        println!(
+            "{:<width$}  {:<42}  {}",
+            p.moniker,
+            p.owner.to_string(),
+            p.pool.to_string()
+        );
+    }
+}
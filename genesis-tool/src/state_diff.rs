@@ -0,0 +1,540 @@
+//! Semantic diff of two generated genesis states
+//!
+//! `diff-state old/bundle_state.json new/bundle_state.json` lists what a
+//! regenerated genesis changes, account by account and slot by slot, so
+//! auditors of a hardfork review exactly the state it rewrites.
+//!
+//! Slots of system contracts are named from the `storageLayout` of their forge
+//! artifacts when `--byte-code-dir` holds output built with
+//! `extra_output = ["storageLayout"]`:
+//!
+//! - value variables and struct members by label, decoding packed slots
+//!   variable by variable (`_initialized`, `config.minimumBond`)
+//! - dynamic arrays and long `bytes`/`string` by element or data word, sized
+//!   from the length in either state (`_activeValidators[3]`)
+//! - mappings for keys the diff can guess: every account in either state,
+//!   every address-sized value stored in one, and integers up to
+//!   [`MAX_INTEGER_KEY`] (`_validators[0x…].moniker`)
+//!
+//! Other slots, e.g. mappings keyed by hashes, are listed by number.
+
+use alloy_primitives::{hex, keccak256, Address, B256, U256};
+use anyhow::{Context, Result};
+use revm::db::BundleState;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs::{self, File},
+    io::BufReader,
+};
+use tracing::{info, warn};
+
+use crate::{abi::read_artifact, registry::ContractsManifest};
+
+/// Integer mapping keys tried when naming slots
+pub const MAX_INTEGER_KEY: u64 = 256;
+
+/// Nesting of mappings followed when naming slots
+const MAX_MAPPING_DEPTH: usize = 2;
+
+/// Elements of a dynamic array, or data words of `bytes`, named at most
+const MAX_ELEMENTS: u64 = 4096;
+
+/// Present state of an account; storage without zero slots
+#[derive(Debug, Clone, Default)]
+struct AccountState {
+    balance: U256,
+    nonce: u64,
+    code_hash: B256,
+    storage: BTreeMap<U256, U256>,
+}
+
+pub fn load_bundle_state(path: &str) -> Result<BundleState> {
+    let file = File::open(path).context(format!("Failed to open {}", path))?;
+    serde_json::from_reader(BufReader::new(file))
+        .context(format!("Failed to decode bundle state {}", path))
+}
+
+/// Accounts the bundle leaves in place; destroyed accounts are absent
+fn accounts(bundle_state: &BundleState) -> BTreeMap<Address, AccountState> {
+    bundle_state
+        .state
+        .iter()
+        .filter_map(|(address, account)| {
+            let info = account.info.as_ref()?;
+            let storage = account
+                .storage
+                .iter()
+                .map(|(slot, value)| (*slot, value.present_value()))
+                .filter(|(_, value)| !value.is_zero())
+                .collect();
+            Some((
+                *address,
+                AccountState {
+                    balance: info.balance,
+                    nonce: info.nonce,
+                    code_hash: info.code_hash,
+                    storage,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Candidate key of a mapping, with its slot encoding
+#[derive(Debug, Clone)]
+enum MappingKey {
+    Address(Address),
+    Integer(u64),
+}
+
+impl MappingKey {
+    fn matches(&self, key_type: &str) -> bool {
+        match self {
+            MappingKey::Address(_) => key_type == "address" || key_type.starts_with("contract "),
+            MappingKey::Integer(n) => {
+                key_type.starts_with("uint") || key_type.starts_with("int") || (key_type == "bool" && *n < 2)
+            }
+        }
+    }
+
+    fn word(&self) -> B256 {
+        match self {
+            MappingKey::Address(address) => address.into_word(),
+            MappingKey::Integer(n) => U256::from(*n).into(),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            MappingKey::Address(address) => address.to_string(),
+            MappingKey::Integer(n) => n.to_string(),
+        }
+    }
+}
+
+/// A variable, or the part of one, held in a slot
+#[derive(Debug, Clone)]
+struct SlotVar {
+    label: String,
+    /// Byte offset from the low end of the word
+    offset: usize,
+    size: usize,
+    /// Solidity type label (`address`, `uint64`, `bytes`, ...)
+    type_label: String,
+}
+
+impl SlotVar {
+    fn segment(&self, word: U256) -> U256 {
+        let shifted = word >> (self.offset * 8);
+        if self.size >= 32 {
+            shifted
+        } else {
+            shifted & ((U256::from(1) << (self.size * 8)) - U256::from(1))
+        }
+    }
+
+    fn display(&self, word: U256) -> String {
+        let value = self.segment(word);
+        let ty = self.type_label.as_str();
+        if ty == "bool" {
+            (!value.is_zero()).to_string()
+        } else if ty.starts_with("address") || ty.starts_with("contract ") {
+            Address::from_word(value.into()).to_string()
+        } else if ty.starts_with("uint") || ty.starts_with("enum ") {
+            value.to_string()
+        } else if ty == "string" || ty == "bytes" {
+            // Short values sit in the slot with twice their length in the low
+            // byte; long ones store twice their length plus one
+            let bytes = value.to_be_bytes::<32>();
+            if value.bit(0) {
+                format!("({} bytes)", (value >> 1usize).saturating_to::<u64>())
+            } else {
+                let data = &bytes[..(bytes[31] as usize / 2).min(31)];
+                match ty {
+                    "string" => format!("{:?}", String::from_utf8_lossy(data)),
+                    _ => format!("0x{}", hex::encode(data)),
+                }
+            }
+        } else {
+            format!("0x{}", hex::encode(&value.to_be_bytes::<32>()[32 - self.size.min(32)..]))
+        }
+    }
+}
+
+/// Slot names of one contract, from its artifact's `storageLayout`
+struct LayoutWalker<'a> {
+    types: &'a Map<String, Value>,
+    /// Value of a slot in whichever state has it set, to size arrays and bytes
+    storage: &'a dyn Fn(U256) -> U256,
+    keys: &'a [MappingKey],
+    names: HashMap<U256, Vec<SlotVar>>,
+}
+
+fn number(value: &Value) -> u64 {
+    match value {
+        Value::String(s) => s.parse().unwrap_or(0),
+        other => other.as_u64().unwrap_or(0),
+    }
+}
+
+impl LayoutWalker<'_> {
+    fn push(&mut self, slot: U256, label: String, offset: usize, size: usize, type_label: &str) {
+        self.names.entry(slot).or_default().push(SlotVar {
+            label,
+            offset,
+            size,
+            type_label: type_label.to_string(),
+        });
+    }
+
+    fn walk(&mut self, type_id: &str, slot: U256, offset: usize, label: String, depth: usize) {
+        let Some(ty) = self.types.get(type_id) else {
+            return;
+        };
+        let size = number(&ty["numberOfBytes"]) as usize;
+        let type_label = ty["label"].as_str().unwrap_or_default();
+        match ty["encoding"].as_str().unwrap_or_default() {
+            "mapping" => {
+                let (Some(key_type), Some(value_type)) = (ty["key"].as_str(), ty["value"].as_str()) else {
+                    return;
+                };
+                if depth >= MAX_MAPPING_DEPTH {
+                    return;
+                }
+                let key_label = self.types.get(key_type).and_then(|t| t["label"].as_str()).unwrap_or_default();
+                let base = B256::from(slot);
+                for key in self.keys.iter().filter(|key| key.matches(key_label)) {
+                    let entry = keccak256([key.word().as_slice(), base.as_slice()].concat());
+                    self.walk(value_type, entry.into(), 0, format!("{}[{}]", label, key.label()), depth + 1);
+                }
+            }
+            "dynamic_array" => {
+                self.push(slot, format!("{}.length", label), 0, 32, "uint256");
+                let Some(base) = ty["base"].as_str() else {
+                    return;
+                };
+                let length = (self.storage)(slot).saturating_to::<u64>().min(MAX_ELEMENTS);
+                let data: U256 = keccak256(B256::from(slot)).into();
+                self.walk_elements(base, data, length, &label, depth);
+            }
+            "bytes" => {
+                self.push(slot, label.clone(), 0, 32, type_label);
+                let word = (self.storage)(slot);
+                if word.bit(0) {
+                    let length = ((word - U256::from(1)) >> 1usize).saturating_to::<u64>();
+                    let data: U256 = keccak256(B256::from(slot)).into();
+                    for k in 0..length.div_ceil(32).min(MAX_ELEMENTS) {
+                        self.push(data + U256::from(k), format!("{} (data word {})", label, k), 0, 32, "bytes32");
+                    }
+                }
+            }
+            _ => {
+                if let Some(members) = ty["members"].as_array() {
+                    for member in members {
+                        let (Some(member_type), Some(member_label)) =
+                            (member["type"].as_str(), member["label"].as_str())
+                        else {
+                            continue;
+                        };
+                        self.walk(
+                            member_type,
+                            slot + U256::from(number(&member["slot"])),
+                            number(&member["offset"]) as usize,
+                            format!("{}.{}", label, member_label),
+                            depth,
+                        );
+                    }
+                } else if let Some(base) = ty["base"].as_str().filter(|_| size > 32) {
+                    // Static array: elements are packed like a dynamic array's data
+                    let element_size = self.types.get(base).map_or(32, |t| number(&t["numberOfBytes"]).max(1));
+                    let length = match element_size {
+                        n if n >= 32 => size as u64 / n,
+                        n => (size as u64 / 32) * (32 / n),
+                    };
+                    self.walk_elements(base, slot, length.min(MAX_ELEMENTS), &label, depth);
+                } else if size <= 32 {
+                    self.push(slot, label, offset, size, type_label);
+                } else {
+                    for k in 0..size.div_ceil(32) {
+                        self.push(slot + U256::from(k), format!("{} (word {})", label, k), 0, 32, "bytes32");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Elements of an array whose data starts at `data`
+    fn walk_elements(&mut self, base: &str, data: U256, length: u64, label: &str, depth: usize) {
+        let element_size = self.types.get(base).map_or(32, |t| number(&t["numberOfBytes"]).max(1));
+        for i in 0..length {
+            let (slot, offset) = if element_size >= 32 {
+                (data + U256::from(i * element_size.div_ceil(32)), 0)
+            } else {
+                let per_slot = 32 / element_size;
+                (data + U256::from(i / per_slot), ((i % per_slot) * element_size) as usize)
+            };
+            self.walk(base, slot, offset, format!("{}[{}]", label, i), depth);
+        }
+    }
+}
+
+/// Slot names of a contract from its `storageLayout`
+fn slot_names(
+    layout: &Value,
+    storage: &dyn Fn(U256) -> U256,
+    keys: &[MappingKey],
+) -> HashMap<U256, Vec<SlotVar>> {
+    let empty = Map::new();
+    let mut walker = LayoutWalker {
+        types: layout["types"].as_object().unwrap_or(&empty),
+        storage,
+        keys,
+        names: HashMap::new(),
+    };
+    for var in layout["storage"].as_array().into_iter().flatten() {
+        let (Some(type_id), Some(label)) = (var["type"].as_str(), var["label"].as_str()) else {
+            continue;
+        };
+        let slot = var["slot"]
+            .as_str()
+            .and_then(|s| U256::from_str_radix(s, 10).ok())
+            .unwrap_or_default();
+        walker.walk(type_id, slot, number(&var["offset"]) as usize, label.to_string(), 0);
+    }
+    walker.names
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ValueChange {
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotChange {
+    pub slot: U256,
+    /// Variable the slot holds, when the storage layout names it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variable: Option<String>,
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDiff {
+    pub address: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub kind: ChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<ValueChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<ValueChange>,
+    /// Code hash change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<ValueChange>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub storage: Vec<SlotChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDiff {
+    pub old: String,
+    pub new: String,
+    pub accounts: Vec<AccountDiff>,
+}
+
+fn change<T: PartialEq + ToString>(old: T, new: T) -> Option<ValueChange> {
+    (old != new).then(|| ValueChange {
+        old: old.to_string(),
+        new: new.to_string(),
+    })
+}
+
+/// Storage changes of one account, named through `names` where possible
+fn storage_changes(
+    old: &BTreeMap<U256, U256>,
+    new: &BTreeMap<U256, U256>,
+    names: &HashMap<U256, Vec<SlotVar>>,
+) -> Vec<SlotChange> {
+    let slots: BTreeSet<U256> = old.keys().chain(new.keys()).copied().collect();
+    let mut changes = Vec::new();
+    for slot in slots {
+        let before = old.get(&slot).copied().unwrap_or_default();
+        let after = new.get(&slot).copied().unwrap_or_default();
+        if before == after {
+            continue;
+        }
+        let named: Vec<SlotChange> = names
+            .get(&slot)
+            .into_iter()
+            .flatten()
+            .filter(|var| var.segment(before) != var.segment(after))
+            .map(|var| SlotChange {
+                slot,
+                variable: Some(var.label.clone()),
+                old: var.display(before),
+                new: var.display(after),
+            })
+            .collect();
+        if named.is_empty() {
+            changes.push(SlotChange {
+                slot,
+                variable: None,
+                old: format!("{:#x}", before),
+                new: format!("{:#x}", after),
+            });
+        } else {
+            changes.extend(named);
+        }
+    }
+    changes
+}
+
+/// Diff `new` against `old`; `byte_code_dir` supplies contract names and
+/// storage layouts
+pub fn diff_states(
+    old_path: &str,
+    new_path: &str,
+    byte_code_dir: Option<&str>,
+) -> Result<StateDiff> {
+    let old = accounts(&load_bundle_state(old_path)?);
+    let new = accounts(&load_bundle_state(new_path)?);
+    let contracts = match byte_code_dir {
+        Some(dir) => ContractsManifest::resolve(dir, None)?,
+        None => ContractsManifest::default(),
+    };
+    let names: HashMap<Address, &str> = contracts
+        .contracts
+        .iter()
+        .map(|contract| (contract.address, contract.name.as_str()))
+        .collect();
+
+    let addresses: BTreeSet<Address> = old.keys().chain(new.keys()).copied().collect();
+    // Stored values between 2^64 and 2^160 are most likely addresses
+    let stored = old
+        .values()
+        .chain(new.values())
+        .flat_map(|account| account.storage.values())
+        .filter(|value| value.bit_len() > 64 && value.bit_len() <= 160)
+        .map(|value| Address::from_word((*value).into()));
+    let keys: Vec<MappingKey> = addresses
+        .iter()
+        .chain(names.keys())
+        .copied()
+        .chain(stored)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(MappingKey::Address)
+        .chain((0..=MAX_INTEGER_KEY).map(MappingKey::Integer))
+        .collect();
+
+    let mut layouts_missing = 0;
+    let mut diffs = Vec::new();
+    for address in addresses {
+        let name = names.get(&address).copied();
+        let (before, after) = (old.get(&address), new.get(&address));
+        let kind = match (before, after) {
+            (None, Some(_)) => ChangeKind::Added,
+            (Some(_), None) => ChangeKind::Removed,
+            _ => ChangeKind::Changed,
+        };
+        let (before, after) = (before.cloned().unwrap_or_default(), after.cloned().unwrap_or_default());
+
+        let layout = match (name, byte_code_dir) {
+            (Some(name), Some(dir)) => {
+                let layout = read_artifact(dir, name).ok().map(|artifact| artifact["storageLayout"].clone());
+                if layout.as_ref().is_none_or(Value::is_null) {
+                    layouts_missing += 1;
+                }
+                layout
+            }
+            _ => None,
+        };
+        let storage = |slot: U256| {
+            let value = |state: &AccountState| state.storage.get(&slot).copied().unwrap_or_default();
+            value(&before).max(value(&after))
+        };
+        let slot_names = layout
+            .map(|layout| slot_names(&layout, &storage, &keys))
+            .unwrap_or_default();
+
+        let diff = AccountDiff {
+            address,
+            name: name.map(str::to_string),
+            kind,
+            balance: change(before.balance, after.balance),
+            nonce: change(before.nonce, after.nonce),
+            code: change(before.code_hash, after.code_hash),
+            storage: storage_changes(&before.storage, &after.storage, &slot_names),
+        };
+        let unchanged = diff.balance.is_none()
+            && diff.nonce.is_none()
+            && diff.code.is_none()
+            && diff.storage.is_empty();
+        if kind != ChangeKind::Changed || !unchanged {
+            diffs.push(diff);
+        }
+    }
+    if layouts_missing > 0 {
+        warn!(
+            "{} system contract artifact(s) have no storageLayout; their slots are listed by number. \
+             Build with `extra_output = [\"storageLayout\"]` to name them",
+            layouts_missing
+        );
+    }
+    Ok(StateDiff {
+        old: old_path.to_string(),
+        new: new_path.to_string(),
+        accounts: diffs,
+    })
+}
+
+impl StateDiff {
+    pub fn log(&self) {
+        for account in &self.accounts {
+            let marker = match account.kind {
+                ChangeKind::Added => '+',
+                ChangeKind::Removed => '-',
+                ChangeKind::Changed => '~',
+            };
+            match &account.name {
+                Some(name) => info!("{} {} ({})", marker, name, account.address),
+                None => info!("{} {}", marker, account.address),
+            }
+            for (field, change) in [("balance", &account.balance), ("nonce", &account.nonce), ("code hash", &account.code)] {
+                if let Some(change) = change {
+                    info!("    {}: {} -> {}", field, change.old, change.new);
+                }
+            }
+            for slot in &account.storage {
+                match &slot.variable {
+                    Some(variable) => info!("    {}: {} -> {}", variable, slot.old, slot.new),
+                    None => info!("    slot {:#x}: {} -> {}", slot.slot, slot.old, slot.new),
+                }
+            }
+        }
+        info!(
+            "{} account(s) differ, {} storage change(s)",
+            self.accounts.len(),
+            self.accounts.iter().map(|account| account.storage.len()).sum::<usize>()
+        );
+    }
+}
+
+pub fn write_diff(diff: &StateDiff, path: &str) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(diff)? + "\n").context(format!("Failed to write {}", path))
+}
@@ -0,0 +1,150 @@
+//! Accounts from anvil and hardhat state dumps
+//!
+//! `generate --import-state <file>` bakes contracts prototyped on a local node
+//! into the genesis alloc. Two layouts are read:
+//!
+//! - `anvil --dump-state` files, whose `accounts` object maps each address to
+//!   `{ nonce, balance, code, storage }`;
+//! - a bare object of the same accounts, as hardhat state exports and genesis
+//!   allocs write them.
+//!
+//! Nonces may be numbers or hex strings, balances hex or decimal strings.
+//! Accounts are merged over forked state and under `extraAlloc`, so a config
+//! entry still wins. `anvil_dumpState` over RPC returns the dump gzipped; it has
+//! to be decompressed before importing.
+
+use alloy_primitives::{Address, Bytes, U256};
+use anyhow::{Context, Result};
+use revm::db::PlainAccount;
+use revm_primitives::{AccountInfo, Bytecode};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{collections::HashMap, fs};
+use tracing::{info, warn};
+
+use crate::{
+    genesis::{format_ether, GenesisConfig},
+    utils::is_reserved_system_address,
+};
+
+/// Leading bytes of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, Deserialize)]
+struct DumpAccount {
+    #[serde(default)]
+    nonce: Option<Value>,
+    #[serde(default)]
+    balance: Option<Value>,
+    #[serde(default)]
+    code: Option<Bytes>,
+    #[serde(default)]
+    storage: HashMap<U256, U256>,
+}
+
+/// A JSON number, or a hex (`0x`) or decimal string
+fn parse_quantity(value: &Value) -> Result<U256> {
+    match value {
+        Value::Number(n) => n
+            .as_u64()
+            .map(U256::from)
+            .ok_or_else(|| anyhow::anyhow!("{} is not a non-negative integer", n)),
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some("") => Ok(U256::ZERO),
+            Some(digits) => U256::from_str_radix(digits, 16).context(format!("invalid hex {}", s)),
+            None => U256::from_str_radix(s, 10).context(format!("invalid number {}", s)),
+        },
+        _ => anyhow::bail!("expected a number or string, got {}", value),
+    }
+}
+
+fn to_plain_account(account: DumpAccount) -> Result<PlainAccount> {
+    let nonce = match &account.nonce {
+        Some(nonce) => u64::try_from(parse_quantity(nonce).context("nonce")?)
+            .map_err(|_| anyhow::anyhow!("nonce does not fit in 64 bits"))?,
+        None => 0,
+    };
+    let balance = match &account.balance {
+        Some(balance) => parse_quantity(balance).context("balance")?,
+        None => U256::ZERO,
+    };
+    let code = account.code.filter(|code| !code.is_empty());
+    let mut info = AccountInfo {
+        balance,
+        nonce,
+        ..AccountInfo::default()
+    };
+    if let Some(code) = code {
+        let bytecode = Bytecode::new_raw(code);
+        info.code_hash = bytecode.hash_slow();
+        info.code = Some(bytecode);
+    }
+    Ok(PlainAccount {
+        info,
+        storage: account
+            .storage
+            .into_iter()
+            .filter(|(_, value)| !value.is_zero())
+            .collect(),
+    })
+}
+
+/// Read the accounts of the state dump at `path`; accounts in the reserved
+/// system range are rejected unless the config's `allowReservedAlloc` lists them
+pub fn import_state_dump(path: &str, config: &GenesisConfig) -> Result<HashMap<Address, PlainAccount>> {
+    let content = fs::read(path).context(format!("Failed to read state dump {}", path))?;
+    if content.starts_with(&GZIP_MAGIC) || content.starts_with(b"\"0x1f8b") {
+        anyhow::bail!(
+            "{} is gzip-compressed (anvil_dumpState output); decompress it or use anvil --dump-state",
+            path
+        );
+    }
+    let mut dump: Value =
+        serde_json::from_slice(&content).context(format!("Failed to decode state dump {}", path))?;
+    let accounts = match dump.get_mut("accounts") {
+        Some(accounts) => accounts.take(),
+        None => dump,
+    };
+    let accounts: HashMap<Address, DumpAccount> = serde_json::from_value(accounts)
+        .context(format!("{} has no accounts object of address to account", path))?;
+
+    let mut imported = HashMap::new();
+    for (address, account) in accounts {
+        if is_reserved_system_address(address)
+            && !config.allow_reserved_alloc.iter().any(|a| a.parse() == Ok(address))
+        {
+            anyhow::bail!(
+                "{} has account {:?} in the reserved system range; list it in allowReservedAlloc if intended",
+                path,
+                address
+            );
+        }
+        let account = to_plain_account(account).context(format!("{}: account {:?}", path, address))?;
+        imported.insert(address, account);
+    }
+
+    let has_code = |a: &PlainAccount| a.info.code.as_ref().is_some_and(|c| !c.is_empty());
+    let with_code = imported.values().filter(|a| has_code(a)).count();
+    let slots: usize = imported.values().map(|a| a.storage.len()).sum();
+    info!(
+        "Imported {} account(s) from {}: {} with code, {} storage slot(s)",
+        imported.len(),
+        path,
+        with_code,
+        slots
+    );
+    let funded: Vec<U256> = imported
+        .values()
+        .filter(|a| !has_code(a) && !a.info.balance.is_zero())
+        .map(|a| a.info.balance)
+        .collect();
+    if !funded.is_empty() {
+        let total = funded.iter().fold(U256::ZERO, |sum, b| sum.saturating_add(*b));
+        warn!(
+            "{} imported account(s) without code hold {}; these are usually the node's dev accounts, drop them from the dump if the chain should not fund them",
+            funded.len(),
+            format_ether(total)
+        );
+    }
+    Ok(imported)
+}
@@ -0,0 +1,209 @@
+//! Native token supply audit of genesis execution
+//!
+//! Genesis transactions run with a zero gas price, so execution only moves
+//! balances: their sum over every account is the same before and after. The
+//! alloc keeps all of it except the scaffolding, the system caller's and the
+//! `Genesis` contract's funding buffers, which are dropped when the outputs are
+//! assembled. What remains must be the validators' stake, the value of the hook
+//! calls and replayed broadcast transactions, and the vesting amounts; together with the balances `extraAlloc`,
+//! `treasury` and `feeCollector` put into the alloc directly, it is the supply
+//! at block 0, checked against `totalSupply` when the config sets it. Accounts
+//! taken from a forked chain are not part of the audit.
+
+use revm::{db::BundleState, InMemoryDB};
+use revm_primitives::{Address, U256};
+use std::collections::HashMap;
+use tracing::info;
+
+use crate::{
+    analyze::{basis_points, format_basis_points},
+    broadcast,
+    genesis::{calculate_total_stake, format_ether, genesis_calls, parse_amount, GenesisConfig},
+    utils::{DEAD_ADDRESS, GENESIS_ADDR, SYSTEM_CALLER},
+    vesting,
+};
+
+/// Balance sums of one genesis execution
+#[derive(Debug, Clone)]
+pub struct SupplyAudit {
+    /// Sum of all balances the deployment database was funded with
+    pub before: U256,
+    /// Sum of all balances after the genesis transactions
+    pub after: U256,
+    /// Balance left at the system caller and `Genesis`, not written to the alloc
+    pub scaffolding: U256,
+    /// Stake, hook call and broadcast values and vesting amounts execution
+    /// should hand out
+    pub expected_emitted: U256,
+    /// Balances of `extraAlloc`, `treasury` and `feeCollector`
+    pub allocated: U256,
+    /// Supply by holder category, in display order
+    pub breakdown: Vec<(&'static str, U256)>,
+    /// Executed accounts holding a balance that an alloc entry replaces
+    pub overwritten: Vec<(Address, U256)>,
+}
+
+fn sum<'a>(amounts: impl IntoIterator<Item = &'a U256>) -> U256 {
+    amounts
+        .into_iter()
+        .fold(U256::ZERO, |sum, amount| sum.saturating_add(*amount))
+}
+
+impl SupplyAudit {
+    /// Audit the execution that turned `db` into `bundle_state`; `pools` are the
+    /// validators' stake pools
+    pub fn new(
+        db: &InMemoryDB,
+        bundle_state: &BundleState,
+        config: &GenesisConfig,
+        pools: &[Address],
+    ) -> Result<Self, String> {
+        let mut balances: HashMap<Address, U256> = db
+            .accounts
+            .iter()
+            .map(|(address, account)| (*address, account.info.balance))
+            .collect();
+        let before = sum(balances.values());
+        for (address, account) in &bundle_state.state {
+            let balance = account.info.as_ref().map(|info| info.balance).unwrap_or_default();
+            balances.insert(*address, balance);
+        }
+        let after = sum(balances.values());
+
+        let mut take = |addresses: &[Address]| {
+            let taken: Vec<U256> = addresses
+                .iter()
+                .filter_map(|address| balances.remove(address))
+                .collect();
+            sum(&taken)
+        };
+        let scaffolding = take(&[SYSTEM_CALLER, GENESIS_ADDR]);
+        let staked = take(pools);
+        let vested = take(&vesting::vesting_addresses(config));
+        let burned = take(&[DEAD_ADDRESS]);
+        let contracts = sum(balances.values());
+
+        let parse = |label: &str, amount: &str| {
+            parse_amount(amount).map_err(|e| format!("{}: {}", label, e))
+        };
+        let mut call_values = U256::ZERO;
+        for (label, call) in genesis_calls(config) {
+            let value = call.value_wei().map_err(|e| format!("{}: {}", label, e))?;
+            call_values = call_values.saturating_add(value);
+        }
+        for tx in broadcast::replay_txns(config).map_err(|e| format!("replayBroadcast: {:#}", e))? {
+            call_values = call_values.saturating_add(tx.value);
+        }
+        let expected_emitted = calculate_total_stake(config)
+            .map_err(|e| format!("{:#}", e))?
+            .saturating_add(call_values)
+            .saturating_add(vesting::total_vesting(config));
+
+        let mut treasury = U256::ZERO;
+        let mut alloc_addresses = Vec::new();
+        for (key, account) in config.funded_accounts() {
+            if let Some(balance) = &account.balance {
+                treasury = treasury.saturating_add(parse(key, balance)?);
+            }
+            alloc_addresses.push(account.address.as_str());
+        }
+        let mut faucet = U256::ZERO;
+        for (address, account) in &config.extra_alloc {
+            if let Some(balance) = &account.balance {
+                faucet = faucet.saturating_add(parse(&format!("extraAlloc {}", address), balance)?);
+            }
+            alloc_addresses.push(address.as_str());
+        }
+        let overwritten = alloc_addresses
+            .into_iter()
+            .filter_map(|address| address.parse::<Address>().ok())
+            .filter_map(|address| {
+                let balance = balances.get(&address).copied().unwrap_or_default();
+                (balance > U256::ZERO).then_some((address, balance))
+            })
+            .collect();
+
+        Ok(SupplyAudit {
+            before,
+            after,
+            scaffolding,
+            expected_emitted,
+            allocated: treasury.saturating_add(faucet),
+            breakdown: vec![
+                ("staked", staked),
+                ("vesting", vested),
+                ("treasury", treasury),
+                ("faucet", faucet),
+                ("contracts", contracts),
+                ("burned", burned),
+            ],
+            overwritten,
+        })
+    }
+
+    /// Balance execution left outside the scaffolding accounts
+    pub fn emitted(&self) -> U256 {
+        self.after.saturating_sub(self.scaffolding)
+    }
+
+    /// Native supply of the genesis alloc
+    pub fn total_supply(&self) -> U256 {
+        self.emitted().saturating_add(self.allocated)
+    }
+
+    /// Every way the supply differs from what the config intends
+    pub fn problems(&self, config: &GenesisConfig) -> Result<Vec<String>, String> {
+        let mut problems = Vec::new();
+        if self.after != self.before {
+            problems.push(format!(
+                "genesis execution changed the sum of all balances from {} to {}",
+                format_ether(self.before),
+                format_ether(self.after)
+            ));
+        }
+        if self.emitted() != self.expected_emitted {
+            problems.push(format!(
+                "execution leaves {} outside the system caller and Genesis, expected {} (stake, hook call and broadcast values and vesting)",
+                format_ether(self.emitted()),
+                format_ether(self.expected_emitted)
+            ));
+        }
+        for (address, balance) in &self.overwritten {
+            problems.push(format!(
+                "{:?} received {} during execution, which its alloc entry replaces",
+                address,
+                format_ether(*balance)
+            ));
+        }
+        if let Some(total_supply) = &config.total_supply {
+            let expected = parse_amount(total_supply).map_err(|e| format!("totalSupply: {}", e))?;
+            if self.total_supply() != expected {
+                problems.push(format!(
+                    "total supply is {}, totalSupply is {}",
+                    format_ether(self.total_supply()),
+                    format_ether(expected)
+                ));
+            }
+        }
+        Ok(problems)
+    }
+
+    pub fn log(&self) {
+        let total = self.total_supply();
+        info!(
+            "Genesis supply {} ({} wei); execution was funded with {} and left {} in scaffolding accounts",
+            format_ether(total),
+            total,
+            format_ether(self.before),
+            format_ether(self.scaffolding)
+        );
+        for (category, amount) in &self.breakdown {
+            info!(
+                "  {:<9} {:>32} {:>8}",
+                category,
+                format_ether(*amount),
+                format_basis_points(basis_points(*amount, total))
+            );
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Synthetic validator sets for scale testing
+//!
+//! `generate --synthetic-validators <n>` replaces the config's validators with
+//! `n` generated ones: BLS consensus keys with proofs of possession, operator
+//! EOAs and noise keys derived from `--synthetic-seed` the way `devnet --seed`
+//! derives them, so a seed always yields the same set. Stakes (and voting
+//! power) follow `--stake-distribution`:
+//!
+//! - `uniform:<amount>`: every validator stakes the amount
+//! - `range:<min>..<max>`: amounts drawn uniformly from the range, seeded
+//! - `zipf:<top>:<exponent>`: validator `i` (from 0) stakes
+//!   `top / (i + 1)^exponent`, a few large validators and a long tail
+//!
+//! Without a spec every validator stakes `validatorConfig.minimumBond`, and no
+//! distribution goes below it.
+//!
+//! Before the real run, the scale report executes genesis in memory for
+//! prefixes of the set, doubling from one validator up to `n`, and records the
+//! heaviest transaction's gas and the storage slots written against a
+//! block-sized budget ([`GAS_BUDGET`]) and the fresh slots such a block can
+//! write ([`SLOT_BUDGET`]). It names the first size over each bound, or
+//! projects from the two largest sizes where the set would cross it.
+
+use alloy_primitives::U256;
+use anyhow::{Context, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use revm::db::BundleState;
+use serde::Serialize;
+use std::{fs, path::Path};
+use tracing::{info, warn};
+
+use crate::{
+    devnet::{devnet_config, DevnetOptions},
+    execute::{config_env, deployment_db, BuildInputs},
+    genesis::{parse_amount, GenesisConfig},
+    profile::GAS_BUDGET,
+    registry::ContractsManifest,
+    utils::{analyze_txn_result, execute_revm_sequential},
+};
+
+/// Gas of an SSTORE to a fresh slot, cold access included
+const SSTORE_SET_GAS: u64 = 22_100;
+
+/// Fresh storage slots a block of [`GAS_BUDGET`] can write
+pub const SLOT_BUDGET: u64 = GAS_BUDGET / SSTORE_SET_GAS;
+
+pub const SCALE_REPORT_FILE: &str = "scale_report.json";
+
+/// First port of the synthetic network addresses, as for `devnet`
+const BASE_PORT: u16 = 6180;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StakeDistribution {
+    Uniform(U256),
+    Range(U256, U256),
+    Zipf { top: U256, exponent: f64 },
+}
+
+impl StakeDistribution {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let amount = |s: &str| parse_amount(s).map_err(|e| anyhow::anyhow!("{}", e));
+        let (kind, params) = spec
+            .split_once(':')
+            .context(format!("stake distribution {:?} is not <kind>:<params>", spec))?;
+        match kind.trim() {
+            "uniform" => Ok(StakeDistribution::Uniform(amount(params)?)),
+            "range" => {
+                let (min, max) = params
+                    .split_once("..")
+                    .context(format!("range distribution {:?} is not range:<min>..<max>", spec))?;
+                let (min, max) = (amount(min)?, amount(max)?);
+                if min > max {
+                    anyhow::bail!("range distribution {:?} has min above max", spec);
+                }
+                Ok(StakeDistribution::Range(min, max))
+            }
+            "zipf" => {
+                let (top, exponent) = params
+                    .rsplit_once(':')
+                    .context(format!("zipf distribution {:?} is not zipf:<top>:<exponent>", spec))?;
+                let exponent: f64 = exponent
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|e: &f64| e.is_finite() && *e >= 0.0)
+                    .context(format!("invalid zipf exponent in {:?}", spec))?;
+                Ok(StakeDistribution::Zipf { top: amount(top)?, exponent })
+            }
+            other => anyhow::bail!("unknown stake distribution {:?}: expected uniform, range or zipf", other),
+        }
+    }
+
+    /// Stakes of `count` validators, none below `floor`
+    fn stakes(&self, count: usize, seed: u64, floor: U256) -> Vec<U256> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|i| {
+                let stake = match self {
+                    StakeDistribution::Uniform(amount) => *amount,
+                    StakeDistribution::Range(min, max) => {
+                        let span = (*max - *min).saturating_to::<u128>();
+                        *min + U256::from(rng.gen_range(0..=span))
+                    }
+                    StakeDistribution::Zipf { top, exponent } => {
+                        let share = ((i + 1) as f64).powf(-exponent);
+                        U256::from((top.saturating_to::<u128>() as f64 * share) as u128)
+                    }
+                };
+                stake.max(floor)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntheticValidators {
+    pub count: usize,
+    pub seed: u64,
+    pub distribution: Option<StakeDistribution>,
+}
+
+/// Replace the config's validators with the synthetic set
+pub fn apply_synthetic_validators(config: &mut GenesisConfig, synthetic: &SyntheticValidators) -> Result<()> {
+    let floor = parse_amount(&config.validator_config.minimum_bond)
+        .map_err(|e| anyhow::anyhow!("validatorConfig.minimumBond: {}", e))?;
+    let distribution = synthetic
+        .distribution
+        .clone()
+        .unwrap_or(StakeDistribution::Uniform(floor));
+    warn!(
+        "Replacing the {} configured validator(s) with {} synthetic ones (seed {}); never use them for real networks",
+        config.validators.len(),
+        synthetic.count,
+        synthetic.seed
+    );
+    let mut validators = devnet_config(&DevnetOptions {
+        validators: synthetic.count,
+        chain_id: config.chain_id,
+        host: "127.0.0.1".to_string(),
+        base_port: BASE_PORT,
+        stake_amount: floor.to_string(),
+        seed: Some(synthetic.seed),
+    })?
+    .validators;
+    let stakes = distribution.stakes(synthetic.count, synthetic.seed, floor);
+    for (validator, stake) in validators.iter_mut().zip(stakes) {
+        validator.stake_amount = stake.to_string();
+        validator.voting_power = stake.to_string();
+    }
+    config.validators = validators;
+    Ok(())
+}
+
+/// Genesis executed with the first `validators` validators of the set
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaleStep {
+    pub validators: usize,
+    pub total_gas: u64,
+    /// Gas of the heaviest transaction, `Genesis.initialize` in practice
+    pub max_transaction_gas: u64,
+    pub storage_slots_written: usize,
+    /// Why execution failed at this size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+}
+
+/// Where the set first crosses a bound
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoundCrossing {
+    pub bound: u64,
+    /// Smallest measured size over the bound (or failing, for gas)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exceeded_at: Option<usize>,
+    /// Size at which the bound would be reached, extrapolated linearly from
+    /// the two largest successful sizes when none exceeded it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaleReport {
+    pub seed: u64,
+    pub steps: Vec<ScaleStep>,
+    pub gas: BoundCrossing,
+    pub storage: BoundCrossing,
+}
+
+impl ScaleReport {
+    pub fn log(&self) {
+        info!(
+            "{:>10} {:>14} {:>14} {:>10}",
+            "validators", "max tx gas", "total gas", "slots"
+        );
+        for step in &self.steps {
+            match &step.failure {
+                Some(failure) => info!("{:>10} failed: {}", step.validators, failure),
+                None => info!(
+                    "{:>10} {:>14} {:>14} {:>10}",
+                    step.validators, step.max_transaction_gas, step.total_gas, step.storage_slots_written
+                ),
+            }
+        }
+        log_crossing("gas", &self.gas);
+        log_crossing("fresh-slot", &self.storage);
+    }
+}
+
+fn log_crossing(budget: &str, crossing: &BoundCrossing) {
+    match (crossing.exceeded_at, crossing.projected_at) {
+        (Some(size), _) => warn!(
+            "⚠️  Genesis exceeds the {} {} budget at {} validator(s)",
+            crossing.bound, budget, size
+        ),
+        (None, Some(size)) => info!(
+            "Within the {} {} budget; projected to reach it at about {} validator(s)",
+            crossing.bound, budget, size
+        ),
+        (None, None) => info!("Within the {} {} budget", crossing.bound, budget),
+    }
+}
+
+/// Prefix sizes executed for a set of `count`: 1, 2, 4, ... and `count`
+fn ladder(count: usize) -> Vec<usize> {
+    let mut sizes: Vec<usize> = std::iter::successors(Some(1usize), |n| n.checked_mul(2))
+        .take_while(|n| *n < count)
+        .collect();
+    sizes.push(count);
+    sizes
+}
+
+fn storage_slots_written(bundle_state: &BundleState) -> usize {
+    bundle_state
+        .state
+        .values()
+        .map(|account| account.storage.values().filter(|slot| slot.is_changed()).count())
+        .sum()
+}
+
+fn crossing(steps: &[ScaleStep], bound: u64, measure: impl Fn(&ScaleStep) -> u64, failure_counts: bool) -> BoundCrossing {
+    let exceeded_at = steps
+        .iter()
+        .find(|step| (failure_counts && step.failure.is_some()) || measure(step) > bound)
+        .map(|step| step.validators);
+    let succeeded: Vec<&ScaleStep> = steps.iter().filter(|step| step.failure.is_none()).collect();
+    let projected_at = match (exceeded_at, succeeded.as_slice()) {
+        (None, [.., a, b]) if measure(b) > measure(a) => {
+            let per_validator = (measure(b) - measure(a)) as f64 / (b.validators - a.validators) as f64;
+            Some(b.validators as u64 + ((bound - measure(b)) as f64 / per_validator) as u64)
+        }
+        _ => None,
+    };
+    BoundCrossing {
+        bound,
+        exceeded_at,
+        projected_at,
+    }
+}
+
+/// Execute genesis in memory for growing prefixes of the config's validators;
+/// `initialize_call_data` encodes `Genesis.initialize` for a config when the
+/// built-in encoding is not used
+pub fn scale_report(
+    byte_code_dir: &str,
+    contracts: &ContractsManifest,
+    config: &GenesisConfig,
+    seed: u64,
+    initialize_call_data: impl Fn(&GenesisConfig) -> Result<Option<Vec<u8>>>,
+) -> Result<ScaleReport> {
+    let mut steps = Vec::new();
+    for size in ladder(config.validators.len()) {
+        info!("=== Scale report: executing genesis with {} validator(s) ===", size);
+        let mut prefix = config.clone();
+        prefix.validators.truncate(size);
+        let inputs = BuildInputs::prepare(byte_code_dir, contracts, &prefix, initialize_call_data(&prefix)?)?;
+        let db = deployment_db(contracts, &inputs);
+        let step = match execute_revm_sequential(&db, prefix.spec_id(), config_env(&prefix), &inputs.transactions, None) {
+            Ok((results, bundle_state)) => ScaleStep {
+                validators: size,
+                total_gas: results.iter().map(|r| r.gas_used()).sum(),
+                max_transaction_gas: results.iter().map(|r| r.gas_used()).max().unwrap_or_default(),
+                storage_slots_written: storage_slots_written(&bundle_state),
+                failure: results
+                    .iter()
+                    .enumerate()
+                    .find(|(_, r)| !r.is_success())
+                    .map(|(i, r)| format!("transaction {}: {}", i + 1, analyze_txn_result(r))),
+            },
+            Err(e) => ScaleStep {
+                validators: size,
+                total_gas: 0,
+                max_transaction_gas: 0,
+                storage_slots_written: 0,
+                failure: Some(format!("{:?}", e.map_db_err(|_| "Database error".to_string()))),
+            },
+        };
+        steps.push(step);
+    }
+    let gas = crossing(&steps, GAS_BUDGET, |step| step.max_transaction_gas, true);
+    let storage = crossing(&steps, SLOT_BUDGET, |step| step.storage_slots_written as u64, false);
+    Ok(ScaleReport {
+        seed,
+        steps,
+        gas,
+        storage,
+    })
+}
+
+pub fn write_scale_report(report: &ScaleReport, output_dir: &str) -> Result<()> {
+    let path = Path::new(output_dir).join(SCALE_REPORT_FILE);
+    fs::write(&path, serde_json::to_string_pretty(report)? + "\n")
+        .context(format!("Failed to write {}", path.display()))?;
+    info!("Scale report written to {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,233 @@
+//! Call tracing for genesis transactions
+//!
+//! [`CallTracer`] is a revm inspector recording every call and create frame
+//! (depth, target, input, gas, outcome). [`format_trace`] renders the tree in
+//! the style of `cast run`, naming system contracts, functions and custom
+//! errors from the artifacts' ABIs.
+
+use alloy_primitives::{Address, Bytes, U256};
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter, InterpreterResult,
+    },
+    Database, EvmContext, Inspector,
+};
+use revm_primitives::hex;
+use serde::Serialize;
+
+use crate::abi::AbiRegistry;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    pub depth: usize,
+    /// CALL, STATICCALL, DELEGATECALL, CALLCODE, CREATE or CREATE2
+    pub kind: String,
+    pub from: Address,
+    /// Code being executed; for creates, the new contract once known
+    pub to: Option<Address>,
+    pub input: Bytes,
+    pub value: U256,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub success: bool,
+    /// Instruction result the frame ended with, e.g. `Return`, `Revert`, `OutOfGas`
+    pub status: String,
+    pub output: Bytes,
+    /// Program counter of the last instruction executed in this frame, i.e.
+    /// the `REVERT` for frames that reverted
+    pub pc: usize,
+}
+
+/// Records the call tree of each transaction; take it with [`CallTracer::take`]
+#[derive(Debug, Default)]
+pub struct CallTracer {
+    frames: Vec<CallFrame>,
+    /// Indices of frames that have been entered but not exited
+    open: Vec<usize>,
+}
+
+impl CallTracer {
+    fn enter(&mut self, mut frame: CallFrame) {
+        frame.depth = self.open.len();
+        self.open.push(self.frames.len());
+        self.frames.push(frame);
+    }
+
+    fn exit(&mut self, result: &InterpreterResult, created: Option<Address>) {
+        let Some(index) = self.open.pop() else {
+            return;
+        };
+        let frame = &mut self.frames[index];
+        frame.gas_used = result.gas.spent();
+        frame.success = result.result.is_ok();
+        frame.status = format!("{:?}", result.result);
+        frame.output = result.output.clone();
+        if created.is_some() {
+            frame.to = created;
+        }
+    }
+
+    /// Frames recorded since the last call, in call order
+    pub fn take(&mut self) -> Vec<CallFrame> {
+        self.open.clear();
+        std::mem::take(&mut self.frames)
+    }
+}
+
+/// `Call`/`Create2 { salt: .. }` style debug names to opcode names
+fn kind_name(debug: String) -> String {
+    debug
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_uppercase()
+}
+
+impl<DB: Database> Inspector<DB> for CallTracer {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if let Some(&index) = self.open.last() {
+            self.frames[index].pc = interp.program_counter();
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.enter(CallFrame {
+            depth: 0,
+            kind: kind_name(format!("{:?}", inputs.scheme)),
+            from: inputs.caller,
+            to: Some(inputs.bytecode_address),
+            input: inputs.input.clone(),
+            value: inputs.value.get(),
+            gas_limit: inputs.gas_limit,
+            gas_used: 0,
+            success: false,
+            status: String::new(),
+            output: Bytes::new(),
+            pc: 0,
+        });
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit(&outcome.result, None);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.enter(CallFrame {
+            depth: 0,
+            kind: kind_name(format!("{:?}", inputs.scheme)),
+            from: inputs.caller,
+            to: None,
+            input: inputs.init_code.clone(),
+            value: inputs.value,
+            gas_limit: inputs.gas_limit,
+            gas_used: 0,
+            success: false,
+            status: String::new(),
+            output: Bytes::new(),
+            pc: 0,
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.exit(&outcome.result, outcome.address);
+        outcome
+    }
+}
+
+/// Frame where a failed call tree started failing: from the outermost failed
+/// frame, follow the last failed child down as far as it goes
+pub fn revert_origin(frames: &[CallFrame]) -> Option<&CallFrame> {
+    let mut origin = frames.iter().position(|f| !f.success)?;
+    loop {
+        let depth = frames[origin].depth;
+        let child = frames[origin + 1..]
+            .iter()
+            .take_while(|f| f.depth > depth)
+            .enumerate()
+            .filter(|(_, f)| f.depth == depth + 1 && !f.success)
+            .last();
+        match child {
+            Some((offset, _)) => origin += 1 + offset,
+            None => return Some(&frames[origin]),
+        }
+    }
+}
+
+/// Custom error, `Error(string)`/`Panic(uint256)` or raw bytes of a revert
+pub fn describe_revert(output: &[u8], abi: &AbiRegistry) -> String {
+    if output.is_empty() {
+        return "(no revert data)".to_string();
+    }
+    if let Some(error) = abi.error(output) {
+        return match output.len() > 4 {
+            true => format!("{} 0x{}", error, hex::encode(&output[4..])),
+            false => error.to_string(),
+        };
+    }
+    alloy_sol_types::decode_revert_reason(output)
+        .unwrap_or_else(|| format!("0x{}", hex::encode(output)))
+}
+
+/// Render frames as an indented call tree with gas and revert reasons
+pub fn format_trace(frames: &[CallFrame], abi: &AbiRegistry) -> String {
+    let mut out = String::new();
+    for frame in frames {
+        let indent = "  ".repeat(frame.depth);
+        let target = match frame.to {
+            Some(address) => abi
+                .name_of(&address)
+                .map_or_else(|| format!("{:?}", address), str::to_string),
+            None => "<create failed>".to_string(),
+        };
+        let call = if frame.kind.starts_with("CREATE") {
+            format!("new {}", target)
+        } else {
+            let function = match frame.input.get(..4) {
+                None => "fallback()".to_string(),
+                Some(selector) => abi
+                    .function(&frame.input)
+                    .map_or_else(|| format!("0x{}", hex::encode(selector)), str::to_string),
+            };
+            format!("{}::{}", target, function)
+        };
+        let value = match frame.value.is_zero() {
+            true => String::new(),
+            false => format!(" {{value: {}}}", frame.value),
+        };
+        out.push_str(&format!(
+            "{}[{}] {} {}{}\n",
+            indent, frame.gas_used, frame.kind, call, value
+        ));
+        if !frame.success {
+            out.push_str(&format!(
+                "{}  └─ ← [{}] {}\n",
+                indent,
+                frame.status,
+                describe_revert(&frame.output, abi)
+            ));
+        }
+    }
+    out
+}
@@ -0,0 +1,217 @@
+//! Governance upgrade payload generation
+//!
+//! Compares two bytecode artifact directories and assembles the governance
+//! proposal for shipping the change. System contracts live at fixed addresses
+//! without proxies, so changed bytecode itself can only land through a client
+//! hardfork; what Governance can carry is the on-chain side of the rollout
+//! (the `VersionConfig` bump gating the new behaviour, an immediate
+//! reconfiguration, and any extra config calls). The payload lists both so the
+//! release can be prepared from one place.
+//!
+//! Contracts whose code differs only in the solc metadata trailer are flagged
+//! `metadataOnly`; with `ignore_metadata` they are listed as equivalent instead
+//! of counting as changed.
+
+use alloy_primitives::{keccak256, Address, Bytes, B256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::{SolCall, SolValue};
+use anyhow::{Context, Result};
+use revm_primitives::hex;
+use serde::Serialize;
+use std::{fs, path::Path};
+use tracing::{info, warn};
+
+use crate::{
+    bytecode::{compare_code, CodeComparison},
+    registry::{ContractsManifest, SystemContract},
+    simulate::governance::ProposalAction,
+    utils::{GOVERNANCE_ADDR, RECONFIGURATION_ADDR, VERSION_CONFIG_ADDR},
+};
+
+sol! {
+    interface IUpgradeTargets {
+        function setForNextEpoch(uint64 majorVersion) external;
+        function governanceReconfigure() external;
+        function createProposal(address stakePool, address[] targets, bytes[] datas, string metadataUri) external returns (uint64 proposalId);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeOptions {
+    /// New `VersionConfig.majorVersion` to schedule
+    pub major_version: Option<u64>,
+    /// Force the epoch transition right after execution
+    pub reconfigure: bool,
+    /// Extra calls appended to the proposal
+    pub extra_actions: Vec<ProposalAction>,
+    /// Pool used to build the `createProposal` calldata
+    pub stake_pool: Option<Address>,
+    pub metadata_uri: String,
+    /// Treat code differing only in solc metadata as unchanged
+    pub ignore_metadata: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedContract {
+    pub name: String,
+    pub address: Address,
+    pub old_code_hash: Option<B256>,
+    pub new_code_hash: B256,
+    pub old_size: usize,
+    pub new_size: usize,
+    /// Only the trailing solc metadata differs
+    pub metadata_only: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradePayload {
+    pub changed_contracts: Vec<ChangedContract>,
+    /// Contracts left out of `changedContracts` as equivalent modulo metadata
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub equivalent_modulo_metadata: Vec<String>,
+    /// Bytecode changes can only be applied by a client hardfork
+    pub requires_hardfork: bool,
+    pub governance: Address,
+    pub targets: Vec<Address>,
+    pub datas: Vec<Bytes>,
+    /// `keccak256(abi.encode(targets, datas))`, as checked by `Governance.execute`
+    pub execution_hash: B256,
+    pub metadata_uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create_proposal_calldata: Option<Bytes>,
+}
+
+fn read_runtime_code(dir: &Path, contract_name: &str) -> Result<Option<Vec<u8>>> {
+    let path = dir.join(format!("{}.hex", contract_name));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        fs::read_to_string(&path).context(format!("Failed to read {}", path.display()))?;
+    let trimmed = content.trim();
+    let code = hex::decode(trimmed.strip_prefix("0x").unwrap_or(trimmed))
+        .context(format!("Invalid hex in {}", path.display()))?;
+    Ok(Some(code))
+}
+
+/// Diff system contract bytecode between two artifact directories, using the
+/// contract registry shipped with the new artifacts
+pub fn diff_artifacts(old_dir: &str, new_dir: &str) -> Result<Vec<ChangedContract>> {
+    let contracts = ContractsManifest::resolve(new_dir, None)?;
+    let old_dir = Path::new(old_dir);
+    let new_dir = Path::new(new_dir);
+    let mut changed = Vec::new();
+
+    for SystemContract { name, address, .. } in contracts.contracts {
+        let name = name.as_str();
+        let Some(new_code) = read_runtime_code(new_dir, name)? else {
+            anyhow::bail!("{}.hex missing from {}", name, new_dir.display());
+        };
+        let old_code = read_runtime_code(old_dir, name)?;
+        let comparison = old_code
+            .as_deref()
+            .map(|old| compare_code(old, &new_code));
+        if comparison == Some(CodeComparison::Identical) {
+            continue;
+        }
+        changed.push(ChangedContract {
+            name: name.to_string(),
+            address,
+            old_code_hash: old_code.as_ref().map(keccak256),
+            new_code_hash: keccak256(&new_code),
+            old_size: old_code.as_ref().map_or(0, |c| c.len()),
+            new_size: new_code.len(),
+            metadata_only: comparison == Some(CodeComparison::EquivalentModuloMetadata),
+        });
+    }
+
+    Ok(changed)
+}
+
+/// Build the governance proposal accompanying an artifact upgrade
+pub fn build_upgrade_payload(
+    old_dir: &str,
+    new_dir: &str,
+    options: &UpgradeOptions,
+) -> Result<UpgradePayload> {
+    let (equivalent, changed_contracts): (Vec<_>, Vec<_>) = diff_artifacts(old_dir, new_dir)?
+        .into_iter()
+        .partition(|contract| options.ignore_metadata && contract.metadata_only);
+    for contract in &equivalent {
+        info!(
+            "{} ({:?}) is equivalent modulo metadata",
+            contract.name, contract.address
+        );
+    }
+    for contract in &changed_contracts {
+        info!(
+            "{} ({:?}) changed{}: {} -> {} bytes",
+            contract.name,
+            contract.address,
+            if contract.metadata_only { " (metadata only)" } else { "" },
+            contract.old_size,
+            contract.new_size
+        );
+    }
+
+    let mut actions = Vec::new();
+    if let Some(version) = options.major_version {
+        actions.push(ProposalAction {
+            target: VERSION_CONFIG_ADDR,
+            data: IUpgradeTargets::setForNextEpochCall {
+                majorVersion: version,
+            }
+            .abi_encode()
+            .into(),
+        });
+    }
+    actions.extend(options.extra_actions.iter().cloned());
+    if options.reconfigure {
+        actions.push(ProposalAction {
+            target: RECONFIGURATION_ADDR,
+            data: IUpgradeTargets::governanceReconfigureCall {}
+                .abi_encode()
+                .into(),
+        });
+    }
+
+    if actions.is_empty() {
+        anyhow::bail!("Proposal would be empty; pass --major-version, --reconfigure or --action");
+    }
+    if !changed_contracts.is_empty() {
+        warn!(
+            "{} system contract(s) changed bytecode; these require a client hardfork, \
+             the proposal only carries the on-chain rollout",
+            changed_contracts.len()
+        );
+    }
+
+    let targets: Vec<Address> = actions.iter().map(|a| a.target).collect();
+    let datas: Vec<Bytes> = actions.iter().map(|a| a.data.clone()).collect();
+    let execution_hash = keccak256((targets.clone(), datas.clone()).abi_encode_params());
+
+    let create_proposal_calldata = options.stake_pool.map(|pool| {
+        IUpgradeTargets::createProposalCall {
+            stakePool: pool,
+            targets: targets.clone(),
+            datas: datas.clone(),
+            metadataUri: options.metadata_uri.clone(),
+        }
+        .abi_encode()
+        .into()
+    });
+
+    Ok(UpgradePayload {
+        requires_hardfork: !changed_contracts.is_empty(),
+        changed_contracts,
+        equivalent_modulo_metadata: equivalent.into_iter().map(|c| c.name).collect(),
+        governance: GOVERNANCE_ADDR,
+        targets,
+        datas,
+        execution_hash,
+        metadata_uri: options.metadata_uri.clone(),
+        create_proposal_calldata,
+    })
+}
@@ -3,13 +3,19 @@ use alloy_primitives::address;
 use alloy_sol_macro::sol;
 use alloy_sol_types::SolEvent;
 use revm::{
-    DatabaseCommit, DatabaseRef, EvmBuilder, StateBuilder,
+    DatabaseCommit, DatabaseRef, EvmBuilder, StateBuilder, inspector_handle_register,
     db::{BundleState, states::bundle_state::BundleRetention},
     primitives::{Address, EVMError, Env, ExecutionResult, SpecId, TxEnv, U256},
 };
 use revm_primitives::{AccountInfo, Bytes, KECCAK_EMPTY, TxKind, hex, uint};
 use std::u64;
-use tracing::info;
+use tracing::{info, info_span};
+
+use crate::{
+    precompiles::{self, PrecompileMode},
+    sourcemap::SourceLocation,
+    trace::{CallFrame, CallTracer},
+};
 
 pub const DEAD_ADDRESS: Address = address!("000000000000000000000000000000000000dEaD");
 
@@ -102,6 +108,17 @@ pub const ORACLE_REQUEST_QUEUE_ADDR: Address = address!("00000000000000000000000
 /// Native mint precompile
 pub const NATIVE_MINT_PRECOMPILE_ADDR: Address = address!("00000000000000000000000000000001625F5000");
 
+/// BLS12-381 proof-of-possession verification precompile
+pub const BLS_POP_VERIFY_PRECOMPILE_ADDR: Address = address!("00000000000000000000000000000001625F5001");
+
+/// Inclusive bounds of the address range reserved for system contracts and precompiles
+pub const RESERVED_RANGE_START: Address = address!("00000000000000000000000000000001625F0000");
+pub const RESERVED_RANGE_END: Address = address!("00000000000000000000000000000001625F5FFF");
+
+pub fn is_reserved_system_address(address: Address) -> bool {
+    (RESERVED_RANGE_START..=RESERVED_RANGE_END).contains(&address)
+}
+
 // ============================================================================
 // CONTRACTS ARRAY - All contracts to deploy at genesis
 // Note: StakePool is created dynamically during Genesis.initialize, not pre-deployed
@@ -143,6 +160,11 @@ sol! {
 }
 
 pub fn analyze_txn_result(result: &ExecutionResult) -> String {
+    analyze_txn_result_at(result, None)
+}
+
+/// [`analyze_txn_result`], pointing reverts at the Solidity source they came from
+pub fn analyze_txn_result_at(result: &ExecutionResult, location: Option<&SourceLocation>) -> String {
     match result {
         ExecutionResult::Revert { gas_used, output } => {
             let mut reason = format!("Revert with gas used: {}", gas_used);
@@ -170,6 +192,10 @@ pub fn analyze_txn_result(result: &ExecutionResult) -> String {
                 ));
             }
 
+            if let Some(location) = location {
+                reason.push_str(&format!("\nReverted at: {}", location));
+            }
+
             reason
         }
         ExecutionResult::Success { gas_used, logs, .. } => {
@@ -200,6 +226,23 @@ pub(crate) fn execute_revm_sequential<DB>(
     txs: &[TxEnv],
     pre_bundle: Option<BundleState>,
 ) -> Result<(Vec<ExecutionResult>, BundleState), EVMError<DB::Error>>
+where
+    DB: DatabaseRef,
+{
+    execute_revm_sequential_traced(db, spec_id, env, PrecompileMode::default(), txs, pre_bundle)
+        .map(|(results, bundle, _)| (results, bundle))
+}
+
+/// Like [`execute_revm_sequential`], also returning the call tree of each
+/// transaction; chain precompiles are installed according to `precompiles`
+pub(crate) fn execute_revm_sequential_traced<DB>(
+    db: DB,
+    spec_id: SpecId,
+    env: Env,
+    precompiles: PrecompileMode,
+    txs: &[TxEnv],
+    pre_bundle: Option<BundleState>,
+) -> Result<(Vec<ExecutionResult>, BundleState, Vec<Vec<CallFrame>>), EVMError<DB::Error>>
 where
     DB: DatabaseRef,
 {
@@ -216,12 +259,18 @@ where
     };
     let mut evm = EvmBuilder::default()
         .with_db(db)
+        .with_external_context(CallTracer::default())
         .with_spec_id(spec_id)
         .with_env(Box::new(env))
+        .append_handler_register(inspector_handle_register)
+        .append_handler_register(precompiles::handle_register(precompiles))
         .build();
 
     let mut results = Vec::with_capacity(txs.len());
+    let mut traces = Vec::with_capacity(txs.len());
+
     for (i, tx) in txs.iter().enumerate() {
+        let _span = info_span!("transaction", index = i + 1, to = ?tx.transact_to).entered();
         info!("=== Executing transaction {} ===", i + 1);
         info!("Transaction details:");
         info!("  Caller: {:?}", tx.caller);
@@ -239,15 +288,45 @@ where
         evm.db_mut().commit(result_and_state.state);
 
         info!(
+            success = result_and_state.result.is_success(),
+            gas_used = result_and_state.result.gas_used(),
             "Transaction result: {}",
             analyze_txn_result(&result_and_state.result)
         );
         results.push(result_and_state.result);
+        traces.push(evm.context.external.take());
         info!("=== Transaction {} completed ===", i + 1);
     }
     evm.db_mut().merge_transitions(BundleRetention::Reverts);
 
-    Ok((results, evm.db_mut().take_bundle()))
+    Ok((results, evm.db_mut().take_bundle(), traces))
+}
+
+/// EIP-55 status of a textual address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressChecksum {
+    /// Mixed case with a correct checksum
+    Valid,
+    /// All lower- or upper-case, carrying no checksum
+    Missing,
+    /// Mixed case that does not match the checksum, most likely a typo
+    Invalid,
+}
+
+/// Parse an address and report whether its casing is a valid EIP-55 checksum
+pub fn parse_address_checksum(s: &str) -> Result<(Address, AddressChecksum), String> {
+    let address = s
+        .parse::<Address>()
+        .map_err(|e| format!("invalid address {:?}: {}", s, e))?;
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    let status = if Address::parse_checksummed(s, None).is_ok() {
+        AddressChecksum::Valid
+    } else if digits == digits.to_lowercase() || digits == digits.to_uppercase() {
+        AddressChecksum::Missing
+    } else {
+        AddressChecksum::Invalid
+    };
+    Ok((address, status))
 }
 
 pub fn new_system_call_txn(contract: Address, input: Bytes) -> TxEnv {
@@ -275,6 +354,21 @@ pub fn new_system_call_txn_with_value(contract: Address, input: Bytes, value: U2
     }
 }
 
+/// Call from `coinbase` with a zero gas price, for functions guarded by
+/// `onlyCoinbase` (`msg.sender == block.coinbase`) and `onlyZeroGasPrice`; the
+/// block's coinbase must be set to the same address, see [`override_coinbase`]
+pub fn new_coinbase_call_txn(coinbase: Address, contract: Address, input: Bytes) -> TxEnv {
+    TxEnv {
+        caller: coinbase,
+        ..new_system_call_txn(contract, input)
+    }
+}
+
+/// Set the block coinbase of `env`, returning the previous one
+pub fn override_coinbase(env: &mut Env, coinbase: Address) -> Address {
+    std::mem::replace(&mut env.block.coinbase, coinbase)
+}
+
 pub fn new_system_create_txn(hex_code: &str, args: Bytes) -> TxEnv {
     let mut data = hex::decode(hex_code).expect("Invalid hex string");
     data.extend_from_slice(&args);
@@ -292,3 +386,59 @@ pub fn new_system_create_txn(hex_code: &str, args: Bytes) -> TxEnv {
 pub fn read_hex_from_file(path: &str) -> String {
     std::fs::read_to_string(path).expect(&format!("Failed to open {}", path))
 }
+
+/// Whether a single path component matches a pattern with `*` and `?` wildcards
+fn wildcard_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            wildcard_match(rest, name) || (!name.is_empty() && wildcard_match(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name_rest))) => wildcard_match(rest, name_rest),
+        (Some((p, rest)), Some((n, name_rest))) => p == n && wildcard_match(rest, name_rest),
+        _ => false,
+    }
+}
+
+/// Expand `*`/`?` wildcards in any path component, e.g. `envs/*/genesis.json`.
+/// A path without wildcards is returned as is; matches are sorted.
+pub fn expand_glob(pattern: &str) -> anyhow::Result<Vec<std::path::PathBuf>> {
+    use std::path::{Component, PathBuf};
+
+    if !pattern.contains(['*', '?']) {
+        return Ok(vec![PathBuf::from(pattern)]);
+    }
+    let mut candidates = vec![PathBuf::new()];
+    for component in std::path::Path::new(pattern).components() {
+        let part = component.as_os_str().to_string_lossy();
+        if !matches!(component, Component::Normal(_)) || !part.contains(['*', '?']) {
+            candidates.iter_mut().for_each(|c| c.push(component));
+            continue;
+        }
+        let mut next = Vec::new();
+        for dir in &candidates {
+            let read_from = if dir.as_os_str().is_empty() {
+                std::path::Path::new(".")
+            } else {
+                dir
+            };
+            let Ok(entries) = std::fs::read_dir(read_from) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                let hidden = name.to_string_lossy().starts_with('.') && !part.starts_with('.');
+                if !hidden && wildcard_match(part.as_bytes(), name.to_string_lossy().as_bytes()) {
+                    next.push(dir.join(name));
+                }
+            }
+        }
+        candidates = next;
+    }
+    candidates.retain(|c| c.exists());
+    candidates.sort();
+    if candidates.is_empty() {
+        anyhow::bail!("No files match {}", pattern);
+    }
+    Ok(candidates)
+}
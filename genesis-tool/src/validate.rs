@@ -0,0 +1,1898 @@
+//! Semantic validation of the genesis config
+//!
+//! Runs before any EVM work so operator mistakes are reported together, with a
+//! rule id and a readable message, instead of surfacing as an opaque revert
+//! inside `Genesis.initialize`.
+
+use tracing::{error, info, warn};
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use revm_primitives::{hex, Address, SpecId, U256};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+};
+
+use crate::{
+    analyze::{basis_points, format_basis_points, StakeAnalysis},
+    genesis::{
+        extra_alloc_accounts, format_ether, genesis_calls, parse_amount, GenesisConfig,
+        DEFAULT_CHAIN_ID,
+    },
+    multiaddr::parse_network_address,
+    spec::{ethereum_fork_keys, is_ethereum_fork, GRAVITY_FORKS},
+    rpc::RpcClient,
+    onchain_config::{OnChainConsensusConfig, ProposerElectionType},
+    utils::{
+        is_reserved_system_address, parse_address_checksum, AddressChecksum, CONTRACTS,
+        RESERVED_RANGE_END, RESERVED_RANGE_START,
+    },
+    multisig, vesting,
+};
+
+/// Chain IDs treated as local development networks
+pub const DEVNET_CHAIN_IDS: [u64; 2] = [1337, 31337];
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Noted for review; never fails validation
+    Info,
+    /// Fails validation only with `--strict`
+    Warning,
+    Error,
+}
+
+/// Suppresses findings of one rule that are known to be acceptable for this
+/// config, e.g. localhost addresses on a devnet. Exempted findings are still
+/// reported, downgraded to [`Severity::Info`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Exemption {
+    pub rule: String,
+    /// Only exempt findings whose message contains this text
+    #[serde(default)]
+    pub contains: Option<String>,
+    /// Why the deviation is acceptable, echoed next to each exempted finding
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl Exemption {
+    fn matches(&self, finding: &Finding) -> bool {
+        self.rule == finding.rule
+            && self
+                .contains
+                .as_ref()
+                .is_none_or(|text| finding.message.contains(text.as_str()))
+    }
+}
+
+/// Load an exemptions file: a JSON array of `{ "rule", "contains"?, "reason"? }`
+pub fn load_exemptions(path: &str) -> Result<Vec<Exemption>> {
+    let content =
+        fs::read_to_string(path).context(format!("Failed to read exemptions file {}", path))?;
+    serde_json::from_str(&content).context(format!("Failed to parse exemptions file {}", path))
+}
+
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    pub rule: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub findings: Vec<Finding>,
+    /// Parsed values echoed back for human review
+    pub summary: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn push(&mut self, severity: Severity, rule: &'static str, message: impl Into<String>) {
+        self.findings.push(Finding {
+            severity,
+            rule,
+            message: message.into(),
+        });
+    }
+
+    pub fn error(&mut self, rule: &'static str, message: impl Into<String>) {
+        self.push(Severity::Error, rule, message);
+    }
+
+    pub fn warn(&mut self, rule: &'static str, message: impl Into<String>) {
+        self.push(Severity::Warning, rule, message);
+    }
+
+    pub fn info(&mut self, rule: &'static str, message: impl Into<String>) {
+        self.push(Severity::Info, rule, message);
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    /// Findings that fail validation: errors, and with `strict` warnings too
+    pub fn failure_count(&self, strict: bool) -> usize {
+        let threshold = if strict { Severity::Warning } else { Severity::Error };
+        self.findings
+            .iter()
+            .filter(|f| f.severity >= threshold)
+            .count()
+    }
+
+    /// Downgrade the findings matched by an exemption to info
+    pub fn apply_exemptions(&mut self, exemptions: &[Exemption]) {
+        for finding in &mut self.findings {
+            let Some(exemption) = exemptions.iter().find(|e| e.matches(finding)) else {
+                continue;
+            };
+            finding.severity = Severity::Info;
+            finding.message = match &exemption.reason {
+                Some(reason) => format!("{} (exempt: {})", finding.message, reason),
+                None => format!("{} (exempt)", finding.message),
+            };
+        }
+        for exemption in exemptions {
+            if !self.findings.iter().any(|f| f.rule == exemption.rule) {
+                warn!("Exemption for rule {} matched no finding", exemption.rule);
+            }
+        }
+    }
+
+    /// Log the summary, then every finding at its severity
+    pub fn log(&self) {
+        for line in &self.summary {
+            info!("{}", line);
+        }
+        for finding in &self.findings {
+            match finding.severity {
+                Severity::Error => error!("❌ [{}] {}", finding.rule, finding.message),
+                Severity::Warning => warn!("⚠️ [{}] {}", finding.rule, finding.message),
+                Severity::Info => info!("ℹ️ [{}] {}", finding.rule, finding.message),
+            }
+        }
+        if self.findings.iter().all(|f| f.severity == Severity::Info) {
+            info!("✅ Genesis config validation passed");
+        }
+    }
+}
+
+pub fn is_devnet_chain_id(chain_id: u64) -> bool {
+    DEVNET_CHAIN_IDS.contains(&chain_id)
+}
+
+/// Chain IDs of public networks, which a new chain must not reuse: wallets and
+/// EIP-155 replay protection tell chains apart by ID alone
+pub const KNOWN_CHAIN_IDS: &[(u64, &str)] = &[
+    (1, "Ethereum Mainnet"),
+    (5, "Goerli"),
+    (10, "OP Mainnet"),
+    (56, "BNB Smart Chain"),
+    (97, "BNB Smart Chain Testnet"),
+    (100, "Gnosis"),
+    (137, "Polygon PoS"),
+    (250, "Fantom Opera"),
+    (324, "zkSync Era"),
+    (1101, "Polygon zkEVM"),
+    (5000, "Mantle"),
+    (8453, "Base"),
+    (17000, "Holesky"),
+    (42161, "Arbitrum One"),
+    (42220, "Celo"),
+    (43114, "Avalanche C-Chain"),
+    (59144, "Linea"),
+    (80002, "Polygon Amoy"),
+    (81457, "Blast"),
+    (84532, "Base Sepolia"),
+    (421614, "Arbitrum Sepolia"),
+    (534352, "Scroll"),
+    (560048, "Hoodi"),
+    (7777777, "Zora"),
+    (11155111, "Sepolia"),
+    (11155420, "OP Sepolia"),
+];
+
+pub fn known_chain_name(chain_id: u64) -> Option<&'static str> {
+    KNOWN_CHAIN_IDS
+        .iter()
+        .find(|(id, _)| *id == chain_id)
+        .map(|(_, name)| *name)
+}
+
+/// Every validator advertises a localhost network address, as local devnets do
+fn is_local_validator_set(config: &GenesisConfig) -> bool {
+    config.validators.iter().all(|v| {
+        parse_network_address(&v.network_addresses).is_ok_and(|addr| addr.host.is_localhost())
+    })
+}
+
+/// The chain ID must be set on purpose and must not collide with a public
+/// network. A config without `chainId` falls back to [`DEFAULT_CHAIN_ID`],
+/// which is only accepted silently for a local validator set; anything else
+/// needs `--allow-default-chain-id` or `allowDefaultChainId`.
+fn check_chain_id(config: &GenesisConfig, options: &ValidationOptions, report: &mut ValidationReport) {
+    if config.chain_id == 0 {
+        report.error("chain-id", "chainId 0 is not a valid EIP-155 chain ID");
+    }
+    if let Some(name) = known_chain_name(config.chain_id) {
+        report.warn(
+            "chain-id",
+            format!(
+                "chainId {} is {}; transactions signed for one chain replay on the other",
+                config.chain_id, name
+            ),
+        );
+    }
+    if config.chain_id_defaulted {
+        let allowed = options.allow_default_chain_id || config.allow_default_chain_id;
+        if allowed {
+            report.info(
+                "chain-id",
+                format!("chainId is not set; using the default {} as allowed", DEFAULT_CHAIN_ID),
+            );
+        } else if is_local_validator_set(config) {
+            report.warn(
+                "chain-id",
+                format!("chainId is not set; using the devnet default {}", DEFAULT_CHAIN_ID),
+            );
+        } else {
+            report.error(
+                "chain-id",
+                format!(
+                    "chainId is not set and validators are not all local; set chainId, or pass --allow-default-chain-id to use {}",
+                    DEFAULT_CHAIN_ID
+                ),
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    /// Treat addresses without an EIP-55 checksum as errors instead of warnings
+    pub strict_checksums: bool,
+    /// JSON-RPC endpoint of the bridge's source chain, to check the trusted
+    /// bridge is deployed there
+    pub bridge_rpc: Option<String>,
+    /// Accept the default chain ID for a config that is not a local devnet
+    pub allow_default_chain_id: bool,
+    /// Fail validation on warnings as well as errors
+    pub strict: bool,
+    /// Known-acceptable findings, downgraded to info
+    pub exemptions: Vec<Exemption>,
+    /// Severity of shared owner/operator findings, over the config's `roleSeparation`
+    pub role_separation: Option<Severity>,
+}
+
+/// Run all config validation rules
+pub fn validate_config(config: &GenesisConfig, options: &ValidationOptions) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    check_chain_id(config, options, &mut report);
+    check_block_params(config, &mut report);
+    check_evm_env(config, &mut report);
+    check_hardforks(config, &mut report);
+    check_addresses(config, options, &mut report);
+    check_amounts(config, &mut report);
+    check_extra_alloc(config, &mut report);
+    check_genesis_calls(config, &mut report);
+    check_vesting(config, &mut report);
+    check_governance_multisig(config, &mut report);
+    check_funded_accounts(config, &mut report);
+    check_stake_invariants(config, &mut report);
+    check_validator_uniqueness(config, &mut report);
+    check_monikers(config, &mut report);
+    check_role_separation(config, options, &mut report);
+    check_stake_concentration(&StakeAnalysis::new(config), &mut report);
+    check_config_bounds(config, &mut report);
+    check_time_units(config, &mut report);
+    check_governance_timing(config, &mut report);
+    check_network_addresses(config, &mut report);
+    check_onchain_configs(config, &mut report);
+    check_pending_callbacks(config, &mut report);
+    check_on_demand_tasks(config, &mut report);
+    check_bridge(config, options, &mut report);
+    check_jwks(config, &mut report);
+    report.apply_exemptions(&options.exemptions);
+    report
+}
+
+/// Gas a plain value transfer uses; a block must fit at least one
+const TRANSFER_GAS: u64 = 21_000;
+/// Largest gas limit greth accepts in a header (`2^63 - 1`)
+const MAX_GAS_LIMIT: u64 = i64::MAX as u64;
+
+/// Genesis header `baseFeePerGas` and `gasLimit`, and the EIP-1559
+/// `baseFeeParams`, against what greth accepts: a base fee needs London at
+/// genesis, a zero denominator or elasticity divides by zero when computing
+/// the next base fee, and the gas target must fit a transfer
+fn check_block_params(config: &GenesisConfig, report: &mut ValidationReport) {
+    if let Some(base_fee) = config.base_fee_per_gas {
+        if config.spec_id() < SpecId::LONDON {
+            report.error(
+                "block-params",
+                format!("baseFeePerGas is set but evmSpec {:?} predates London", config.spec_id()),
+            );
+        } else if base_fee == 0 && !is_devnet_chain_id(config.chain_id) {
+            report.warn(
+                "block-params",
+                "baseFeePerGas is 0; transactions are free until blocks fill past the gas target",
+            );
+        }
+        report.summary.push(format!("baseFeePerGas: {} wei", base_fee));
+    }
+    if let Some(gas_limit) = config.gas_limit {
+        if gas_limit < TRANSFER_GAS {
+            report.error(
+                "block-params",
+                format!("gasLimit {} cannot fit a {}-gas transfer", gas_limit, TRANSFER_GAS),
+            );
+        } else if gas_limit > MAX_GAS_LIMIT {
+            report.error(
+                "block-params",
+                format!("gasLimit {} is over the {} greth accepts", gas_limit, MAX_GAS_LIMIT),
+            );
+        }
+        report.summary.push(format!("gasLimit: {}", gas_limit));
+    }
+    let Some(params) = config.base_fee_params else {
+        return;
+    };
+    if params.max_change_denominator == 0 || params.elasticity_multiplier == 0 {
+        report.error(
+            "block-params",
+            "baseFeeParams.maxChangeDenominator and elasticityMultiplier must be positive",
+        );
+        return;
+    }
+    if let Some(gas_limit) = config.gas_limit {
+        let target = gas_limit / params.elasticity_multiplier;
+        if target < TRANSFER_GAS {
+            report.error(
+                "block-params",
+                format!(
+                    "gas target {} (gasLimit / elasticityMultiplier) cannot fit a transfer; the base fee would only rise",
+                    target
+                ),
+            );
+        } else if gas_limit % params.elasticity_multiplier != 0 {
+            report.warn(
+                "block-params",
+                format!(
+                    "gasLimit {} is not a multiple of elasticityMultiplier {}; the gas target rounds down to {}",
+                    gas_limit, params.elasticity_multiplier, target
+                ),
+            );
+        }
+    }
+    report.summary.push(format!(
+        "baseFeeParams: maxChangeDenominator {}, elasticityMultiplier {}",
+        params.max_change_denominator, params.elasticity_multiplier
+    ));
+}
+
+/// `evmEnv` fields the execution spec ignores, and block fields that differ
+/// from the genesis header, so contracts would see other values at genesis than
+/// the chain's first blocks give them
+fn check_evm_env(config: &GenesisConfig, report: &mut ValidationReport) {
+    let Some(env) = &config.evm_env else {
+        return;
+    };
+    let spec_id = config.spec_id();
+    let blob = env.excess_blob_gas.is_some() || env.blob_base_fee.is_some();
+    let ignored = [
+        (env.basefee.is_some() && spec_id < SpecId::LONDON, "basefee", "predates London"),
+        (env.difficulty.is_some() && spec_id >= SpecId::MERGE, "difficulty", "reads prevrandao instead"),
+        (env.prevrandao.is_some() && spec_id < SpecId::MERGE, "prevrandao", "predates the merge"),
+        (blob && spec_id < SpecId::CANCUN, "excessBlobGas/blobBaseFee", "predates Cancun"),
+    ];
+    for (_, field, reason) in ignored.iter().filter(|(ignored, ..)| *ignored) {
+        report.warn(
+            "evm-env",
+            format!("evmEnv.{} has no effect: evmSpec {:?} {}", field, spec_id, reason),
+        );
+    }
+    if let (Some(block), Some(header)) = (env.block_gas_limit, config.gas_limit) {
+        if block != header {
+            report.warn(
+                "evm-env",
+                format!("evmEnv.blockGasLimit {} differs from the header gasLimit {}", block, header),
+            );
+        }
+    }
+    if let (Some(block), Some(header)) = (env.basefee, config.base_fee_per_gas) {
+        if block != header {
+            report.warn(
+                "evm-env",
+                format!("evmEnv.basefee {} differs from the header baseFeePerGas {}", block, header),
+            );
+        }
+    }
+    report.summary.push(format!(
+        "evmEnv: {}",
+        serde_json::to_string(env).unwrap_or_default()
+    ));
+}
+
+/// Report a fork that activates before one listed ahead of it in `order`
+fn check_fork_order(
+    order: impl Iterator<Item = String>,
+    hardforks: &BTreeMap<String, u64>,
+    report: &mut ValidationReport,
+) {
+    let mut previous: Option<(String, u64)> = None;
+    for key in order {
+        let Some(&at) = hardforks.get(&key) else {
+            continue;
+        };
+        if let Some((previous_key, previous_at)) = &previous {
+            if at < *previous_at {
+                report.error(
+                    "hardforks",
+                    format!(
+                        "{} at {} activates before {} at {}",
+                        key, at, previous_key, previous_at
+                    ),
+                );
+            }
+        }
+        previous = Some((key, at));
+    }
+}
+
+/// The `hardforks` schedule: every key names a block or time activation,
+/// Ethereum forks activate in their canonical order and Gravity's Greek-letter
+/// forks in alphabet order, timestamps are in seconds, and an explicit
+/// `evmSpec` agrees with what the schedule enables at genesis
+fn check_hardforks(config: &GenesisConfig, report: &mut ValidationReport) {
+    let hardforks = &config.hardforks;
+    if hardforks.is_empty() {
+        return;
+    }
+    for (key, &at) in hardforks {
+        let (name, is_time) = match (key.strip_suffix("Block"), key.strip_suffix("Time")) {
+            (Some(name), _) => (name, false),
+            (_, Some(name)) => (name, true),
+            _ => {
+                report.error(
+                    "hardforks",
+                    format!("hardforks.{} is neither a <fork>Block nor a <fork>Time key", key),
+                );
+                continue;
+            }
+        };
+        if !is_ethereum_fork(key) && !GRAVITY_FORKS.contains(&name) {
+            report.warn(
+                "hardforks",
+                format!("hardforks.{}: unknown fork; greth ignores chain config keys it does not know", key),
+            );
+        }
+        if GRAVITY_FORKS.contains(&name) && !is_time && hardforks.contains_key(&format!("{}Time", name)) {
+            report.error(
+                "hardforks",
+                format!("{} is scheduled both by block and by time", name),
+            );
+        }
+        if is_time && at >= MAX_UNIX_SECS {
+            report.error(
+                "hardforks",
+                format!("hardforks.{} {} looks like milliseconds; activation times are Unix seconds", key, at),
+            );
+        }
+    }
+
+    let ethereum = |suffix: &'static str| {
+        ethereum_fork_keys()
+            .filter(move |key| key.ends_with(suffix))
+            .map(str::to_string)
+    };
+    check_fork_order(ethereum("Block"), hardforks, report);
+    check_fork_order(ethereum("Time"), hardforks, report);
+    for suffix in ["Block", "Time"] {
+        let gravity = GRAVITY_FORKS.iter().map(|name| format!("{}{}", name, suffix));
+        check_fork_order(gravity, hardforks, report);
+    }
+
+    if let (Some(explicit), Some(scheduled)) = (config.evm_spec, config.scheduled_spec_id()) {
+        if explicit.spec_id() != scheduled {
+            report.warn(
+                "hardforks",
+                format!(
+                    "evmSpec {:?} differs from {:?}, which hardforks activate at genesis; genesis executes under evmSpec",
+                    explicit.spec_id(),
+                    scheduled
+                ),
+            );
+        }
+    }
+    report.summary.push(format!(
+        "hardforks: {} scheduled, {:?} at genesis",
+        hardforks.len(),
+        config.spec_id()
+    ));
+}
+
+/// Every address field of the config, labelled for messages
+fn config_addresses(config: &GenesisConfig) -> Vec<(String, &str)> {
+    let mut addresses = vec![(
+        "governanceOwner".to_string(),
+        config.governance_owner.as_str(),
+    )];
+    for v in &config.validators {
+        for (field, value) in [
+            ("operator", &v.operator),
+            ("owner", &v.owner),
+            ("staker", &v.staker),
+        ] {
+            addresses.push((format!("validator {} {}", v.moniker, field), value.as_str()));
+        }
+    }
+    for (i, callback) in config.oracle_config.callbacks.iter().enumerate() {
+        addresses.push((format!("oracleConfig.callbacks[{}]", i), callback.as_str()));
+    }
+    for (i, pending) in config.oracle_config.pending_callbacks.iter().enumerate() {
+        addresses.push((
+            format!("oracleConfig.pendingCallbacks[{}]", i),
+            pending.callback.as_str(),
+        ));
+    }
+    for address in config.extra_alloc.keys() {
+        addresses.push(("extraAlloc".to_string(), address.as_str()));
+    }
+    if let Some(treasury) = config
+        .oracle_config
+        .request_queue
+        .as_ref()
+        .and_then(|queue| queue.treasury.as_ref())
+    {
+        addresses.push((
+            "oracleConfig.requestQueue.treasury".to_string(),
+            treasury.as_str(),
+        ));
+    }
+    for (label, call) in genesis_calls(config) {
+        if let Some(caller) = &call.caller {
+            addresses.push((format!("{}.caller", label), caller.as_str()));
+        }
+    }
+    for (i, schedule) in config.vesting.iter().flat_map(|v| &v.schedules).enumerate() {
+        addresses.push((
+            format!("vesting.schedules[{}].beneficiary", i),
+            schedule.beneficiary.as_str(),
+        ));
+        if let Some(revoker) = &schedule.revoker {
+            addresses.push((format!("vesting.schedules[{}].revoker", i), revoker.as_str()));
+        }
+    }
+    for (key, account) in config.funded_accounts() {
+        addresses.push((key.to_string(), account.address.as_str()));
+    }
+    for (i, owner) in config.governance_multisig.iter().flat_map(|m| &m.owners).enumerate() {
+        addresses.push((format!("governanceMultisig.owners[{}]", i), owner.as_str()));
+    }
+    if let Some(handler) = config
+        .governance_multisig
+        .as_ref()
+        .and_then(|m| m.fallback_handler.as_ref())
+    {
+        addresses.push((
+            "governanceMultisig.fallbackHandler".to_string(),
+            handler.as_str(),
+        ));
+    }
+    let bridge = &config.oracle_config.bridge_config;
+    if bridge.deploy || !bridge.trusted_bridge.is_empty() {
+        addresses.push((
+            "oracleConfig.bridgeConfig.trustedBridge".to_string(),
+            bridge.trusted_bridge.as_str(),
+        ));
+    }
+    addresses
+}
+
+fn check_addresses(
+    config: &GenesisConfig,
+    options: &ValidationOptions,
+    report: &mut ValidationReport,
+) {
+    for (field, value) in config_addresses(config) {
+        match parse_address_checksum(value) {
+            Ok((_, AddressChecksum::Valid)) => {}
+            Ok((address, AddressChecksum::Missing)) => {
+                let message = format!(
+                    "{}: {} has no EIP-55 checksum (checksummed: {})",
+                    field,
+                    value,
+                    address.to_checksum(None)
+                );
+                if options.strict_checksums {
+                    report.error("address-checksum", message);
+                } else {
+                    report.warn("address-checksum", message);
+                }
+            }
+            Ok((address, AddressChecksum::Invalid)) => report.error(
+                "address-checksum",
+                format!(
+                    "{}: {} fails its EIP-55 checksum, likely a typo (checksum of these digits: {})",
+                    field,
+                    value,
+                    address.to_checksum(None)
+                ),
+            ),
+            Err(e) => report.error("address", format!("{}: {}", field, e)),
+        }
+    }
+}
+
+fn check_amounts(config: &GenesisConfig, report: &mut ValidationReport) {
+    let mut amounts = vec![
+        (
+            "validatorConfig.minimumBond".to_string(),
+            &config.validator_config.minimum_bond,
+        ),
+        (
+            "validatorConfig.maximumBond".to_string(),
+            &config.validator_config.maximum_bond,
+        ),
+        (
+            "stakingConfig.minimumStake".to_string(),
+            &config.staking_config.minimum_stake,
+        ),
+        (
+            "governanceConfig.minVotingThreshold".to_string(),
+            &config.governance_config.min_voting_threshold,
+        ),
+        (
+            "governanceConfig.requiredProposerStake".to_string(),
+            &config.governance_config.required_proposer_stake,
+        ),
+    ];
+    if let Some(total_supply) = &config.total_supply {
+        amounts.push(("totalSupply".to_string(), total_supply));
+    }
+    for v in &config.validators {
+        amounts.push((
+            format!("validator {} stakeAmount", v.moniker),
+            &v.stake_amount,
+        ));
+        amounts.push((
+            format!("validator {} votingPower", v.moniker),
+            &v.voting_power,
+        ));
+    }
+
+    for (field, value) in amounts {
+        match parse_amount(value) {
+            Ok(wei) => {
+                report
+                    .summary
+                    .push(format!("{}: {} ({} wei)", field, format_ether(wei), wei))
+            }
+            Err(e) => report.error("amount", format!("{}: {}", field, e)),
+        }
+    }
+    if let Ok(threshold) = parse_amount(&config.governance_config.min_voting_threshold) {
+        if u128::try_from(threshold).is_err() {
+            report.error(
+                "amount",
+                format!(
+                    "governanceConfig.minVotingThreshold: {} exceeds uint128",
+                    threshold
+                ),
+            );
+        }
+    }
+}
+
+/// `extraAlloc` must stay clear of the reserved system range unless whitelisted
+/// Hook calls must resolve to a target, encode their arguments and parse their value
+fn check_genesis_calls(config: &GenesisConfig, report: &mut ValidationReport) {
+    for (label, call) in genesis_calls(config) {
+        if let Err(e) = call.to_txn() {
+            report.error("genesis-calls", format!("{} {}: {:#}", label, call.signature, e));
+        }
+    }
+}
+
+/// `treasury` and `feeCollector` must be plain accounts outside the system
+/// range, since their alloc entry replaces whatever genesis put there, and the
+/// treasury should agree with the one `OracleRequestQueue` is expected to pay
+fn check_funded_accounts(config: &GenesisConfig, report: &mut ValidationReport) {
+    for (key, account) in config.funded_accounts() {
+        match account.balance.as_deref().map(parse_amount).transpose() {
+            Ok(balance) => report.summary.push(format!(
+                "{}: {} with {}",
+                key,
+                account.address,
+                format_ether(balance.unwrap_or_default())
+            )),
+            Err(e) => report.error("treasury", format!("{}.balance: {}", key, e)),
+        }
+        if account
+            .address
+            .parse::<Address>()
+            .is_ok_and(is_reserved_system_address)
+        {
+            report.error(
+                "treasury",
+                format!(
+                    "{} {} is in the reserved system range; its alloc entry would replace a system account",
+                    key, account.address
+                ),
+            );
+        }
+    }
+    let queue_treasury = config
+        .oracle_config
+        .request_queue
+        .as_ref()
+        .and_then(|queue| queue.treasury.as_deref());
+    if let (Some(treasury), Some(queue_treasury)) = (&config.treasury, queue_treasury) {
+        if treasury.address.parse::<Address>().ok() != queue_treasury.parse().ok() {
+            report.warn(
+                "treasury",
+                format!(
+                    "oracleConfig.requestQueue.treasury {} differs from treasury {}; request fees will not reach the treasury",
+                    queue_treasury, treasury.address
+                ),
+            );
+        }
+    }
+}
+
+/// The governance multisig against what Safe's `setup` accepts (GS200-GS204),
+/// and against handing it a config that leaves governance with one key
+fn check_governance_multisig(config: &GenesisConfig, report: &mut ValidationReport) {
+    let Some(multisig) = &config.governance_multisig else {
+        return;
+    };
+    let address = multisig::multisig_address();
+    let owners = multisig.owners.len() as u64;
+    if multisig.threshold == 0 || multisig.threshold > owners {
+        report.error(
+            "governance-multisig",
+            format!("threshold {} is outside 1..={} owners", multisig.threshold, owners),
+        );
+    } else if multisig.threshold == 1 {
+        report.warn(
+            "governance-multisig",
+            format!("threshold is 1; any single owner of {} controls Governance", owners),
+        );
+    }
+    let mut seen = HashSet::new();
+    for owner in &multisig.owners {
+        let Ok(parsed) = owner.parse::<Address>() else {
+            // Reported by check_addresses
+            continue;
+        };
+        // Safe's owner list uses address(1) as its sentinel
+        if parsed == Address::ZERO || parsed == Address::with_last_byte(1) || parsed == address {
+            report.error(
+                "governance-multisig",
+                format!("{} cannot be a Safe owner", owner),
+            );
+        }
+        if !seen.insert(parsed) {
+            report.error("governance-multisig", format!("owner {} is listed twice", owner));
+        }
+    }
+    match config.governance_owner.parse::<Address>() {
+        Ok(owner) if owner == address => report.summary.push(format!(
+            "Governance owner: {}-of-{} multisig at {}",
+            multisig.threshold,
+            owners,
+            address.to_checksum(None)
+        )),
+        Ok(_) => report.warn(
+            "governance-multisig",
+            format!(
+                "governanceOwner {} is not the multisig at {}; leave it out to have the multisig own Governance",
+                config.governance_owner,
+                address.to_checksum(None)
+            ),
+        ),
+        Err(_) => {}
+    }
+}
+
+/// Vesting schedules must lock a positive amount over a whole number of
+/// seconds, since the contracts take seconds and the tool truncates, and must
+/// not collide with an `extraAlloc` account.
+fn check_vesting(config: &GenesisConfig, report: &mut ValidationReport) {
+    let Some(vesting) = &config.vesting else {
+        return;
+    };
+    let deployed: HashSet<Address> = vesting::vesting_addresses(config).into_iter().collect();
+    for address in config.extra_alloc.keys() {
+        if address.parse::<Address>().is_ok_and(|a| deployed.contains(&a)) {
+            report.error(
+                "vesting",
+                format!("extraAlloc {} is the address of a vesting contract", address),
+            );
+        }
+    }
+    for (i, schedule) in vesting.schedules.iter().enumerate() {
+        let label = format!("vesting.schedules[{}]", i);
+        match parse_amount(&schedule.amount) {
+            Ok(amount) if amount.is_zero() => {
+                report.error("vesting", format!("{}: amount is zero", label))
+            }
+            Ok(amount) => report.summary.push(format!(
+                "{} ({}): {} over {}",
+                label,
+                schedule.beneficiary,
+                format_ether(amount),
+                format_micros(schedule.duration_micros)
+            )),
+            Err(e) => report.error("vesting", format!("{}: invalid amount: {}", label, e)),
+        }
+        if schedule.duration_micros == 0 {
+            report.error("vesting", format!("{}: durationMicros is zero", label));
+        }
+        if schedule.cliff_micros > schedule.duration_micros {
+            report.error(
+                "vesting",
+                format!(
+                    "{}: cliffMicros {} is longer than durationMicros {}",
+                    label,
+                    format_micros(schedule.cliff_micros),
+                    format_micros(schedule.duration_micros)
+                ),
+            );
+        }
+        match schedule.start_micros(config) {
+            None => report.error(
+                "vesting",
+                format!("{}: no startMicros and no genesisTimestampSecs to default to", label),
+            ),
+            Some(start) if start < MIN_UNIX_MICROS => report.error(
+                "vesting",
+                format!("{}: start {} is not a Unix time in microseconds", label, start),
+            ),
+            Some(_) => {}
+        }
+        let times = [
+            ("startMicros", schedule.start_micros.unwrap_or_default()),
+            ("cliffMicros", schedule.cliff_micros),
+            ("durationMicros", schedule.duration_micros),
+        ];
+        for (field, micros) in times {
+            if micros % MICROS_PER_SECOND != 0 {
+                report.error(
+                    "vesting",
+                    format!("{}: {} {} is not a whole number of seconds", label, field, micros),
+                );
+            }
+        }
+        match (schedule.revocable, &schedule.revoker) {
+            (true, None) => report.error(
+                "vesting",
+                format!("{}: revocable without a revoker", label),
+            ),
+            (false, Some(revoker)) => report.warn(
+                "vesting",
+                format!("{}: revoker {} is ignored, the schedule is not revocable", label, revoker),
+            ),
+            _ => {}
+        }
+    }
+}
+
+fn check_extra_alloc(config: &GenesisConfig, report: &mut ValidationReport) {
+    let whitelist: HashSet<Address> = config
+        .allow_reserved_alloc
+        .iter()
+        .filter_map(|a| a.parse().ok())
+        .collect();
+
+    for address in config.extra_alloc.keys() {
+        let Ok(parsed) = address.parse::<Address>() else {
+            // Reported by check_addresses
+            continue;
+        };
+        if !is_reserved_system_address(parsed) || whitelist.contains(&parsed) {
+            continue;
+        }
+        let detail = match CONTRACTS.iter().find(|(_, a)| *a == parsed) {
+            Some((name, _)) => format!("would overwrite system contract {}", name),
+            None => "falls in the reserved system range".to_string(),
+        };
+        report.error(
+            "reserved-address",
+            format!(
+                "extraAlloc {} {} ({:?}..={:?}); list it in allowReservedAlloc if intended",
+                address, detail, RESERVED_RANGE_START, RESERVED_RANGE_END
+            ),
+        );
+    }
+
+    if let Err(e) = extra_alloc_accounts(config) {
+        report.error("extra-alloc", e.to_string());
+    }
+}
+
+/// Smallest validator set tolerating one Byzantine fault (n >= 3f + 1)
+const BFT_MIN_VALIDATORS: usize = 4;
+
+/// Stake and voting power against the validator config bounds.
+///
+/// From the first epoch boundary on, a validator's voting power is its pool's
+/// voting power capped at `maximumBond`, so genesis `votingPower` should already
+/// equal `min(stakeAmount, maximumBond)`.
+fn check_stake_invariants(config: &GenesisConfig, report: &mut ValidationReport) {
+    let validator_count = config.validators.len();
+    if validator_count == 0 {
+        report.error("validator-set", "no initial validators");
+        return;
+    }
+    if validator_count < BFT_MIN_VALIDATORS && !is_devnet_chain_id(config.chain_id) {
+        report.warn(
+            "validator-set",
+            format!(
+                "{} validator(s) cannot tolerate a single Byzantine fault; at least {} are needed",
+                validator_count, BFT_MIN_VALIDATORS
+            ),
+        );
+    }
+    if let Ok(max_size) = parse_amount(&config.validator_config.max_validator_set_size) {
+        if U256::from(validator_count) > max_size {
+            report.error(
+                "validator-set",
+                format!(
+                    "{} initial validators exceed maxValidatorSetSize {}",
+                    validator_count, max_size
+                ),
+            );
+        }
+    }
+
+    // Amount parse failures are already reported by check_amounts
+    let (Ok(min_bond), Ok(max_bond)) = (
+        parse_amount(&config.validator_config.minimum_bond),
+        parse_amount(&config.validator_config.maximum_bond),
+    ) else {
+        return;
+    };
+    if min_bond > max_bond {
+        report.error(
+            "stake-bounds",
+            format!(
+                "minimumBond {} exceeds maximumBond {}",
+                format_ether(min_bond),
+                format_ether(max_bond)
+            ),
+        );
+        return;
+    }
+
+    let mut total_power = U256::ZERO;
+    let mut above_u64 = Vec::new();
+    for v in &config.validators {
+        let (Ok(stake), Ok(power)) = (parse_amount(&v.stake_amount), parse_amount(&v.voting_power))
+        else {
+            continue;
+        };
+        if stake < min_bond {
+            report.error(
+                "stake-bounds",
+                format!(
+                    "validator {}: stakeAmount {} is below minimumBond {}",
+                    v.moniker,
+                    format_ether(stake),
+                    format_ether(min_bond)
+                ),
+            );
+        } else if stake > max_bond {
+            report.warn(
+                "stake-bounds",
+                format!(
+                    "validator {}: stakeAmount {} exceeds maximumBond {}; the excess carries no voting power",
+                    v.moniker,
+                    format_ether(stake),
+                    format_ether(max_bond)
+                ),
+            );
+        }
+
+        let effective = stake.min(max_bond);
+        if power.is_zero() {
+            report.error(
+                "voting-power",
+                format!("validator {}: votingPower is zero", v.moniker),
+            );
+        } else if power > effective {
+            report.error(
+                "voting-power",
+                format!(
+                    "validator {}: votingPower {} exceeds the {} its stake backs",
+                    v.moniker,
+                    format_ether(power),
+                    format_ether(effective)
+                ),
+            );
+        } else if power < effective {
+            report.warn(
+                "voting-power",
+                format!(
+                    "validator {}: votingPower {} will become {} at the first epoch boundary",
+                    v.moniker,
+                    format_ether(power),
+                    format_ether(effective)
+                ),
+            );
+        }
+
+        if power > U256::from(u64::MAX) {
+            above_u64.push(v.moniker.as_str());
+        }
+        total_power = total_power.saturating_add(power);
+    }
+
+    if total_power > U256::from(u128::MAX) {
+        report.error(
+            "voting-power",
+            format!("total voting power {} does not fit in uint128", total_power),
+        );
+    }
+    if !above_u64.is_empty() {
+        report.warn(
+            "voting-power",
+            format!(
+                "votingPower of {} exceeds uint64, which the consensus engine uses for per-validator power",
+                above_u64.join(", ")
+            ),
+        );
+    }
+}
+
+/// Consensus keys and network addresses shared between initial validators.
+///
+/// `ValidatorManagement` skips its `DuplicateConsensusPubkey` check for genesis
+/// validators, so a repeated key would silently map to the last pool using it.
+fn check_validator_uniqueness(config: &GenesisConfig, report: &mut ValidationReport) {
+    let mut pubkeys: HashMap<String, &str> = HashMap::new();
+    let mut network_addresses: HashMap<&str, &str> = HashMap::new();
+    for v in &config.validators {
+        let pubkey = v.consensus_pubkey.trim_start_matches("0x").to_lowercase();
+        if let Some(first) = pubkeys.get(&pubkey) {
+            report.error(
+                "validator-unique",
+                format!(
+                    "validator {}: consensusPubkey {} is also used by validator {}",
+                    v.moniker, v.consensus_pubkey, first
+                ),
+            );
+        } else {
+            pubkeys.insert(pubkey, &v.moniker);
+        }
+        if let Some(first) = network_addresses.get(v.network_addresses.as_str()) {
+            report.warn(
+                "validator-unique",
+                format!(
+                    "validator {}: networkAddresses {} is also used by validator {}",
+                    v.moniker, v.network_addresses, first
+                ),
+            );
+        } else {
+            network_addresses.insert(&v.network_addresses, &v.moniker);
+        }
+    }
+}
+
+/// `ValidatorManagement.MAX_MONIKER_LENGTH`, in bytes
+const MAX_MONIKER_LENGTH: usize = 31;
+
+/// Monikers against `ValidatorManagement`'s length limit, which reverts
+/// `Genesis.initialize` with `MonikerTooLong`, and against what explorers and
+/// logs display well. The contract checks nothing else, so the rest are warnings.
+fn check_monikers(config: &GenesisConfig, report: &mut ValidationReport) {
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for (i, v) in config.validators.iter().enumerate() {
+        let moniker = &v.moniker;
+        if moniker.len() > MAX_MONIKER_LENGTH {
+            report.error(
+                "moniker",
+                format!(
+                    "validators[{}]: moniker {:?} is {} bytes, over the {}-byte limit",
+                    i,
+                    moniker,
+                    moniker.len(),
+                    MAX_MONIKER_LENGTH
+                ),
+            );
+        }
+        if moniker.trim().is_empty() {
+            report.warn("moniker", format!("validators[{}]: moniker is empty", i));
+            continue;
+        }
+        if moniker.chars().any(char::is_control) {
+            report.warn(
+                "moniker",
+                format!("validators[{}]: moniker {:?} contains control characters", i, moniker),
+            );
+        }
+        if moniker.trim() != moniker {
+            report.warn(
+                "moniker",
+                format!("validators[{}]: moniker {:?} has surrounding whitespace", i, moniker),
+            );
+        }
+        match seen.get(&moniker.to_lowercase()) {
+            Some(first) => report.warn(
+                "moniker",
+                format!(
+                    "validators[{}]: moniker {:?} repeats {:?}; monikers are how operators and tools tell validators apart",
+                    i, moniker, first
+                ),
+            ),
+            None => {
+                seen.insert(moniker.to_lowercase(), moniker);
+            }
+        }
+    }
+}
+
+/// Owner and operator addresses shared between initial validators, and
+/// validators whose owner is also their operator.
+///
+/// Nothing on chain forbids either, but public networks may require each
+/// validator's roles to be held by separate keys. The severity comes from
+/// `--role-separation` or `roleSeparation`, defaulting to a warning (info on
+/// devnet chain IDs).
+fn check_role_separation(config: &GenesisConfig, options: &ValidationOptions, report: &mut ValidationReport) {
+    let severity = options
+        .role_separation
+        .or(config.role_separation)
+        .unwrap_or(if is_devnet_chain_id(config.chain_id) {
+            Severity::Info
+        } else {
+            Severity::Warning
+        });
+
+    // Keyed case-insensitively, keeping the first spelling for messages
+    let mut owners: BTreeMap<String, (&str, Vec<&str>)> = BTreeMap::new();
+    let mut operators: BTreeMap<String, (&str, Vec<&str>)> = BTreeMap::new();
+    for v in &config.validators {
+        if v.owner.eq_ignore_ascii_case(&v.operator) {
+            report.push(
+                severity,
+                "role-separation",
+                format!("validator {}: owner {} is also its operator", v.moniker, v.owner),
+            );
+        }
+        owners
+            .entry(v.owner.to_lowercase())
+            .or_insert((&v.owner, Vec::new()))
+            .1
+            .push(&v.moniker);
+        operators
+            .entry(v.operator.to_lowercase())
+            .or_insert((&v.operator, Vec::new()))
+            .1
+            .push(&v.moniker);
+    }
+    for (role, by_address) in [("owner", owners), ("operator", operators)] {
+        for (address, monikers) in by_address.into_values() {
+            if monikers.len() > 1 {
+                report.push(
+                    severity,
+                    "role-separation",
+                    format!(
+                        "{} {} is shared by validators {}",
+                        role,
+                        address,
+                        monikers.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Operators whose validators together hold a super-majority, or enough power
+/// to halt the chain
+pub fn check_stake_concentration(analysis: &StakeAnalysis, report: &mut ValidationReport) {
+    let super_majority = analysis.operators_exceeding(2);
+    for o in &super_majority {
+        report.warn(
+            "stake-concentration",
+            format!(
+                "operator {} controls {} of the voting power through {}, more than 2/3",
+                o.operator,
+                format_basis_points(basis_points(o.power, analysis.total_power)),
+                o.monikers.join(", ")
+            ),
+        );
+    }
+    for o in analysis.operators_exceeding(1) {
+        if super_majority.iter().any(|s| s.operator == o.operator) {
+            continue;
+        }
+        report.info(
+            "stake-concentration",
+            format!(
+                "operator {} controls {} of the voting power through {}, enough to halt the chain",
+                o.operator,
+                format_basis_points(basis_points(o.power, analysis.total_power)),
+                o.monikers.join(", ")
+            ),
+        );
+    }
+}
+
+/// `ValidatorConfig.MAX_VOTING_POWER_INCREASE_LIMIT`
+const MAX_VOTING_POWER_INCREASE_LIMIT_PCT: u64 = 50;
+/// `ValidatorConfig.MAX_VALIDATOR_SET_SIZE`
+const MAX_VALIDATOR_SET_SIZE: u64 = 65536;
+/// `ValidatorConfig.MAX_UNBONDING_DELAY` and `StakingConfig.MAX_UNBONDING_DELAY`
+const MAX_UNBONDING_DELAY_MICROS: u64 = 365 * MICROS_PER_DAY;
+/// `StakingConfig.MAX_LOCKUP_DURATION`
+const MAX_LOCKUP_DURATION_MICROS: u64 = 4 * 365 * MICROS_PER_DAY;
+
+/// Staking and validator config against the ranges their `_validateConfig`
+/// enforces, so `Genesis.initialize` does not revert on them, and against each
+/// other.
+///
+/// Only `StakingConfig.unbondingDelayMicros` is read when stake is withdrawn;
+/// the ValidatorConfig copy is informational, so the two should agree.
+fn check_config_bounds(config: &GenesisConfig, report: &mut ValidationReport) {
+    let validator = &config.validator_config;
+    let staking = &config.staking_config;
+
+    // Amount parse failures are already reported by check_amounts
+    let min_bond = parse_amount(&validator.minimum_bond).ok();
+    if min_bond == Some(U256::ZERO) {
+        report.error("config-bounds", "validatorConfig.minimumBond must be positive");
+    }
+    if let Ok(min_stake) = parse_amount(&staking.minimum_stake) {
+        if min_stake.is_zero() {
+            report.error("config-bounds", "stakingConfig.minimumStake must be positive");
+        } else if let Some(min_bond) = min_bond.filter(|bond| min_stake > *bond) {
+            report.warn(
+                "config-bounds",
+                format!(
+                    "stakingConfig.minimumStake {} exceeds validatorConfig.minimumBond {}; pools between the two cannot exist",
+                    format_ether(min_stake),
+                    format_ether(min_bond)
+                ),
+            );
+        }
+    }
+
+    if config.validator_batch_size == Some(0) {
+        report.error("config-bounds", "validatorBatchSize must be positive");
+    }
+
+    for (field, value) in [
+        ("validatorConfig.unbondingDelayMicros", validator.unbonding_delay_micros),
+        ("stakingConfig.unbondingDelayMicros", staking.unbonding_delay_micros),
+    ] {
+        if value == 0 || value > MAX_UNBONDING_DELAY_MICROS {
+            report.error(
+                "config-bounds",
+                format!(
+                    "{} {} is outside 1..={}",
+                    field,
+                    value,
+                    MAX_UNBONDING_DELAY_MICROS
+                ),
+            );
+        }
+    }
+    if validator.unbonding_delay_micros != staking.unbonding_delay_micros {
+        report.warn(
+            "config-bounds",
+            format!(
+                "validatorConfig.unbondingDelayMicros ({}) differs from stakingConfig.unbondingDelayMicros ({}), which withdrawals follow",
+                format_micros(validator.unbonding_delay_micros),
+                format_micros(staking.unbonding_delay_micros)
+            ),
+        );
+    }
+    if staking.lockup_duration_micros > MAX_LOCKUP_DURATION_MICROS {
+        report.error(
+            "config-bounds",
+            format!(
+                "stakingConfig.lockupDurationMicros {} exceeds {}",
+                staking.lockup_duration_micros,
+                format_micros(MAX_LOCKUP_DURATION_MICROS)
+            ),
+        );
+    }
+
+    let pct = validator.voting_power_increase_limit_pct;
+    if pct == 0 || pct > MAX_VOTING_POWER_INCREASE_LIMIT_PCT {
+        report.error(
+            "config-bounds",
+            format!(
+                "validatorConfig.votingPowerIncreaseLimitPct {} is outside 1..={}",
+                pct, MAX_VOTING_POWER_INCREASE_LIMIT_PCT
+            ),
+        );
+    }
+    if let Ok(size) = parse_amount(&validator.max_validator_set_size) {
+        if size.is_zero() || size > U256::from(MAX_VALIDATOR_SET_SIZE) {
+            report.error(
+                "config-bounds",
+                format!(
+                    "validatorConfig.maxValidatorSetSize {} is outside 1..={}",
+                    size, MAX_VALIDATOR_SET_SIZE
+                ),
+            );
+        }
+    }
+
+    let evict = validator.auto_evict_threshold_pct;
+    if evict > 100 {
+        report.error(
+            "config-bounds",
+            format!("validatorConfig.autoEvictThresholdPct {} exceeds 100", evict),
+        );
+    } else if validator.auto_evict_enabled {
+        if evict == 0 {
+            report.warn(
+                "config-bounds",
+                "autoEvictEnabled with autoEvictThresholdPct 0 never evicts",
+            );
+        } else if evict == 100 {
+            report.warn(
+                "config-bounds",
+                "autoEvictThresholdPct 100 evicts a validator for a single failed proposal",
+            );
+        }
+    } else if evict != 0 {
+        report.warn(
+            "config-bounds",
+            format!(
+                "autoEvictThresholdPct {} has no effect while autoEvictEnabled is false",
+                evict
+            ),
+        );
+    }
+}
+
+const MICROS_PER_SECOND: u64 = 1_000_000;
+const MICROS_PER_MINUTE: u64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_DAY: u64 = 24 * 60 * MICROS_PER_MINUTE;
+
+/// Shortest epoch that leaves room for a DKG round and the transition itself
+const MIN_EPOCH_INTERVAL_MICROS: u64 = MICROS_PER_MINUTE;
+/// Longest plausible epoch, duration or lockup; beyond are nanoseconds by mistake
+const MAX_EPOCH_INTERVAL_MICROS: u64 = 30 * MICROS_PER_DAY;
+const MAX_DURATION_MICROS: u64 = 10 * 365 * MICROS_PER_DAY;
+/// Unix time in microseconds of 2001-09-09; a smaller absolute timestamp is in
+/// seconds or milliseconds
+const MIN_UNIX_MICROS: u64 = 1_000_000_000_000_000;
+/// Unix time in seconds far past any launch date but below today in milliseconds
+const MAX_UNIX_SECS: u64 = 1_000_000_000_000;
+
+fn format_micros(micros: u64) -> String {
+    match micros {
+        m if m >= MICROS_PER_DAY && m % MICROS_PER_DAY == 0 => format!("{} day(s)", m / MICROS_PER_DAY),
+        m if m >= MICROS_PER_MINUTE && m % MICROS_PER_MINUTE == 0 => {
+            format!("{} minute(s)", m / MICROS_PER_MINUTE)
+        }
+        m => format!("{}s", m as f64 / MICROS_PER_SECOND as f64),
+    }
+}
+
+/// Duration and timestamp fields against plausible ranges.
+///
+/// Every time field of the config is in microseconds while most tooling speaks
+/// seconds, so a value a million times too small (or a thousand times too large,
+/// from nanoseconds) is the usual mistake. Durations are also checked against
+/// the epoch they are enforced at: a lockup or unbonding delay shorter than an
+/// epoch effectively ends at the next boundary.
+fn check_time_units(config: &GenesisConfig, report: &mut ValidationReport) {
+    let epoch = config.epoch_interval_micros;
+    if epoch < MIN_EPOCH_INTERVAL_MICROS {
+        let message = format!(
+            "epochIntervalMicros {} is {}, below {}; seconds given as microseconds?",
+            epoch,
+            format_micros(epoch),
+            format_micros(MIN_EPOCH_INTERVAL_MICROS)
+        );
+        if epoch == 0 || !is_devnet_chain_id(config.chain_id) {
+            report.error("time-units", message);
+        } else {
+            report.warn("time-units", message);
+        }
+    } else if epoch > MAX_EPOCH_INTERVAL_MICROS {
+        report.warn(
+            "time-units",
+            format!(
+                "epochIntervalMicros {} is {}, above {}",
+                epoch,
+                format_micros(epoch),
+                format_micros(MAX_EPOCH_INTERVAL_MICROS)
+            ),
+        );
+    }
+
+    let durations = [
+        ("stakingConfig.lockupDurationMicros", config.staking_config.lockup_duration_micros),
+        ("stakingConfig.unbondingDelayMicros", config.staking_config.unbonding_delay_micros),
+        ("validatorConfig.unbondingDelayMicros", config.validator_config.unbonding_delay_micros),
+    ];
+    for (field, value) in durations {
+        if value > MAX_DURATION_MICROS {
+            report.warn(
+                "time-units",
+                format!(
+                    "{} {} is {}, above {}; nanoseconds given as microseconds?",
+                    field,
+                    value,
+                    format_micros(value),
+                    format_micros(MAX_DURATION_MICROS)
+                ),
+            );
+        } else if value < epoch {
+            let message = format!(
+                "{} {} is {}, shorter than the {} epoch",
+                field,
+                value,
+                format_micros(value),
+                format_micros(epoch)
+            );
+            if field == "stakingConfig.lockupDurationMicros" {
+                report.error("time-units", message);
+            } else {
+                report.warn("time-units", message);
+            }
+        }
+    }
+
+    let locked_until = config.initial_locked_until_micros;
+    if locked_until > 0 && locked_until < MIN_UNIX_MICROS {
+        report.error(
+            "time-units",
+            format!(
+                "initialLockedUntilMicros {} is not a Unix time in microseconds; seconds or milliseconds?",
+                locked_until
+            ),
+        );
+    } else if let Some(secs) = config.genesis_timestamp_secs {
+        let genesis_micros = secs.saturating_mul(MICROS_PER_SECOND);
+        if locked_until <= genesis_micros {
+            report.warn(
+                "time-units",
+                format!(
+                    "initialLockedUntilMicros {} is not after the genesis timestamp; initial stake is unlocked from block 0",
+                    locked_until
+                ),
+            );
+        } else if locked_until - genesis_micros > MAX_DURATION_MICROS {
+            report.warn(
+                "time-units",
+                format!(
+                    "initialLockedUntilMicros {} locks initial stake for more than {}",
+                    locked_until,
+                    format_micros(MAX_DURATION_MICROS)
+                ),
+            );
+        }
+    }
+    if let Some(secs) = config.genesis_timestamp_secs {
+        if secs >= MAX_UNIX_SECS {
+            report.error(
+                "time-units",
+                format!("genesisTimestampSecs {} is not a Unix time in seconds; milliseconds?", secs),
+            );
+        }
+    }
+}
+
+/// `GovernanceConfig.MIN_VOTING_DURATION` and `MAX_VOTING_DURATION`
+const MIN_VOTING_DURATION_MICROS: u64 = MICROS_PER_SECOND;
+const MAX_VOTING_DURATION_MICROS: u64 = 365 * MICROS_PER_DAY;
+
+/// Governance parameters against what `GovernanceConfig` accepts and what lets
+/// a proposal pass at all.
+///
+/// A proposal counts each pool's voting power at its expiration time, so only
+/// stake locked until the end of the voting period can propose or vote. There
+/// is no execution delay or window: a resolved, succeeded proposal stays
+/// executable, so the voting period is the only timing constraint.
+fn check_governance_timing(config: &GenesisConfig, report: &mut ValidationReport) {
+    let governance = &config.governance_config;
+    let voting = governance.voting_duration_micros;
+    if !(MIN_VOTING_DURATION_MICROS..=MAX_VOTING_DURATION_MICROS).contains(&voting) {
+        report.error(
+            "governance-timing",
+            format!(
+                "votingDurationMicros {} is outside GovernanceConfig's {}..{} range",
+                voting,
+                format_micros(MIN_VOTING_DURATION_MICROS),
+                format_micros(MAX_VOTING_DURATION_MICROS)
+            ),
+        );
+    }
+
+    // Amount parse failures are already reported by check_amounts
+    let threshold = parse_amount(&governance.min_voting_threshold).ok();
+    if let Some(threshold) = threshold {
+        // Above uint128 is already reported by check_amounts
+        if threshold == U256::from(u128::MAX) {
+            report.error(
+                "governance-timing",
+                format!("minVotingThreshold {} (uint128 max) is unreachable", threshold),
+            );
+        }
+    }
+    let Ok(required) = parse_amount(&governance.required_proposer_stake) else {
+        return;
+    };
+    if required > U256::from(u128::MAX) {
+        report.error(
+            "governance-timing",
+            format!("requiredProposerStake {} exceeds uint128, which pool voting power is clamped to", required),
+        );
+    }
+    if let Ok(max_bond) = parse_amount(&config.validator_config.maximum_bond) {
+        if required > max_bond {
+            report.warn(
+                "governance-timing",
+                format!(
+                    "requiredProposerStake {} exceeds maximumBond {}; only pools staking more than a validator can bond may propose",
+                    format_ether(required),
+                    format_ether(max_bond)
+                ),
+            );
+        }
+    }
+
+    let stakes: Vec<U256> = config
+        .validators
+        .iter()
+        .filter_map(|v| parse_amount(&v.stake_amount).ok())
+        .collect();
+    let largest = stakes.iter().copied().max().unwrap_or_default();
+    if required > largest {
+        report.warn(
+            "governance-timing",
+            format!(
+                "requiredProposerStake {} exceeds every initial pool's stake (largest {}); no proposal can be created until more stake arrives",
+                format_ether(required),
+                format_ether(largest)
+            ),
+        );
+    }
+    let total = stakes.iter().fold(U256::ZERO, |sum, stake| sum.saturating_add(*stake));
+    if let Some(threshold) = threshold {
+        if threshold > total {
+            report.warn(
+                "governance-timing",
+                format!(
+                    "minVotingThreshold {} exceeds the total initial stake {}; no proposal can pass on genesis stake alone",
+                    format_ether(threshold),
+                    format_ether(total)
+                ),
+            );
+        }
+    }
+
+    if let Some(secs) = config.genesis_timestamp_secs {
+        let first_expiry = secs.saturating_mul(MICROS_PER_SECOND).saturating_add(voting);
+        if config.initial_locked_until_micros < first_expiry {
+            report.warn(
+                "governance-timing",
+                format!(
+                    "initialLockedUntilMicros {} ends before a proposal created at genesis closes ({}); initial pools cannot propose or vote until they renew their lockup",
+                    config.initial_locked_until_micros, first_expiry
+                ),
+            );
+        }
+    }
+    let lockup = config.staking_config.lockup_duration_micros;
+    if lockup < voting {
+        report.warn(
+            "governance-timing",
+            format!(
+                "lockupDurationMicros {} is shorter than votingDurationMicros {}; stake with the minimum lockup has no voting power on new proposals",
+                format_micros(lockup),
+                format_micros(voting)
+            ),
+        );
+    }
+}
+
+fn decode_config_hex(field: &str, value: &str, report: &mut ValidationReport) -> Option<Vec<u8>> {
+    match hex::decode(value.strip_prefix("0x").unwrap_or(value)) {
+        Ok(bytes) if bytes.is_empty() => {
+            report.error("onchain-config", format!("{} is empty", field));
+            None
+        }
+        Ok(bytes) => Some(bytes),
+        Err(e) => {
+            report.error(
+                "onchain-config",
+                format!("{} is not valid hex: {}", field, e),
+            );
+            None
+        }
+    }
+}
+
+/// Reject config blobs the node would fail to parse when starting epoch 0
+fn check_onchain_configs(config: &GenesisConfig, report: &mut ValidationReport) {
+    if let Some(bytes) = decode_config_hex("consensusConfig", &config.consensus_config, report) {
+        match OnChainConsensusConfig::from_bcs(&bytes) {
+            Ok(consensus) => match consensus.proposer_election_type() {
+                ProposerElectionType::FixedProposer(0) | ProposerElectionType::RotatingProposer(0) => {
+                    report.error(
+                        "onchain-config",
+                        "consensusConfig: proposerElectionType must give each proposer at least one contiguous round",
+                    )
+                }
+                _ => {}
+            },
+            Err(e) => report.error(
+                "onchain-config",
+                format!(
+                    "consensusConfig ({} bytes) is not a valid BCS OnChainConsensusConfig: {}",
+                    bytes.len(),
+                    e
+                ),
+            ),
+        }
+    }
+
+    // No typed layout for executionConfig; only require well-formed, non-empty bytes
+    decode_config_hex("executionConfig", &config.execution_config, report);
+}
+
+/// One staged callback per source type, and staging must change something
+fn check_pending_callbacks(config: &GenesisConfig, report: &mut ValidationReport) {
+    let oracle = &config.oracle_config;
+    let mut seen = HashSet::new();
+    for pending in &oracle.pending_callbacks {
+        if !seen.insert(pending.source_type) {
+            report.error(
+                "oracle-callbacks",
+                format!(
+                    "oracleConfig.pendingCallbacks stages sourceType {} more than once",
+                    pending.source_type
+                ),
+            );
+        }
+        let active = oracle
+            .source_types
+            .iter()
+            .position(|t| *t == pending.source_type)
+            .and_then(|i| oracle.callbacks.get(i));
+        if active.is_some_and(|a| a.eq_ignore_ascii_case(&pending.callback)) {
+            report.warn(
+                "oracle-callbacks",
+                format!(
+                    "oracleConfig.pendingCallbacks stages the already active callback {} for sourceType {}",
+                    pending.callback, pending.source_type
+                ),
+            );
+        }
+    }
+}
+
+/// OnDemandOracleTaskConfig rejects empty configs; a repeated key silently
+/// keeps only the last entry
+fn check_on_demand_tasks(config: &GenesisConfig, report: &mut ValidationReport) {
+    let mut seen = HashSet::new();
+    for (i, task) in config.oracle_config.on_demand_tasks.iter().enumerate() {
+        if task.config.is_empty() {
+            report.error(
+                "on-demand-tasks",
+                format!("oracleConfig.onDemandTasks[{}]: config must not be empty", i),
+            );
+        }
+        if !seen.insert((task.source_type, task.source_id)) {
+            report.warn(
+                "on-demand-tasks",
+                format!(
+                    "oracleConfig.onDemandTasks[{}]: sourceType {} sourceId {} is set again and overrides the earlier entry",
+                    i, task.source_type, task.source_id
+                ),
+            );
+        }
+    }
+    let fees = config.oracle_config.request_queue.iter().flat_map(|queue| &queue.fees);
+    for (source_type, fee) in fees {
+        if let Err(e) = parse_amount(fee) {
+            report.error(
+                "on-demand-tasks",
+                format!("oracleConfig.requestQueue.fees.{}: {}", source_type, e),
+            );
+        }
+    }
+}
+
+/// A deployed bridge receiver mints for whatever its trusted bridge reports,
+/// so a zero or codeless trusted bridge is almost certainly a mistake
+fn check_bridge(config: &GenesisConfig, options: &ValidationOptions, report: &mut ValidationReport) {
+    let bridge = &config.oracle_config.bridge_config;
+    if !bridge.deploy {
+        if !bridge.trusted_bridge.is_empty() {
+            report.warn(
+                "bridge",
+                "bridgeConfig.trustedBridge is set but bridgeConfig.deploy is false; no bridge receiver is deployed",
+            );
+        }
+        return;
+    }
+    let trusted_bridge = bridge.trusted_bridge.parse::<Address>().ok();
+    if trusted_bridge == Some(Address::ZERO) {
+        report.warn(
+            "bridge",
+            "bridgeConfig.trustedBridge is the zero address; no deposit can ever be accepted",
+        );
+    }
+    let source_id = match bridge.trusted_source_id.as_str() {
+        "" => Some(U256::ZERO),
+        id => parse_amount(id).ok(),
+    };
+    match source_id {
+        Some(id) if id.is_zero() => report.warn(
+            "bridge",
+            "bridgeConfig.trustedSourceId is 0; set the chain ID of the bridge's source chain",
+        ),
+        Some(id) if id == U256::from(config.chain_id) => report.warn(
+            "bridge",
+            format!("bridgeConfig.trustedSourceId {} is this chain's own chainId", id),
+        ),
+        Some(_) => {}
+        None => report.error(
+            "bridge",
+            format!("bridgeConfig.trustedSourceId {} is not a number", bridge.trusted_source_id),
+        ),
+    }
+
+    let (Some(rpc_url), Some(trusted_bridge)) = (&options.bridge_rpc, trusted_bridge) else {
+        return;
+    };
+    let client = RpcClient::new(rpc_url);
+    match client.chain_id() {
+        Ok(chain_id) if source_id.is_some_and(|id| id != U256::from(chain_id)) => report.warn(
+            "bridge",
+            format!(
+                "{} reports chain ID {}, bridgeConfig.trustedSourceId is {}",
+                rpc_url, chain_id, bridge.trusted_source_id
+            ),
+        ),
+        Ok(_) => {}
+        Err(e) => {
+            report.warn("bridge", format!("cannot check the source chain: {:#}", e));
+            return;
+        }
+    }
+    match client.get_code(trusted_bridge, "latest") {
+        Ok(code) if code.is_empty() => report.warn(
+            "bridge",
+            format!(
+                "bridgeConfig.trustedBridge {:?} has no code on {}; expected the bridge contract there",
+                trusted_bridge, rpc_url
+            ),
+        ),
+        Ok(_) => {}
+        Err(e) => report.warn("bridge", format!("cannot check the source chain: {:#}", e)),
+    }
+}
+
+fn check_network_addresses(config: &GenesisConfig, report: &mut ValidationReport) {
+    let devnet = is_devnet_chain_id(config.chain_id);
+
+    for v in &config.validators {
+        for (field, value) in [
+            ("networkAddresses", &v.network_addresses),
+            ("fullnodeAddresses", &v.fullnode_addresses),
+        ] {
+            match parse_network_address(value) {
+                Ok(addr) => {
+                    if addr.host.is_localhost() && !devnet {
+                        report.warn(
+                            "network-address-localhost",
+                            format!(
+                                "validator {}: {} {} is a localhost address on non-devnet chainId {}",
+                                v.moniker, field, addr, config.chain_id
+                            ),
+                        );
+                    }
+                }
+                Err(e) => report.error(
+                    "network-address",
+                    format!("validator {}: invalid {}: {}", v.moniker, field, e),
+                ),
+            }
+        }
+    }
+}
+
+/// Signature algorithms the JWK consensus layer accepts for RSA keys
+const RSA_JWK_ALGS: [&str; 3] = ["RS256", "RS384", "RS512"];
+
+/// Decode a JWK base64url field (unpadded, per RFC 7518)
+fn decode_base64url(value: &str) -> Option<Vec<u8>> {
+    URL_SAFE_NO_PAD.decode(value).ok().filter(|b| !b.is_empty())
+}
+
+fn check_jwks(config: &GenesisConfig, report: &mut ValidationReport) {
+    let jwk_config = &config.jwk_config;
+    if jwk_config.jwks.len() != jwk_config.issuers.len() {
+        report.error(
+            "jwk",
+            format!(
+                "jwkConfig has {} JWK lists for {} issuers",
+                jwk_config.jwks.len(),
+                jwk_config.issuers.len()
+            ),
+        );
+    }
+
+    for (issuer, jwks) in jwk_config.issuers.iter().zip(&jwk_config.jwks) {
+        let issuer = hex::decode(issuer.strip_prefix("0x").unwrap_or(issuer))
+            .ok()
+            .and_then(|b| String::from_utf8(b).ok())
+            .unwrap_or_else(|| issuer.clone());
+        let mut kids = HashSet::new();
+        for jwk in jwks {
+            let key = format!("issuer {} kid {:?}", issuer, jwk.kid);
+            if jwk.kid.is_empty() {
+                report.error("jwk", format!("issuer {}: JWK with empty kid", issuer));
+            } else if !kids.insert(jwk.kid.as_str()) {
+                report.error("jwk", format!("{}: duplicate kid", key));
+            }
+            if jwk.kty != "RSA" {
+                report.error("jwk", format!("{}: kty {:?} is not RSA", key, jwk.kty));
+                continue;
+            }
+            if !RSA_JWK_ALGS.contains(&jwk.alg.as_str()) {
+                report.error(
+                    "jwk",
+                    format!(
+                        "{}: alg {:?} is not an RSA signature algorithm ({})",
+                        key,
+                        jwk.alg,
+                        RSA_JWK_ALGS.join(", ")
+                    ),
+                );
+            }
+
+            match decode_base64url(&jwk.n) {
+                Some(n) => {
+                    let n = &n[n.iter().take_while(|b| **b == 0).count()..];
+                    let bits = n.len() * 8 - n.first().map_or(8, |b| b.leading_zeros() as usize);
+                    if !(2048..=4096).contains(&bits) {
+                        report.error(
+                            "jwk",
+                            format!("{}: {}-bit modulus, expected 2048 to 4096 bits", key, bits),
+                        );
+                    } else if bits != 2048 && bits != 4096 {
+                        report.warn(
+                            "jwk",
+                            format!(
+                                "{}: unusual {}-bit modulus (expected 2048 or 4096)",
+                                key, bits
+                            ),
+                        );
+                    }
+                }
+                None => report.error("jwk", format!("{}: n is not valid base64url", key)),
+            }
+            match decode_base64url(&jwk.e) {
+                Some(e) if e.last().is_some_and(|b| b & 1 == 1) => {}
+                Some(_) => report.error("jwk", format!("{}: exponent e must be odd", key)),
+                None => report.error("jwk", format!("{}: e is not valid base64url", key)),
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! `validators add|remove|update` editing of the config's validator set
+//!
+//! Edits are made on the raw JSON of the config file, so key order, `_comment`
+//! keys and the rest of the file survive. The edited file is then loaded and
+//! validated the way `generate` would load it; it is only written back when
+//! validation reports no errors, and the resulting stake distribution is
+//! printed either way.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use tracing::info;
+
+use crate::{
+    analyze::print_stake_distribution,
+    genesis::{self, InitialValidator},
+    validate::{self, ValidationOptions},
+};
+
+/// A change to the validators array
+#[derive(Debug, Clone)]
+pub enum ValidatorEdit {
+    /// Append a validator
+    Add(InitialValidator),
+    /// Remove the validator with this moniker or consensus pubkey
+    Remove(String),
+    /// Set fields (`<field>=<value>`, as for `generate --set`) of the validator
+    /// with this moniker or consensus pubkey
+    Update(String, Vec<String>),
+}
+
+/// Index of the validator whose moniker or consensus pubkey is `target`
+fn find_validator(validators: &[Value], target: &str) -> Result<usize> {
+    let normalize = |s: &str| s.trim_start_matches("0x").to_lowercase();
+    let matches: Vec<usize> = validators
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| {
+            v.get("moniker").and_then(Value::as_str) == Some(target)
+                || v.get("consensusPubkey")
+                    .and_then(Value::as_str)
+                    .is_some_and(|pubkey| normalize(pubkey) == normalize(target))
+        })
+        .map(|(i, _)| i)
+        .collect();
+    match matches.as_slice() {
+        [index] => Ok(*index),
+        [] => anyhow::bail!("No validator with moniker or consensusPubkey {}", target),
+        _ => anyhow::bail!(
+            "{} validators match {}; select one by consensusPubkey",
+            matches.len(),
+            target
+        ),
+    }
+}
+
+fn apply_edit(validators: &mut Vec<Value>, edit: &ValidatorEdit) -> Result<()> {
+    match edit {
+        ValidatorEdit::Add(validator) => {
+            let taken = validators
+                .iter()
+                .any(|v| v.get("moniker").and_then(Value::as_str) == Some(&validator.moniker));
+            if taken {
+                anyhow::bail!(
+                    "A validator with moniker {} already exists",
+                    validator.moniker
+                );
+            }
+            validators.push(serde_json::to_value(validator)?);
+            info!("Adding validator {}", validator.moniker);
+        }
+        ValidatorEdit::Remove(target) => {
+            let index = find_validator(validators, target)?;
+            validators.remove(index);
+            info!("Removing validator {} (index {})", target, index);
+        }
+        ValidatorEdit::Update(target, fields) => {
+            let index = find_validator(validators, target)?;
+            let validator = &mut validators[index];
+            for spec in fields {
+                let field = spec.split_once('=').map_or(spec.as_str(), |(field, _)| field);
+                if validator.get(field).is_none() {
+                    let known: Vec<&str> = validator
+                        .as_object()
+                        .map(|map| map.keys().map(String::as_str).collect())
+                        .unwrap_or_default();
+                    anyhow::bail!(
+                        "Validator {} has no field {}; fields: {}",
+                        target,
+                        field,
+                        known.join(", ")
+                    );
+                }
+                genesis::apply_config_override(validator, spec)?;
+            }
+            info!("Updating validator {} (index {})", target, index);
+        }
+    }
+    Ok(())
+}
+
+/// Apply `edit` to the validators of `config_file`, validate the result and
+/// write it back unless validation fails or `dry_run` is set
+pub fn edit_validators(
+    config_file: &str,
+    edit: &ValidatorEdit,
+    options: &ValidationOptions,
+    dry_run: bool,
+) -> Result<()> {
+    let raw = fs::read_to_string(config_file)
+        .context(format!("Failed to read config file {}", config_file))?;
+    let mut value: Value = serde_json::from_str(&raw).context(format!(
+        "{} is not plain JSON (a ${{VAR}} outside a string?); edit it by hand",
+        config_file
+    ))?;
+    let validators = match value.get_mut("validators") {
+        Some(Value::Array(validators)) => validators,
+        Some(Value::Object(map)) if map.contains_key("$include") => anyhow::bail!(
+            "validators of {} are included from {}; edit that file instead",
+            config_file,
+            map["$include"]
+        ),
+        _ => anyhow::bail!("{} has no validators array", config_file),
+    };
+    apply_edit(validators, edit)?;
+
+    let content = serde_json::to_string_pretty(&value)? + "\n";
+    let config = genesis::parse_genesis_config(&content, config_file)?;
+    let report = validate::validate_config(&config, options);
+    report.log();
+    print_stake_distribution(&config);
+    if report.has_errors() {
+        anyhow::bail!(
+            "Edited config fails validation with {} error(s); {} was not changed",
+            report.error_count(),
+            config_file
+        );
+    }
+
+    if dry_run {
+        info!("Dry run; {} was not changed", config_file);
+    } else {
+        fs::write(config_file, content)
+            .context(format!("Failed to write config file {}", config_file))?;
+        info!("Wrote {} validators to {}", config.validators.len(), config_file);
+    }
+    Ok(())
+}
@@ -8,16 +8,33 @@ use alloy_primitives::{Address, Bytes, U256};
 use alloy_sol_macro::sol;
 use alloy_sol_types::SolCall;
 use anyhow::{anyhow, Context, Result};
-use revm::{db::BundleState, DatabaseCommit, EvmBuilder, StateBuilder};
-use revm_primitives::{hex, AccountInfo, Bytecode, ExecutionResult, SpecId, TxEnv};
-use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs};
-use tracing::{error, info, warn};
+use rayon::prelude::*;
+use revm::{db::BundleState, DatabaseCommit, DatabaseRef, EvmBuilder, StateBuilder};
+use revm_primitives::{hex, AccountInfo, Bytecode, Env, ExecutionResult, SpecId, TxEnv};
+use serde::{
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::BufReader,
+};
+use tracing::{error, info, info_span, warn};
 
-use crate::execute::prepare_env;
+use crate::bytecode::{compare_code, CodeComparison};
+use crate::db::{DbBackend, DbOptions, DecodedAccount, StateStore};
+use crate::execute::{load_runtime_bytecode, prepare_env};
+use crate::genesis::{parse_amount, EvmEnvConfig, GenesisConfig};
+use crate::interfaces::{cross_check_interfaces, InterfaceRequirement};
+use crate::multiaddr::{normalize_network_address, parse_network_address};
+use crate::onchain_config::OnChainConsensusConfig;
+use crate::registry::ContractsManifest;
+use crate::spec::{resolve_spec, EvmSpec};
 use crate::utils::{
-    execute_revm_sequential, new_system_call_txn, EPOCH_CONFIG_ADDR, SYSTEM_CALLER,
-    VALIDATOR_MANAGER_ADDR,
+    execute_revm_sequential, new_system_call_txn, CONSENSUS_CONFIG_ADDR, EPOCH_CONFIG_ADDR,
+    EXECUTION_CONFIG_ADDR, JWK_MANAGER_ADDR, RANDOMNESS_CONFIG_ADDR, RECONFIGURATION_ADDR,
+    SYSTEM_CALLER, VALIDATOR_MANAGER_ADDR, VERSION_CONFIG_ADDR,
 };
 
 // ============================================================================
@@ -26,9 +43,74 @@ use crate::utils::{
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct GenesisJson {
+    /// Genesis block timestamp (hex seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
     pub alloc: HashMap<String, AllocEntry>,
 }
 
+/// Top-level genesis.json fields besides the alloc, as read while streaming
+#[derive(Debug, Clone, Default)]
+pub struct GenesisHeader {
+    /// Genesis block timestamp (hex seconds)
+    pub timestamp: Option<String>,
+    /// Chain config section with the fork schedule
+    pub config: Option<serde_json::Value>,
+}
+
+impl GenesisHeader {
+    /// Timestamp in seconds, 0 when absent
+    pub fn timestamp_secs(&self) -> Result<u64> {
+        match self.timestamp.as_deref() {
+            Some(timestamp) => u64::from_str_radix(timestamp.trim_start_matches("0x"), 16)
+                .map_err(|e| anyhow!("Invalid genesis timestamp {}: {}", timestamp, e)),
+            None => Ok(0),
+        }
+    }
+
+    /// `config.chainId`, as a number or a hex string
+    pub fn chain_id(&self) -> Result<Option<u64>> {
+        let Some(chain_id) = self.config.as_ref().and_then(|config| config.get("chainId")) else {
+            return Ok(None);
+        };
+        let parsed = match chain_id {
+            serde_json::Value::Number(number) => number.as_u64(),
+            serde_json::Value::String(text) => match text.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => text.parse().ok(),
+            },
+            _ => None,
+        };
+        parsed
+            .map(Some)
+            .ok_or_else(|| anyhow!("Invalid genesis config.chainId {}", chain_id))
+    }
+}
+
+/// Chain id views run under when the genesis names none
+const DEFAULT_VERIFY_CHAIN_ID: u64 = 1337;
+
+/// Chain id to verify under: `explicit`, else the genesis `config.chainId`, so
+/// contracts reading `block.chainid` see the chain they were generated for
+pub fn resolve_chain_id(explicit: Option<u64>, header: &GenesisHeader) -> Result<u64> {
+    let from_genesis = header.chain_id()?;
+    let (chain_id, source) = match (explicit, from_genesis) {
+        (Some(chain_id), _) => (chain_id, "explicit setting"),
+        (None, Some(chain_id)) => (chain_id, "genesis config"),
+        (None, None) => (DEFAULT_VERIFY_CHAIN_ID, "default"),
+    };
+    if let (Some(explicit), Some(genesis)) = (explicit, from_genesis) {
+        if explicit != genesis {
+            warn!(
+                "⚠️ Verifying under chain id {} although the genesis config.chainId is {}",
+                explicit, genesis
+            );
+        }
+    }
+    info!("Chain id: {} (from {})", chain_id, source);
+    Ok(chain_id)
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct AllocEntry {
     pub balance: Option<String>,
@@ -60,6 +142,170 @@ sol! {
     function epochIntervalMicros() external view returns (uint64);
 }
 
+// Remaining views gravity-reth reads at startup and at epoch boundaries, each
+// with the return layout the node decodes
+sol! {
+    interface NodeReconfiguration {
+        function currentEpoch() external view returns (uint64);
+    }
+}
+
+sol! {
+    interface NodeConsensusConfig {
+        function getCurrentConfig() external view returns (bytes memory);
+    }
+}
+
+sol! {
+    interface NodeExecutionConfig {
+        function getCurrentConfig() external view returns (bytes memory);
+    }
+}
+
+sol! {
+    interface NodeRandomnessConfig {
+        struct ConfigV2Data {
+            uint128 secrecyThreshold;
+            uint128 reconstructionThreshold;
+            uint128 fastPathSecrecyThreshold;
+        }
+
+        struct RandomnessConfigData {
+            uint8 variant;
+            ConfigV2Data configV2;
+        }
+
+        function getCurrentConfig() external view returns (RandomnessConfigData memory);
+    }
+}
+
+sol! {
+    interface NodeJWKManager {
+        struct RSA_JWK {
+            string kid;
+            string kty;
+            string alg;
+            string e;
+            string n;
+        }
+
+        struct ProviderJWKs {
+            bytes issuer;
+            uint64 version;
+            RSA_JWK[] jwks;
+        }
+
+        struct AllProvidersJWKs {
+            ProviderJWKs[] entries;
+        }
+
+        function getObservedJWKs() external view returns (AllProvidersJWKs memory);
+        function getPatchedJWKs() external view returns (AllProvidersJWKs memory);
+    }
+}
+
+sol! {
+    interface NodeVersionConfig {
+        function majorVersion() external view returns (uint64);
+    }
+}
+
+// Earlier ValidatorConsensusInfo layouts, for recognising inherited genesis files
+sol! {
+    interface PreFullnodeAddresses {
+        struct ValidatorConsensusInfo {
+            address validator;
+            bytes consensusPubkey;
+            bytes consensusPop;
+            uint256 votingPower;
+            uint64 validatorIndex;
+            bytes networkAddresses;
+        }
+
+        function getActiveValidators() external view returns (ValidatorConsensusInfo[] memory);
+    }
+}
+
+sol! {
+    interface PreNetworkAddresses {
+        struct ValidatorConsensusInfo {
+            address validator;
+            bytes consensusPubkey;
+            bytes consensusPop;
+            uint256 votingPower;
+            uint64 validatorIndex;
+        }
+
+        function getActiveValidators() external view returns (ValidatorConsensusInfo[] memory);
+    }
+}
+
+sol! {
+    interface PreProofOfPossession {
+        struct ValidatorConsensusInfo {
+            address validator;
+            bytes consensusPubkey;
+            uint256 votingPower;
+            uint64 validatorIndex;
+        }
+
+        function getActiveValidators() external view returns (ValidatorConsensusInfo[] memory);
+    }
+}
+
+/// Contract generation identified by the `ValidatorConsensusInfo` layout that
+/// `getActiveValidators()` returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiGeneration {
+    /// 7 fields, with networkAddresses and fullnodeAddresses
+    Current,
+    /// 6 fields, networkAddresses only
+    PreFullnodeAddresses,
+    /// 5 fields, before network addresses were stored on chain
+    PreNetworkAddresses,
+    /// 4 fields, before proofs of possession
+    PreProofOfPossession,
+}
+
+impl AbiGeneration {
+    pub fn describe(self) -> &'static str {
+        match self {
+            AbiGeneration::Current => "current (7-field, with network and fullnode addresses)",
+            AbiGeneration::PreFullnodeAddresses => "6-field (pre-fullnodeAddresses)",
+            AbiGeneration::PreNetworkAddresses => "5-field (pre-networkAddresses)",
+            AbiGeneration::PreProofOfPossession => "4-field (pre-consensusPop)",
+        }
+    }
+}
+
+/// Match `getActiveValidators()` output against the legacy layouts, newest
+/// first; returns the generation and its validator count. A layout only
+/// matches when its decoding re-encodes to the exact output, so data of one
+/// layout cannot pass as a shorter one.
+fn detect_legacy_generation(output: &[u8]) -> Option<(AbiGeneration, usize)> {
+    type Detector = fn(&[u8]) -> Option<usize>;
+    let layouts: [(AbiGeneration, Detector); 3] = [
+        (AbiGeneration::PreFullnodeAddresses, |output| {
+            use PreFullnodeAddresses::getActiveValidatorsCall as Call;
+            let decoded = Call::abi_decode_returns(output, true).ok()?;
+            (Call::abi_encode_returns(&(decoded._0.clone(),)) == output).then_some(decoded._0.len())
+        }),
+        (AbiGeneration::PreNetworkAddresses, |output| {
+            use PreNetworkAddresses::getActiveValidatorsCall as Call;
+            let decoded = Call::abi_decode_returns(output, true).ok()?;
+            (Call::abi_encode_returns(&(decoded._0.clone(),)) == output).then_some(decoded._0.len())
+        }),
+        (AbiGeneration::PreProofOfPossession, |output| {
+            use PreProofOfPossession::getActiveValidatorsCall as Call;
+            let decoded = Call::abi_decode_returns(output, true).ok()?;
+            (Call::abi_encode_returns(&(decoded._0.clone(),)) == output).then_some(decoded._0.len())
+        }),
+    ];
+    layouts
+        .into_iter()
+        .find_map(|(generation, detect)| Some((generation, detect(output)?)))
+}
+
 /// Result of genesis verification
 #[derive(Debug)]
 pub struct VerifyResult {
@@ -68,94 +314,339 @@ pub struct VerifyResult {
     pub validators: Vec<ValidatorInfo>,
     pub epoch_interval_micros: Option<u64>,
     pub errors: Vec<String>,
+    /// System contracts whose code matches the artifacts only modulo solc metadata
+    pub metadata_only_diffs: Vec<String>,
+    /// Layout generation `getActiveValidators()` returned, when one decoded
+    pub abi_generation: Option<AbiGeneration>,
+    /// The other on-chain configs the node reads, in read order
+    pub node_configs: Vec<NodeConfigRead>,
+}
+
+/// One on-chain config view as gravity-reth reads it
+#[derive(Debug)]
+pub struct NodeConfigRead {
+    /// `Contract.function()`
+    pub view: &'static str,
+    /// Summary of the decoded value, or why the node could not read it
+    pub outcome: std::result::Result<String, String>,
 }
 
 #[derive(Debug)]
 pub struct ValidatorInfo {
     pub address: Address,
+    pub consensus_pubkey: Bytes,
+    pub consensus_pop: Bytes,
     pub voting_power: U256,
     pub validator_index: u64,
     pub has_network_addresses: bool,
     pub has_fullnode_addresses: bool,
+    /// BCS-decoded network address, if it round-tripped to a valid multiaddr
+    pub network_addresses: Option<String>,
+    /// BCS-decoded fullnode address, if it round-tripped to a valid multiaddr
+    pub fullnode_addresses: Option<String>,
 }
 
-/// Verify an existing genesis.json file
-pub fn verify_genesis_file(genesis_path: &str) -> Result<VerifyResult> {
-    info!("=== Genesis Verification ===");
-    info!("Loading genesis file: {}", genesis_path);
+/// BCS-decode an on-chain address blob back into a string and validate it as a multiaddr.
+///
+/// The whole buffer must be consumed, which catches double-encoding (a BCS string
+/// wrapping another length prefix) and truncation.
+fn decode_bcs_network_address(bytes: &[u8]) -> std::result::Result<String, String> {
+    let decoded: String =
+        bcs::from_bytes(bytes).map_err(|e| format!("BCS decode failed: {}", e))?;
+    parse_network_address(&decoded)
+        .map_err(|e| format!("{:?} is not a valid multiaddr: {}", decoded, e))?;
+    Ok(decoded)
+}
 
-    // 1. Load genesis.json
+/// Read and parse a genesis.json file
+pub fn read_genesis_json(genesis_path: &str) -> Result<GenesisJson> {
     let genesis_content = fs::read_to_string(genesis_path)
         .context(format!("Failed to read genesis file: {}", genesis_path))?;
 
-    let genesis: GenesisJson =
-        serde_json::from_str(&genesis_content).context("Failed to parse genesis.json")?;
+    serde_json::from_str(&genesis_content).context("Failed to parse genesis.json")
+}
 
-    info!(
-        "Genesis loaded successfully, {} accounts in alloc",
-        genesis.alloc.len()
-    );
+/// Accounts decoded per parallel batch while streaming an alloc
+const ALLOC_BATCH: usize = 4096;
 
-    // 2. Create in-memory EVM with genesis state
+/// Build an in-memory database holding the genesis alloc
+pub fn build_genesis_db(genesis: &GenesisJson) -> Result<revm::InMemoryDB> {
     let mut db = revm::InMemoryDB::default();
 
-    for (addr_str, entry) in &genesis.alloc {
-        let addr: Address = addr_str
-            .parse()
-            .context(format!("Invalid address: {}", addr_str))?;
-
-        let balance = entry
-            .balance
-            .as_ref()
-            .map(|b| parse_u256_hex(b))
-            .unwrap_or(U256::ZERO);
-
-        let nonce = entry.nonce.unwrap_or(0);
-
-        let code = entry
-            .code
-            .as_ref()
-            .map(|c| {
-                let hex_str = c.strip_prefix("0x").unwrap_or(c);
-                hex::decode(hex_str).unwrap_or_else(|e| {
-                    panic!("FATAL: Failed to decode hex bytecode: {}", e)
-                })
+    let decoded = genesis
+        .alloc
+        .par_iter()
+        .map(|(addr_str, entry)| {
+            let addr: Address = addr_str
+                .parse()
+                .context(format!("Invalid address: {}", addr_str))?;
+            Ok(decode_alloc_entry(addr, entry))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    db.insert_accounts(decoded)?;
+
+    Ok(db)
+}
+
+/// Account info and storage of one alloc entry; hex decoding and code hashing
+/// dominate loading, so this runs in parallel across entries
+fn decode_alloc_entry(addr: Address, entry: &AllocEntry) -> DecodedAccount {
+    let balance = entry
+        .balance
+        .as_ref()
+        .map(|b| parse_u256_hex(b))
+        .unwrap_or(U256::ZERO);
+
+    let nonce = entry.nonce.unwrap_or(0);
+
+    let code = entry
+        .code
+        .as_ref()
+        .map(|c| {
+            let hex_str = c.strip_prefix("0x").unwrap_or(c);
+            hex::decode(hex_str).unwrap_or_else(|e| {
+                panic!("FATAL: Failed to decode hex bytecode: {}", e)
             })
-            .unwrap_or_default();
+        })
+        .unwrap_or_default();
 
-        let bytecode = if code.is_empty() {
-            Bytecode::default()
-        } else {
-            Bytecode::new_raw(code.into())
-        };
+    let bytecode = if code.is_empty() {
+        Bytecode::default()
+    } else {
+        Bytecode::new_raw(code.into())
+    };
 
-        let account_info = AccountInfo {
-            balance,
-            nonce,
-            code_hash: bytecode.hash_slow(),
-            code: Some(bytecode),
-        };
+    let account_info = AccountInfo {
+        balance,
+        nonce,
+        code_hash: bytecode.hash_slow(),
+        code: Some(bytecode),
+    };
+
+    let storage = entry
+        .storage
+        .iter()
+        .flatten()
+        .map(|(key_str, value_str)| (parse_u256_hex(key_str), parse_u256_hex(value_str)))
+        .collect();
+
+    (addr, account_info, storage)
+}
+
+/// Decode a batch of streamed accounts in parallel, then insert them in order
+fn insert_alloc_batch<S: StateStore>(store: &mut S, batch: Vec<(Address, AllocEntry)>) -> Result<()> {
+    let decoded = batch
+        .into_par_iter()
+        .map(|(addr, entry)| decode_alloc_entry(addr, &entry))
+        .collect();
+    store.insert_accounts(decoded)
+}
+
+/// Top-level genesis.json visitor handing each alloc account to a callback as
+/// soon as it is parsed, so the alloc is never held in memory as a whole
+struct StreamVisitor<'a, F> {
+    on_account: &'a mut F,
+}
 
-        db.insert_account_info(addr, account_info);
+/// Streams the `alloc` object's entries into the callback
+struct AllocSeed<'a, F> {
+    on_account: &'a mut F,
+}
+
+impl<'de, F> Visitor<'de> for StreamVisitor<'_, F>
+where
+    F: FnMut(Address, AllocEntry) -> Result<()>,
+{
+    type Value = GenesisHeader;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a genesis.json object")
+    }
 
-        // Insert storage
-        if let Some(storage) = &entry.storage {
-            for (key_str, value_str) in storage {
-                let key = parse_u256_hex(key_str);
-                let value = parse_u256_hex(value_str);
-                db.insert_account_storage(addr, key, value)
-                    .expect("Failed to insert storage");
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut header = GenesisHeader::default();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "alloc" => map.next_value_seed(AllocSeed {
+                    on_account: &mut *self.on_account,
+                })?,
+                "timestamp" => header.timestamp = map.next_value()?,
+                "config" => header.config = map.next_value()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
             }
         }
+        Ok(header)
     }
+}
+
+impl<'de, F> DeserializeSeed<'de> for AllocSeed<'_, F>
+where
+    F: FnMut(Address, AllocEntry) -> Result<()>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for AllocSeed<'_, F>
+where
+    F: FnMut(Address, AllocEntry) -> Result<()>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("an alloc object keyed by address")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(addr_str) = map.next_key::<String>()? {
+            let entry: AllocEntry = map.next_value()?;
+            let addr: Address = addr_str
+                .parse()
+                .map_err(|_| de::Error::custom(format!("Invalid address: {}", addr_str)))?;
+            (self.on_account)(addr, entry).map_err(|e| de::Error::custom(format!("{:#}", e)))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a genesis.json from disk one alloc account at a time, returning its
+/// timestamp and chain config. Memory stays bounded by the largest single account rather than
+/// the file size, which matters for multi-GB forked-mainnet genesis files.
+pub fn stream_genesis_alloc<F>(genesis_path: &str, mut on_account: F) -> Result<GenesisHeader>
+where
+    F: FnMut(Address, AllocEntry) -> Result<()>,
+{
+    let file = File::open(genesis_path)
+        .context(format!("Failed to read genesis file: {}", genesis_path))?;
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(file));
+    let header = deserializer
+        .deserialize_map(StreamVisitor {
+            on_account: &mut on_account,
+        })
+        .context("Failed to parse genesis.json")?;
+    deserializer.end().context("Failed to parse genesis.json")?;
+    Ok(header)
+}
+
+/// Stream a genesis.json into a state store; returns the number of alloc
+/// accounts and the genesis header fields
+pub fn load_genesis_into<S: StateStore>(
+    genesis_path: &str,
+    store: &mut S,
+) -> Result<(usize, GenesisHeader)> {
+    let mut accounts = 0;
+    let mut batch = Vec::with_capacity(ALLOC_BATCH);
+    let header = stream_genesis_alloc(genesis_path, |addr, entry| {
+        batch.push((addr, entry));
+        accounts += 1;
+        if batch.len() == ALLOC_BATCH {
+            insert_alloc_batch(store, std::mem::take(&mut batch))?;
+        }
+        Ok(())
+    })?;
+    insert_alloc_batch(store, batch)?;
+    Ok((accounts, header))
+}
+
+/// Stream a genesis.json straight into an in-memory database; returns the
+/// database, the number of alloc accounts and the genesis header fields
+pub fn load_genesis_db(genesis_path: &str) -> Result<(revm::InMemoryDB, usize, GenesisHeader)> {
+    let mut db = revm::InMemoryDB::default();
+    let (accounts, header) = load_genesis_into(genesis_path, &mut db)?;
+    Ok((db, accounts, header))
+}
+
+/// Verify an existing genesis.json file
+pub fn verify_genesis_file(genesis_path: &str) -> Result<VerifyResult> {
+    verify_genesis_file_with(genesis_path, &VerifyOptions::default())
+}
+
+/// How verification loads and executes a genesis
+#[derive(Debug, Clone, Default)]
+pub struct VerifyOptions {
+    pub db: DbOptions,
+    /// Hardfork to execute under; the genesis fork schedule when unset
+    pub evm_spec: Option<EvmSpec>,
+    /// Chain id to execute under; the genesis `config.chainId` when unset
+    pub chain_id: Option<u64>,
+    /// Block fields views execute under, as genesis was generated with them;
+    /// `run_verify` takes them from the expected config
+    pub evm_env: Option<EvmEnvConfig>,
+}
+
+impl VerifyOptions {
+    /// Environment verification calls execute under
+    pub fn env(&self, chain_id: u64, spec_id: SpecId) -> Env {
+        let mut env = prepare_env(chain_id);
+        if let Some(evm_env) = &self.evm_env {
+            evm_env.apply(&mut env, spec_id);
+        }
+        env
+    }
+}
+
+/// Verify an existing genesis.json file, loading its state into the chosen
+/// backend and executing under the genesis chain id and fork schedule unless
+/// `options` override them
+pub fn verify_genesis_file_with(genesis_path: &str, options: &VerifyOptions) -> Result<VerifyResult> {
+    info!("=== Genesis Verification ===");
+    info!("Loading genesis file: {}", genesis_path);
+
+    let db_options = &options.db;
+    match db_options.backend {
+        DbBackend::Memory => {
+            verify_genesis_state(genesis_path, revm::InMemoryDB::default(), options)
+        }
+        #[cfg(feature = "redb")]
+        DbBackend::Redb => {
+            let dir = db_options.dir.clone().unwrap_or_else(std::env::temp_dir);
+            fs::create_dir_all(&dir).context(format!("Failed to create {}", dir.display()))?;
+            let path = dir.join(format!(
+                "genesis-verify-{}-{}.redb",
+                std::process::id(),
+                hex::encode(&alloy_primitives::keccak256(genesis_path.as_bytes())[..4])
+            ));
+            info!("Loading state into on-disk database {}", path.display());
+            let result = crate::db::RedbStore::create(&path)
+                .and_then(|store| verify_genesis_state(genesis_path, store, options));
+            let _ = fs::remove_file(&path);
+            result
+        }
+        #[cfg(not(feature = "redb"))]
+        DbBackend::Redb => Err(anyhow!(
+            "--db-backend redb requires building genesis-tool with --features redb"
+        )),
+    }
+}
+
+fn verify_genesis_state<DB>(
+    genesis_path: &str,
+    mut db: DB,
+    options: &VerifyOptions,
+) -> Result<VerifyResult>
+where
+    DB: StateStore,
+    DB::Error: std::fmt::Debug,
+{
+    // 1. Stream genesis.json into the EVM database
+    let (account_count, header) = load_genesis_into(genesis_path, &mut db)?;
+
+    info!(
+        "Genesis loaded successfully, {} accounts in alloc",
+        account_count
+    );
+    let spec_id = resolve_spec(options.evm_spec, header.config.as_ref(), header.timestamp_secs()?);
+    let chain_id = resolve_chain_id(options.chain_id, &header)?;
+    let env = options.env(chain_id, spec_id);
 
     // Check if ValidatorManager contract exists
     let vm_addr = VALIDATOR_MANAGER_ADDR;
-    let vm_addr_str = format!("{:?}", vm_addr).to_lowercase();
-    let has_vm = genesis
-        .alloc
-        .keys()
-        .any(|k| k.to_lowercase() == vm_addr_str);
+    let has_vm = db.has_account(vm_addr);
 
     if !has_vm {
         return Ok(VerifyResult {
@@ -167,6 +658,9 @@ pub fn verify_genesis_file(genesis_path: &str) -> Result<VerifyResult> {
                 "ValidatorManagement contract not found at expected address: {:?}",
                 vm_addr
             )],
+            metadata_only_diffs: vec![],
+            abi_generation: None,
+            node_configs: vec![],
         });
     }
 
@@ -174,31 +668,51 @@ pub fn verify_genesis_file(genesis_path: &str) -> Result<VerifyResult> {
 
     // 3. First verify epoch interval from EpochConfig
     info!("Verifying epoch interval from EpochConfig...");
-    let epoch_interval = verify_epoch_interval(&db);
+    let epoch_interval = info_span!("verification", step = "epoch_interval")
+        .in_scope(|| verify_epoch_interval(&db, spec_id, &env));
     match &epoch_interval {
-        Some(micros) => {
+        Ok(micros) => {
             let hours = *micros as f64 / 3_600_000_000.0;
             info!("✅ Epoch interval: {} micros ({:.4} hours)", micros, hours);
         }
-        None => {
-            warn!("⚠️ Could not read epoch interval from EpochConfig");
+        Err(e) => {
+            error!("❌ EpochConfig.epochIntervalMicros(): {}", e);
         }
     }
+    let mut node_configs = vec![NodeConfigRead {
+        view: "EpochConfig.epochIntervalMicros()",
+        outcome: epoch_interval.as_ref().map(|micros| format!("{} micros", micros)).map_err(Clone::clone),
+    }];
+    let epoch_interval = epoch_interval.ok();
+
+    // Then the rest of the configs the node reads, each with its ABI
+    info!("Reading on-chain configs with the node ABI...");
+    node_configs.extend(
+        info_span!("verification", step = "node_configs").in_scope(|| read_node_configs(&db, spec_id, &env)),
+    );
 
     // 4. Simulate getActiveValidators() call
+    let _span = info_span!("verification", step = "active_validators").entered();
     info!("Simulating getActiveValidators() call...");
 
     let call = getActiveValidatorsCall {};
     let input: Bytes = call.abi_encode().into();
     let tx = new_system_call_txn(vm_addr, input);
 
-    let env = prepare_env(1337);
-    let result = execute_revm_sequential(db, SpecId::LATEST, env, &[tx], None);
+    let result = execute_revm_sequential(db, spec_id, env, &[tx], None);
 
     match result {
         Ok((results, _)) => {
             if let Some(exec_result) = results.first() {
-                return process_execution_result(exec_result, epoch_interval);
+                let mut verify_result = process_execution_result(exec_result, epoch_interval)?;
+                for read in &node_configs {
+                    if let Err(e) = &read.outcome {
+                        verify_result.success = false;
+                        verify_result.errors.push(format!("{}: {}", read.view, e));
+                    }
+                }
+                verify_result.node_configs = node_configs;
+                return Ok(verify_result);
             }
             Err(anyhow!("No execution result returned"))
         }
@@ -206,32 +720,187 @@ pub fn verify_genesis_file(genesis_path: &str) -> Result<VerifyResult> {
     }
 }
 
+/// Decode a single-value view output with the node's return layout. Lenient
+/// decoding lets a shorter or differently shaped layout through, so only
+/// output that re-encodes exactly counts as matching.
+macro_rules! decode_exact {
+    ($call:ty, $output:expr) => {{
+        let output: &[u8] = $output;
+        if output.is_empty() {
+            Err("empty output; is the contract deployed?".to_string())
+        } else {
+            <$call>::abi_decode_returns(output, true)
+                .map_err(|e| format!("ABI decode failed: {}", e))
+                .and_then(|decoded| {
+                    if <$call>::abi_encode_returns(&(decoded._0.clone(),)) == output {
+                        Ok(decoded._0)
+                    } else {
+                        Err(format!(
+                            "output ({} bytes) does not re-encode as the node's layout",
+                            output.len()
+                        ))
+                    }
+                })
+        }
+    }};
+}
+
 /// Verify epoch interval by calling EpochConfig.epochIntervalMicros()
-fn verify_epoch_interval(db: &revm::InMemoryDB) -> Option<u64> {
-    let call = epochIntervalMicrosCall {};
-    let input: Bytes = call.abi_encode().into();
-    let tx = new_system_call_txn(EPOCH_CONFIG_ADDR, input);
+fn verify_epoch_interval<DB>(
+    db: DB,
+    spec_id: SpecId,
+    env: &Env,
+) -> std::result::Result<u64, String>
+where
+    DB: DatabaseRef,
+    DB::Error: std::fmt::Debug,
+{
+    let output = call_view(
+        db,
+        spec_id,
+        env,
+        EPOCH_CONFIG_ADDR,
+        epochIntervalMicrosCall::SELECTOR,
+    )?;
+    decode_exact!(epochIntervalMicrosCall, &output)
+}
 
-    let env = prepare_env(1337);
-    let result = execute_revm_sequential(db.clone(), SpecId::LATEST, env, &[tx], None);
+/// Config views read besides the epoch interval, as (view, address, selector, decoder)
+type NodeConfigView = (
+    &'static str,
+    Address,
+    [u8; 4],
+    fn(&[u8]) -> std::result::Result<String, String>,
+);
 
-    match result {
-        Ok((results, _)) => {
-            if let Some(ExecutionResult::Success { output, .. }) = results.first() {
-                let output_bytes = match output {
-                    revm_primitives::Output::Call(bytes) => bytes,
-                    revm_primitives::Output::Create(bytes, _) => bytes,
-                };
-
-                if let Ok(decoded) =
-                    epochIntervalMicrosCall::abi_decode_returns(output_bytes, false)
-                {
-                    return Some(decoded._0);
-                }
+const NODE_CONFIG_VIEWS: [NodeConfigView; 7] = [
+    (
+        "Reconfiguration.currentEpoch()",
+        RECONFIGURATION_ADDR,
+        NodeReconfiguration::currentEpochCall::SELECTOR,
+        |output| {
+            let epoch = decode_exact!(NodeReconfiguration::currentEpochCall, output)?;
+            Ok(format!("epoch {}", epoch))
+        },
+    ),
+    (
+        "ConsensusConfig.getCurrentConfig()",
+        CONSENSUS_CONFIG_ADDR,
+        NodeConsensusConfig::getCurrentConfigCall::SELECTOR,
+        |output| {
+            let bytes = decode_exact!(NodeConsensusConfig::getCurrentConfigCall, output)?;
+            OnChainConsensusConfig::from_bcs(&bytes).map_err(|e| {
+                format!("{} bytes are not a valid BCS OnChainConsensusConfig: {}", bytes.len(), e)
+            })?;
+            Ok(format!("{} bytes, valid BCS OnChainConsensusConfig", bytes.len()))
+        },
+    ),
+    (
+        "ExecutionConfig.getCurrentConfig()",
+        EXECUTION_CONFIG_ADDR,
+        NodeExecutionConfig::getCurrentConfigCall::SELECTOR,
+        |output| {
+            let bytes = decode_exact!(NodeExecutionConfig::getCurrentConfigCall, output)?;
+            if bytes.is_empty() {
+                return Err("config is empty".to_string());
             }
-            None
+            Ok(format!("{} bytes", bytes.len()))
+        },
+    ),
+    (
+        "RandomnessConfig.getCurrentConfig()",
+        RANDOMNESS_CONFIG_ADDR,
+        NodeRandomnessConfig::getCurrentConfigCall::SELECTOR,
+        |output| {
+            let config = decode_exact!(NodeRandomnessConfig::getCurrentConfigCall, output)?;
+            match config.variant {
+                0 => Ok("Off".to_string()),
+                1 => Ok(format!(
+                    "V2 (secrecy {}, reconstruction {}, fast path {})",
+                    config.configV2.secrecyThreshold,
+                    config.configV2.reconstructionThreshold,
+                    config.configV2.fastPathSecrecyThreshold
+                )),
+                variant => Err(format!("unknown ConfigVariant {}", variant)),
+            }
+        },
+    ),
+    (
+        "JWKManager.getObservedJWKs()",
+        JWK_MANAGER_ADDR,
+        NodeJWKManager::getObservedJWKsCall::SELECTOR,
+        |output| {
+            let jwks = decode_exact!(NodeJWKManager::getObservedJWKsCall, output)?;
+            Ok(describe_jwks(&jwks))
+        },
+    ),
+    (
+        "JWKManager.getPatchedJWKs()",
+        JWK_MANAGER_ADDR,
+        NodeJWKManager::getPatchedJWKsCall::SELECTOR,
+        |output| {
+            let jwks = decode_exact!(NodeJWKManager::getPatchedJWKsCall, output)?;
+            Ok(describe_jwks(&jwks))
+        },
+    ),
+    (
+        "VersionConfig.majorVersion()",
+        VERSION_CONFIG_ADDR,
+        NodeVersionConfig::majorVersionCall::SELECTOR,
+        |output| {
+            let version = decode_exact!(NodeVersionConfig::majorVersionCall, output)?;
+            Ok(format!("version {}", version))
+        },
+    ),
+];
+
+fn describe_jwks(jwks: &NodeJWKManager::AllProvidersJWKs) -> String {
+    let keys: usize = jwks.entries.iter().map(|provider| provider.jwks.len()).sum();
+    format!("{} provider(s), {} key(s)", jwks.entries.len(), keys)
+}
+
+/// Read every view in [`NODE_CONFIG_VIEWS`]; a failed read does not stop the others
+fn read_node_configs<DB>(db: &DB, spec_id: SpecId, env: &Env) -> Vec<NodeConfigRead>
+where
+    DB: DatabaseRef,
+    DB::Error: std::fmt::Debug,
+{
+    NODE_CONFIG_VIEWS
+        .iter()
+        .map(|(view, address, selector, decode)| {
+            let outcome = call_view(db, spec_id, env, *address, *selector)
+                .and_then(|output| decode(&output));
+            match &outcome {
+                Ok(summary) => info!("✅ {}: {}", view, summary),
+                Err(e) => error!("❌ {}: {}", view, e),
+            }
+            NodeConfigRead { view, outcome }
+        })
+        .collect()
+}
+
+/// Call a view with no arguments as the system caller and return its output
+fn call_view<DB>(
+    db: DB,
+    spec_id: SpecId,
+    env: &Env,
+    address: Address,
+    selector: [u8; 4],
+) -> std::result::Result<Bytes, String>
+where
+    DB: DatabaseRef,
+    DB::Error: std::fmt::Debug,
+{
+    let tx = new_system_call_txn(address, Bytes::copy_from_slice(&selector));
+    let (results, _) = execute_revm_sequential(db, spec_id, env.clone(), &[tx], None)
+        .map_err(|e| format!("EVM execution failed: {:?}", e))?;
+    match results.into_iter().next() {
+        Some(ExecutionResult::Success { output, .. }) => Ok(output.into_data()),
+        Some(ExecutionResult::Revert { output, .. }) => {
+            Err(format!("call reverted: 0x{}", hex::encode(output)))
         }
-        Err(_) => None,
+        Some(ExecutionResult::Halt { reason, .. }) => Err(format!("call halted: {:?}", reason)),
+        None => Err("no execution result returned".to_string()),
     }
 }
 
@@ -249,8 +918,20 @@ fn process_execution_result(
             info!("getActiveValidators() call successful");
             info!("Output length: {} bytes", output_bytes.len());
 
-            // Try to decode with the new ABI (7 fields)
-            match getActiveValidatorsCall::abi_decode_returns(output_bytes, false) {
+            // Try to decode with the new ABI (7 fields). Shorter layouts can decode
+            // leniently as this one, so only an exact re-encoding counts.
+            let decoded = getActiveValidatorsCall::abi_decode_returns(output_bytes, false)
+                .and_then(|decoded| {
+                    let reencoded = getActiveValidatorsCall::abi_encode_returns(&(decoded._0.clone(),));
+                    if reencoded == output_bytes.as_ref() {
+                        Ok(decoded)
+                    } else {
+                        Err(alloy_sol_types::Error::custom(
+                            "output does not re-encode as the current layout",
+                        ))
+                    }
+                });
+            match decoded {
                 Ok(decoded) => {
                     let validators = &decoded._0;
                     info!(
@@ -259,37 +940,93 @@ fn process_execution_result(
                     );
 
                     let mut validator_infos = Vec::new();
+                    let mut errors = Vec::new();
                     for (i, v) in validators.iter().enumerate() {
                         info!("--- Validator {} ---", i);
                         info!("  Address: {:?}", v.validator);
                         info!("  Voting Power: {}", v.votingPower);
                         info!("  Index: {}", v.validatorIndex);
-                        info!("  Network Addresses: {} bytes", v.networkAddresses.len());
-                        info!("  Fullnode Addresses: {} bytes", v.fullnodeAddresses.len());
+
+                        let mut decode_address = |field: &str, bytes: &Bytes| {
+                            match decode_bcs_network_address(bytes) {
+                                Ok(addr) => {
+                                    info!("  {}: {} ({} bytes)", field, addr, bytes.len());
+                                    Some(addr)
+                                }
+                                Err(e) => {
+                                    error!("❌ {}: {} ({} bytes)", field, e, bytes.len());
+                                    errors.push(format!(
+                                        "Validator {} ({:?}) {}: {}",
+                                        i, v.validator, field, e
+                                    ));
+                                    None
+                                }
+                            }
+                        };
+                        let network_addresses =
+                            decode_address("Network Addresses", &v.networkAddresses);
+                        let fullnode_addresses =
+                            decode_address("Fullnode Addresses", &v.fullnodeAddresses);
 
                         validator_infos.push(ValidatorInfo {
                             address: v.validator,
+                            consensus_pubkey: v.consensusPubkey.clone(),
+                            consensus_pop: v.consensusPop.clone(),
                             voting_power: v.votingPower,
                             validator_index: v.validatorIndex,
                             has_network_addresses: !v.networkAddresses.is_empty(),
                             has_fullnode_addresses: !v.fullnodeAddresses.is_empty(),
+                            network_addresses,
+                            fullnode_addresses,
                         });
                     }
 
-                    info!("🎉 Genesis verification PASSED - ABI is compatible with gravity-reth");
+                    if errors.is_empty() {
+                        info!("🎉 Genesis verification PASSED - ABI is compatible with gravity-reth");
+                    } else {
+                        error!(
+                            "❌ ABI decode succeeded but {} validator address(es) are malformed",
+                            errors.len()
+                        );
+                    }
 
                     Ok(VerifyResult {
-                        success: true,
+                        success: errors.is_empty(),
                         validator_count: validators.len(),
                         validators: validator_infos,
                         epoch_interval_micros,
-                        errors: vec![],
+                        errors,
+                        metadata_only_diffs: vec![],
+                        abi_generation: Some(AbiGeneration::Current),
+                        node_configs: vec![],
                     })
                 }
                 Err(decode_err) => {
                     error!("❌ ABI decode FAILED: {:?}", decode_err);
-                    error!("This indicates the genesis.json was created with old contracts");
-                    error!("Solution: Recompile contracts and regenerate genesis.json");
+
+                    if let Some((generation, count)) = detect_legacy_generation(output_bytes) {
+                        error!(
+                            "Genesis matches the {} ValidatorConsensusInfo layout ({} validators)",
+                            generation.describe(),
+                            count
+                        );
+                        error!("Solution: Recompile contracts and regenerate genesis.json");
+                        return Ok(VerifyResult {
+                            success: false,
+                            validator_count: count,
+                            validators: vec![],
+                            epoch_interval_micros,
+                            errors: vec![format!(
+                                "getActiveValidators() returns the {} ValidatorConsensusInfo layout; gravity-reth expects the {}",
+                                generation.describe(),
+                                AbiGeneration::Current.describe()
+                            )],
+                            metadata_only_diffs: vec![],
+                            abi_generation: Some(generation),
+                            node_configs: vec![],
+                        });
+                    }
+                    error!("Output matches no known ValidatorConsensusInfo layout");
 
                     // Try to provide more diagnostic info
                     if output_bytes.len() > 64 {
@@ -306,8 +1043,11 @@ fn process_execution_result(
                         epoch_interval_micros,
                         errors: vec![
                             format!("ABI decode failed: {:?}", decode_err),
-                            "This likely means the genesis.json was created with old contracts lacking networkAddresses/fullnodeAddresses fields".to_string(),
+                            "Output matches no known ValidatorConsensusInfo layout".to_string(),
                         ],
+                        metadata_only_diffs: vec![],
+                        abi_generation: None,
+                        node_configs: vec![],
                     })
                 }
             }
@@ -322,6 +1062,9 @@ fn process_execution_result(
                 validators: vec![],
                 epoch_interval_micros,
                 errors: vec![format!("Call reverted: 0x{}", hex::encode(output))],
+                metadata_only_diffs: vec![],
+                abi_generation: None,
+                node_configs: vec![],
             })
         }
         ExecutionResult::Halt { reason, .. } => {
@@ -333,11 +1076,182 @@ fn process_execution_result(
                 validators: vec![],
                 epoch_interval_micros,
                 errors: vec![format!("Call halted: {:?}", reason)],
+                metadata_only_diffs: vec![],
+                abi_generation: None,
+                node_configs: vec![],
             })
         }
     }
 }
 
+/// Compare verification results against the GenesisConfig the genesis was built from.
+///
+/// Validators are matched by consensus pubkey; every differing field is reported as a
+/// separate error and marks the result as failed.
+pub fn cross_check_with_config(result: &mut VerifyResult, config: &GenesisConfig) {
+    let _span = info_span!("verification", step = "config_cross_check").entered();
+    info!("Cross-checking verification results against expected config...");
+    let mut mismatches = Vec::new();
+
+    if result.validator_count != config.validators.len() {
+        mismatches.push(format!(
+            "validator count: expected {}, got {}",
+            config.validators.len(),
+            result.validator_count
+        ));
+    }
+
+    if let Some(actual) = result.epoch_interval_micros {
+        if actual != config.epoch_interval_micros {
+            mismatches.push(format!(
+                "epochIntervalMicros: expected {}, got {}",
+                config.epoch_interval_micros, actual
+            ));
+        }
+    }
+
+    for expected in &config.validators {
+        let expected_pubkey = expected
+            .consensus_pubkey
+            .strip_prefix("0x")
+            .unwrap_or(&expected.consensus_pubkey)
+            .to_lowercase();
+        let Some(actual) = result
+            .validators
+            .iter()
+            .find(|v| hex::encode(&v.consensus_pubkey) == expected_pubkey)
+        else {
+            mismatches.push(format!(
+                "validator {}: consensusPubkey 0x{} not found in active validator set",
+                expected.moniker, expected_pubkey
+            ));
+            continue;
+        };
+
+        let mut compare = |field: &str, expected_value: String, actual_value: String| {
+            if expected_value != actual_value {
+                mismatches.push(format!(
+                    "validator {}: {} expected {}, got {}",
+                    expected.moniker, field, expected_value, actual_value
+                ));
+            }
+        };
+
+        compare(
+            "consensusPop",
+            expected
+                .consensus_pop
+                .strip_prefix("0x")
+                .unwrap_or(&expected.consensus_pop)
+                .to_lowercase(),
+            hex::encode(&actual.consensus_pop),
+        );
+        compare(
+            "votingPower",
+            parse_amount(&expected.voting_power)
+                .map_or_else(|_| expected.voting_power.clone(), |v| v.to_string()),
+            actual.voting_power.to_string(),
+        );
+        compare(
+            "networkAddresses",
+            normalize_network_address(&expected.network_addresses)
+                .unwrap_or_else(|_| expected.network_addresses.clone()),
+            actual
+                .network_addresses
+                .clone()
+                .unwrap_or_else(|| "<undecodable>".to_string()),
+        );
+        compare(
+            "fullnodeAddresses",
+            normalize_network_address(&expected.fullnode_addresses)
+                .unwrap_or_else(|_| expected.fullnode_addresses.clone()),
+            actual
+                .fullnode_addresses
+                .clone()
+                .unwrap_or_else(|| "<undecodable>".to_string()),
+        );
+    }
+
+    if mismatches.is_empty() {
+        info!("✅ Genesis state matches expected config");
+    } else {
+        for m in &mismatches {
+            error!("❌ Mismatch: {}", m);
+        }
+        result.success = false;
+        result.errors.extend(mismatches);
+    }
+}
+
+/// Compare system contract code in the genesis alloc against a bytecode directory.
+///
+/// Code differing only in the solc metadata trailer is a mismatch unless
+/// `ignore_metadata` is set, in which case it is listed separately.
+pub fn cross_check_bytecode(
+    result: &mut VerifyResult,
+    genesis_path: &str,
+    byte_code_dir: &str,
+    ignore_metadata: bool,
+) -> Result<()> {
+    let _span = info_span!("verification", step = "bytecode_cross_check").entered();
+    info!("Comparing system contract code against {}...", byte_code_dir);
+    let contracts = ContractsManifest::resolve(byte_code_dir, None)?;
+    contracts.preflight(byte_code_dir)?;
+    let mut deployed = HashMap::new();
+    stream_genesis_alloc(genesis_path, |addr, entry| {
+        if let (Some(code), true) = (&entry.code, contracts.contains_address(&addr)) {
+            let code = hex::decode(code.strip_prefix("0x").unwrap_or(code))
+                .context(format!("Invalid code at {:?}", addr))?;
+            deployed.insert(addr, code);
+        }
+        Ok(())
+    })?;
+
+    let mut mismatches = Vec::new();
+    for contract in &contracts.contracts {
+        let expected = load_runtime_bytecode(byte_code_dir, contract, &contracts.libraries)?;
+        let Some(actual) = deployed.get(&contract.address) else {
+            mismatches.push(format!(
+                "{} ({:?}) has no code in the genesis",
+                contract.name, contract.address
+            ));
+            continue;
+        };
+        match compare_code(actual, &expected) {
+            CodeComparison::Identical => {}
+            CodeComparison::EquivalentModuloMetadata if ignore_metadata => {
+                warn!(
+                    "⚠️ {} ({:?}) is equivalent modulo metadata",
+                    contract.name, contract.address
+                );
+                result.metadata_only_diffs.push(contract.name.clone());
+            }
+            CodeComparison::EquivalentModuloMetadata => mismatches.push(format!(
+                "{} ({:?}) code differs from the artifact in solc metadata only (pass --ignore-metadata to accept)",
+                contract.name, contract.address
+            )),
+            CodeComparison::Different => mismatches.push(format!(
+                "{} ({:?}) code differs from the artifact: {} bytes deployed, {} expected",
+                contract.name,
+                contract.address,
+                actual.len(),
+                expected.len()
+            )),
+        }
+    }
+
+    if mismatches.is_empty() {
+        info!("✅ System contract code matches {}", byte_code_dir);
+    } else {
+        for m in &mismatches {
+            error!("❌ Mismatch: {}", m);
+        }
+        result.success = false;
+        result.errors.extend(mismatches);
+    }
+    Ok(())
+}
+
 fn parse_u256_hex(s: &str) -> U256 {
     let s = s.strip_prefix("0x").unwrap_or(s);
     if s.is_empty() {
@@ -346,6 +1260,74 @@ fn parse_u256_hex(s: &str) -> U256 {
     U256::from_str_radix(s, 16).unwrap_or(U256::ZERO)
 }
 
+/// Verification outcome of one genesis file among several
+#[derive(Debug)]
+pub struct FileVerifyResult {
+    pub genesis_file: String,
+    /// `Err` when the file could not be verified at all (unreadable, bad JSON, ...)
+    pub result: Result<VerifyResult>,
+}
+
+/// Verify several genesis files concurrently, each against the same expected
+/// config, bytecode directory and interface manifest; results keep the order of `genesis_files`
+pub fn verify_genesis_files(
+    genesis_files: &[String],
+    expected_config: Option<&GenesisConfig>,
+    byte_code_dir: Option<&str>,
+    ignore_metadata: bool,
+    interfaces: Option<&[InterfaceRequirement]>,
+    options: &VerifyOptions,
+) -> Vec<FileVerifyResult> {
+    genesis_files
+        .par_iter()
+        .map(|genesis_file| {
+            let _span = info_span!("genesis", file = genesis_file.as_str()).entered();
+            let result = verify_genesis_file_with(genesis_file, options).and_then(|mut result| {
+                if let Some(config) = expected_config {
+                    cross_check_with_config(&mut result, config);
+                }
+                if let Some(byte_code_dir) = byte_code_dir {
+                    cross_check_bytecode(&mut result, genesis_file, byte_code_dir, ignore_metadata)?;
+                }
+                if let Some(requirements) = interfaces {
+                    cross_check_interfaces(&mut result, genesis_file, requirements, options)?;
+                }
+                Ok(result)
+            });
+            FileVerifyResult {
+                genesis_file: genesis_file.clone(),
+                result,
+            }
+        })
+        .collect()
+}
+
+/// Print one line per file and the overall pass count
+pub fn print_aggregate_summary(results: &[FileVerifyResult]) {
+    let passed = results
+        .iter()
+        .filter(|r| r.result.as_ref().is_ok_and(|r| r.success))
+        .count();
+    println!("\n========================================");
+    println!("   GENESIS VERIFICATION: {}/{} PASSED", passed, results.len());
+    println!("========================================\n");
+    for file in results {
+        match &file.result {
+            Ok(result) if result.success => println!(
+                "✅ {} ({} validators)",
+                file.genesis_file, result.validator_count
+            ),
+            Ok(result) => println!(
+                "❌ {} ({} error(s))",
+                file.genesis_file,
+                result.errors.len()
+            ),
+            Err(e) => println!("❌ {} (could not verify: {:#})", file.genesis_file, e),
+        }
+    }
+    println!("\n========================================\n");
+}
+
 /// Print verification summary
 pub fn print_verify_summary(result: &VerifyResult) {
     println!("\n========================================");
@@ -361,6 +1343,15 @@ pub fn print_verify_summary(result: &VerifyResult) {
             println!("Epoch Interval: {} micros ({:.4} hours)", micros, hours);
         }
 
+        if !result.node_configs.is_empty() {
+            println!("On-chain configs:");
+            for read in &result.node_configs {
+                if let Ok(summary) = &read.outcome {
+                    println!("  {}: {}", read.view, summary);
+                }
+            }
+        }
+
         println!("Validators: {}", result.validator_count);
         println!("\nValidator Details:");
         for (i, v) in result.validators.iter().enumerate() {
@@ -370,22 +1361,26 @@ pub fn print_verify_summary(result: &VerifyResult) {
                 v.voting_power, v.validator_index
             );
             println!(
-                "      Network Addrs: {}, Fullnode Addrs: {}",
-                if v.has_network_addresses {
-                    "✓"
-                } else {
-                    "✗"
-                },
-                if v.has_fullnode_addresses {
-                    "✓"
-                } else {
-                    "✗"
-                }
+                "      Network Addrs: {}",
+                v.network_addresses.as_deref().unwrap_or("✗")
+            );
+            println!(
+                "      Fullnode Addrs: {}",
+                v.fullnode_addresses.as_deref().unwrap_or("✗")
+            );
+        }
+        if !result.metadata_only_diffs.is_empty() {
+            println!(
+                "\nEquivalent modulo metadata: {}",
+                result.metadata_only_diffs.join(", ")
             );
         }
         println!("\n🎉 Genesis is compatible with gravity-reth!");
     } else {
         println!("❌ STATUS: FAILED\n");
+        if let Some(generation) = result.abi_generation {
+            println!("Contract generation: {}\n", generation.describe());
+        }
         println!("Errors:");
         for err in &result.errors {
             println!("  - {}", err);
@@ -0,0 +1,164 @@
+//! Vesting contracts deployed and funded at genesis
+//!
+//! Each `vesting.schedules` entry becomes a CREATE transaction that runs after
+//! `Genesis.initialize` and the `postGenesisCalls`, sent by [`VESTING_DEPLOYER`]
+//! with the schedule's amount as value, so the contract starts out holding the
+//! locked funds. Schedule `i` lands at `VESTING_DEPLOYER.create(i)`; the
+//! deployer stays in the alloc with a zero balance and its nonce advanced past
+//! the schedules.
+//!
+//! The contract is any artifact in the bytecode directory; its constructor
+//! inputs are filled by name (leading underscores and case ignored) from the
+//! schedule: `beneficiary`, `start`/`startTimestamp`, `cliff`/`cliffSeconds`,
+//! `duration`/`durationSeconds`, `revocable` and `revoker`/`owner`/`admin`.
+//! Times are passed in seconds, as OpenZeppelin's `VestingWallet(beneficiary,
+//! startTimestamp, durationSeconds)` expects. A constructor input that matches
+//! none of these is an error rather than a silent zero.
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::address;
+use anyhow::{Context, Result};
+use revm_primitives::{Address, Bytes, TxEnv, TxKind, U256};
+use serde_json::{json, Value};
+
+use crate::{
+    abi::{canonical_type, creation_code, read_artifact},
+    dynamic_abi::coerce_json,
+    genesis::{parse_amount, GenesisConfig, VestingSchedule},
+};
+
+/// Sender of the vesting deployments, funded with their total amount
+pub const VESTING_DEPLOYER: Address = address!("00000000000000000000000000000000005E5700");
+
+/// Address schedule `index` is deployed at
+pub fn vesting_address(index: usize) -> Address {
+    VESTING_DEPLOYER.create(index as u64)
+}
+
+/// Deployed address of each schedule, in config order
+pub fn vesting_addresses(config: &GenesisConfig) -> Vec<Address> {
+    let count = config.vesting.as_ref().map_or(0, |v| v.schedules.len());
+    (0..count).map(vesting_address).collect()
+}
+
+/// Sum of all schedule amounts, sent by [`VESTING_DEPLOYER`]
+pub fn total_vesting(config: &GenesisConfig) -> U256 {
+    config
+        .vesting
+        .iter()
+        .flat_map(|v| &v.schedules)
+        .map(|s| parse_amount(&s.amount).unwrap_or_default())
+        .fold(U256::ZERO, |sum, amount| sum.saturating_add(amount))
+}
+
+/// Constructor argument for the input called `name`
+fn constructor_arg(
+    name: &str,
+    schedule: &VestingSchedule,
+    config: &GenesisConfig,
+) -> Result<Value> {
+    let secs = |micros: u64| json!(micros / 1_000_000);
+    let arg = match name.trim_start_matches('_').to_lowercase().as_str() {
+        "beneficiary" => json!(schedule.beneficiary),
+        "start" | "starttimestamp" | "startseconds" => {
+            let start = schedule
+                .start_micros(config)
+                .context("no startMicros and no genesisTimestampSecs")?;
+            secs(start)
+        }
+        "cliff" | "cliffseconds" | "cliffduration" => secs(schedule.cliff_micros),
+        "duration" | "durationseconds" => secs(schedule.duration_micros),
+        "revocable" => json!(schedule.revocable),
+        "revoker" | "owner" | "admin" => match (&schedule.revoker, schedule.revocable) {
+            (Some(revoker), true) => json!(revoker),
+            _ => json!(Address::ZERO.to_string()),
+        },
+        _ => anyhow::bail!("constructor input {} has no vesting schedule field", name),
+    };
+    Ok(arg)
+}
+
+/// One CREATE transaction per schedule, in config order
+pub fn deployment_txns(byte_code_dir: &str, config: &GenesisConfig) -> Result<Vec<TxEnv>> {
+    let Some(vesting) = &config.vesting else {
+        return Ok(Vec::new());
+    };
+    let contract = vesting.contract.as_str();
+    let artifact = read_artifact(byte_code_dir, contract)?;
+    let code = creation_code(&artifact, contract)?;
+    let inputs: Vec<Value> = artifact
+        .get("abi")
+        .and_then(Value::as_array)
+        .and_then(|abi| {
+            abi.iter()
+                .find(|item| item.get("type").and_then(Value::as_str) == Some("constructor"))
+        })
+        .and_then(|constructor| constructor.get("inputs"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let names: Vec<String> = inputs
+        .iter()
+        .map(|input| {
+            input
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .trim_start_matches('_')
+                .to_lowercase()
+        })
+        .collect();
+    let revocation = names
+        .iter()
+        .any(|n| matches!(n.as_str(), "revocable" | "revoker" | "owner" | "admin"));
+
+    vesting
+        .schedules
+        .iter()
+        .enumerate()
+        .map(|(i, schedule)| {
+            let label = format!("vesting.schedules[{}]", i);
+            if schedule.revocable && !revocation {
+                anyhow::bail!(
+                    "{}: revocable, but the {} constructor takes no revoker",
+                    label,
+                    contract
+                );
+            }
+            let has_cliff = names
+                .iter()
+                .any(|n| matches!(n.as_str(), "cliff" | "cliffseconds" | "cliffduration"));
+            if schedule.cliff_micros > 0 && !has_cliff {
+                anyhow::bail!(
+                    "{}: cliffMicros is set, but the {} constructor takes no cliff",
+                    label,
+                    contract
+                );
+            }
+            let value = parse_amount(&schedule.amount)
+                .map_err(|e| anyhow::anyhow!("{}: invalid amount: {}", label, e))?;
+            let args = inputs
+                .iter()
+                .zip(&names)
+                .map(|(input, name)| {
+                    let ty = canonical_type(input);
+                    let ty = DynSolType::parse(&ty)
+                        .context(format!("{}: unsupported type {}", label, ty))?;
+                    let arg = constructor_arg(name, schedule, config)
+                        .context(format!("{}: {}", label, contract))?;
+                    coerce_json(&ty, &arg).context(format!("{}: constructor input {}", label, name))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let encoded = DynSolValue::Tuple(args).abi_encode_params();
+            Ok(TxEnv {
+                caller: VESTING_DEPLOYER,
+                gas_limit: u64::MAX,
+                gas_price: U256::ZERO,
+                transact_to: TxKind::Create,
+                value,
+                data: Bytes::from([code.as_slice(), &encoded].concat()),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
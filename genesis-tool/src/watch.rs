@@ -0,0 +1,241 @@
+//! `generate --watch`
+//!
+//! Polls the bytecode directory, the config file with its `$include` fragments
+//! and the other files `generate` reads (`--validators-csv`, `--exemptions`,
+//! ...) and reruns `generate` in a child process whenever they change, with `--resume` so execution is skipped
+//! while the code and genesis transactions stay the same. Running it as a child
+//! keeps a failed generation, which may panic and exit, from ending the watch.
+//! After each run the output files are compared with the previous run's and a
+//! short summary of what changed is printed.
+
+use crate::genesis;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+use tracing::{info, warn};
+
+/// How often inputs are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Output file whose accounts are compared one by one
+const ACCOUNTS_FILE: &str = "genesis_accounts.json";
+
+/// Size and modification time of every file under the watched paths
+type InputSnapshot = BTreeMap<PathBuf, (u64, Option<SystemTime>)>;
+
+/// What a `generate` run reads besides the bytecode directory and config file
+#[derive(Debug, Clone, Default)]
+pub struct WatchSources {
+    /// `--set` overrides, which may point `replayBroadcast` at another file
+    pub config_overrides: Vec<String>,
+    /// File arguments: `--validators-csv`, `--exemptions`, ...
+    pub files: Vec<String>,
+}
+
+/// `fixed` plus the files the config pulls in with its overrides applied;
+/// `None` while the config does not load, in which case the previous paths
+/// stay watched
+fn watched_paths(fixed: &[String], config_file: &str, sources: &WatchSources) -> Option<Vec<String>> {
+    let files = match genesis::config_input_files(config_file, &sources.config_overrides) {
+        Ok(files) => files,
+        Err(e) => {
+            warn!("Could not collect the files {} pulls in: {:#}", config_file, e);
+            return None;
+        }
+    };
+    let mut paths = fixed.to_vec();
+    for path in files.iter().map(|path| path.display().to_string()) {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    Some(paths)
+}
+
+fn snapshot_into(path: &Path, skip: &Path, snapshot: &mut InputSnapshot) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+    if metadata.is_dir() {
+        if path.canonicalize().is_ok_and(|path| path == skip) {
+            return;
+        }
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            snapshot_into(&entry.path(), skip, snapshot);
+        }
+    } else {
+        snapshot.insert(path.to_path_buf(), (metadata.len(), metadata.modified().ok()));
+    }
+}
+
+/// Files under `paths`, leaving out the directory `skip` (the output directory,
+/// which generation itself writes)
+fn snapshot_inputs(paths: &[String], skip: &Path) -> InputSnapshot {
+    let mut snapshot = InputSnapshot::new();
+    for path in paths {
+        snapshot_into(Path::new(path), skip, &mut snapshot);
+    }
+    snapshot
+}
+
+/// Block until the files under `paths` differ from `previous` and then stay
+/// unchanged for one poll interval, so a build writing many files triggers once
+fn wait_for_change(paths: &[String], skip: &Path, previous: &InputSnapshot) -> InputSnapshot {
+    let mut current = snapshot_inputs(paths, skip);
+    while &current == previous {
+        thread::sleep(POLL_INTERVAL);
+        current = snapshot_inputs(paths, skip);
+    }
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let settled = snapshot_inputs(paths, skip);
+        if settled == current {
+            return settled;
+        }
+        current = settled;
+    }
+}
+
+/// Hashes of the output files, and of each account for the accounts file
+#[derive(Debug, Default)]
+struct OutputSnapshot {
+    files: BTreeMap<String, (u64, String)>,
+    accounts: BTreeMap<String, String>,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    revm_primitives::hex::encode(Sha256::digest(bytes))
+}
+
+fn snapshot_outputs(output_dir: &str) -> OutputSnapshot {
+    let mut snapshot = OutputSnapshot::default();
+    let Ok(entries) = fs::read_dir(output_dir) else {
+        return snapshot;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Ok(content) = fs::read(entry.path()) else {
+            continue;
+        };
+        if name == ACCOUNTS_FILE {
+            if let Ok(Value::Object(accounts)) = serde_json::from_slice::<Value>(&content) {
+                snapshot.accounts = accounts
+                    .iter()
+                    .map(|(address, account)| {
+                        (address.clone(), sha256_hex(account.to_string().as_bytes()))
+                    })
+                    .collect();
+            }
+        }
+        snapshot
+            .files
+            .insert(name, (content.len() as u64, sha256_hex(&content)));
+    }
+    snapshot
+}
+
+/// One line per output file that changed between two runs
+fn describe_changes(before: &OutputSnapshot, after: &OutputSnapshot) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (name, (size, hash)) in &after.files {
+        match before.files.get(name) {
+            None => lines.push(format!("{}: new ({} bytes)", name, size)),
+            Some((_, old_hash)) if old_hash == hash => {}
+            Some((old_size, _)) if name == ACCOUNTS_FILE => {
+                let added = after.accounts.keys().filter(|a| !before.accounts.contains_key(*a)).count();
+                let removed = before.accounts.keys().filter(|a| !after.accounts.contains_key(*a)).count();
+                let changed = after
+                    .accounts
+                    .iter()
+                    .filter(|(a, hash)| before.accounts.get(*a).is_some_and(|old| old != *hash))
+                    .count();
+                lines.push(format!(
+                    "{}: {} account(s) changed, {} added, {} removed ({} -> {} bytes)",
+                    name, changed, added, removed, old_size, size
+                ));
+            }
+            Some((old_size, _)) => {
+                lines.push(format!("{}: changed ({} -> {} bytes)", name, old_size, size))
+            }
+        }
+    }
+    for name in before.files.keys().filter(|name| !after.files.contains_key(*name)) {
+        lines.push(format!("{}: removed", name));
+    }
+    lines
+}
+
+/// Rerun the current `generate` invocation, without `--watch` and with
+/// `--resume`, each time the bytecode directory, the config or a file it reads
+/// changes
+pub fn watch_generate(
+    byte_code_dir: &str,
+    config_file: &str,
+    output_dir: &str,
+    sources: &WatchSources,
+) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the genesis-tool executable")?;
+    let mut args: Vec<String> = std::env::args().skip(1).filter(|arg| arg != "--watch").collect();
+    if !args.iter().any(|arg| arg == "--resume") {
+        args.push("--resume".to_string());
+    }
+    if !Path::new(config_file).is_file() {
+        anyhow::bail!("Config file {} not found", config_file);
+    }
+    fs::create_dir_all(output_dir).context(format!("Failed to create {}", output_dir))?;
+    let skip = Path::new(output_dir)
+        .canonicalize()
+        .context(format!("Failed to resolve {}", output_dir))?;
+
+    let config_path = Path::new(config_file)
+        .canonicalize()
+        .context(format!("Failed to resolve {}", config_file))?;
+    let mut fixed = vec![byte_code_dir.to_string(), config_path.display().to_string()];
+    fixed.extend(sources.files.iter().cloned());
+    let mut watched = watched_paths(&fixed, config_file, sources).unwrap_or_else(|| fixed.clone());
+    let mut inputs = snapshot_inputs(&watched, &skip);
+    let mut outputs = snapshot_outputs(output_dir);
+    loop {
+        let status = Command::new(&exe)
+            .args(&args)
+            .status()
+            .context(format!("Failed to run {}", exe.display()))?;
+        if status.success() {
+            info!("Genesis regenerated in {}", output_dir);
+        } else {
+            warn!(
+                "generate exited with {}; fix the inputs and save to retry",
+                status.code().map_or("a signal".to_string(), |code| format!("code {}", code))
+            );
+        }
+
+        let current = snapshot_outputs(output_dir);
+        let changes = describe_changes(&outputs, &current);
+        if changes.is_empty() {
+            println!("Outputs unchanged");
+        }
+        for change in changes {
+            println!("{}", change);
+        }
+        outputs = current;
+
+        // Collected after each run, as an edit may add or drop an include
+        if let Some(paths) = watched_paths(&fixed, config_file, sources) {
+            if paths != watched {
+                watched = paths;
+                inputs = snapshot_inputs(&watched, &skip);
+            }
+        }
+        info!("Watching {} for changes", watched.join(", "));
+        inputs = wait_for_change(&watched, &skip, &inputs);
+        info!("Inputs changed; regenerating");
+    }
+}
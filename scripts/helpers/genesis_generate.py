@@ -63,6 +63,19 @@ def create_genesis_json(
             new_ts = hex(config["genesisTimestampSecs"])
             genesis["timestamp"] = new_ts
             print(f"🔧 Overriding timestamp: {old_ts} -> {new_ts}")
+        # EIP-1559 header fields and chain config (validated by genesis-tool)
+        for key in ("baseFeePerGas", "gasLimit"):
+            if config.get(key) is not None:
+                old_value = genesis.get(key, "unset")
+                genesis[key] = hex(config[key])
+                print(f"🔧 Overriding {key}: {old_value} -> {genesis[key]}")
+        for key, activation in (config.get("hardforks") or {}).items():
+            old_value = genesis["config"].get(key, "unset")
+            genesis["config"][key] = activation
+            print(f"🔧 Setting hardfork {key}: {old_value} -> {activation}")
+        if config.get("baseFeeParams") is not None:
+            genesis["config"]["baseFeeParams"] = config["baseFeeParams"]
+            print(f"🔧 Setting baseFeeParams: {config['baseFeeParams']}")
     
     # Merge account allocation into genesis alloc field
     print("🔧 Merging account allocation into genesis...")
@@ -121,6 +134,8 @@ def create_genesis_json(
     print(f"  - Total accounts: {len(genesis['alloc'])}")
     print(f"  - Chain ID: {genesis['config']['chainId']}")
     print(f"  - Gas limit: {genesis['gasLimit']}")
+    if "baseFeePerGas" in genesis:
+        print(f"  - Base fee: {int(genesis['baseFeePerGas'], 16)} wei")
     print(f"  - Timestamp: {genesis['timestamp']}")
     
     # Count accounts with code (contracts)
@@ -3,7 +3,7 @@
 #
 # Source this file from any hardfork verification script.
 # Provides: pass(), fail(), skip(), check_call(), check_reverts(), check_exists(),
-#           normalize_cast_output(), verify_codehashes()
+#           normalize_cast_output(), verify_codehashes(), check_activation()
 
 # ── Counters ──────────────────────────────────────────────────────────
 PASS=0
@@ -31,6 +31,42 @@ normalize_cast_output() {
     echo "$1" | sed 's/\[.*\]//g' | tr -d ' \n' | tr '[:upper:]' '[:lower:]'
 }
 
+# ── Fork activation ──────────────────────────────────────────────────
+
+# check_activation: Check the chain is past the hardfork's activation, read
+# from the genesis chain config (`<name>Block` or `<name>Time`, as written from
+# the genesis config's `hardforks`).
+#   check_activation "genesis.json" "gamma"
+check_activation() {
+    local genesis="$1" name="$2" activation kind head
+    activation=$(python3 -c '
+import json, sys
+config = json.load(open(sys.argv[1])).get("config", {})
+for kind in ("Block", "Time"):
+    if sys.argv[2] + kind in config:
+        print(kind, config[sys.argv[2] + kind])
+        break
+' "$genesis" "$name")
+    if [ -z "$activation" ]; then
+        skip "${name}: no ${name}Block or ${name}Time in ${genesis}"
+        return
+    fi
+    read -r kind activation <<< "$activation"
+    if [ "$kind" = "Block" ]; then
+        head=$(cast block-number --rpc-url "$RPC_URL" 2>/dev/null || echo "")
+    else
+        head=$(cast block latest --field timestamp --rpc-url "$RPC_URL" 2>/dev/null || echo "")
+    fi
+    head=$(normalize_cast_output "$head")
+    if [ -z "$head" ]; then
+        fail "${name}: could not read the chain head"
+    elif [ "$head" -ge "$activation" ]; then
+        pass "${name} activated (${kind,,} ${activation}, head at ${head})"
+    else
+        fail "${name} not active yet (${kind,,} ${activation}, head at ${head}); checks below see pre-fork code"
+    fi
+}
+
 # ── Functional smoke test helpers ────────────────────────────────────
 
 # check_call: Call a view function and compare result to expected value.
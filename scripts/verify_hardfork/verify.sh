@@ -11,6 +11,10 @@
 # Examples:
 #   bash scripts/verify_hardfork/verify.sh gamma http://localhost:8545
 #   bash scripts/verify_hardfork/verify.sh delta https://testnet-rpc.example.com
+#   GENESIS_FILE=genesis.json bash scripts/verify_hardfork/verify.sh gamma http://localhost:8545
+#
+# With GENESIS_FILE set, the chain head is first checked against the fork's
+# activation in the genesis chain config (<name>Block or <name>Time).
 #
 # Prerequisites:
 #   1. Generate expected hashes first:
@@ -78,6 +82,11 @@ echo ""
 echo "RPC: ${RPC_URL}"
 echo ""
 
+if [ -n "${GENESIS_FILE:-}" ]; then
+    check_activation "$GENESIS_FILE" "$HARDFORK_NAME"
+    echo ""
+fi
+
 verify_system_contracts
 
 if [ "${VERIFY_STAKEPOOL:-false}" = true ]; then
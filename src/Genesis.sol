@@ -26,6 +26,7 @@ import { Governance } from "./governance/Governance.sol";
 import { NativeOracle } from "./oracle/NativeOracle.sol";
 import { JWKManager, IJWKManager } from "./oracle/jwk/JWKManager.sol";
 import { OracleTaskConfig } from "./oracle/OracleTaskConfig.sol";
+import { OnDemandOracleTaskConfig } from "./oracle/ondemand/OnDemandOracleTaskConfig.sol";
 import { GBridgeReceiver } from "./oracle/evm/native_token_bridge/GBridgeReceiver.sol";
 
 /// @title Genesis
@@ -67,6 +68,12 @@ contract Genesis {
         bytes config;
     }
 
+    struct OnDemandTaskParams {
+        uint32 sourceType;
+        uint256 sourceId;
+        bytes config;
+    }
+
     struct BridgeConfig {
         bool deploy;
         address trustedBridge;
@@ -77,6 +84,7 @@ contract Genesis {
         uint32[] sourceTypes;
         address[] callbacks;
         OracleTaskParams[] tasks;
+        OnDemandTaskParams[] onDemandTasks;
         BridgeConfig bridgeConfig;
     }
 
@@ -128,6 +136,15 @@ contract Genesis {
 
     bool private _isInitialized;
 
+    /// @notice Set by `beginInitialize` while a chunked genesis is in progress
+    bool private _isInitializing;
+
+    /// @notice Validators registered so far by a chunked genesis
+    uint256 private _batchedValidatorCount;
+
+    /// @notice `initialLockedUntilMicros` of a chunked genesis, for later batches
+    uint64 private _batchLockedUntilMicros;
+
     // ========================================================================
     // INITIALIZATION
     // ========================================================================
@@ -147,10 +164,77 @@ contract Genesis {
         // We'll allow SYSTEM_CALLER.
         requireAllowed(SystemAddresses.SYSTEM_CALLER);
 
+        if (_isInitialized || _isInitializing) {
+            revert Errors.AlreadyInitialized();
+        }
+
+        // 1-3. Initialize Configs, Governance and Oracles
+        _initializeSystem(params);
+
+        // 4. Create Stake Pools & Prepare Validator Data
+        GenesisValidator[] memory genesisValidators =
+            _createPoolsAndValidators(params.validators, params.initialLockedUntilMicros);
+
+        // 5-8. Initialize Validator Management and the epoch machinery
+        _finalize(genesisValidators, params.validators.length);
+    }
+
+    /// @notice Start a chunked genesis, for validator sets too large for one transaction
+    /// @dev Runs everything `initialize` does up to validator registration and registers
+    ///      `params.validators` as the first batch. Further batches go through
+    ///      `addValidatorBatch`, and `finishInitialize` completes genesis. Each call is
+    ///      funded with the stake of the validators it registers.
+    function beginInitialize(
+        GenesisInitParams calldata params
+    ) external payable {
+        requireAllowed(SystemAddresses.SYSTEM_CALLER);
+
+        if (_isInitialized || _isInitializing) {
+            revert Errors.AlreadyInitialized();
+        }
+        _isInitializing = true;
+        _batchLockedUntilMicros = params.initialLockedUntilMicros;
+
+        _initializeSystem(params);
+        _addValidatorBatch(params.validators);
+    }
+
+    /// @notice Register the next batch of validators of a chunked genesis
+    /// @dev Validators are appended to the active set in call order
+    function addValidatorBatch(
+        InitialValidator[] calldata validators
+    ) external payable {
+        requireAllowed(SystemAddresses.SYSTEM_CALLER);
+        _requireInitializing();
+
+        _addValidatorBatch(validators);
+    }
+
+    /// @notice Complete a chunked genesis once every validator batch is registered
+    function finishInitialize() external {
+        requireAllowed(SystemAddresses.SYSTEM_CALLER);
+        _requireInitializing();
+
+        _isInitializing = false;
+        _finalize(new GenesisValidator[](0), _batchedValidatorCount);
+    }
+
+    // ========================================================================
+    // INTERNAL FUNCTIONS
+    // ========================================================================
+
+    function _requireInitializing() internal view {
         if (_isInitialized) {
             revert Errors.AlreadyInitialized();
         }
+        if (!_isInitializing) {
+            revert Errors.GenesisNotStarted();
+        }
+    }
 
+    function _initializeSystem(
+        GenesisInitParams calldata params
+    ) internal {
         // 1. Initialize Configs
         _initializeConfigs(params);
 
@@ -160,16 +244,27 @@ contract Genesis {
 
         // 3. Initialize Oracles
         _initializeOracles(params.oracleConfig, params.jwkConfig);
+    }
 
-        // 4. Create Stake Pools & Prepare Validator Data
-        GenesisValidator[] memory genesisValidators =
-            _createPoolsAndValidators(params.validators, params.initialLockedUntilMicros);
+    function _addValidatorBatch(
+        InitialValidator[] calldata validators
+    ) internal {
+        GenesisValidator[] memory genesisValidators = _createPoolsAndValidators(validators, _batchLockedUntilMicros);
+        ValidatorManagement(SystemAddresses.VALIDATOR_MANAGER).addGenesisValidators(genesisValidators);
+        _batchedValidatorCount += validators.length;
+    }
 
+    /// @param genesisValidators Validators not yet registered, appended after any batches
+    /// @param validatorCount Size of the whole genesis validator set
+    function _finalize(
+        GenesisValidator[] memory genesisValidators,
+        uint256 validatorCount
+    ) internal {
         // 5. Initialize Validator Management
         ValidatorManagement(SystemAddresses.VALIDATOR_MANAGER).initialize(genesisValidators);
 
         // 6. Initialize Performance Tracker (before Reconfiguration, since first epoch needs tracking)
-        ValidatorPerformanceTracker(SystemAddresses.PERFORMANCE_TRACKER).initialize(params.validators.length);
+        ValidatorPerformanceTracker(SystemAddresses.PERFORMANCE_TRACKER).initialize(validatorCount);
 
         // 7. Initialize Reconfiguration
         Reconfiguration(SystemAddresses.RECONFIGURATION).initialize();
@@ -178,13 +273,9 @@ contract Genesis {
         Blocker(SystemAddresses.BLOCK).initialize();
 
         _isInitialized = true;
-        emit GenesisCompleted(params.validators.length, uint64(block.timestamp));
+        emit GenesisCompleted(validatorCount, uint64(block.timestamp));
     }
 
-    // ========================================================================
-    // INTERNAL FUNCTIONS
-    // ========================================================================
-
     function _initializeConfigs(
         GenesisInitParams calldata params
     ) internal {
@@ -269,6 +360,13 @@ contract Genesis {
             OracleTaskConfig(SystemAddresses.ORACLE_TASK_CONFIG)
                 .setTask(task.sourceType, task.sourceId, task.taskName, task.config);
         }
+
+        // Set On-Demand Task Types
+        for (uint256 i = 0; i < oracleConfig.onDemandTasks.length; i++) {
+            OnDemandTaskParams calldata task = oracleConfig.onDemandTasks[i];
+            OnDemandOracleTaskConfig(SystemAddresses.ON_DEMAND_ORACLE_TASK_CONFIG)
+                .setTaskType(task.sourceType, task.sourceId, task.config);
+        }
     }
 
     function _createPoolsAndValidators(
@@ -342,6 +342,9 @@ library Errors {
     /// @notice Contract has already been initialized
     error AlreadyInitialized();
 
+    /// @notice A chunked genesis step was called before `Genesis.beginInitialize`
+    error GenesisNotStarted();
+
     /// @notice Array lengths do not match
     /// @param expected Expected length
     /// @param actual Actual length
@@ -40,7 +40,7 @@ interface IOnDemandOracleTaskConfig {
     // ========================================================================
 
     /// @notice Create or update an on-demand task type
-    /// @dev Only callable by GOVERNANCE. Config cannot be empty.
+    /// @dev Only callable by GENESIS (seeding) or GOVERNANCE. Config cannot be empty.
     /// @param sourceType The source type
     /// @param sourceId The source identifier
     /// @param config The task type configuration bytes
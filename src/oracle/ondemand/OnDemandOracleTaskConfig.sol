@@ -29,7 +29,7 @@ contract OnDemandOracleTaskConfig is IOnDemandOracleTaskConfig {
         uint256 sourceId,
         bytes calldata config
     ) external {
-        requireAllowed(SystemAddresses.GOVERNANCE);
+        requireAllowed(SystemAddresses.GENESIS, SystemAddresses.GOVERNANCE);
 
         if (config.length == 0) {
             revert Errors.EmptyConfig();
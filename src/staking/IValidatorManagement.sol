@@ -128,6 +128,15 @@ interface IValidatorManagement {
         GenesisValidator[] calldata validators
     ) external;
 
+    /// @notice Add a batch of genesis validators ahead of `initialize`
+    /// @dev Only callable by GENESIS before initialization. Lets Genesis register a
+    ///      large validator set across several transactions; `initialize` appends
+    ///      its own validators after every batch and finalizes the set.
+    /// @param validators Array of genesis validator info, appended in order
+    function addGenesisValidators(
+        GenesisValidator[] calldata validators
+    ) external;
+
     /// @notice Check if the contract has been initialized
     /// @return True if initialized
     function isInitialized() external view returns (bool);
@@ -131,17 +131,38 @@ contract ValidatorManagement is IValidatorManagement {
             revert Errors.AlreadyInitialized();
         }
 
-        uint256 length = validators.length;
-        uint256 totalPower = 0;
+        // Validators added in earlier genesis batches keep their indices and power
+        uint256 offset = _activeValidators.length;
+        uint256 totalPower = totalVotingPower;
 
-        for (uint256 i = 0; i < length; i++) {
-            totalPower += _initializeGenesisValidator(validators[i], uint64(i));
+        for (uint256 i = 0; i < validators.length; i++) {
+            totalPower += _initializeGenesisValidator(validators[i], uint64(offset + i));
         }
 
         totalVotingPower = totalPower;
         _initialized = true;
 
-        emit ValidatorManagementInitialized(length, totalPower);
+        emit ValidatorManagementInitialized(_activeValidators.length, totalPower);
+    }
+
+    /// @inheritdoc IValidatorManagement
+    function addGenesisValidators(
+        GenesisValidator[] calldata validators
+    ) external {
+        requireAllowed(SystemAddresses.GENESIS);
+
+        if (_initialized) {
+            revert Errors.AlreadyInitialized();
+        }
+
+        uint256 offset = _activeValidators.length;
+        uint256 addedPower = 0;
+
+        for (uint256 i = 0; i < validators.length; i++) {
+            addedPower += _initializeGenesisValidator(validators[i], uint64(offset + i));
+        }
+
+        totalVotingPower += addedPower;
     }
 
     /// @notice Initialize a single genesis validator record
@@ -123,8 +123,9 @@ contract GenesisTest is Test {
         address[] memory callbacks = new address[](1);
         callbacks[0] = SystemAddresses.JWK_MANAGER;
         Genesis.OracleTaskParams[] memory tasks = new Genesis.OracleTaskParams[](0);
+        Genesis.OnDemandTaskParams[] memory onDemandTasks = new Genesis.OnDemandTaskParams[](0);
         Genesis.BridgeConfig memory bridgeConfig = Genesis.BridgeConfig(false, address(0), 0);
-        params.oracleConfig = Genesis.OracleInitParams(sourceTypes, callbacks, tasks, bridgeConfig);
+        params.oracleConfig = Genesis.OracleInitParams(sourceTypes, callbacks, tasks, onDemandTasks, bridgeConfig);
 
         // JWK Config
         bytes[] memory issuers = new bytes[](1);
@@ -205,11 +206,92 @@ contract GenesisTest is Test {
         Governance(SystemAddresses.GOVERNANCE).initialize(address(0));
     }
 
+    function test_ChunkedGenesis_RegistersEveryBatch() public {
+        Genesis.GenesisInitParams memory params = _genesisParams(makeAddr("governanceOwner"));
+        params.validators = _validatorBatch(0, 2);
+
+        vm.deal(SystemAddresses.GENESIS, 1000 ether);
+        vm.startPrank(SystemAddresses.SYSTEM_CALLER);
+        genesis.beginInitialize(params);
+        genesis.addValidatorBatch(_validatorBatch(2, 2));
+        genesis.addValidatorBatch(_validatorBatch(4, 1));
+
+        // Nothing past validator registration runs until the last batch is in
+        assertFalse(ValidatorManagement(SystemAddresses.VALIDATOR_MANAGER).isInitialized());
+
+        genesis.finishInitialize();
+        vm.stopPrank();
+
+        ValidatorManagement validatorManager = ValidatorManagement(SystemAddresses.VALIDATOR_MANAGER);
+        assertTrue(validatorManager.isInitialized());
+        assertEq(validatorManager.getActiveValidatorCount(), 5);
+        assertEq(validatorManager.getTotalVotingPower(), 5 * 200 ether);
+        assertTrue(Governance(SystemAddresses.GOVERNANCE).isInitialized());
+    }
+
+    function test_RevertWhen_AddValidatorBatchBeforeBeginInitialize() public {
+        vm.prank(SystemAddresses.SYSTEM_CALLER);
+        vm.expectRevert(Errors.GenesisNotStarted.selector);
+        genesis.addValidatorBatch(_validatorBatch(0, 1));
+    }
+
+    function test_RevertWhen_InitializeDuringChunkedGenesis() public {
+        Genesis.GenesisInitParams memory params = _genesisParams(makeAddr("governanceOwner"));
+
+        vm.deal(SystemAddresses.GENESIS, 1000 ether);
+        vm.startPrank(SystemAddresses.SYSTEM_CALLER);
+        genesis.beginInitialize(params);
+        vm.expectRevert(Errors.AlreadyInitialized.selector);
+        genesis.initialize(params);
+        vm.stopPrank();
+    }
+
+    function test_RevertWhen_AddValidatorBatchAfterFinishInitialize() public {
+        _runGenesis(makeAddr("governanceOwner"));
+
+        vm.prank(SystemAddresses.SYSTEM_CALLER);
+        vm.expectRevert(Errors.AlreadyInitialized.selector);
+        genesis.addValidatorBatch(_validatorBatch(1, 1));
+    }
+
     // Helper to run a minimal Genesis flow with a configurable governance owner.
     function _runGenesis(
         address governanceOwner
     ) internal {
-        Genesis.GenesisInitParams memory params;
+        Genesis.GenesisInitParams memory params = _genesisParams(governanceOwner);
+
+        vm.deal(SystemAddresses.GENESIS, 1000 ether);
+        vm.prank(SystemAddresses.SYSTEM_CALLER);
+        genesis.initialize(params);
+    }
+
+    // Validators `first` to `first + count - 1`, each with a distinct key and 200 ether of stake.
+    function _validatorBatch(
+        uint256 first,
+        uint256 count
+    ) internal returns (Genesis.InitialValidator[] memory validators) {
+        validators = new Genesis.InitialValidator[](count);
+        for (uint256 i; i < count; i++) {
+            string memory id = vm.toString(first + i);
+            validators[i] = Genesis.InitialValidator({
+                operator: makeAddr(string.concat("op", id)),
+                owner: makeAddr(string.concat("ow", id)),
+                staker: makeAddr(string.concat("st", id)),
+                stakeAmount: 200 ether,
+                moniker: string.concat("v", id),
+                consensusPubkey: abi.encodePacked(keccak256(bytes(id)), bytes16(0)),
+                consensusPop: hex"5678",
+                networkAddresses: bytes("/ip4/127.0.0.1/tcp/8000"),
+                fullnodeAddresses: bytes("/ip4/127.0.0.1/tcp/9000"),
+                votingPower: 200 ether
+            });
+        }
+    }
+
+    // Minimal Genesis parameters with one validator.
+    function _genesisParams(
+        address governanceOwner
+    ) internal returns (Genesis.GenesisInitParams memory params) {
         params.validatorConfig.minimumBond = 100 ether;
         params.validatorConfig.maximumBond = 10000 ether;
         params.validatorConfig.unbondingDelayMicros = 7 days * 1_000_000;
@@ -250,7 +332,11 @@ contract GenesisTest is Test {
         address[] memory callbacks = new address[](1);
         callbacks[0] = SystemAddresses.JWK_MANAGER;
         params.oracleConfig = Genesis.OracleInitParams(
-            sourceTypes, callbacks, new Genesis.OracleTaskParams[](0), Genesis.BridgeConfig(false, address(0), 0)
+            sourceTypes,
+            callbacks,
+            new Genesis.OracleTaskParams[](0),
+            new Genesis.OnDemandTaskParams[](0),
+            Genesis.BridgeConfig(false, address(0), 0)
         );
 
         bytes[] memory issuers = new bytes[](1);
@@ -259,9 +345,5 @@ contract GenesisTest is Test {
         jwks[0] = new IJWKManager.RSA_JWK[](1);
         jwks[0][0] = IJWKManager.RSA_JWK("kid1", "RSA", "RS256", "e", "n");
         params.jwkConfig = Genesis.JWKInitParams(issuers, jwks);
-
-        vm.deal(SystemAddresses.GENESIS, 1000 ether);
-        vm.prank(SystemAddresses.SYSTEM_CALLER);
-        genesis.initialize(params);
     }
 }
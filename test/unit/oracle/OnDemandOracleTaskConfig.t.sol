@@ -70,6 +70,15 @@ contract OnDemandOracleTaskConfigTest is Test {
         assertTrue(taskType2.updatedAt > updatedAt1);
     }
 
+    function test_SetTaskType_ByGenesis() public {
+        bytes memory config = abi.encode("https://api.nasdaq.com/prices");
+
+        vm.prank(SystemAddresses.GENESIS);
+        taskConfig.setTaskType(SOURCE_TYPE_PRICE_FEED, NASDAQ_SOURCE_ID, config);
+
+        assertTrue(taskConfig.isSupported(SOURCE_TYPE_PRICE_FEED, NASDAQ_SOURCE_ID));
+    }
+
     function test_SetTaskType_RevertWhenNotGovernance() public {
         bytes memory config = abi.encode("config");
 